@@ -5,13 +5,15 @@ use clap::{Parser, Subcommand};
 use tokio::io::{self, AsyncReadExt};
 use zksync_block_reverter::{
     eth_client::{
-        clients::{Client, PKSigningClient},
+        clients::{signing_client_for_wallet, Client},
         EthInterface,
     },
     BlockReverter, BlockReverterEthConfig, NodeRole,
 };
 use zksync_config::{
-    configs::{chain::NetworkConfig, DatabaseSecrets, L1Secrets, ObservabilityConfig},
+    configs::{
+        chain::NetworkConfig, wallets::Wallets, DatabaseSecrets, L1Secrets, ObservabilityConfig,
+    },
     ContractsConfig, DBConfig, EthConfig, PostgresConfig,
 };
 use zksync_dal::{ConnectionPool, Core};
@@ -157,30 +159,29 @@ async fn main() -> anyhow::Result<()> {
             let eth_client = Client::http(l1_secrets.l1_rpc_url.clone())
                 .context("Ethereum client")?
                 .build();
-            #[allow(deprecated)]
-            let reverter_private_key = eth_sender
-                .sender
-                .context("eth_sender_config")?
-                .private_key()
-                .context("eth_sender_config.private_key")?
-                .context("eth_sender_config.private_key is not set")?;
+            let reverter_wallet = Wallets::from_env()
+                .context("Wallets::from_env()")?
+                .eth_sender
+                .context("operator wallet (private key or remote signer) is not set")?
+                .operator;
 
             let priority_fee_per_gas = priority_fee_per_gas.unwrap_or(default_priority_fee_per_gas);
             let l1_chain_id = eth_client
                 .fetch_chain_id()
                 .await
                 .context("cannot fetch Ethereum chain ID")?;
-            let eth_client = PKSigningClient::new_raw(
-                reverter_private_key,
+            let eth_client = signing_client_for_wallet(
+                &reverter_wallet,
                 contracts.diamond_proxy_addr,
                 priority_fee_per_gas,
                 l1_chain_id,
                 Box::new(eth_client),
-            );
+            )
+            .context("failed creating signing Ethereum client")?;
 
             block_reverter
                 .send_ethereum_revert_transaction(
-                    &eth_client,
+                    eth_client.as_ref(),
                     &config,
                     L1BatchNumber(l1_batch_number),
                     nonce,
@@ -8,105 +8,39 @@ use zksync_config::{
     configs::{ObservabilityConfig, PrometheusConfig},
     ApiConfig, ContractVerifierConfig,
 };
-use zksync_contract_verifier_lib::ContractVerifier;
+use zksync_contract_verifier_lib::{
+    compilers::{CompilerKind, CompilerManager},
+    ContractVerifier,
+};
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_env_config::FromEnv;
 use zksync_queued_job_processor::JobProcessor;
-use zksync_utils::{wait_for_tasks::ManagedTasks, workspace_dir_or_current_dir};
+use zksync_utils::wait_for_tasks::ManagedTasks;
 use zksync_vlog::prometheus::PrometheusExporterConfig;
 
-async fn update_compiler_versions(connection_pool: &ConnectionPool<Core>) {
-    let mut storage = connection_pool.connection().await.unwrap();
-    let mut transaction = storage.start_transaction().await.unwrap();
-
-    let zksync_home = workspace_dir_or_current_dir();
-
-    let zksolc_path = zksync_home.join("etc/zksolc-bin/");
-    let zksolc_versions: Vec<String> = std::fs::read_dir(zksolc_path)
-        .unwrap()
-        .filter_map(|file| {
-            let file = file.unwrap();
-            let Ok(file_type) = file.file_type() else {
-                return None;
-            };
-            if file_type.is_dir() {
-                file.file_name().into_string().ok()
-            } else {
-                None
-            }
-        })
-        .collect();
-    transaction
-        .contract_verification_dal()
-        .set_zksolc_versions(zksolc_versions)
-        .await
-        .unwrap();
-
-    let solc_path = zksync_home.join("etc/solc-bin/");
-    let solc_versions: Vec<String> = std::fs::read_dir(solc_path)
-        .unwrap()
-        .filter_map(|file| {
-            let file = file.unwrap();
-            let Ok(file_type) = file.file_type() else {
-                return None;
-            };
-            if file_type.is_dir() {
-                file.file_name().into_string().ok()
-            } else {
-                None
-            }
-        })
-        .collect();
-    transaction
-        .contract_verification_dal()
-        .set_solc_versions(solc_versions)
-        .await
-        .unwrap();
-
-    let zkvyper_path = zksync_home.join("etc/zkvyper-bin/");
-    let zkvyper_versions: Vec<String> = std::fs::read_dir(zkvyper_path)
-        .unwrap()
-        .filter_map(|file| {
-            let file = file.unwrap();
-            let Ok(file_type) = file.file_type() else {
-                return None;
-            };
-            if file_type.is_dir() {
-                file.file_name().into_string().ok()
-            } else {
-                None
-            }
-        })
-        .collect();
-    transaction
-        .contract_verification_dal()
-        .set_zkvyper_versions(zkvyper_versions)
-        .await
-        .unwrap();
-
-    let vyper_path = zksync_home.join("etc/vyper-bin/");
-    let vyper_versions: Vec<String> = std::fs::read_dir(vyper_path)
-        .unwrap()
-        .filter_map(|file| {
-            let file = file.unwrap();
-            let Ok(file_type) = file.file_type() else {
-                return None;
-            };
-            if file_type.is_dir() {
-                file.file_name().into_string().ok()
-            } else {
-                None
-            }
-        })
-        .collect();
-
-    transaction
-        .contract_verification_dal()
-        .set_vyper_versions(vyper_versions)
-        .await
-        .unwrap();
+async fn update_compiler_versions(
+    connection_pool: &ConnectionPool<Core>,
+    compilers: &CompilerManager,
+) -> anyhow::Result<()> {
+    let mut storage = connection_pool.connection().await?;
+    let mut transaction = storage.start_transaction().await?;
+
+    for compiler in CompilerKind::ALL {
+        let versions = compilers
+            .list_versions(compiler)
+            .await
+            .with_context(|| format!("failed listing {compiler} versions"))?;
+        let mut dal = transaction.contract_verification_dal();
+        match compiler {
+            CompilerKind::ZkSolc => dal.set_zksolc_versions(versions).await?,
+            CompilerKind::Solc => dal.set_solc_versions(versions).await?,
+            CompilerKind::ZkVyper => dal.set_zkvyper_versions(versions).await?,
+            CompilerKind::Vyper => dal.set_vyper_versions(versions).await?,
+        }
+    }
 
-    transaction.commit().await.unwrap();
+    transaction.commit().await?;
+    Ok(())
 }
 
 use zksync_config::configs::DatabaseSecrets;
@@ -171,10 +105,17 @@ async fn main() -> anyhow::Result<()> {
         .expect("Error setting Ctrl+C handler");
     }
 
-    update_compiler_versions(&pool).await;
-
-    let contract_verifier = ContractVerifier::new(verifier_config, pool);
+    let contract_verifier =
+        ContractVerifier::new(verifier_config, pool.clone()).context("ContractVerifier::new()")?;
+    update_compiler_versions(&pool, contract_verifier.compilers())
+        .await
+        .context("update_compiler_versions()")?;
     let tasks = vec![
+        tokio::spawn(
+            contract_verifier
+                .clone()
+                .run_known_bytecode_verification(stop_receiver.clone()),
+        ),
         // TODO PLA-335: Leftovers after the prover DB split.
         // The prover connection pool is not used by the contract verifier, but we need to pass it
         // since `JobProcessor` trait requires it.
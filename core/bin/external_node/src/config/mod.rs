@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     env,
     ffi::OsString,
     num::{NonZeroU32, NonZeroU64, NonZeroUsize},
@@ -13,11 +14,12 @@ use zksync_config::{
         api::{MaxResponseSize, MaxResponseSizeOverrides},
         consensus::{ConsensusConfig, ConsensusSecrets},
         en_config::ENConfig,
-        GeneralConfig, Secrets,
+        GeneralConfig, PruningConfig, Secrets,
     },
     ObjectStoreConfig,
 };
 use zksync_core_leftovers::temp_config_store::{decode_yaml_repr, read_yaml_repr};
+use zksync_dal::pruning_dal::PrunedDataKind;
 #[cfg(test)]
 use zksync_dal::{ConnectionPool, Core};
 use zksync_metadata_calculator::MetadataCalculatorRecoveryConfig;
@@ -314,6 +316,10 @@ pub(crate) struct OptionalENConfig {
     /// (hundreds or thousands RPS).
     #[serde(default = "OptionalENConfig::default_extended_api_tracing")]
     pub extended_rpc_tracing: bool,
+    /// Whether to read VM storage for `eth_call`-like requests from the Merkle tree instead of Postgres
+    /// when the tree retains the necessary state.
+    #[serde(default)]
+    pub vm_storage_from_merkle_tree: bool,
 
     // Health checks
     /// Time limit in milliseconds to mark a health check as slow and log the corresponding warning.
@@ -442,6 +448,32 @@ pub(crate) struct OptionalENConfig {
     /// If set to 0, L1 batches will not be retained based on their timestamp. The default value is 7 days.
     #[serde(default = "OptionalENConfig::default_pruning_data_retention_sec")]
     pruning_data_retention_sec: u64,
+    /// If set, L1 batches will only be pruned while the estimated Postgres database size (in megabytes) exceeds this budget.
+    /// Other pruning criteria still apply; e.g., `pruning_data_retention_sec` effectively becomes the minimum retention period.
+    #[serde(default)]
+    pruning_db_size_budget_mb: Option<u64>,
+    /// If set, events and L2-to-L1 logs will be retained for at least this period (in seconds) since the L1 batch timestamp,
+    /// even if the rest of L1 batch data is pruned. Events are never retained for a shorter period than transactions.
+    #[serde(default)]
+    pruning_events_retention_sec: Option<u64>,
+    /// If set, transactions and their receipts will be retained for at least this period (in seconds) since the L1 batch timestamp,
+    /// even if the rest of L1 batch data is pruned.
+    #[serde(default)]
+    pruning_transactions_retention_sec: Option<u64>,
+    /// If set, L2 blocks, transactions, receipts and events will be archived to this object store before being pruned.
+    /// The API server will serve archived data for pruned blocks. Configured using `EN_PRUNING_ARCHIVE_OBJECT_STORE_*`
+    /// env variables.
+    #[serde(default)]
+    pub pruning_archive_object_store: Option<ObjectStoreConfig>,
+    /// If set, the Merkle tree will retain at least this number of its latest versions (= L1 batches), so that `zks_getProof`
+    /// can be served for them. This setting is independent of Postgres pruning; if set, the tree will be pruned even if
+    /// Postgres pruning is disabled. If not set, the tree is pruned following Postgres.
+    #[serde(default)]
+    pruning_tree_retained_l1_batches: Option<NonZeroU32>,
+    /// Whether the Merkle tree should retain versions for L1 batches of snapshots that are being created.
+    /// The default value is `true`.
+    #[serde(default = "OptionalENConfig::default_pruning_tree_retain_for_snapshots")]
+    pub pruning_tree_retain_for_snapshots: bool,
 }
 
 impl OptionalENConfig {
@@ -641,6 +673,28 @@ impl OptionalENConfig {
                 data_retention_sec,
                 default_pruning_data_retention_sec
             ),
+            pruning_db_size_budget_mb: load_config!(general_config.pruning, db_size_budget_mb),
+            pruning_events_retention_sec: load_config!(
+                general_config.pruning,
+                events_retention_sec
+            ),
+            pruning_transactions_retention_sec: load_config!(
+                general_config.pruning,
+                transactions_retention_sec
+            ),
+            pruning_archive_object_store: load_config!(
+                general_config.pruning,
+                archive_object_store
+            ),
+            pruning_tree_retained_l1_batches: load_config!(
+                general_config.pruning,
+                tree_retained_l1_batches
+            ),
+            pruning_tree_retain_for_snapshots: load_optional_config_or_default!(
+                general_config.pruning,
+                tree_retain_for_snapshots,
+                default_pruning_tree_retain_for_snapshots
+            ),
             protective_reads_persistence_enabled: general_config
                 .db_config
                 .as_ref()
@@ -661,6 +715,11 @@ impl OptionalENConfig {
                 web3_json_rpc.extended_api_tracing,
                 default_extended_api_tracing
             ),
+            vm_storage_from_merkle_tree: general_config
+                .api_config
+                .as_ref()
+                .map(|a| a.web3_json_rpc.vm_storage_from_merkle_tree)
+                .unwrap_or_default(),
             main_node_rate_limit_rps: enconfig
                 .main_node_rate_limit_rps
                 .unwrap_or_else(Self::default_main_node_rate_limit_rps),
@@ -804,11 +863,16 @@ impl OptionalENConfig {
         3_600 * 24 * 7 // 7 days
     }
 
+    const fn default_pruning_tree_retain_for_snapshots() -> bool {
+        true
+    }
+
     fn from_env() -> anyhow::Result<Self> {
         let mut result: OptionalENConfig = envy::prefixed("EN_")
             .from_env()
             .context("could not load external node config")?;
         result.snapshot_recover_object_store = snapshot_recovery_object_store_config().ok();
+        result.pruning_archive_object_store = pruning_archive_object_store_config().ok();
         Ok(result)
     }
 
@@ -888,6 +952,12 @@ impl OptionalENConfig {
         Duration::from_millis(self.mempool_cache_update_interval_ms)
     }
 
+    /// Returns the validated number of retained Merkle tree versions.
+    pub fn pruning_tree_retained_l1_batches(&self) -> anyhow::Result<Option<NonZeroU32>> {
+        PruningConfig::check_tree_retained_l1_batches(self.pruning_tree_retained_l1_batches)
+            .context("invalid `pruning_tree_retained_l1_batches`")
+    }
+
     pub fn pruning_removal_delay(&self) -> Duration {
         Duration::from_secs(self.pruning_removal_delay_sec.get())
     }
@@ -896,6 +966,27 @@ impl OptionalENConfig {
         Duration::from_secs(self.pruning_data_retention_sec)
     }
 
+    /// Returns the database size budget for pruning in bytes.
+    pub fn pruning_db_size_budget(&self) -> Option<u64> {
+        self.pruning_db_size_budget_mb
+            .map(|size_mb| size_mb * BYTES_IN_MEGABYTE as u64)
+    }
+
+    /// Returns retention periods for data kinds pruned separately from the rest of L1 batch data.
+    pub fn pruning_data_kinds_retention(&self) -> HashMap<PrunedDataKind, Duration> {
+        let retention = [
+            (PrunedDataKind::Events, self.pruning_events_retention_sec),
+            (
+                PrunedDataKind::Transactions,
+                self.pruning_transactions_retention_sec,
+            ),
+        ];
+        retention
+            .into_iter()
+            .filter_map(|(kind, secs)| Some((kind, Duration::from_secs(secs?))))
+            .collect()
+    }
+
     #[cfg(test)]
     fn mock() -> Self {
         // Set all values to their defaults
@@ -1041,6 +1132,12 @@ pub(crate) struct ExperimentalENConfig {
     // Snapshot recovery
     /// L1 batch number of the snapshot to use during recovery. Specifying this parameter is mostly useful for testing.
     pub snapshots_recovery_l1_batch: Option<L1BatchNumber>,
+    /// L1 batch number to recover to using differential snapshots on top of a full snapshot. If specified,
+    /// overrides `snapshots_recovery_l1_batch`.
+    pub snapshots_recovery_diff_l1_batch: Option<L1BatchNumber>,
+    /// Path to a local snapshot archive to recover from. If specified, neither the main node API nor the snapshot
+    /// object store is used during Postgres recovery.
+    pub snapshots_recovery_archive_path: Option<PathBuf>,
     /// Approximate chunk size (measured in the number of entries) to recover in a single iteration.
     /// Reasonable values are order of 100,000 (meaning an iteration takes several seconds).
     ///
@@ -1059,6 +1156,15 @@ pub(crate) struct ExperimentalENConfig {
     /// Maximum degree of parallelism during commitment generation, i.e., the maximum number of L1 batches being processed in parallel.
     /// If not specified, commitment generator will use a value roughly equal to the number of CPU cores with some clamping applied.
     pub commitment_generator_max_parallelism: Option<NonZeroU32>,
+
+    // RocksDB checkpoints
+    /// Interval between uploading checkpoints of the Merkle tree and state keeper cache RocksDB instances to
+    /// the object store configured by `rocksdb_checkpoints_object_store`. If the instances don't exist on node start,
+    /// they are restored from the latest checkpoints. If not set, checkpoints are disabled.
+    pub rocksdb_checkpoints_interval_sec: Option<u64>,
+    /// Object store for RocksDB checkpoints. Configured using `EN_ROCKSDB_CHECKPOINTS_OBJECT_STORE_*` env variables.
+    #[serde(default)]
+    pub rocksdb_checkpoints_object_store: Option<ObjectStoreConfig>,
 }
 
 impl ExperimentalENConfig {
@@ -1077,12 +1183,40 @@ impl ExperimentalENConfig {
                 Self::default_state_keeper_db_block_cache_capacity_mb(),
             state_keeper_db_max_open_files: None,
             snapshots_recovery_l1_batch: None,
+            snapshots_recovery_diff_l1_batch: None,
+            snapshots_recovery_archive_path: None,
             snapshots_recovery_tree_chunk_size: Self::default_snapshots_recovery_tree_chunk_size(),
             snapshots_recovery_tree_parallel_persistence_buffer: None,
             commitment_generator_max_parallelism: None,
+            rocksdb_checkpoints_interval_sec: None,
+            rocksdb_checkpoints_object_store: None,
         }
     }
 
+    fn from_env() -> anyhow::Result<Self> {
+        let mut result: Self = envy::prefixed("EN_EXPERIMENTAL_")
+            .from_env()
+            .context("could not load external node config (experimental params)")?;
+        result.rocksdb_checkpoints_object_store = rocksdb_checkpoints_object_store_config().ok();
+        Ok(result)
+    }
+
+    /// Returns the interval between uploading RocksDB checkpoints and the object store to upload them to,
+    /// or `None` if checkpoints are disabled.
+    pub fn rocksdb_checkpoints(&self) -> anyhow::Result<Option<(Duration, ObjectStoreConfig)>> {
+        let Some(interval_sec) = self.rocksdb_checkpoints_interval_sec else {
+            return Ok(None);
+        };
+        let object_store_config = self
+            .rocksdb_checkpoints_object_store
+            .clone()
+            .context("RocksDB checkpoints are enabled, but their object store is not configured")?;
+        Ok(Some((
+            Duration::from_secs(interval_sec),
+            object_store_config,
+        )))
+    }
+
     /// Returns the size of block cache for the state keeper RocksDB cache in bytes.
     pub fn state_keeper_db_block_cache_capacity(&self) -> usize {
         self.state_keeper_db_block_cache_capacity_mb * BYTES_IN_MEGABYTE
@@ -1101,6 +1235,14 @@ impl ExperimentalENConfig {
                 experimental.state_keeper_db_max_open_files
             ),
             snapshots_recovery_l1_batch: load_config!(general_config.snapshot_recovery, l1_batch),
+            snapshots_recovery_diff_l1_batch: load_config!(
+                general_config.snapshot_recovery,
+                diff_l1_batch
+            ),
+            snapshots_recovery_archive_path: load_config!(
+                general_config.snapshot_recovery,
+                archive_path
+            ),
             snapshots_recovery_tree_chunk_size: load_optional_config_or_default!(
                 general_config.snapshot_recovery,
                 tree.chunk_size,
@@ -1114,6 +1256,11 @@ impl ExperimentalENConfig {
                 .commitment_generator
                 .as_ref()
                 .map(|a| a.max_parallelism),
+            rocksdb_checkpoints_interval_sec: load_config!(
+                general_config.db_config,
+                experimental.rocksdb_checkpoints_interval_sec
+            ),
+            rocksdb_checkpoints_object_store: general_config.core_object_store.clone(),
         })
     }
 }
@@ -1146,6 +1293,20 @@ pub(crate) fn snapshot_recovery_object_store_config() -> anyhow::Result<ObjectSt
         .context("failed loading snapshot object store config from env variables")
 }
 
+/// Configuration of the object store used for RocksDB checkpoints. Should be loaded optionally.
+pub(crate) fn rocksdb_checkpoints_object_store_config() -> anyhow::Result<ObjectStoreConfig> {
+    envy::prefixed("EN_ROCKSDB_CHECKPOINTS_OBJECT_STORE_")
+        .from_env::<ObjectStoreConfig>()
+        .context("failed loading RocksDB checkpoints object store config from env variables")
+}
+
+/// Configuration of the object store used to archive pruned data. Should be loaded optionally.
+pub(crate) fn pruning_archive_object_store_config() -> anyhow::Result<ObjectStoreConfig> {
+    envy::prefixed("EN_PRUNING_ARCHIVE_OBJECT_STORE_")
+        .from_env::<ObjectStoreConfig>()
+        .context("failed loading pruning archive object store config from env variables")
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ApiComponentConfig {
     /// Address of the tree API used by this EN in case it does not have a
@@ -1203,9 +1364,7 @@ impl ExternalNodeConfig<()> {
             postgres: PostgresConfig::from_env()?,
             optional: OptionalENConfig::from_env()?,
             observability: ObservabilityENConfig::from_env()?,
-            experimental: envy::prefixed("EN_EXPERIMENTAL_")
-                .from_env::<ExperimentalENConfig>()
-                .context("could not load external node config (experimental params)")?,
+            experimental: ExperimentalENConfig::from_env()?,
             consensus: read_consensus_config().context("read_consensus_config()")?,
             api_component: envy::prefixed("EN_API_")
                 .from_env::<ApiComponentConfig>()
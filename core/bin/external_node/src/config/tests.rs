@@ -191,6 +191,33 @@ fn parsing_renamed_optional_parameters() {
     assert_eq!(config.merkle_tree_max_l1_batches_per_iter, 15);
 }
 
+#[test]
+fn validating_tree_retained_l1_batches() {
+    let config: OptionalENConfig = envy::prefixed("EN_").from_iter([]).unwrap();
+    assert_eq!(config.pruning_tree_retained_l1_batches().unwrap(), None);
+
+    let env_vars = [(
+        "EN_PRUNING_TREE_RETAINED_L1_BATCHES".to_owned(),
+        "100".to_owned(),
+    )];
+    let config: OptionalENConfig = envy::prefixed("EN_").from_iter(env_vars).unwrap();
+    assert_eq!(
+        config.pruning_tree_retained_l1_batches().unwrap(),
+        NonZeroU32::new(100)
+    );
+
+    let env_vars = [(
+        "EN_PRUNING_TREE_RETAINED_L1_BATCHES".to_owned(),
+        "2".to_owned(),
+    )];
+    let config: OptionalENConfig = envy::prefixed("EN_").from_iter(env_vars).unwrap();
+    let err = config.pruning_tree_retained_l1_batches().unwrap_err();
+    assert!(
+        format!("{err:#}").contains("less than the minimum"),
+        "{err:#}"
+    );
+}
+
 #[test]
 fn parsing_experimental_config_from_empty_env() {
     let config: ExperimentalENConfig = envy::prefixed("EN_EXPERIMENTAL_").from_iter([]).unwrap();
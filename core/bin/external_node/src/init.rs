@@ -1,6 +1,6 @@
 //! EN initialization logic.
 
-use std::time::Instant;
+use std::{path::PathBuf, sync::Arc, time::Instant};
 
 use anyhow::Context as _;
 use zksync_config::ObjectStoreConfig;
@@ -9,7 +9,7 @@ use zksync_health_check::AppHealthCheck;
 use zksync_node_sync::genesis::perform_genesis_if_needed;
 use zksync_object_store::ObjectStoreFactory;
 use zksync_shared_metrics::{SnapshotRecoveryStage, APP_METRICS};
-use zksync_snapshots_applier::{SnapshotsApplierConfig, SnapshotsApplierTask};
+use zksync_snapshots_applier::{SnapshotArchive, SnapshotsApplierConfig, SnapshotsApplierTask};
 use zksync_types::{L1BatchNumber, L2ChainId};
 use zksync_web3_decl::client::{DynClient, L2};
 
@@ -17,6 +17,11 @@ use zksync_web3_decl::client::{DynClient, L2};
 pub(crate) struct SnapshotRecoveryConfig {
     /// If not specified, the latest snapshot will be used.
     pub snapshot_l1_batch_override: Option<L1BatchNumber>,
+    /// If specified, the node will recover to this L1 batch using differential snapshots.
+    pub snapshot_diff_l1_batch: Option<L1BatchNumber>,
+    /// If specified, the node will recover from a local snapshot archive instead of using the main node
+    /// and the object store.
+    pub archive_path: Option<PathBuf>,
     pub object_store_config: Option<ObjectStoreConfig>,
 }
 
@@ -90,20 +95,34 @@ pub(crate) async fn ensure_storage_initialized(
             )?;
 
             tracing::warn!("Proceeding with snapshot recovery. This is an experimental feature; use at your own risk");
-            let object_store_config = recovery_config.object_store_config.context(
-                "Snapshot object store must be presented if snapshot recovery is activated",
-            )?;
-            let object_store = ObjectStoreFactory::new(object_store_config)
-                .create_store()
-                .await?;
-
             let config = SnapshotsApplierConfig::default();
-            let mut snapshots_applier_task = SnapshotsApplierTask::new(
-                config,
-                pool,
-                Box::new(main_node_client.for_component("snapshot_recovery")),
-                object_store,
-            );
+            let mut snapshots_applier_task =
+                if let Some(archive_path) = recovery_config.archive_path {
+                    tracing::info!(
+                        "Recovering from snapshot archive at {}",
+                        archive_path.display()
+                    );
+                    let archive = SnapshotArchive::open(archive_path).await?;
+                    SnapshotsApplierTask::new(
+                        config,
+                        pool,
+                        Box::new(archive.clone()),
+                        Arc::new(archive),
+                    )
+                } else {
+                    let object_store_config = recovery_config.object_store_config.context(
+                        "Snapshot object store must be presented if snapshot recovery is activated",
+                    )?;
+                    let object_store = ObjectStoreFactory::new(object_store_config)
+                        .create_store()
+                        .await?;
+                    SnapshotsApplierTask::new(
+                        config,
+                        pool,
+                        Box::new(main_node_client.for_component("snapshot_recovery")),
+                        object_store,
+                    )
+                };
             if let Some(snapshot_l1_batch) = recovery_config.snapshot_l1_batch_override {
                 tracing::info!(
                     "Using a specific snapshot with L1 batch #{snapshot_l1_batch}; this may not work \
@@ -111,6 +130,12 @@ pub(crate) async fn ensure_storage_initialized(
                 );
                 snapshots_applier_task.set_snapshot_l1_batch(snapshot_l1_batch);
             }
+            if let Some(diff_l1_batch) = recovery_config.snapshot_diff_l1_batch {
+                tracing::info!(
+                    "Recovering to L1 batch #{diff_l1_batch} using differential snapshots"
+                );
+                snapshots_applier_task.set_snapshot_diff_l1_batch(diff_l1_batch);
+            }
             app_health.insert_component(snapshots_applier_task.health_check())?;
 
             let recovery_started_at = Instant::now();
@@ -23,7 +23,8 @@ use zksync_db_connection::{
 use zksync_health_check::{AppHealthCheck, HealthStatus, ReactiveHealthCheck};
 use zksync_metadata_calculator::{
     api_server::{TreeApiClient, TreeApiHttpClient},
-    MetadataCalculator, MetadataCalculatorConfig, MetadataCalculatorRecoveryConfig,
+    LazyAsyncTreeReader, MetadataCalculator, MetadataCalculatorConfig,
+    MetadataCalculatorRecoveryConfig,
 };
 use zksync_node_api_server::{
     execution_sandbox::VmConcurrencyLimiter,
@@ -32,13 +33,14 @@ use zksync_node_api_server::{
     web3::{mempool_cache::MempoolCache, ApiBuilder, Namespace},
 };
 use zksync_node_consensus as consensus;
-use zksync_node_db_pruner::{DbPruner, DbPrunerConfig};
+use zksync_node_db_pruner::{DbPruner, DbPrunerConfig, PrunedDataArchiver};
 use zksync_node_fee_model::l1_gas_price::MainNodeFeeParamsFetcher;
 use zksync_node_sync::{
     batch_status_updater::BatchStatusUpdater, external_io::ExternalIO,
     tree_data_fetcher::TreeDataFetcher, validate_chain_ids_task::ValidateChainIdsTask, ActionQueue,
     MainNodeHealthCheck, SyncState,
 };
+use zksync_object_store::ObjectStoreFactory;
 use zksync_reorg_detector::ReorgDetector;
 use zksync_shared_metrics::rustc::RUST_METRICS;
 use zksync_state::{PostgresStorageCaches, RocksdbStorageOptions};
@@ -125,7 +127,7 @@ async fn run_tree(
     app_health: &AppHealthCheck,
     stop_receiver: watch::Receiver<bool>,
     tree_pool: ConnectionPool<Core>,
-) -> anyhow::Result<Arc<dyn TreeApiClient>> {
+) -> anyhow::Result<LazyAsyncTreeReader> {
     let metadata_calculator_config = MetadataCalculatorConfig {
         db_path: config.required.merkle_tree_path.clone(),
         max_open_files: config.optional.merkle_tree_max_open_files,
@@ -169,14 +171,17 @@ async fn run_tree(
             .context("failed initializing metadata calculator")?
             .with_recovery_pool(recovery_pool);
 
-    let tree_reader = Arc::new(metadata_calculator.tree_reader());
+    let tree_reader = metadata_calculator.tree_reader();
     app_health.insert_custom_component(Arc::new(metadata_calculator.tree_health_check()))?;
 
-    if config.optional.pruning_enabled {
+    let tree_retained_l1_batches = config.optional.pruning_tree_retained_l1_batches()?;
+    if config.optional.pruning_enabled || tree_retained_l1_batches.is_some() {
         tracing::warn!("Proceeding with node state pruning for the Merkle tree. This is an experimental feature; use at your own risk");
 
-        let pruning_task =
-            metadata_calculator.pruning_task(config.optional.pruning_removal_delay() / 2);
+        let pruning_task = metadata_calculator
+            .pruning_task(config.optional.pruning_removal_delay() / 2)
+            .with_retained_l1_batches(tree_retained_l1_batches)
+            .with_snapshot_retention(config.optional.pruning_tree_retain_for_snapshots);
         app_health.insert_component(pruning_task.health_check())?;
         let pruning_task_handle = tokio::spawn(pruning_task.run(stop_receiver.clone()));
         task_futures.push(pruning_task_handle);
@@ -309,14 +314,26 @@ async fn run_core(
         tracing::info!(
             "Configured pruning of batches after they become {minimum_l1_batch_age:?} old"
         );
-        let db_pruner = DbPruner::new(
+        let mut db_pruner = DbPruner::new(
             DbPrunerConfig {
                 removal_delay: config.optional.pruning_removal_delay(),
                 pruned_batch_chunk_size: config.optional.pruning_chunk_size,
                 minimum_l1_batch_age,
+                database_size_budget: config.optional.pruning_db_size_budget(),
+                data_retention: config.optional.pruning_data_kinds_retention(),
             },
             connection_pool.clone(),
         );
+        if let Some(archive_config) = config.optional.pruning_archive_object_store.clone() {
+            tracing::info!("Pruned L1 batches will be archived to the object store");
+            let blob_store = ObjectStoreFactory::new(archive_config)
+                .create_store()
+                .await?;
+            db_pruner = db_pruner.with_archiver(PrunedDataArchiver::new(
+                blob_store,
+                config.required.l2_chain_id,
+            ));
+        }
         app_health.insert_component(db_pruner.health_check())?;
         task_handles.push(tokio::spawn(db_pruner.run(stop_receiver.clone())));
     }
@@ -401,20 +418,20 @@ async fn run_api(
     connection_pool: ConnectionPool<Core>,
     stop_receiver: watch::Receiver<bool>,
     sync_state: SyncState,
-    tree_reader: Option<Arc<dyn TreeApiClient>>,
+    local_tree_reader: Option<LazyAsyncTreeReader>,
     main_node_client: Box<DynClient<L2>>,
     singleton_pool_builder: &ConnectionPoolBuilder<Core>,
     fee_params_fetcher: Arc<MainNodeFeeParamsFetcher>,
     components: &HashSet<Component>,
 ) -> anyhow::Result<()> {
-    let tree_reader = match tree_reader {
+    let tree_reader = match &local_tree_reader {
         Some(tree_reader) => {
             if let Some(url) = &config.api_component.tree_api_remote_url {
                 tracing::warn!(
                     "Tree component is run locally; the specified tree API URL {url} is ignored"
                 );
             }
-            Some(tree_reader)
+            Some(Arc::new(tree_reader.clone()) as Arc<dyn TreeApiClient>)
         }
         None => config
             .api_component
@@ -444,8 +461,18 @@ async fn run_api(
         tokio::spawn(fee_params_fetcher.clone().run(stop_receiver.clone()));
     task_handles.push(fee_params_fetcher_handle);
 
-    let tx_sender_builder =
+    let mut tx_sender_builder =
         TxSenderBuilder::new(config.into(), connection_pool.clone(), Arc::new(tx_proxy));
+    if config.optional.vm_storage_from_merkle_tree {
+        if let Some(tree_reader) = local_tree_reader {
+            tx_sender_builder = tx_sender_builder.with_tree_reader(tree_reader);
+        } else {
+            tracing::warn!(
+                "Reading VM storage from the Merkle tree is enabled, but the tree component is not run locally; \
+                 VM storage will be read from Postgres"
+            );
+        }
+    }
 
     let max_concurrency = config.optional.vm_concurrency_limit;
     let (vm_concurrency_limiter, vm_barrier) = VmConcurrencyLimiter::new(max_concurrency);
@@ -511,6 +538,14 @@ async fn run_api(
     // The refresh interval should be several times lower than the pruning removal delay, so that
     // soft-pruning will timely propagate to the API server.
     let pruning_info_refresh_interval = config.optional.pruning_removal_delay() / 5;
+    let pruned_data_archive = match config.optional.pruning_archive_object_store.clone() {
+        Some(archive_config) => Some(
+            ObjectStoreFactory::new(archive_config)
+                .create_store()
+                .await?,
+        ),
+        None => None,
+    };
 
     if components.contains(&Component::HttpApi) {
         let mut builder = ApiBuilder::jsonrpsee_backend(config.into(), connection_pool.clone())
@@ -528,6 +563,9 @@ async fn run_api(
         if let Some(tree_reader) = &tree_reader {
             builder = builder.with_tree_api(tree_reader.clone());
         }
+        if let Some(blob_store) = &pruned_data_archive {
+            builder = builder.with_pruned_data_archive(blob_store.clone());
+        }
 
         let http_server_handles = builder
             .build()
@@ -557,6 +595,9 @@ async fn run_api(
         if let Some(tree_reader) = tree_reader {
             builder = builder.with_tree_api(tree_reader);
         }
+        if let Some(blob_store) = pruned_data_archive {
+            builder = builder.with_pruned_data_archive(blob_store);
+        }
 
         let ws_server_handles = builder
             .build()
@@ -603,7 +644,7 @@ async fn init_tasks(
     // we can get this tree's reader and use it right away. Otherwise, if configuration has
     // specified address of another instance hosting tree API, create a tree reader to that
     // remote API. A tree reader is necessary for `zks_getProof` method to work.
-    let tree_reader: Option<Arc<dyn TreeApiClient>> = if components.contains(&Component::Tree) {
+    let tree_reader = if components.contains(&Component::Tree) {
         let tree_api_config = if components.contains(&Component::TreeApi) {
             Some(MerkleTreeApiConfig {
                 port: config
@@ -971,6 +1012,8 @@ async fn run_node(
             .snapshots_recovery_enabled
             .then_some(SnapshotRecoveryConfig {
                 snapshot_l1_batch_override: config.experimental.snapshots_recovery_l1_batch,
+                snapshot_diff_l1_batch: config.experimental.snapshots_recovery_diff_l1_batch,
+                archive_path: config.experimental.snapshots_recovery_archive_path.clone(),
                 object_store_config: config.optional.snapshot_recover_object_store.clone(),
             });
     ensure_storage_initialized(
@@ -1,6 +1,8 @@
 //! This module provides a "builder" for the external node,
 //! as well as an interface to run the node with the specified components.
 
+use std::path::Path;
+
 use anyhow::Context as _;
 use zksync_config::{
     configs::{
@@ -22,7 +24,7 @@ use zksync_node_framework::{
         l1_batch_commitment_mode_validation::L1BatchCommitmentModeValidationLayer,
         main_node_client::MainNodeClientLayer,
         main_node_fee_params_fetcher::MainNodeFeeParamsFetcherLayer,
-        metadata_calculator::MetadataCalculatorLayer,
+        metadata_calculator::{MetadataCalculatorLayer, RocksdbCheckpointConfig},
         pools_layer::PoolsLayerBuilder,
         postgres_metrics::PostgresMetricsLayer,
         prometheus_exporter::PrometheusExporterLayer,
@@ -193,10 +195,20 @@ impl ExternalNodeBuilder {
                 .state_keeper_db_block_cache_capacity(),
             max_open_files: self.config.experimental.state_keeper_db_max_open_files,
         };
-        let state_keeper_layer = StateKeeperLayer::new(
+        let mut state_keeper_layer = StateKeeperLayer::new(
             self.config.required.state_cache_path.clone(),
             rocksdb_options,
         );
+        if let Some((interval, object_store_config)) =
+            self.config.experimental.rocksdb_checkpoints()?
+        {
+            let checkpoint_config = RocksdbCheckpointConfig::for_db_path(
+                Path::new(&self.config.required.state_cache_path),
+                interval,
+            );
+            state_keeper_layer =
+                state_keeper_layer.with_checkpoint_config(checkpoint_config, object_store_config);
+        }
         self.node
             .add_layer(persistence_layer)
             .add_layer(io_layer)
@@ -224,6 +236,12 @@ impl ExternalNodeBuilder {
                 self.config.optional.pruning_removal_delay(),
                 self.config.optional.pruning_chunk_size,
                 self.config.optional.pruning_data_retention(),
+            )
+            .with_database_size_budget(self.config.optional.pruning_db_size_budget())
+            .with_data_retention(self.config.optional.pruning_data_kinds_retention())
+            .with_archive(
+                self.config.optional.pruning_archive_object_store.clone(),
+                self.config.required.l2_chain_id,
             );
             self.node.add_layer(layer);
         } else {
@@ -334,9 +352,26 @@ impl ExternalNodeBuilder {
             layer = layer.with_tree_api_config(merkle_tree_api_config);
         }
 
+        // Add tree checkpoints if needed.
+        if let Some((interval, object_store_config)) =
+            self.config.experimental.rocksdb_checkpoints()?
+        {
+            let checkpoint_config = RocksdbCheckpointConfig::for_db_path(
+                Path::new(&self.config.required.merkle_tree_path),
+                interval,
+            );
+            layer = layer.with_checkpoint_config(checkpoint_config, object_store_config);
+        }
+
         // Add tree pruning if needed.
-        if self.config.optional.pruning_enabled {
-            layer = layer.with_pruning_config(self.config.optional.pruning_removal_delay());
+        let tree_retained_l1_batches = self.config.optional.pruning_tree_retained_l1_batches()?;
+        if self.config.optional.pruning_enabled || tree_retained_l1_batches.is_some() {
+            layer = layer
+                .with_pruning_config(self.config.optional.pruning_removal_delay())
+                .with_tree_retention(
+                    tree_retained_l1_batches,
+                    self.config.optional.pruning_tree_retain_for_snapshots,
+                );
         }
 
         self.node.add_layer(layer);
@@ -357,7 +392,8 @@ impl ExternalNodeBuilder {
             max_vm_concurrency,
             api_contracts,
         )
-        .with_whitelisted_tokens_for_aa_cache(true);
+        .with_whitelisted_tokens_for_aa_cache(true)
+        .with_vm_storage_from_merkle_tree(self.config.optional.vm_storage_from_merkle_tree);
 
         self.node.add_layer(TxSinkLayer::ProxySink);
         self.node.add_layer(tx_sender_layer);
@@ -397,6 +433,7 @@ impl ExternalNodeBuilder {
             response_body_size_limit: Some(self.config.optional.max_response_body_size()),
             with_extended_tracing: self.config.optional.extended_rpc_tracing,
             pruning_info_refresh_interval: Some(pruning_info_refresh_interval),
+            pruned_data_archive: self.config.optional.pruning_archive_object_store.clone(),
             websocket_requests_per_minute_limit: None, // To be set by WS server layer method if required.
             replication_lag_limit: None,               // TODO: Support replication lag limit
         }
@@ -179,6 +179,7 @@ async fn external_node_basics(components_str: &'static str) {
             Ok(api::L1BatchDetails {
                 number: L1BatchNumber(0),
                 base: block_details_base(genesis_params.root_hash),
+                rollup_last_leaf_index: None,
             })
         })
         .method("eth_blockNumber", || Ok(U64::from(0)))
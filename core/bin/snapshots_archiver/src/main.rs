@@ -0,0 +1,75 @@
+//! Snapshot archive export utility.
+//!
+//! Exports a snapshot (its header, L1 batch and L2 block info, tokens, factory deps and storage log chunks) into
+//! a single archive file that can be used to recover a node without access to the main node API or the snapshot
+//! object store; see the `snapshots_recovery_archive_path` external node option.
+//!
+//! The object store is configured in the same way as for the snapshot creator (i.e., using `SNAPSHOTS_OBJECT_STORE_*`
+//! env variables).
+
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use clap::Parser;
+use zksync_config::configs::ObservabilityConfig;
+use zksync_env_config::{object_store::SnapshotsObjectStoreConfig, FromEnv};
+use zksync_object_store::ObjectStoreFactory;
+use zksync_snapshots_applier::SnapshotArchive;
+use zksync_types::{url::SensitiveUrl, L1BatchNumber};
+use zksync_web3_decl::client::{Client, DynClient, L2};
+
+#[derive(Debug, Parser)]
+#[command(
+    author = "Matter Labs",
+    version,
+    about = "Snapshot archive exporter",
+    long_about = None
+)]
+struct Cli {
+    /// URL of the main node JSON-RPC API used to fetch the snapshot header and L1 batch / L2 block info.
+    #[arg(long = "main-node-url")]
+    main_node_url: SensitiveUrl,
+    /// L1 batch number of the snapshot to export. If not specified, the newest snapshot on the main node is exported.
+    #[arg(long = "l1-batch")]
+    l1_batch: Option<u32>,
+    /// Path to the output archive file.
+    #[arg(long)]
+    output: PathBuf,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let observability_config =
+        ObservabilityConfig::from_env().context("ObservabilityConfig::from_env()")?;
+    let log_format: zksync_vlog::LogFormat = observability_config
+        .log_format
+        .parse()
+        .context("Invalid log format")?;
+    let mut builder = zksync_vlog::ObservabilityBuilder::new().with_log_format(log_format);
+    if let Some(sentry_url) = observability_config.sentry_url {
+        builder = builder
+            .with_sentry_url(&sentry_url)
+            .context("Invalid Sentry URL")?
+            .with_sentry_environment(observability_config.sentry_environment);
+    }
+    let _guard = builder.build();
+
+    let cli = Cli::parse();
+    let object_store_config =
+        SnapshotsObjectStoreConfig::from_env().context("SnapshotsObjectStoreConfig::from_env()")?;
+    let blob_store = ObjectStoreFactory::new(object_store_config.0)
+        .create_store()
+        .await?;
+    let main_node_client = Client::<L2>::http(cli.main_node_url)
+        .context("failed creating JSON-RPC client for main node")?
+        .build();
+    let main_node_client = Box::new(main_node_client) as Box<DynClient<L2>>;
+
+    SnapshotArchive::export(
+        &main_node_client,
+        blob_store.as_ref(),
+        cli.l1_batch.map(L1BatchNumber),
+        &cli.output,
+    )
+    .await
+}
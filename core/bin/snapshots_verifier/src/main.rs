@@ -0,0 +1,105 @@
+//! Offline snapshot verification utility.
+//!
+//! Reads all storage log chunks and factory deps of a snapshot from the object store, rebuilds the Merkle tree
+//! from storage logs in a temporary RocksDB and compares its root hash and leaf count with the reference values
+//! obtained from the main node. Missing or corrupt snapshot objects are reported.
+//!
+//! The object store is configured in the same way as for the snapshot creator (i.e., using `SNAPSHOTS_OBJECT_STORE_*`
+//! env variables).
+
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use clap::Parser;
+use zksync_config::configs::ObservabilityConfig;
+use zksync_env_config::{object_store::SnapshotsObjectStoreConfig, FromEnv};
+use zksync_object_store::ObjectStoreFactory;
+use zksync_types::{url::SensitiveUrl, L1BatchNumber};
+use zksync_web3_decl::client::{Client, DynClient, L2};
+
+use crate::verifier::SnapshotVerifier;
+
+#[cfg(test)]
+mod tests;
+mod verifier;
+
+#[derive(Debug, Parser)]
+#[command(
+    author = "Matter Labs",
+    version,
+    about = "Offline snapshot verifier",
+    long_about = None
+)]
+struct Cli {
+    /// URL of the main node JSON-RPC API used to fetch the snapshot header and the reference root hash.
+    #[arg(long = "main-node-url")]
+    main_node_url: SensitiveUrl,
+    /// L1 batch number of the snapshot to verify. If not specified, the newest snapshot on the main node is verified.
+    #[arg(long = "l1-batch")]
+    l1_batch: Option<u32>,
+    /// Directory to create the temporary Merkle tree RocksDB in. If not specified, a system temporary directory is used.
+    /// The tree is removed after verification in any case.
+    #[arg(long = "db-dir")]
+    db_dir: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let observability_config =
+        ObservabilityConfig::from_env().context("ObservabilityConfig::from_env()")?;
+    let log_format: zksync_vlog::LogFormat = observability_config
+        .log_format
+        .parse()
+        .context("Invalid log format")?;
+    let mut builder = zksync_vlog::ObservabilityBuilder::new().with_log_format(log_format);
+    if let Some(sentry_url) = observability_config.sentry_url {
+        builder = builder
+            .with_sentry_url(&sentry_url)
+            .context("Invalid Sentry URL")?
+            .with_sentry_environment(observability_config.sentry_environment);
+    }
+    let _guard = builder.build();
+
+    let cli = Cli::parse();
+    let object_store_config =
+        SnapshotsObjectStoreConfig::from_env().context("SnapshotsObjectStoreConfig::from_env()")?;
+    let blob_store = ObjectStoreFactory::new(object_store_config.0)
+        .create_store()
+        .await?;
+    let main_node_client = Client::<L2>::http(cli.main_node_url)
+        .context("failed creating JSON-RPC client for main node")?
+        .build();
+    let main_node_client = Box::new(main_node_client) as Box<DynClient<L2>>;
+
+    let temp_dir = match &cli.db_dir {
+        Some(dir) => tempfile::TempDir::new_in(dir),
+        None => tempfile::TempDir::new(),
+    };
+    let temp_dir = temp_dir.context("failed creating temporary directory for Merkle tree")?;
+    let verifier = SnapshotVerifier {
+        blob_store,
+        main_node_client: Box::new(main_node_client),
+        db_path: temp_dir.path().join("tree"),
+    };
+    let report = verifier.verify(cli.l1_batch.map(L1BatchNumber)).await?;
+
+    if report.is_valid() {
+        tracing::info!(
+            "Snapshot for L1 batch #{} is valid: {} storage logs in {} chunks, {} factory deps",
+            report.l1_batch_number,
+            report.storage_log_count,
+            report.chunk_count,
+            report.factory_dep_count
+        );
+        Ok(())
+    } else {
+        for problem in &report.problems {
+            tracing::error!("{problem}");
+        }
+        anyhow::bail!(
+            "snapshot for L1 batch #{} is invalid: {} problem(s) found",
+            report.l1_batch_number,
+            report.problems.len()
+        )
+    }
+}
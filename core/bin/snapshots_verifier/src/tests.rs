@@ -0,0 +1,236 @@
+//! Tests for the snapshot verifier.
+
+use std::sync::Arc;
+
+use assert_matches::assert_matches;
+use async_trait::async_trait;
+use tempfile::TempDir;
+use zksync_merkle_tree::{recovery::MerkleTreeRecovery, PatchSet, TreeEntry};
+use zksync_object_store::{Bucket, MockObjectStore, ObjectStore, StoredObject};
+use zksync_types::{
+    block::L1BatchTreeData,
+    snapshots::{
+        SnapshotFactoryDependencies, SnapshotFactoryDependency, SnapshotHeader, SnapshotStorageLog,
+        SnapshotStorageLogsChunk, SnapshotStorageLogsChunkMetadata, SnapshotStorageLogsStorageKey,
+        SnapshotVersion,
+    },
+    web3::Bytes,
+    AccountTreeId, Address, L1BatchNumber, L2BlockNumber, StorageKey, H256,
+};
+use zksync_utils::h256_to_u256;
+use zksync_web3_decl::error::EnrichedClientResult;
+
+use crate::verifier::{SnapshotProblem, SnapshotVerifier, VerifierMainNodeClient};
+
+const L1_BATCH_NUMBER: L1BatchNumber = L1BatchNumber(10);
+const CHUNK_COUNT: u64 = 3;
+
+#[derive(Debug)]
+struct MockMainNodeClient {
+    header: SnapshotHeader,
+    tree_data: L1BatchTreeData,
+}
+
+#[async_trait]
+impl VerifierMainNodeClient for MockMainNodeClient {
+    async fn fetch_newest_snapshot_l1_batch_number(
+        &self,
+    ) -> EnrichedClientResult<Option<L1BatchNumber>> {
+        Ok(Some(self.header.l1_batch_number))
+    }
+
+    async fn fetch_snapshot(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<SnapshotHeader>> {
+        Ok((l1_batch_number == self.header.l1_batch_number).then(|| self.header.clone()))
+    }
+
+    async fn fetch_l1_batch_tree_data(
+        &self,
+        number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<L1BatchTreeData>> {
+        Ok((number == self.header.l1_batch_number).then_some(self.tree_data))
+    }
+}
+
+fn mock_storage_logs(count: u64) -> Vec<SnapshotStorageLog> {
+    (0..count)
+        .map(|i| SnapshotStorageLog {
+            key: StorageKey::new(
+                AccountTreeId::new(Address::repeat_byte(1)),
+                H256::from_low_u64_be(i),
+            ),
+            value: H256::from_low_u64_be(i + 1),
+            l1_batch_number_of_initial_write: L1BatchNumber(1),
+            enumeration_index: i + 1,
+        })
+        .collect()
+}
+
+fn expected_root_hash(storage_logs: &[SnapshotStorageLog]) -> H256 {
+    let mut recovery =
+        MerkleTreeRecovery::new(PatchSet::default(), L1_BATCH_NUMBER.0.into()).unwrap();
+    let entries = storage_logs
+        .iter()
+        .map(|log| {
+            let key = h256_to_u256(log.key.hashed_key());
+            TreeEntry::new(key, log.enumeration_index, log.value)
+        })
+        .collect();
+    recovery.extend_random(entries).unwrap();
+    recovery.root_hash()
+}
+
+async fn prepare_snapshot(
+    storage_logs: &[SnapshotStorageLog],
+) -> (Arc<dyn ObjectStore>, MockMainNodeClient) {
+    let object_store = MockObjectStore::arc();
+    let factory_deps = SnapshotFactoryDependencies {
+        factory_deps: vec![SnapshotFactoryDependency {
+            bytecode: Bytes(vec![0; 32]),
+        }],
+    };
+    object_store
+        .put(L1_BATCH_NUMBER, &factory_deps)
+        .await
+        .unwrap();
+
+    let chunk_size = storage_logs.len().div_ceil(CHUNK_COUNT as usize);
+    for (chunk_id, chunk) in storage_logs.chunks(chunk_size).enumerate() {
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: L1_BATCH_NUMBER,
+            chunk_id: chunk_id as u64,
+        };
+        let chunk = SnapshotStorageLogsChunk {
+            storage_logs: chunk.to_vec(),
+        };
+        object_store.put(key, &chunk).await.unwrap();
+    }
+
+    let header = SnapshotHeader {
+        version: SnapshotVersion::Version0.into(),
+        l1_batch_number: L1_BATCH_NUMBER,
+        l2_block_number: L2BlockNumber(20),
+        storage_logs_chunks: (0..CHUNK_COUNT)
+            .map(|chunk_id| SnapshotStorageLogsChunkMetadata {
+                chunk_id,
+                filepath: format!("chunk{chunk_id}"),
+            })
+            .collect(),
+        factory_deps_filepath: "factory_deps".to_owned(),
+    };
+    let client = MockMainNodeClient {
+        header,
+        tree_data: L1BatchTreeData {
+            hash: expected_root_hash(storage_logs),
+            rollup_last_leaf_index: storage_logs.len() as u64 + 1,
+        },
+    };
+    (object_store, client)
+}
+
+#[tokio::test]
+async fn verifying_valid_snapshot() {
+    let temp_dir = TempDir::new().unwrap();
+    let storage_logs = mock_storage_logs(100);
+    let (blob_store, client) = prepare_snapshot(&storage_logs).await;
+    let verifier = SnapshotVerifier {
+        blob_store,
+        main_node_client: Box::new(client),
+        db_path: temp_dir.path().join("tree"),
+    };
+
+    let report = verifier.verify(None).await.unwrap();
+    assert!(report.is_valid(), "{report:?}");
+    assert_eq!(report.l1_batch_number, L1_BATCH_NUMBER);
+    assert_eq!(report.chunk_count, CHUNK_COUNT);
+    assert_eq!(report.storage_log_count, 100);
+    assert_eq!(report.factory_dep_count, 1);
+}
+
+#[tokio::test]
+async fn reporting_missing_and_corrupt_chunks() {
+    let temp_dir = TempDir::new().unwrap();
+    let storage_logs = mock_storage_logs(100);
+    let (blob_store, client) = prepare_snapshot(&storage_logs).await;
+    let missing_key = SnapshotStorageLogsStorageKey {
+        l1_batch_number: L1_BATCH_NUMBER,
+        chunk_id: 0,
+    };
+    blob_store
+        .remove::<SnapshotStorageLogsChunk>(missing_key)
+        .await
+        .unwrap();
+    let corrupt_key = SnapshotStorageLogsStorageKey {
+        l1_batch_number: L1_BATCH_NUMBER,
+        chunk_id: 2,
+    };
+    blob_store
+        .put_raw(
+            Bucket::StorageSnapshot,
+            &SnapshotStorageLogsChunk::encode_key(corrupt_key),
+            vec![1, 2, 3],
+        )
+        .await
+        .unwrap();
+
+    let verifier = SnapshotVerifier {
+        blob_store,
+        main_node_client: Box::new(client),
+        db_path: temp_dir.path().join("tree"),
+    };
+    let report = verifier.verify(Some(L1_BATCH_NUMBER)).await.unwrap();
+    assert_eq!(report.problems.len(), 2, "{report:?}");
+    assert_eq!(report.problems[0], SnapshotProblem::MissingChunk(0));
+    assert_matches!(
+        &report.problems[1],
+        SnapshotProblem::CorruptChunk { chunk_id: 2, .. }
+    );
+}
+
+#[tokio::test]
+async fn reporting_root_hash_mismatch() {
+    let temp_dir = TempDir::new().unwrap();
+    let storage_logs = mock_storage_logs(100);
+    let (blob_store, mut client) = prepare_snapshot(&storage_logs).await;
+    let actual_root_hash = client.tree_data.hash;
+    client.tree_data.hash = H256::repeat_byte(0xff);
+
+    let verifier = SnapshotVerifier {
+        blob_store,
+        main_node_client: Box::new(client),
+        db_path: temp_dir.path().join("tree"),
+    };
+    let report = verifier.verify(None).await.unwrap();
+    assert_eq!(
+        report.problems,
+        [SnapshotProblem::RootHashMismatch {
+            expected: H256::repeat_byte(0xff),
+            actual: actual_root_hash,
+        }]
+    );
+}
+
+#[tokio::test]
+async fn reporting_leaf_count_mismatch_with_main_node() {
+    let temp_dir = TempDir::new().unwrap();
+    let storage_logs = mock_storage_logs(100);
+    let (blob_store, mut client) = prepare_snapshot(&storage_logs).await;
+    // The snapshot is internally consistent, but the main node reports a different number of tree leaves.
+    client.tree_data.rollup_last_leaf_index = 111;
+
+    let verifier = SnapshotVerifier {
+        blob_store,
+        main_node_client: Box::new(client),
+        db_path: temp_dir.path().join("tree"),
+    };
+    let report = verifier.verify(None).await.unwrap();
+    assert_eq!(
+        report.problems,
+        [SnapshotProblem::LeafCountMismatch {
+            expected: 110,
+            actual: 100,
+        }]
+    );
+}
@@ -0,0 +1,373 @@
+//! Snapshot verification logic.
+
+use std::{fmt, path::PathBuf, sync::Arc, time::Instant};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use zksync_merkle_tree::{
+    domain::ZkSyncTreeReader, recovery::MerkleTreeRecovery, RocksDBWrapper, TreeEntry,
+};
+use zksync_object_store::{ObjectStore, ObjectStoreError};
+use zksync_types::{
+    block::L1BatchTreeData,
+    snapshots::{
+        SnapshotFactoryDependencies, SnapshotHeader, SnapshotStorageLog, SnapshotStorageLogsChunk,
+        SnapshotStorageLogsStorageKey, SnapshotVersion,
+    },
+    L1BatchNumber, H256,
+};
+use zksync_utils::{bytecode::validate_bytecode, h256_to_u256};
+use zksync_web3_decl::{
+    client::{DynClient, L2},
+    error::{ClientRpcContext, EnrichedClientResult},
+    namespaces::{SnapshotsNamespaceClient, ZksNamespaceClient},
+};
+
+/// Main node API used by the [`SnapshotVerifier`].
+#[async_trait]
+pub(crate) trait VerifierMainNodeClient: fmt::Debug + Send + Sync {
+    async fn fetch_newest_snapshot_l1_batch_number(
+        &self,
+    ) -> EnrichedClientResult<Option<L1BatchNumber>>;
+
+    async fn fetch_snapshot(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<SnapshotHeader>>;
+
+    /// Fetches the root hash and the last leaf index of the Merkle tree for the specified L1 batch.
+    async fn fetch_l1_batch_tree_data(
+        &self,
+        number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<L1BatchTreeData>>;
+}
+
+#[async_trait]
+impl VerifierMainNodeClient for Box<DynClient<L2>> {
+    async fn fetch_newest_snapshot_l1_batch_number(
+        &self,
+    ) -> EnrichedClientResult<Option<L1BatchNumber>> {
+        let snapshots = self
+            .get_all_snapshots()
+            .rpc_context("get_all_snapshots")
+            .await?;
+        Ok(snapshots.snapshots_l1_batch_numbers.first().copied())
+    }
+
+    async fn fetch_snapshot(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<SnapshotHeader>> {
+        self.get_snapshot_by_l1_batch_number(l1_batch_number)
+            .rpc_context("get_snapshot_by_l1_batch_number")
+            .with_arg("number", &l1_batch_number)
+            .await
+    }
+
+    async fn fetch_l1_batch_tree_data(
+        &self,
+        number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<L1BatchTreeData>> {
+        let details = self
+            .get_l1_batch_details(number)
+            .rpc_context("get_l1_batch_details")
+            .with_arg("number", &number)
+            .await?;
+        Ok(details.and_then(|details| {
+            Some(L1BatchTreeData {
+                hash: details.base.root_hash?,
+                rollup_last_leaf_index: details.rollup_last_leaf_index?,
+            })
+        }))
+    }
+}
+
+/// Problem found in a snapshot.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SnapshotProblem {
+    MissingFactoryDeps,
+    CorruptFactoryDeps(String),
+    MissingChunk(u64),
+    CorruptChunk { chunk_id: u64, reason: String },
+    LeafCountMismatch { expected: u64, actual: u64 },
+    RootHashMismatch { expected: H256, actual: H256 },
+}
+
+impl fmt::Display for SnapshotProblem {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingFactoryDeps => formatter.write_str("factory deps are missing"),
+            Self::CorruptFactoryDeps(reason) => {
+                write!(formatter, "factory deps are corrupt: {reason}")
+            }
+            Self::MissingChunk(chunk_id) => {
+                write!(formatter, "storage logs chunk {chunk_id} is missing")
+            }
+            Self::CorruptChunk { chunk_id, reason } => {
+                write!(
+                    formatter,
+                    "storage logs chunk {chunk_id} is corrupt: {reason}"
+                )
+            }
+            Self::LeafCountMismatch { expected, actual } => write!(
+                formatter,
+                "recovered tree has {actual} leaves, while {expected} were expected"
+            ),
+            Self::RootHashMismatch { expected, actual } => write!(
+                formatter,
+                "recovered tree root hash {actual:?} differs from the expected {expected:?}"
+            ),
+        }
+    }
+}
+
+/// Report produced by the [`SnapshotVerifier`].
+#[derive(Debug)]
+pub(crate) struct VerificationReport {
+    pub l1_batch_number: L1BatchNumber,
+    pub chunk_count: u64,
+    pub storage_log_count: u64,
+    pub factory_dep_count: usize,
+    pub problems: Vec<SnapshotProblem>,
+}
+
+impl VerificationReport {
+    pub fn is_valid(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Verifies a snapshot stored in an object store by rebuilding the Merkle tree from its storage logs
+/// and comparing the tree with the reference data from the main node.
+#[derive(Debug)]
+pub(crate) struct SnapshotVerifier {
+    pub blob_store: Arc<dyn ObjectStore>,
+    pub main_node_client: Box<dyn VerifierMainNodeClient>,
+    /// Path to an empty directory to hold the temporary Merkle tree RocksDB.
+    pub db_path: PathBuf,
+}
+
+impl SnapshotVerifier {
+    pub async fn verify(
+        &self,
+        l1_batch_number: Option<L1BatchNumber>,
+    ) -> anyhow::Result<VerificationReport> {
+        let l1_batch_number = match l1_batch_number {
+            Some(number) => number,
+            None => self
+                .main_node_client
+                .fetch_newest_snapshot_l1_batch_number()
+                .await?
+                .context("no snapshots on main node")?,
+        };
+        let header = self
+            .main_node_client
+            .fetch_snapshot(l1_batch_number)
+            .await?
+            .with_context(|| {
+                format!("snapshot for L1 batch #{l1_batch_number} is not present on main node")
+            })?;
+        let version = SnapshotVersion::try_from(header.version)
+            .with_context(|| format!("unrecognized snapshot version: {}", header.version))?;
+        let expected_tree_data = self
+            .main_node_client
+            .fetch_l1_batch_tree_data(l1_batch_number)
+            .await?
+            .with_context(|| {
+                format!("tree data for L1 batch #{l1_batch_number} is not known to main node")
+            })?;
+        let expected_root_hash = expected_tree_data.hash;
+        // The last leaf index is the enumeration index for the next inserted leaf.
+        let expected_leaf_count = expected_tree_data
+            .rollup_last_leaf_index
+            .checked_sub(1)
+            .context("main node returned zero last leaf index")?;
+        tracing::info!(
+            "Verifying snapshot for L1 batch #{l1_batch_number} (version {version:?}, {} storage log chunks); \
+             expected root hash: {expected_root_hash:?}, expected leaf count: {expected_leaf_count}",
+            header.storage_logs_chunks.len()
+        );
+
+        let mut report = VerificationReport {
+            l1_batch_number,
+            chunk_count: header.storage_logs_chunks.len() as u64,
+            storage_log_count: 0,
+            factory_dep_count: 0,
+            problems: vec![],
+        };
+        self.verify_factory_deps(&mut report).await?;
+
+        let db = RocksDBWrapper::new(&self.db_path).with_context(|| {
+            format!(
+                "failed initializing temporary Merkle tree RocksDB at {}",
+                self.db_path.display()
+            )
+        })?;
+        let mut recovery = MerkleTreeRecovery::new(db, l1_batch_number.0.into())?;
+        let mut max_enumeration_index = 0;
+        for chunk in &header.storage_logs_chunks {
+            let chunk_id = chunk.chunk_id;
+            let Some(storage_logs) = self.load_chunk(&mut report, chunk_id).await? else {
+                continue;
+            };
+            if let Err(reason) = validate_storage_logs(&storage_logs, l1_batch_number) {
+                report
+                    .problems
+                    .push(SnapshotProblem::CorruptChunk { chunk_id, reason });
+                continue;
+            }
+
+            report.storage_log_count += storage_logs.len() as u64;
+            max_enumeration_index = storage_logs
+                .iter()
+                .map(|log| log.enumeration_index)
+                .fold(max_enumeration_index, u64::max);
+            let entries = storage_logs
+                .iter()
+                .map(|log| {
+                    let key = h256_to_u256(log.key.hashed_key());
+                    TreeEntry::new(key, log.enumeration_index, log.value)
+                })
+                .collect();
+            recovery = tokio::task::spawn_blocking(move || {
+                recovery.extend_random(entries)?;
+                anyhow::Ok(recovery)
+            })
+            .await
+            .context("panicked while extending tree")??;
+            tracing::info!(
+                "Processed chunk {chunk_id} with {} storage logs",
+                storage_logs.len()
+            );
+        }
+
+        let has_invalid_chunks = report.problems.iter().any(|problem| {
+            matches!(
+                problem,
+                SnapshotProblem::MissingChunk(_) | SnapshotProblem::CorruptChunk { .. }
+            )
+        });
+        if has_invalid_chunks {
+            tracing::warn!("Some snapshot chunks are invalid; skipping tree verification");
+            return Ok(report);
+        }
+
+        let started_at = Instant::now();
+        let (actual_root_hash, leaf_count) = tokio::task::spawn_blocking(move || {
+            let db = recovery.finalize()?;
+            let tree = ZkSyncTreeReader::new(db)?;
+            tree.root_info(l1_batch_number)
+                .context("recovered tree has no root")
+        })
+        .await
+        .context("panicked while finalizing tree")??;
+        tracing::info!("Finalized tree in {:?}", started_at.elapsed());
+
+        // Enumeration indices must form a contiguous range starting from 1, and each index must correspond
+        // to a distinct key. Thus, duplicate keys or missing entries will lead to a leaf count mismatch.
+        // The leaf count must also match the main node's tree.
+        for expected in [
+            expected_leaf_count,
+            report.storage_log_count,
+            max_enumeration_index,
+        ] {
+            if leaf_count != expected {
+                report.problems.push(SnapshotProblem::LeafCountMismatch {
+                    expected,
+                    actual: leaf_count,
+                });
+                break;
+            }
+        }
+        if actual_root_hash != expected_root_hash {
+            report.problems.push(SnapshotProblem::RootHashMismatch {
+                expected: expected_root_hash,
+                actual: actual_root_hash,
+            });
+        }
+        Ok(report)
+    }
+
+    async fn verify_factory_deps(&self, report: &mut VerificationReport) -> anyhow::Result<()> {
+        let l1_batch_number = report.l1_batch_number;
+        let factory_deps: SnapshotFactoryDependencies =
+            match self.blob_store.get(l1_batch_number).await {
+                Ok(deps) => deps,
+                Err(ObjectStoreError::KeyNotFound(_)) => {
+                    report.problems.push(SnapshotProblem::MissingFactoryDeps);
+                    return Ok(());
+                }
+                Err(ObjectStoreError::Serialization(err)) => {
+                    let problem = SnapshotProblem::CorruptFactoryDeps(err.to_string());
+                    report.problems.push(problem);
+                    return Ok(());
+                }
+                Err(err) => {
+                    return Err(anyhow::Error::from(err).context("failed fetching factory deps"))
+                }
+            };
+
+        for (i, dep) in factory_deps.factory_deps.iter().enumerate() {
+            if let Err(err) = validate_bytecode(&dep.bytecode.0) {
+                let problem = SnapshotProblem::CorruptFactoryDeps(format!(
+                    "factory dep #{i} is invalid: {err}"
+                ));
+                report.problems.push(problem);
+                return Ok(());
+            }
+        }
+        report.factory_dep_count = factory_deps.factory_deps.len();
+        tracing::info!("Verified {} factory deps", report.factory_dep_count);
+        Ok(())
+    }
+
+    async fn load_chunk(
+        &self,
+        report: &mut VerificationReport,
+        chunk_id: u64,
+    ) -> anyhow::Result<Option<Vec<SnapshotStorageLog>>> {
+        let storage_key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: report.l1_batch_number,
+            chunk_id,
+        };
+        match self
+            .blob_store
+            .get::<SnapshotStorageLogsChunk>(storage_key)
+            .await
+        {
+            Ok(chunk) => Ok(Some(chunk.storage_logs)),
+            Err(ObjectStoreError::KeyNotFound(_)) => {
+                report
+                    .problems
+                    .push(SnapshotProblem::MissingChunk(chunk_id));
+                Ok(None)
+            }
+            Err(ObjectStoreError::Serialization(err)) => {
+                report.problems.push(SnapshotProblem::CorruptChunk {
+                    chunk_id,
+                    reason: err.to_string(),
+                });
+                Ok(None)
+            }
+            Err(err) => Err(anyhow::Error::from(err)
+                .context(format!("failed fetching storage logs {storage_key:?}"))),
+        }
+    }
+}
+
+fn validate_storage_logs(
+    storage_logs: &[SnapshotStorageLog],
+    l1_batch_number: L1BatchNumber,
+) -> Result<(), String> {
+    for log in storage_logs {
+        if log.enumeration_index == 0 {
+            return Err(format!("storage log with zero enumeration index: {log:?}"));
+        }
+        if log.l1_batch_number_of_initial_write > l1_batch_number {
+            return Err(format!(
+                "storage log with initial write from the future: {log:?}"
+            ));
+        }
+    }
+    Ok(())
+}
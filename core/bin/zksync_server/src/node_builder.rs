@@ -1,6 +1,8 @@
 //! This module provides a "builder" for the main node,
 //! as well as an interface to run the node with the specified components.
 
+use std::path::Path;
+
 use anyhow::Context;
 use zksync_config::{
     configs::{consensus::ConsensusConfig, wallets::Wallets, GeneralConfig, Secrets},
@@ -24,7 +26,7 @@ use zksync_node_framework::{
         house_keeper::HouseKeeperLayer,
         l1_batch_commitment_mode_validation::L1BatchCommitmentModeValidationLayer,
         l1_gas::SequencerL1GasLayer,
-        metadata_calculator::MetadataCalculatorLayer,
+        metadata_calculator::{MetadataCalculatorLayer, RocksdbCheckpointConfig},
         object_store::ObjectStoreLayer,
         pk_signing_eth_client::PKSigningEthClientLayer,
         pools_layer::PoolsLayerBuilder,
@@ -33,6 +35,7 @@ use zksync_node_framework::{
         proof_data_handler::ProofDataHandlerLayer,
         query_eth_client::QueryEthClientLayer,
         sigint::SigintHandlerLayer,
+        snapshots_creator::SnapshotsCreatorLayer,
         state_keeper::{
             main_batch_executor::MainBatchExecutorLayer, mempool_io::MempoolIOLayer,
             output_handler::OutputHandlerLayer, RocksdbStorageOptions, StateKeeperLayer,
@@ -172,7 +175,8 @@ impl MainNodeBuilder {
     }
 
     fn add_metadata_calculator_layer(mut self, with_tree_api: bool) -> anyhow::Result<Self> {
-        let merkle_tree_env_config = try_load_config!(self.configs.db_config).merkle_tree;
+        let db_config = try_load_config!(self.configs.db_config);
+        let merkle_tree_env_config = db_config.merkle_tree;
         let operations_manager_env_config =
             try_load_config!(self.configs.operations_manager_config);
         let state_keeper_env_config = try_load_config!(self.configs.state_keeper_config);
@@ -186,6 +190,28 @@ impl MainNodeBuilder {
             let merkle_tree_api_config = try_load_config!(self.configs.api_config).merkle_tree;
             layer = layer.with_tree_api_config(merkle_tree_api_config);
         }
+        if let Some(interval) = db_config.experimental.rocksdb_checkpoints_interval() {
+            let checkpoint_config = RocksdbCheckpointConfig::for_db_path(
+                Path::new(&merkle_tree_env_config.path),
+                interval,
+            );
+            let object_store_config = try_load_config!(self.configs.core_object_store);
+            layer = layer.with_checkpoint_config(checkpoint_config, object_store_config);
+        }
+        // The main node doesn't prune Postgres, so the tree is only pruned if tree retention is explicitly configured.
+        if let Some(pruning_config) = &self.configs.pruning {
+            let tree_retained_l1_batches = pruning_config
+                .tree_retained_l1_batches()
+                .context("invalid pruning config")?;
+            if tree_retained_l1_batches.is_some() {
+                layer = layer
+                    .with_pruning_config(pruning_config.removal_delay())
+                    .with_tree_retention(
+                        tree_retained_l1_batches,
+                        pruning_config.tree_retain_for_snapshots.unwrap_or(true),
+                    );
+            }
+        }
         self.node.add_layer(layer);
         Ok(self)
     }
@@ -219,8 +245,17 @@ impl MainNodeBuilder {
                 .state_keeper_db_block_cache_capacity(),
             max_open_files: db_config.experimental.state_keeper_db_max_open_files,
         };
-        let state_keeper_layer =
-            StateKeeperLayer::new(db_config.state_keeper_db_path, rocksdb_options);
+        let mut state_keeper_layer =
+            StateKeeperLayer::new(db_config.state_keeper_db_path.clone(), rocksdb_options);
+        if let Some(interval) = db_config.experimental.rocksdb_checkpoints_interval() {
+            let checkpoint_config = RocksdbCheckpointConfig::for_db_path(
+                Path::new(&db_config.state_keeper_db_path),
+                interval,
+            );
+            let object_store_config = try_load_config!(self.configs.core_object_store);
+            state_keeper_layer =
+                state_keeper_layer.with_checkpoint_config(checkpoint_config, object_store_config);
+        }
         self.node
             .add_layer(persistence_layer)
             .add_layer(mempool_io_layer)
@@ -263,7 +298,7 @@ impl MainNodeBuilder {
 
         // On main node we always use master pool sink.
         self.node.add_layer(TxSinkLayer::MasterPoolSink);
-        self.node.add_layer(TxSenderLayer::new(
+        let tx_sender_layer = TxSenderLayer::new(
             TxSenderConfig::new(
                 &sk_config,
                 &rpc_config,
@@ -275,7 +310,9 @@ impl MainNodeBuilder {
             postgres_storage_caches_config,
             rpc_config.vm_concurrency_limit(),
             ApiContracts::load_from_disk_blocking(), // TODO (BFT-138): Allow to dynamically reload API contracts
-        ));
+        )
+        .with_vm_storage_from_merkle_tree(rpc_config.vm_storage_from_merkle_tree);
+        self.node.add_layer(tx_sender_layer);
         Ok(self)
     }
 
@@ -455,6 +492,13 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_snapshots_creator_layer(mut self) -> anyhow::Result<Self> {
+        let config = try_load_config!(self.configs.snapshot_creator);
+        self.node.add_layer(SnapshotsCreatorLayer::new(config));
+
+        Ok(self)
+    }
+
     pub fn build(mut self, mut components: Vec<Component>) -> anyhow::Result<ZkStackService> {
         // Add "base" layers (resources and helper tasks).
         self = self
@@ -542,6 +586,9 @@ impl MainNodeBuilder {
                 Component::VmRunnerProtectiveReads => {
                     self = self.add_vm_runner_protective_reads_layer()?;
                 }
+                Component::SnapshotsCreator => {
+                    self = self.add_snapshots_creator_layer()?;
+                }
             }
         }
         Ok(self.node.build()?)
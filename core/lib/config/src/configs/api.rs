@@ -220,6 +220,10 @@ pub struct Web3JsonRpcConfig {
     /// (hundreds or thousands RPS).
     #[serde(default)]
     pub extended_api_tracing: bool,
+    /// Whether to read VM storage for `eth_call`-like requests from the Merkle tree instead of Postgres
+    /// when the tree retains the necessary state. Only has effect if the Merkle tree is run in the same process.
+    #[serde(default)]
+    pub vm_storage_from_merkle_tree: bool,
 }
 
 impl Web3JsonRpcConfig {
@@ -260,6 +264,7 @@ impl Web3JsonRpcConfig {
             whitelisted_tokens_for_aa: Default::default(),
             api_namespaces: None,
             extended_api_tracing: false,
+            vm_storage_from_merkle_tree: false,
         }
     }
 
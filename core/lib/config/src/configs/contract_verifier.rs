@@ -16,6 +16,21 @@ pub struct ContractVerifierConfig {
     pub threads_per_server: Option<u16>,
     pub port: u16,
     pub url: String,
+    /// Directory with cached compiler binaries. If not specified, the `etc` directory in the workspace is used.
+    pub compilers_dir: Option<String>,
+    /// Mirror to download missing compiler binaries from: either an HTTP(S) URL or a local directory.
+    /// If not specified, only binaries already present in `compilers_dir` can be used.
+    pub compilers_mirror: Option<String>,
+    /// Path to a JSON file with pinned SHA-256 checksums of compiler binaries, keyed by the compiler name
+    /// and version (e.g., `{ "zksolc": { "v1.5.0": "<hex checksum>" } }`). Required if `compilers_mirror`
+    /// is specified; only binaries with a pinned checksum are downloaded from the mirror.
+    pub compilers_checksums: Option<String>,
+    /// Maximum number of compilations running concurrently.
+    pub max_concurrent_compilations: Option<usize>,
+    /// Max time of a single `zksolc` compilation (in s). If not specified, `compilation_timeout` is used.
+    pub zksolc_compilation_timeout: Option<u64>,
+    /// Max time of a single `zkvyper` compilation (in s). If not specified, `compilation_timeout` is used.
+    pub zkvyper_compilation_timeout: Option<u64>,
 }
 
 impl ContractVerifierConfig {
@@ -23,6 +38,20 @@ impl ContractVerifierConfig {
         Duration::from_secs(self.compilation_timeout)
     }
 
+    pub fn zksolc_compilation_timeout(&self) -> Duration {
+        Duration::from_secs(
+            self.zksolc_compilation_timeout
+                .unwrap_or(self.compilation_timeout),
+        )
+    }
+
+    pub fn zkvyper_compilation_timeout(&self) -> Duration {
+        Duration::from_secs(
+            self.zkvyper_compilation_timeout
+                .unwrap_or(self.compilation_timeout),
+        )
+    }
+
     pub fn polling_interval(&self) -> Duration {
         Duration::from_millis(self.polling_interval.unwrap_or(1000))
     }
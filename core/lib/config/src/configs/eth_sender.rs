@@ -40,6 +40,10 @@ impl EthConfig {
                 l1_batch_min_age_before_execute_seconds: None,
                 max_acceptable_priority_fee_in_gwei: 100000000000,
                 pubdata_sending_mode: PubdataSendingMode::Calldata,
+                fee_escalation_strategy: FeeEscalationStrategy::Minimal,
+                fee_escalation_bump_percent: SenderConfig::default_fee_escalation_bump_percent(),
+                fee_escalation_max_multiplier: None,
+                max_l1_spend_per_batch_gwei: None,
             }),
             gas_adjuster: Some(GasAdjusterConfig {
                 default_priority_fee_per_gas: 1000000000,
@@ -83,6 +87,25 @@ pub enum PubdataSendingMode {
     Blobs,
 }
 
+/// Strategy used to escalate fees of L1 transactions that are resent because they got stuck in the mempool.
+/// Regardless of the strategy, fees are never lower than the current network fees and are always bumped
+/// enough for L1 nodes to accept the replacement transaction.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum FeeEscalationStrategy {
+    /// Fees are bumped by the minimum amount required to replace the transaction.
+    #[default]
+    Minimal,
+    /// Fees grow by `fee_escalation_bump_percent` of the first attempt fees on each resend.
+    Linear,
+    /// Fees are multiplied by `1 + fee_escalation_bump_percent / 100` on each resend,
+    /// up to `fee_escalation_max_multiplier` times the first attempt fees.
+    Exponential,
+    /// Fees grow quadratically with the fraction of the operation deadline (`aggregated_block_*_deadline`)
+    /// elapsed since the transaction was created, reaching `fee_escalation_max_multiplier` times
+    /// the first attempt fees at the deadline.
+    DeadlineAware,
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct SenderConfig {
     pub aggregated_proof_sizes: Vec<usize>,
@@ -116,6 +139,18 @@ pub struct SenderConfig {
 
     /// The mode in which we send pubdata, either Calldata or Blobs
     pub pubdata_sending_mode: PubdataSendingMode,
+
+    /// Strategy used to escalate fees of resent transactions.
+    #[serde(default)]
+    pub fee_escalation_strategy: FeeEscalationStrategy,
+    /// Percentage by which fees grow on each resend for `Linear` and `Exponential` fee escalation strategies.
+    #[serde(default = "SenderConfig::default_fee_escalation_bump_percent")]
+    pub fee_escalation_bump_percent: u64,
+    /// Max fees relative to the first sending attempt for `Exponential` and `DeadlineAware` fee escalation strategies.
+    pub fee_escalation_max_multiplier: Option<f64>,
+    /// Max L1 spend per L1 batch (in gwei) for a single transaction attempt, estimated using the worst-case fees
+    /// and the gas predicted for the transaction. Transactions exceeding the budget are not sent until L1 fees go down.
+    pub max_l1_spend_per_batch_gwei: Option<u64>,
 }
 
 impl SenderConfig {
@@ -129,6 +164,15 @@ impl SenderConfig {
         Duration::from_secs(self.aggregate_tx_poll_period)
     }
 
+    pub const fn default_fee_escalation_bump_percent() -> u64 {
+        20
+    }
+
+    /// Returns the max fee multiplier for fee escalation, defaulting to 3.
+    pub fn fee_escalation_max_multiplier(&self) -> f64 {
+        self.fee_escalation_max_multiplier.unwrap_or(3.0)
+    }
+
     // Don't load private key, if it's not required.
     #[deprecated]
     pub fn private_key(&self) -> anyhow::Result<Option<K256PrivateKey>> {
@@ -1,6 +1,6 @@
 //! Experimental part of configuration.
 
-use std::num::NonZeroU32;
+use std::{num::NonZeroU32, time::Duration};
 
 use serde::Deserialize;
 
@@ -27,6 +27,9 @@ pub struct ExperimentalDBConfig {
     /// correspondingly; otherwise, RocksDB performance can significantly degrade.
     #[serde(default)]
     pub include_indices_and_filters_in_block_cache: bool,
+    /// Interval between uploading checkpoints of the Merkle tree and state keeper cache RocksDB instances
+    /// to the object store. If not set, checkpoints are neither uploaded nor used to bootstrap the instances.
+    pub rocksdb_checkpoints_interval_sec: Option<u64>,
 }
 
 impl Default for ExperimentalDBConfig {
@@ -39,6 +42,7 @@ impl Default for ExperimentalDBConfig {
                 Self::default_protective_reads_persistence_enabled(),
             processing_delay_ms: Self::default_merkle_tree_processing_delay_ms(),
             include_indices_and_filters_in_block_cache: false,
+            rocksdb_checkpoints_interval_sec: None,
         }
     }
 }
@@ -59,4 +63,9 @@ impl ExperimentalDBConfig {
     const fn default_merkle_tree_processing_delay_ms() -> u64 {
         100
     }
+
+    pub fn rocksdb_checkpoints_interval(&self) -> Option<Duration> {
+        self.rocksdb_checkpoints_interval_sec
+            .map(Duration::from_secs)
+    }
 }
@@ -1,7 +1,12 @@
-use std::num::NonZeroU64;
+use std::{
+    num::{NonZeroU32, NonZeroU64},
+    time::Duration,
+};
 
 use serde::Deserialize;
 
+use crate::ObjectStoreConfig;
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct PruningConfig {
     pub enabled: bool,
@@ -16,4 +21,58 @@ pub struct PruningConfig {
     /// the retention period greater than that implicitly imposed by other criteria (e.g., 7 or 30 days).
     /// If set to 0, L1 batches will not be retained based on their timestamp. The default value is 1 hour.
     pub data_retention_sec: Option<u64>,
+    /// If set, L1 batches will only be pruned while the estimated Postgres database size (in megabytes) exceeds this budget.
+    /// Other pruning criteria still apply; e.g., `data_retention_sec` effectively becomes the minimum retention period.
+    pub db_size_budget_mb: Option<u64>,
+    /// If set, events and L2-to-L1 logs will be retained for at least this period (in seconds) since the L1 batch timestamp,
+    /// even if the rest of L1 batch data is pruned. Events are never retained for a shorter period than transactions.
+    pub events_retention_sec: Option<u64>,
+    /// If set, transactions and their receipts will be retained for at least this period (in seconds) since the L1 batch timestamp,
+    /// even if the rest of L1 batch data is pruned.
+    pub transactions_retention_sec: Option<u64>,
+    /// If set, L2 blocks, transactions, receipts and events will be archived to this object store before being pruned.
+    /// The API server will serve archived data for pruned blocks.
+    pub archive_object_store: Option<ObjectStoreConfig>,
+    /// If set, the Merkle tree will retain at least this number of its latest versions (= L1 batches) so that
+    /// proofs can be served for them, independently of Postgres pruning. If not set, the tree is pruned following Postgres.
+    /// Must be at least [`Self::MIN_TREE_RETAINED_L1_BATCHES`].
+    pub tree_retained_l1_batches: Option<NonZeroU32>,
+    /// Whether the Merkle tree should retain versions for L1 batches of snapshots that are being created.
+    /// Snapshots holding back tree pruning for too long (10,000 L1 batches) are considered abandoned and are ignored.
+    /// The default value is `true`.
+    pub tree_retain_for_snapshots: Option<bool>,
+}
+
+impl PruningConfig {
+    const DEFAULT_REMOVAL_DELAY: Duration = Duration::from_secs(60);
+    /// Minimum allowed number of retained Merkle tree versions. Smaller values would make proofs unavailable
+    /// almost immediately after L1 batches are processed by the tree.
+    pub const MIN_TREE_RETAINED_L1_BATCHES: u32 = 10;
+
+    /// Checks that the number of retained Merkle tree versions is not too small.
+    pub fn check_tree_retained_l1_batches(
+        count: Option<NonZeroU32>,
+    ) -> anyhow::Result<Option<NonZeroU32>> {
+        if let Some(count) = count {
+            anyhow::ensure!(
+                count.get() >= Self::MIN_TREE_RETAINED_L1_BATCHES,
+                "number of retained Merkle tree versions ({count}) is less than the minimum allowed value ({})",
+                Self::MIN_TREE_RETAINED_L1_BATCHES
+            );
+        }
+        Ok(count)
+    }
+
+    /// Returns the validated number of retained Merkle tree versions.
+    pub fn tree_retained_l1_batches(&self) -> anyhow::Result<Option<NonZeroU32>> {
+        Self::check_tree_retained_l1_batches(self.tree_retained_l1_batches)
+    }
+
+    /// Returns the delta between soft- and hard-removing data from Postgres.
+    pub fn removal_delay(&self) -> Duration {
+        self.removal_delay_sec
+            .map_or(Self::DEFAULT_REMOVAL_DELAY, |delay| {
+                Duration::from_secs(delay.get())
+            })
+    }
 }
@@ -38,6 +38,14 @@ pub struct SnapshotRecoveryConfig {
     pub enabled: bool,
     /// L1 batch number of the snapshot to use during recovery. Specifying this parameter is mostly useful for testing.
     pub l1_batch: Option<L1BatchNumber>,
+    /// L1 batch number to recover to using differential snapshots on top of a full snapshot. If specified,
+    /// overrides `l1_batch`.
+    #[serde(default)]
+    pub diff_l1_batch: Option<L1BatchNumber>,
+    /// Path to a local snapshot archive. If specified, the node recovers from the archive instead of using
+    /// the main node API and the snapshot object store.
+    #[serde(default)]
+    pub archive_path: Option<String>,
     pub tree: TreeRecoveryConfig,
     pub postgres: PostgresRecoveryConfig,
     pub object_store: Option<ObjectStoreConfig>,
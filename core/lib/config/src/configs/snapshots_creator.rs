@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use serde::Deserialize;
 
 use crate::ObjectStoreConfig;
@@ -10,6 +12,48 @@ pub struct SnapshotsCreatorConfig {
     #[serde(default = "snapshots_creator_concurrent_queries_count")]
     pub concurrent_queries_count: u32,
     pub object_store: Option<ObjectStoreConfig>,
+    /// If set, the creator produces differential snapshots on top of the newest existing snapshot instead of
+    /// full snapshots. A full snapshot is still created if there are no complete snapshots yet, or if the newest
+    /// complete snapshot is at least `full_snapshot_l1_batch_interval` L1 batches old.
+    #[serde(default)]
+    pub diff_snapshots: bool,
+    /// Minimum number of L1 batches between consecutive full snapshots if `diff_snapshots` are enabled.
+    /// Differential snapshots older than the newest full snapshot are removed by the periodic snapshots creator.
+    #[serde(default = "SnapshotsCreatorConfig::default_full_snapshot_l1_batch_interval")]
+    pub full_snapshot_l1_batch_interval: u32,
+    /// Minimum number of L1 batches between consecutive snapshots created by the periodic snapshots creator.
+    /// Not used by one-off snapshot creation.
+    #[serde(default = "SnapshotsCreatorConfig::default_l1_batch_interval")]
+    pub l1_batch_interval: u32,
+    /// Number of newest complete full snapshots retained by the periodic snapshots creator. Older snapshots
+    /// are removed together with their object store data.
+    #[serde(default = "SnapshotsCreatorConfig::default_retained_snapshots_count")]
+    pub retained_snapshots_count: u32,
+    /// Polling interval for the periodic snapshots creator in milliseconds.
+    #[serde(default = "SnapshotsCreatorConfig::default_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+}
+
+impl SnapshotsCreatorConfig {
+    pub const fn default_l1_batch_interval() -> u32 {
+        1_000
+    }
+
+    pub const fn default_full_snapshot_l1_batch_interval() -> u32 {
+        10_000
+    }
+
+    pub const fn default_retained_snapshots_count() -> u32 {
+        3
+    }
+
+    pub const fn default_poll_interval_ms() -> u64 {
+        60_000
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms)
+    }
 }
 
 fn snapshots_creator_storage_logs_chunk_size_default() -> u64 {
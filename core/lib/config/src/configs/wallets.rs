@@ -1,4 +1,4 @@
-use zksync_basic_types::{Address, H160, H256};
+use zksync_basic_types::{url::SensitiveUrl, Address, H160, H256};
 use zksync_crypto_primitives::K256PrivateKey;
 
 #[derive(Debug, Clone)]
@@ -58,10 +58,61 @@ impl Wallet {
     }
 }
 
+/// Wallet with the private key managed by a remote signer (e.g., Web3Signer) accessible over HTTP JSON-RPC.
+#[derive(Debug, Clone)]
+pub struct RemoteSignerWallet {
+    address: Address,
+    url: SensitiveUrl,
+}
+
+impl RemoteSignerWallet {
+    pub fn new(address: Address, url: SensitiveUrl) -> Self {
+        Self { address, url }
+    }
+
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    pub fn url(&self) -> &SensitiveUrl {
+        &self.url
+    }
+}
+
+/// Wallet capable of signing L1 transactions.
+#[derive(Debug, Clone)]
+pub enum SignerWallet {
+    /// Private key is stored in the node secrets.
+    PrivateKey(Wallet),
+    /// Private key is managed by a remote signer.
+    Remote(RemoteSignerWallet),
+}
+
+impl SignerWallet {
+    pub fn address(&self) -> Address {
+        match self {
+            Self::PrivateKey(wallet) => wallet.address(),
+            Self::Remote(wallet) => wallet.address(),
+        }
+    }
+}
+
+impl From<Wallet> for SignerWallet {
+    fn from(wallet: Wallet) -> Self {
+        Self::PrivateKey(wallet)
+    }
+}
+
+impl From<RemoteSignerWallet> for SignerWallet {
+    fn from(wallet: RemoteSignerWallet) -> Self {
+        Self::Remote(wallet)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct EthSender {
-    pub operator: Wallet,
-    pub blob_operator: Option<Wallet>,
+    pub operator: SignerWallet,
+    pub blob_operator: Option<SignerWallet>,
 }
 
 #[derive(Debug, Clone)]
@@ -79,9 +130,13 @@ impl Wallets {
     pub fn for_tests() -> Wallets {
         Wallets {
             eth_sender: Some(EthSender {
-                operator: Wallet::from_private_key_bytes(H256::repeat_byte(0x1), None).unwrap(),
+                operator: Wallet::from_private_key_bytes(H256::repeat_byte(0x1), None)
+                    .unwrap()
+                    .into(),
                 blob_operator: Some(
-                    Wallet::from_private_key_bytes(H256::repeat_byte(0x2), None).unwrap(),
+                    Wallet::from_private_key_bytes(H256::repeat_byte(0x2), None)
+                        .unwrap()
+                        .into(),
                 ),
             }),
             state_keeper: Some(StateKeeper {
@@ -100,6 +100,7 @@ impl Distribution<configs::api::Web3JsonRpcConfig> for EncodeDist {
             api_namespaces: self
                 .sample_opt(|| self.sample_range(rng).map(|_| self.sample(rng)).collect()),
             extended_api_tracing: self.sample(rng),
+            vm_storage_from_merkle_tree: self.sample(rng),
         }
     }
 }
@@ -229,6 +230,12 @@ impl Distribution<configs::ContractVerifierConfig> for EncodeDist {
             threads_per_server: self.sample(rng),
             port: self.sample(rng),
             url: self.sample(rng),
+            compilers_dir: self.sample(rng),
+            compilers_mirror: self.sample(rng),
+            compilers_checksums: self.sample(rng),
+            max_concurrent_compilations: self.sample(rng),
+            zksolc_compilation_timeout: self.sample(rng),
+            zkvyper_compilation_timeout: self.sample(rng),
         }
     }
 }
@@ -287,6 +294,7 @@ impl Distribution<configs::ExperimentalDBConfig> for EncodeDist {
             protective_reads_persistence_enabled: self.sample(rng),
             processing_delay_ms: self.sample(rng),
             include_indices_and_filters_in_block_cache: self.sample(rng),
+            rocksdb_checkpoints_interval_sec: self.sample(rng),
         }
     }
 }
@@ -357,6 +365,18 @@ impl Distribution<configs::eth_sender::PubdataSendingMode> for EncodeDist {
     }
 }
 
+impl Distribution<configs::eth_sender::FeeEscalationStrategy> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::eth_sender::FeeEscalationStrategy {
+        type T = configs::eth_sender::FeeEscalationStrategy;
+        match rng.gen_range(0..4) {
+            0 => T::Minimal,
+            1 => T::Linear,
+            2 => T::Exponential,
+            _ => T::DeadlineAware,
+        }
+    }
+}
+
 impl Distribution<configs::eth_sender::SenderConfig> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::eth_sender::SenderConfig {
         configs::eth_sender::SenderConfig {
@@ -377,6 +397,10 @@ impl Distribution<configs::eth_sender::SenderConfig> for EncodeDist {
             l1_batch_min_age_before_execute_seconds: self.sample(rng),
             max_acceptable_priority_fee_in_gwei: self.sample(rng),
             pubdata_sending_mode: PubdataSendingMode::Calldata,
+            fee_escalation_strategy: self.sample(rng),
+            fee_escalation_bump_percent: self.sample(rng),
+            fee_escalation_max_multiplier: self.sample(rng),
+            max_l1_spend_per_batch_gwei: self.sample(rng),
         }
     }
 }
@@ -644,6 +668,11 @@ impl Distribution<configs::SnapshotsCreatorConfig> for EncodeDist {
             storage_logs_chunk_size: self.sample(rng),
             concurrent_queries_count: self.sample(rng),
             object_store: self.sample(rng),
+            diff_snapshots: self.sample(rng),
+            full_snapshot_l1_batch_interval: self.sample(rng),
+            l1_batch_interval: self.sample(rng),
+            retained_snapshots_count: self.sample(rng),
+            poll_interval_ms: self.sample(rng),
         }
     }
 }
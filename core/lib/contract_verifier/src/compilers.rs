@@ -0,0 +1,474 @@
+//! Management of compiler binaries used by the verifier.
+//!
+//! Binaries are cached in the compilers directory using the `{compiler}-bin/{version}/{compiler}` layout.
+//! Missing binaries are downloaded on demand from a mirror (either an HTTP(S) URL or a local directory)
+//! with the following layout:
+//!
+//! - `{compiler}/versions.json`: JSON array with available versions (only required for HTTP mirrors;
+//!   local mirrors are listed directly)
+//! - `{compiler}/{version}/{compiler}`: compiler binary
+//!
+//! The mirror is not trusted: downloaded binaries are checked against SHA-256 checksums pinned
+//! in a local checksums file, and versions without a pinned checksum are never downloaded.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use sha2::{Digest, Sha256};
+use tokio::sync::{Semaphore, SemaphorePermit};
+use url::Url;
+use zksync_config::ContractVerifierConfig;
+
+use crate::error::ContractVerifierError;
+
+/// Timeout for downloading a single file from the mirror.
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(300);
+/// Default number of compilations that can run concurrently.
+const DEFAULT_MAX_CONCURRENT_COMPILATIONS: usize = 4;
+
+/// Compiler binaries managed by [`CompilerManager`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompilerKind {
+    ZkSolc,
+    Solc,
+    ZkVyper,
+    Vyper,
+}
+
+impl CompilerKind {
+    pub const ALL: [Self; 4] = [Self::ZkSolc, Self::Solc, Self::ZkVyper, Self::Vyper];
+
+    /// Returns the name of the compiler binary.
+    pub fn binary_name(self) -> &'static str {
+        match self {
+            Self::ZkSolc => "zksolc",
+            Self::Solc => "solc",
+            Self::ZkVyper => "zkvyper",
+            Self::Vyper => "vyper",
+        }
+    }
+}
+
+impl fmt::Display for CompilerKind {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.binary_name())
+    }
+}
+
+#[derive(Debug)]
+enum CompilerMirror {
+    Http(Url),
+    Local(PathBuf),
+}
+
+impl CompilerMirror {
+    fn new(location: &str) -> anyhow::Result<Self> {
+        if location.starts_with("http://") || location.starts_with("https://") {
+            let mut url: Url = location
+                .parse()
+                .with_context(|| format!("invalid compilers mirror URL: {location}"))?;
+            if !url.path().ends_with('/') {
+                url.set_path(&format!("{}/", url.path()));
+            }
+            Ok(Self::Http(url))
+        } else {
+            let path = location.strip_prefix("file://").unwrap_or(location);
+            Ok(Self::Local(path.into()))
+        }
+    }
+
+    async fn read(&self, relative_path: &str) -> anyhow::Result<Vec<u8>> {
+        match self {
+            Self::Http(url) => {
+                let url = url.join(relative_path)?;
+                let response = reqwest::Client::new()
+                    .get(url.clone())
+                    .timeout(DOWNLOAD_TIMEOUT)
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status)
+                    .with_context(|| format!("failed fetching {url}"))?;
+                Ok(response.bytes().await?.to_vec())
+            }
+            Self::Local(dir) => {
+                let path = dir.join(relative_path);
+                tokio::fs::read(&path)
+                    .await
+                    .with_context(|| format!("failed reading {path:?}"))
+            }
+        }
+    }
+
+    async fn list_versions(&self, compiler: CompilerKind) -> anyhow::Result<Vec<String>> {
+        match self {
+            Self::Http(_) => {
+                let list = self.read(&format!("{compiler}/versions.json")).await?;
+                serde_json::from_slice(&list).context("invalid versions list")
+            }
+            Self::Local(dir) => list_subdirectories(&dir.join(compiler.binary_name())).await,
+        }
+    }
+}
+
+/// Loads pinned checksums of compiler binaries from a JSON file with the `{ compiler: { version: checksum } }`
+/// structure.
+fn load_checksums(path: &Path) -> anyhow::Result<HashMap<(CompilerKind, String), [u8; 32]>> {
+    let contents = std::fs::read(path).with_context(|| format!("failed reading {path:?}"))?;
+    let manifest: HashMap<String, HashMap<String, String>> = serde_json::from_slice(&contents)
+        .with_context(|| format!("invalid checksums file {path:?}"))?;
+
+    let mut checksums = HashMap::new();
+    for (compiler_name, versions) in manifest {
+        let compiler = CompilerKind::ALL
+            .into_iter()
+            .find(|compiler| compiler.binary_name() == compiler_name)
+            .with_context(|| format!("unknown compiler `{compiler_name}` in checksums file"))?;
+        for (version, checksum) in versions {
+            let mut bytes = [0_u8; 32];
+            hex::decode_to_slice(&checksum, &mut bytes)
+                .with_context(|| format!("invalid checksum for {compiler} {version}"))?;
+            checksums.insert((compiler, version), bytes);
+        }
+    }
+    Ok(checksums)
+}
+
+async fn list_subdirectories(dir: &Path) -> anyhow::Result<Vec<String>> {
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(anyhow::Error::new(err).context(format!("cannot read {dir:?}"))),
+    };
+    let mut names = vec![];
+    while let Some(entry) = entries.next_entry().await? {
+        if entry.file_type().await?.is_dir() {
+            names.extend(entry.file_name().into_string().ok());
+        }
+    }
+    Ok(names)
+}
+
+/// Downloads, verifies and caches compiler binaries, and limits concurrency of compilations.
+#[derive(Debug)]
+pub struct CompilerManager {
+    compilers_dir: PathBuf,
+    mirror: Option<CompilerMirror>,
+    /// Pinned SHA-256 checksums of binaries that can be downloaded from the mirror.
+    checksums: HashMap<(CompilerKind, String), [u8; 32]>,
+    compilation_permits: Semaphore,
+    zksolc_timeout: Duration,
+    zkvyper_timeout: Duration,
+    /// Locks preventing concurrent downloads of the same binary.
+    downloads: Mutex<HashMap<(CompilerKind, String), Arc<tokio::sync::Mutex<()>>>>,
+}
+
+impl CompilerManager {
+    pub fn new(config: &ContractVerifierConfig, default_dir: &Path) -> anyhow::Result<Self> {
+        let mirror = config
+            .compilers_mirror
+            .as_deref()
+            .map(CompilerMirror::new)
+            .transpose()?;
+        let checksums = match &config.compilers_checksums {
+            Some(path) => load_checksums(Path::new(path))?,
+            None => {
+                anyhow::ensure!(
+                    mirror.is_none(),
+                    "`compilers_checksums` must be specified together with `compilers_mirror`"
+                );
+                HashMap::new()
+            }
+        };
+        let max_concurrent_compilations = config
+            .max_concurrent_compilations
+            .unwrap_or(DEFAULT_MAX_CONCURRENT_COMPILATIONS);
+        anyhow::ensure!(
+            max_concurrent_compilations > 0,
+            "`max_concurrent_compilations` must be positive"
+        );
+
+        Ok(Self {
+            compilers_dir: config
+                .compilers_dir
+                .as_deref()
+                .map_or_else(|| default_dir.to_owned(), PathBuf::from),
+            mirror,
+            checksums,
+            compilation_permits: Semaphore::new(max_concurrent_compilations),
+            zksolc_timeout: config.zksolc_compilation_timeout(),
+            zkvyper_timeout: config.zkvyper_compilation_timeout(),
+            downloads: Mutex::default(),
+        })
+    }
+
+    /// Returns the compilation timeout for the specified zk compiler.
+    pub fn compilation_timeout(&self, compiler: CompilerKind) -> Duration {
+        match compiler {
+            CompilerKind::ZkVyper | CompilerKind::Vyper => self.zkvyper_timeout,
+            CompilerKind::ZkSolc | CompilerKind::Solc => self.zksolc_timeout,
+        }
+    }
+
+    /// Waits until a compilation can be started. The returned permit must be held for the duration of the compilation.
+    pub async fn acquire_compilation_permit(&self) -> SemaphorePermit<'_> {
+        self.compilation_permits
+            .acquire()
+            .await
+            .expect("compilation semaphore is never closed")
+    }
+
+    fn cached_binary_path(&self, compiler: CompilerKind, version: &str) -> PathBuf {
+        self.compilers_dir
+            .join(format!("{compiler}-bin"))
+            .join(version)
+            .join(compiler.binary_name())
+    }
+
+    /// Returns versions of the specified compiler available either in cache or on the mirror. Only versions
+    /// with a pinned checksum are listed for the mirror.
+    pub async fn list_versions(&self, compiler: CompilerKind) -> anyhow::Result<Vec<String>> {
+        let cache_dir = self.compilers_dir.join(format!("{compiler}-bin"));
+        let mut versions: BTreeSet<_> =
+            list_subdirectories(&cache_dir).await?.into_iter().collect();
+        if let Some(mirror) = &self.mirror {
+            match mirror.list_versions(compiler).await {
+                Ok(mirror_versions) => {
+                    versions.extend(mirror_versions.into_iter().filter(|version| {
+                        self.checksums.contains_key(&(compiler, version.clone()))
+                    }))
+                }
+                Err(err) => tracing::warn!("Failed listing {compiler} versions on mirror: {err:#}"),
+            }
+        }
+        Ok(versions.into_iter().collect())
+    }
+
+    /// Returns a path to the specified compiler binary, downloading it from the mirror if necessary.
+    pub async fn binary_path(
+        &self,
+        compiler: CompilerKind,
+        version: &str,
+    ) -> Result<PathBuf, ContractVerifierError> {
+        let unknown_version =
+            || ContractVerifierError::UnknownCompilerVersion(compiler.to_string(), version.into());
+        // Versions are used as path components, so they must not allow escaping the cache directory.
+        if version.is_empty() || version.contains(['/', '\\']) || version.starts_with('.') {
+            return Err(unknown_version());
+        }
+
+        let path = self.cached_binary_path(compiler, version);
+        if path.exists() {
+            return Ok(path);
+        }
+        let Some(mirror) = &self.mirror else {
+            return Err(unknown_version());
+        };
+
+        let download_lock = self
+            .downloads
+            .lock()
+            .expect("downloads map is poisoned")
+            .entry((compiler, version.to_owned()))
+            .or_default()
+            .clone();
+        let _guard = download_lock.lock().await;
+        if path.exists() {
+            // The binary was downloaded while we were waiting for the lock.
+            return Ok(path);
+        }
+
+        match self.download(mirror, compiler, version, &path).await {
+            Ok(()) => Ok(path),
+            Err(err) => {
+                tracing::warn!("Failed downloading {compiler} {version}: {err:#}");
+                Err(unknown_version())
+            }
+        }
+    }
+
+    async fn download(
+        &self,
+        mirror: &CompilerMirror,
+        compiler: CompilerKind,
+        version: &str,
+        path: &Path,
+    ) -> anyhow::Result<()> {
+        let binary_name = compiler.binary_name();
+        let expected_checksum = self
+            .checksums
+            .get(&(compiler, version.to_owned()))
+            .with_context(|| format!("no pinned checksum for {compiler} {version}"))?;
+        let binary = mirror
+            .read(&format!("{compiler}/{version}/{binary_name}"))
+            .await?;
+        let actual_checksum = Sha256::digest(&binary);
+        anyhow::ensure!(
+            actual_checksum.as_slice() == expected_checksum,
+            "checksum mismatch for {compiler} {version}: expected {}, got {}",
+            hex::encode(expected_checksum),
+            hex::encode(actual_checksum)
+        );
+
+        let dir = path.parent().context("binary path has no parent")?;
+        tokio::fs::create_dir_all(dir)
+            .await
+            .with_context(|| format!("failed creating {dir:?}"))?;
+        // Write to a temporary file first so that a partially written binary is never used.
+        let tmp_path = dir.join(format!(".{binary_name}.tmp"));
+        tokio::fs::write(&tmp_path, &binary).await?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt as _;
+            tokio::fs::set_permissions(&tmp_path, std::fs::Permissions::from_mode(0o755)).await?;
+        }
+        tokio::fs::rename(&tmp_path, path).await?;
+        tracing::info!("Downloaded {compiler} {version} to {path:?}");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(mirror: &Path, compilers_dir: &Path, checksums: &Path) -> ContractVerifierConfig {
+        ContractVerifierConfig {
+            compilation_timeout: 30,
+            polling_interval: None,
+            prometheus_port: 3314,
+            threads_per_server: None,
+            port: 3070,
+            url: "http://127.0.0.1:3070".to_owned(),
+            compilers_dir: Some(compilers_dir.to_str().unwrap().to_owned()),
+            compilers_mirror: Some(mirror.to_str().unwrap().to_owned()),
+            compilers_checksums: Some(checksums.to_str().unwrap().to_owned()),
+            max_concurrent_compilations: Some(2),
+            zksolc_compilation_timeout: Some(10),
+            zkvyper_compilation_timeout: None,
+        }
+    }
+
+    fn put_binary(mirror: &Path, compiler: CompilerKind, version: &str, binary: &[u8]) {
+        let dir = mirror.join(compiler.binary_name()).join(version);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(compiler.binary_name()), binary).unwrap();
+    }
+
+    #[tokio::test]
+    async fn downloading_compilers_from_local_mirror() {
+        let mirror = tempfile::TempDir::new().unwrap();
+        let compilers_dir = tempfile::TempDir::new().unwrap();
+        let binary = b"#!/bin/sh\necho zksolc";
+        put_binary(mirror.path(), CompilerKind::ZkSolc, "v1.5.0", binary);
+        put_binary(mirror.path(), CompilerKind::ZkSolc, "v1.5.1", binary);
+        // Not pinned in the checksums file.
+        put_binary(mirror.path(), CompilerKind::ZkSolc, "v1.5.2", binary);
+        let checksums_path = compilers_dir.path().join("checksums.json");
+        let checksums = serde_json::json!({
+            "zksolc": {
+                "v1.5.0": hex::encode(Sha256::digest(binary)),
+                "v1.5.1": hex::encode([0_u8; 32]),
+            },
+        });
+        std::fs::write(&checksums_path, checksums.to_string()).unwrap();
+
+        let config = config(mirror.path(), compilers_dir.path(), &checksums_path);
+        let manager = CompilerManager::new(&config, Path::new(".")).unwrap();
+        assert_eq!(
+            manager.compilation_timeout(CompilerKind::ZkSolc),
+            Duration::from_secs(10)
+        );
+        assert_eq!(
+            manager.compilation_timeout(CompilerKind::ZkVyper),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            manager.list_versions(CompilerKind::ZkSolc).await.unwrap(),
+            ["v1.5.0", "v1.5.1"]
+        );
+        assert!(manager
+            .list_versions(CompilerKind::Solc)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let path = manager
+            .binary_path(CompilerKind::ZkSolc, "v1.5.0")
+            .await
+            .unwrap();
+        assert_eq!(path, compilers_dir.path().join("zksolc-bin/v1.5.0/zksolc"));
+        assert_eq!(std::fs::read(&path).unwrap(), binary);
+
+        // Binary with a wrong checksum must not be cached.
+        let err = manager
+            .binary_path(CompilerKind::ZkSolc, "v1.5.1")
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, ContractVerifierError::UnknownCompilerVersion(..)),
+            "{err:?}"
+        );
+        assert!(!compilers_dir
+            .path()
+            .join("zksolc-bin/v1.5.1/zksolc")
+            .exists());
+
+        // Binary without a pinned checksum must not be downloaded.
+        let err = manager
+            .binary_path(CompilerKind::ZkSolc, "v1.5.2")
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, ContractVerifierError::UnknownCompilerVersion(..)),
+            "{err:?}"
+        );
+        assert!(!compilers_dir
+            .path()
+            .join("zksolc-bin/v1.5.2/zksolc")
+            .exists());
+
+        let err = manager
+            .binary_path(CompilerKind::ZkSolc, "../v1.5.0")
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, ContractVerifierError::UnknownCompilerVersion(..)),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn mirror_requires_pinned_checksums() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut config = config(dir.path(), dir.path(), Path::new("checksums.json"));
+        config.compilers_checksums = None;
+        let err = CompilerManager::new(&config, Path::new("."))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("compilers_checksums"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn limiting_concurrent_compilations() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let checksums_path = dir.path().join("checksums.json");
+        std::fs::write(&checksums_path, "{}").unwrap();
+        let config = config(dir.path(), dir.path(), &checksums_path);
+        let manager = CompilerManager::new(&config, Path::new(".")).unwrap();
+        let first = manager.acquire_compilation_permit().await;
+        let _second = manager.acquire_compilation_permit().await;
+        let third = manager.acquire_compilation_permit();
+        tokio::pin!(third);
+        assert!(tokio::time::timeout(Duration::from_millis(50), &mut third)
+            .await
+            .is_err());
+        drop(first);
+        third.await;
+    }
+}
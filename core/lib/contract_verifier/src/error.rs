@@ -22,4 +22,8 @@ pub enum ContractVerifierError {
     AbstractContract(String),
     #[error("Failed to deserialize standard JSON input")]
     FailedToDeserializeInput,
+    #[error("Pre-compiled output is only supported for Solidity sources")]
+    CompilerOutputNotSupported,
+    #[error("Compiler output doesn't correspond to the submitted sources: {0}")]
+    CompilerOutputMismatch(String),
 }
@@ -1,6 +1,7 @@
 use std::{
     collections::HashMap,
     path::Path,
+    sync::Arc,
     time::{Duration, Instant},
 };
 
@@ -9,29 +10,36 @@ use chrono::Utc;
 use ethabi::{Contract, Token};
 use lazy_static::lazy_static;
 use regex::Regex;
-use tokio::time;
+use tokio::{sync::watch, time};
 use zksync_config::ContractVerifierConfig;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
-use zksync_env_config::FromEnv;
 use zksync_queued_job_processor::{async_trait, JobProcessor};
 use zksync_types::{
     contract_verification_api::{
         CompilationArtifacts, CompilerType, DeployContractCalldata, SourceCodeData,
-        VerificationInfo, VerificationRequest,
+        VerificationInfo, VerificationMatch, VerificationRequest,
     },
     Address,
 };
 use zksync_utils::workspace_dir_or_current_dir;
 
 use crate::{
+    compilers::{CompilerKind, CompilerManager},
     error::ContractVerifierError,
+    metadata::match_bytecodes,
     metrics::API_CONTRACT_VERIFIER_METRICS,
+    precompiled::{
+        check_submitted_compiler_output, parse_standard_json_output, split_contract_name,
+    },
     zksolc_utils::{Optimizer, Settings, Source, StandardJson, ZkSolc, ZkSolcInput, ZkSolcOutput},
     zkvyper_utils::{ZkVyper, ZkVyperInput},
 };
 
+pub mod compilers;
 pub mod error;
+mod metadata;
 mod metrics;
+mod precompiled;
 mod zksolc_utils;
 mod zkvyper_utils;
 
@@ -49,27 +57,38 @@ enum ConstructorArgs {
     Ignore,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ContractVerifier {
     config: ContractVerifierConfig,
     connection_pool: ConnectionPool<Core>,
+    compilers: Arc<CompilerManager>,
 }
 
 impl ContractVerifier {
-    pub fn new(config: ContractVerifierConfig, connection_pool: ConnectionPool<Core>) -> Self {
-        Self {
+    pub fn new(
+        config: ContractVerifierConfig,
+        connection_pool: ConnectionPool<Core>,
+    ) -> anyhow::Result<Self> {
+        let default_compilers_dir = home_path().join("etc");
+        let compilers = CompilerManager::new(&config, &default_compilers_dir)
+            .context("failed initializing compiler manager")?;
+        Ok(Self {
             config,
             connection_pool,
-        }
+            compilers: Arc::new(compilers),
+        })
+    }
+
+    /// Returns the manager of compiler binaries used by this verifier.
+    pub fn compilers(&self) -> &CompilerManager {
+        &self.compilers
     }
 
     async fn verify(
         storage: &mut Connection<'_, Core>,
         mut request: VerificationRequest,
-        config: ContractVerifierConfig,
+        compilers: &CompilerManager,
     ) -> Result<VerificationInfo, ContractVerifierError> {
-        let artifacts = Self::compile(request.clone(), config).await?;
-
         // Bytecode should be present because it is checked when accepting request.
         let (deployed_bytecode, creation_tx_calldata) = storage
             .contract_verification_dal()
@@ -79,12 +98,17 @@ impl ContractVerifier {
                 tracing::warn!("Contract is missing in DB for already accepted verification request. Contract address: {:#?}", request.req.contract_address);
                 ContractVerifierError::InternalError
             })?;
+        // Submitted compiler output is only checked against the output reproduced with the requested compilers.
+        let artifacts = Self::compile(request.clone(), compilers).await?;
+        if let Some(output) = &request.req.compiler_output {
+            check_submitted_compiler_output(&request, output, &artifacts)?;
+        }
         let constructor_args = Self::decode_constructor_arguments_from_calldata(
             creation_tx_calldata,
             request.req.contract_address,
         );
 
-        if artifacts.bytecode != deployed_bytecode {
+        let Some(match_type) = match_bytecodes(&deployed_bytecode, &artifacts.bytecode) else {
             tracing::info!(
                 "Bytecode mismatch req {}, deployed: 0x{}, compiled 0x{}",
                 request.id,
@@ -92,6 +116,12 @@ impl ContractVerifier {
                 hex::encode(artifacts.bytecode)
             );
             return Err(ContractVerifierError::BytecodeMismatch);
+        };
+        if match_type == VerificationMatch::Partial {
+            tracing::info!(
+                "Request {} partially matches deployed bytecode (metadata differs)",
+                request.id
+            );
         }
 
         match constructor_args {
@@ -109,100 +139,149 @@ impl ContractVerifier {
             request,
             artifacts,
             verified_at: Utc::now(),
+            match_type,
         })
     }
 
-    async fn compile_zksolc(
-        request: VerificationRequest,
-        config: ContractVerifierConfig,
-    ) -> Result<CompilationArtifacts, ContractVerifierError> {
-        // Users may provide either just contract name or
-        // source file name and contract name joined with ":".
-        let (file_name, contract_name) =
-            if let Some((file_name, contract_name)) = request.req.contract_name.rsplit_once(':') {
-                (file_name.to_string(), contract_name.to_string())
-            } else {
-                (
-                    format!("{}.sol", request.req.contract_name),
-                    request.req.contract_name.clone(),
-                )
+    /// Verifies deployed contracts that have the same bytecode as already verified contracts
+    /// (e.g., ones deployed by factories) by reusing artifacts of the verified contracts.
+    /// Deployments are processed in the order of their events, starting after the persisted cursor;
+    /// contracts deployed before a contract with the same bytecode is verified are not picked up.
+    /// Returns the number of processed deployments.
+    pub async fn verify_contracts_with_known_bytecode(&self) -> anyhow::Result<usize> {
+        const BATCH_SIZE: usize = 100;
+
+        let mut storage = self.connection_pool.connection().await?;
+        let cursor = storage
+            .contract_verification_dal()
+            .get_deploy_events_cursor()
+            .await
+            .context("get_deploy_events_cursor()")?;
+        let contracts = storage
+            .contract_verification_dal()
+            .get_deployed_contracts_with_verified_bytecode(cursor, BATCH_SIZE)
+            .await
+            .context("get_deployed_contracts_with_verified_bytecode()")?;
+
+        for contract in &contracts {
+            let address = contract.address;
+            let Some(verified_address) = contract.verified_address else {
+                continue;
             };
-        let input = Self::build_zksolc_input(request.clone(), file_name.clone())?;
+            let Some(info) = storage
+                .contract_verification_dal()
+                .get_contract_verification_info(verified_address)
+                .await?
+            else {
+                continue;
+            };
+            let Some((_, calldata)) = storage
+                .contract_verification_dal()
+                .get_contract_info_for_verification(address)
+                .await?
+            else {
+                continue;
+            };
+            let constructor_arguments =
+                match Self::decode_constructor_arguments_from_calldata(calldata, address) {
+                    ConstructorArgs::Check(args) => args,
+                    ConstructorArgs::Ignore => vec![],
+                };
 
-        let zksolc_path = Path::new(&home_path())
-            .join("etc")
-            .join("zksolc-bin")
-            .join(request.req.compiler_versions.zk_compiler_version())
-            .join("zksolc");
-        if !zksolc_path.exists() {
-            return Err(ContractVerifierError::UnknownCompilerVersion(
-                "zksolc".to_string(),
-                request.req.compiler_versions.zk_compiler_version(),
-            ));
+            let mut request = info.request;
+            request.req.contract_address = address;
+            request.req.constructor_arguments = constructor_arguments.into();
+            let info = VerificationInfo {
+                request,
+                artifacts: info.artifacts,
+                verified_at: Utc::now(),
+                match_type: info.match_type,
+            };
+            if storage
+                .contract_verification_dal()
+                .save_reused_verification_info(info)
+                .await?
+            {
+                tracing::info!(
+                    "Verified contract {address:?} using artifacts of contract {verified_address:?} with the same bytecode"
+                );
+                API_CONTRACT_VERIFIER_METRICS
+                    .reused_artifacts_verifications
+                    .inc();
+            }
         }
 
-        let solc_path = Path::new(&home_path())
-            .join("etc")
-            .join("solc-bin")
-            .join(request.req.compiler_versions.compiler_version())
-            .join("solc");
-        if !solc_path.exists() {
-            return Err(ContractVerifierError::UnknownCompilerVersion(
-                "solc".to_string(),
-                request.req.compiler_versions.compiler_version(),
-            ));
+        if let Some(last_contract) = contracts.last() {
+            storage
+                .contract_verification_dal()
+                .set_deploy_events_cursor(last_contract.cursor)
+                .await
+                .context("set_deploy_events_cursor()")?;
         }
+        Ok(contracts.len())
+    }
 
-        let zksolc = ZkSolc::new(
-            zksolc_path,
-            solc_path,
-            request.req.compiler_versions.zk_compiler_version(),
-        );
+    /// Periodically verifies contracts with the same bytecode as already verified ones. Errors are logged
+    /// and the verification is retried after the polling interval.
+    pub async fn run_known_bytecode_verification(
+        self,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        let polling_interval = self.config.polling_interval();
+        while !*stop_receiver.borrow() {
+            match self.verify_contracts_with_known_bytecode().await {
+                // There may be more deployments to process; don't wait.
+                Ok(processed_count) if processed_count > 0 => continue,
+                Ok(_) => {}
+                Err(err) => {
+                    // Errors are most likely transient (e.g., lost DB connection); retry on the next tick.
+                    tracing::warn!("Failed verifying contracts with known bytecode: {err:#}");
+                }
+            }
+            if time::timeout(polling_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, shutting down known bytecode verification");
+        Ok(())
+    }
 
-        let output = time::timeout(config.compilation_timeout(), zksolc.async_compile(input))
+    async fn compile_zksolc(
+        request: VerificationRequest,
+        compilers: &CompilerManager,
+    ) -> Result<CompilationArtifacts, ContractVerifierError> {
+        let (file_name, contract_name) = split_contract_name(&request.req.contract_name, "sol");
+        let input = Self::build_zksolc_input(request.clone(), file_name.clone())?;
+
+        let timeout = compilers.compilation_timeout(CompilerKind::ZkSolc);
+        // Downloading compilers and waiting for a permit count towards the timeout; otherwise, a queued job
+        // could exceed the execution time after which it's picked up by another verifier.
+        let compilation = async {
+            let zksolc_version = request.req.compiler_versions.zk_compiler_version();
+            let zksolc_path = compilers
+                .binary_path(CompilerKind::ZkSolc, &zksolc_version)
+                .await?;
+            let solc_path = compilers
+                .binary_path(
+                    CompilerKind::Solc,
+                    &request.req.compiler_versions.compiler_version(),
+                )
+                .await?;
+            let zksolc = ZkSolc::new(zksolc_path, solc_path, zksolc_version);
+
+            let _permit = compilers.acquire_compilation_permit().await;
+            zksolc.async_compile(input).await
+        };
+        let output = time::timeout(timeout, compilation)
             .await
             .map_err(|_| ContractVerifierError::CompilationTimeout)??;
 
         match output {
             ZkSolcOutput::StandardJson(output) => {
-                if let Some(errors) = output.get("errors") {
-                    let errors = errors.as_array().unwrap().clone();
-                    if errors
-                        .iter()
-                        .any(|err| err["severity"].as_str().unwrap() == "error")
-                    {
-                        let error_messages = errors
-                            .into_iter()
-                            .map(|err| err["formattedMessage"].clone())
-                            .collect();
-                        return Err(ContractVerifierError::CompilationError(
-                            serde_json::Value::Array(error_messages),
-                        ));
-                    }
-                }
-
-                let contracts = output["contracts"]
-                    .get(file_name.as_str())
-                    .cloned()
-                    .ok_or(ContractVerifierError::MissingSource(file_name))?;
-                let contract = contracts
-                    .get(&contract_name)
-                    .cloned()
-                    .ok_or(ContractVerifierError::MissingContract(contract_name))?;
-                let bytecode_str = contract["evm"]["bytecode"]["object"].as_str().ok_or(
-                    ContractVerifierError::AbstractContract(request.req.contract_name),
-                )?;
-                let bytecode = hex::decode(bytecode_str).unwrap();
-                let abi = contract["abi"].clone();
-                if !abi.is_array() {
-                    tracing::error!(
-                        "zksolc returned unexpected value for ABI: {}",
-                        serde_json::to_string_pretty(&abi).unwrap()
-                    );
-                    return Err(ContractVerifierError::InternalError);
-                }
-
-                Ok(CompilationArtifacts { bytecode, abi })
+                parse_standard_json_output(&output, file_name, contract_name)
             }
             ZkSolcOutput::YulSingleFile(output) => {
                 let re = Regex::new(r"Contract `.*` bytecode: 0x([\da-f]+)").unwrap();
@@ -219,7 +298,7 @@ impl ContractVerifier {
 
     async fn compile_zkvyper(
         request: VerificationRequest,
-        config: ContractVerifierConfig,
+        compilers: &CompilerManager,
     ) -> Result<CompilationArtifacts, ContractVerifierError> {
         // Users may provide either just contract name or
         // source file name and contract name joined with ":".
@@ -231,33 +310,27 @@ impl ContractVerifier {
             };
         let input = Self::build_zkvyper_input(request.clone())?;
 
-        let zkvyper_path = Path::new(&home_path())
-            .join("etc")
-            .join("zkvyper-bin")
-            .join(request.req.compiler_versions.zk_compiler_version())
-            .join("zkvyper");
-        if !zkvyper_path.exists() {
-            return Err(ContractVerifierError::UnknownCompilerVersion(
-                "zkvyper".to_string(),
-                request.req.compiler_versions.zk_compiler_version(),
-            ));
-        }
-
-        let vyper_path = Path::new(&home_path())
-            .join("etc")
-            .join("vyper-bin")
-            .join(request.req.compiler_versions.compiler_version())
-            .join("vyper");
-        if !vyper_path.exists() {
-            return Err(ContractVerifierError::UnknownCompilerVersion(
-                "vyper".to_string(),
-                request.req.compiler_versions.compiler_version(),
-            ));
-        }
-
-        let zkvyper = ZkVyper::new(zkvyper_path, vyper_path);
+        let timeout = compilers.compilation_timeout(CompilerKind::ZkVyper);
+        // Downloading compilers and waiting for a permit count towards the timeout (see `compile_zksolc()`).
+        let compilation = async {
+            let zkvyper_path = compilers
+                .binary_path(
+                    CompilerKind::ZkVyper,
+                    &request.req.compiler_versions.zk_compiler_version(),
+                )
+                .await?;
+            let vyper_path = compilers
+                .binary_path(
+                    CompilerKind::Vyper,
+                    &request.req.compiler_versions.compiler_version(),
+                )
+                .await?;
+            let zkvyper = ZkVyper::new(zkvyper_path, vyper_path);
 
-        let output = time::timeout(config.compilation_timeout(), zkvyper.async_compile(input))
+            let _permit = compilers.acquire_compilation_permit().await;
+            zkvyper.async_compile(input).await
+        };
+        let output = time::timeout(timeout, compilation)
             .await
             .map_err(|_| ContractVerifierError::CompilationTimeout)??;
 
@@ -285,11 +358,11 @@ impl ContractVerifier {
 
     pub async fn compile(
         request: VerificationRequest,
-        config: ContractVerifierConfig,
+        compilers: &CompilerManager,
     ) -> Result<CompilationArtifacts, ContractVerifierError> {
         match request.req.source_code_data.compiler_type() {
-            CompilerType::Solc => Self::compile_zksolc(request, config).await,
-            CompilerType::Vyper => Self::compile_zkvyper(request, config).await,
+            CompilerType::Solc => Self::compile_zksolc(request, compilers).await,
+            CompilerType::Vyper => Self::compile_zkvyper(request, compilers).await,
         }
     }
 
@@ -492,9 +565,13 @@ impl JobProcessor for ContractVerifier {
         // Considering that jobs that reach compilation timeout will be executed in
         // `compilation_timeout` + `non_compilation_time_overhead` (which is significantly less than `compilation_timeout`),
         // we re-pick up jobs that are being executed for a bit more than `compilation_timeout`.
+        let max_compilation_timeout = self
+            .config
+            .zksolc_compilation_timeout()
+            .max(self.config.zkvyper_compilation_timeout());
         let job = connection
             .contract_verification_dal()
-            .get_next_queued_verification_request(self.config.compilation_timeout() + TIME_OVERHEAD)
+            .get_next_queued_verification_request(max_compilation_timeout + TIME_OVERHEAD)
             .await
             .context("get_next_queued_verification_request()")?;
 
@@ -524,15 +601,14 @@ impl JobProcessor for ContractVerifier {
         started_at: Instant,
     ) -> tokio::task::JoinHandle<anyhow::Result<()>> {
         let connection_pool = self.connection_pool.clone();
+        let compilers = self.compilers.clone();
         tokio::task::spawn(async move {
             tracing::info!("Started to process request with id = {}", job.id);
 
-            let config: ContractVerifierConfig =
-                ContractVerifierConfig::from_env().context("ContractVerifierConfig")?;
             let mut connection = connection_pool.connection().await.unwrap();
 
             let job_id = job.id;
-            let verification_result = Self::verify(&mut connection, job, config).await;
+            let verification_result = Self::verify(&mut connection, job, &compilers).await;
             Self::process_result(&mut connection, job_id, verification_result).await;
 
             API_CONTRACT_VERIFIER_METRICS
@@ -0,0 +1,195 @@
+//! Comparison of bytecodes ignoring the CBOR metadata appended by compilers.
+
+use zksync_types::contract_verification_api::VerificationMatch;
+
+/// Size of an EraVM word in bytes. EraVM bytecode is padded to the word boundary.
+const WORD_SIZE: usize = 32;
+/// Maximum nesting depth of CBOR items accepted in the metadata.
+const MAX_CBOR_DEPTH: usize = 16;
+
+/// Splits the bytecode into the executable part and the CBOR metadata suffix. The suffix is encoded
+/// as a CBOR map followed by its big-endian 2-byte length. EraVM bytecode may additionally be padded
+/// with less than a word of zero bytes up to the word boundary.
+fn split_cbor_metadata(bytecode: &[u8]) -> Option<(&[u8], &[u8])> {
+    split_unpadded_cbor_metadata(bytecode).or_else(|| {
+        if bytecode.len() % WORD_SIZE != 0 {
+            return None;
+        }
+        // Zero bytes may belong to the metadata length (e.g., if its low byte is 0), so all possible padding
+        // lengths within the last word are checked.
+        let max_padding_len = bytecode
+            .iter()
+            .rev()
+            .take(WORD_SIZE - 1)
+            .take_while(|&&byte| byte == 0)
+            .count();
+        (1..=max_padding_len).find_map(|padding_len| {
+            split_unpadded_cbor_metadata(&bytecode[..bytecode.len() - padding_len])
+        })
+    })
+}
+
+fn split_unpadded_cbor_metadata(bytecode: &[u8]) -> Option<(&[u8], &[u8])> {
+    let len_offset = bytecode.len().checked_sub(2)?;
+    let metadata_len = usize::from(u16::from_be_bytes([
+        bytecode[len_offset],
+        bytecode[len_offset + 1],
+    ]));
+    if metadata_len == 0 {
+        return None;
+    }
+    let metadata_offset = len_offset.checked_sub(metadata_len)?;
+    let metadata = &bytecode[metadata_offset..len_offset];
+    // Metadata must be a single CBOR map (major type 5) spanning exactly the declared length.
+    if metadata[0] >> 5 != 5 || cbor_item_len(metadata, 0)? != metadata_len {
+        return None;
+    }
+    Some(bytecode.split_at(metadata_offset))
+}
+
+/// Decodes a single CBOR data item at the start of `bytes` and returns its encoded length, or `None`
+/// if the item is malformed. Only definite-length items are supported, which is sufficient for compiler metadata.
+fn cbor_item_len(bytes: &[u8], depth: usize) -> Option<usize> {
+    if depth > MAX_CBOR_DEPTH {
+        return None;
+    }
+    let (&initial_byte, _) = bytes.split_first()?;
+    let major_type = initial_byte >> 5;
+    let additional_info = initial_byte & 0x1f;
+    let (argument, header_len) = match additional_info {
+        0..=23 => (u64::from(additional_info), 1),
+        24 => (u64::from(*bytes.get(1)?), 2),
+        25 => (
+            u64::from(u16::from_be_bytes(bytes.get(1..3)?.try_into().ok()?)),
+            3,
+        ),
+        26 => (
+            u64::from(u32::from_be_bytes(bytes.get(1..5)?.try_into().ok()?)),
+            5,
+        ),
+        27 => (u64::from_be_bytes(bytes.get(1..9)?.try_into().ok()?), 9),
+        // Reserved values and indefinite lengths
+        _ => return None,
+    };
+
+    match major_type {
+        // Unsigned and negative integers, simple values and floats
+        0 | 1 | 7 => Some(header_len),
+        // Byte and text strings
+        2 | 3 => {
+            let len = header_len.checked_add(usize::try_from(argument).ok()?)?;
+            let payload = bytes.get(header_len..len)?;
+            if major_type == 3 {
+                std::str::from_utf8(payload).ok()?;
+            }
+            Some(len)
+        }
+        // Arrays and maps
+        4 | 5 => {
+            let item_count = if major_type == 5 {
+                argument.checked_mul(2)?
+            } else {
+                argument
+            };
+            let mut len = header_len;
+            for _ in 0..item_count {
+                len += cbor_item_len(bytes.get(len..)?, depth + 1)?;
+            }
+            Some(len)
+        }
+        // Tagged items
+        6 => Some(header_len + cbor_item_len(bytes.get(header_len..)?, depth + 1)?),
+        _ => unreachable!("major type is a 3-bit value"),
+    }
+}
+
+/// Compares the deployed bytecode with the compiled one. Returns `None` if bytecodes don't match.
+pub(crate) fn match_bytecodes(deployed: &[u8], compiled: &[u8]) -> Option<VerificationMatch> {
+    if deployed == compiled {
+        return Some(VerificationMatch::Full);
+    }
+    let (deployed_code, _) = split_cbor_metadata(deployed)?;
+    let (compiled_code, _) = split_cbor_metadata(compiled)?;
+    (deployed_code == compiled_code).then_some(VerificationMatch::Partial)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_metadata(code: &[u8], metadata: &[u8], pad_to: usize) -> Vec<u8> {
+        let mut bytecode = code.to_vec();
+        bytecode.extend_from_slice(metadata);
+        bytecode.extend_from_slice(&(metadata.len() as u16).to_be_bytes());
+        while bytecode.len() % pad_to != 0 {
+            bytecode.push(0);
+        }
+        bytecode
+    }
+
+    /// Builds CBOR metadata `{"ipfs": <hash>}`.
+    fn ipfs_metadata(hash: &[u8]) -> Vec<u8> {
+        assert!(hash.len() <= usize::from(u8::MAX));
+        let mut metadata = vec![0xa1, 0x64, b'i', b'p', b'f', b's', 0x58, hash.len() as u8];
+        metadata.extend_from_slice(hash);
+        metadata
+    }
+
+    #[test]
+    fn matching_bytecodes() {
+        let code = [0x11; 64];
+        // The second length makes the metadata 256 bytes long, so the low byte of its length is 0.
+        for hash_len in [2, 248] {
+            let metadata = ipfs_metadata(&vec![0x01; hash_len]);
+            let other_metadata = ipfs_metadata(&vec![0x02; hash_len]);
+
+            for pad_to in [1, 32] {
+                let deployed = with_metadata(&code, &metadata, pad_to);
+                assert_eq!(
+                    match_bytecodes(&deployed, &deployed),
+                    Some(VerificationMatch::Full)
+                );
+                let compiled = with_metadata(&code, &other_metadata, pad_to);
+                assert_eq!(
+                    match_bytecodes(&deployed, &compiled),
+                    Some(VerificationMatch::Partial),
+                    "hash_len={hash_len}, pad_to={pad_to}"
+                );
+                let compiled = with_metadata(&[0x22; 64], &metadata, pad_to);
+                assert_eq!(match_bytecodes(&deployed, &compiled), None);
+            }
+        }
+    }
+
+    #[test]
+    fn splitting_metadata_requires_exact_cbor_map() {
+        let code = [0x11; 64];
+        let valid = ipfs_metadata(&[0x01, 0x02]);
+        let bytecode = with_metadata(&code, &valid, 32);
+        let (split_code, _) = split_cbor_metadata(&bytecode).unwrap();
+        assert_eq!(split_code, code);
+
+        // The map is followed by a trailing byte not covered by it.
+        let mut trailing_byte = valid.clone();
+        trailing_byte.push(0x00);
+        // The map declares more entries than present.
+        let mut truncated_map = valid.clone();
+        truncated_map[0] = 0xa2;
+        // The byte string overflows the declared metadata length.
+        let mut overflowing_string = valid;
+        overflowing_string[7] = 0x03;
+        for invalid in [trailing_byte, truncated_map, overflowing_string] {
+            let bytecode = with_metadata(&code, &invalid, 1);
+            assert_eq!(split_cbor_metadata(&bytecode), None, "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn bytecodes_without_metadata_require_full_match() {
+        let deployed = [0x11; 64];
+        let mut compiled = deployed;
+        compiled[63] = 0x12;
+        assert_eq!(match_bytecodes(&deployed, &compiled), None);
+        assert_eq!(match_bytecodes(&[], &[0xa0, 0, 1]), None);
+    }
+}
@@ -1,12 +1,14 @@
 use std::time::Duration;
 
-use vise::{Buckets, Histogram, Metrics};
+use vise::{Buckets, Counter, Histogram, Metrics};
 
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "api_contract_verifier")]
 pub(crate) struct ApiContractVerifierMetrics {
     #[metrics(buckets = Buckets::LATENCIES)]
     pub request_processing_time: Histogram<Duration>,
+    /// Number of contracts verified by reusing artifacts of a verified contract with the same bytecode.
+    pub reused_artifacts_verifications: Counter,
 }
 
 #[vise::register]
@@ -0,0 +1,221 @@
+//! Extraction of compilation artifacts from the standard JSON compiler output, and checks of the output
+//! submitted by users together with the sources.
+
+use zksync_types::contract_verification_api::{
+    CompilationArtifacts, CompilerType, VerificationRequest,
+};
+
+use crate::error::ContractVerifierError;
+
+/// Splits the contract name provided by the user into the source file name and the contract name.
+/// Users may provide either just contract name or source file name and contract name joined with ":".
+pub(crate) fn split_contract_name(full_name: &str, default_extension: &str) -> (String, String) {
+    if let Some((file_name, contract_name)) = full_name.rsplit_once(':') {
+        (file_name.to_owned(), contract_name.to_owned())
+    } else {
+        (
+            format!("{full_name}.{default_extension}"),
+            full_name.to_owned(),
+        )
+    }
+}
+
+/// Extracts artifacts for the specified contract from the standard JSON compiler output.
+pub(crate) fn parse_standard_json_output(
+    output: &serde_json::Value,
+    file_name: String,
+    contract_name: String,
+) -> Result<CompilationArtifacts, ContractVerifierError> {
+    if let Some(errors) = output.get("errors") {
+        let errors = errors.as_array().cloned().unwrap_or_default();
+        if errors
+            .iter()
+            .any(|err| err["severity"].as_str() == Some("error"))
+        {
+            let error_messages = errors
+                .into_iter()
+                .map(|err| err["formattedMessage"].clone())
+                .collect();
+            return Err(ContractVerifierError::CompilationError(
+                serde_json::Value::Array(error_messages),
+            ));
+        }
+    }
+
+    let contract = contract_output(output, &file_name, &contract_name)?;
+    let bytecode_str = contract["evm"]["bytecode"]["object"]
+        .as_str()
+        .ok_or_else(|| ContractVerifierError::AbstractContract(contract_name.clone()))?;
+    let bytecode = hex::decode(bytecode_str.trim_start_matches("0x"))
+        .map_err(|_| ContractVerifierError::InternalError)?;
+    let abi = contract["abi"].clone();
+    if !abi.is_array() {
+        tracing::error!(
+            "Compiler output contains unexpected value for ABI: {}",
+            serde_json::to_string_pretty(&abi).unwrap()
+        );
+        return Err(ContractVerifierError::InternalError);
+    }
+    Ok(CompilationArtifacts { bytecode, abi })
+}
+
+fn contract_output<'a>(
+    output: &'a serde_json::Value,
+    file_name: &str,
+    contract_name: &str,
+) -> Result<&'a serde_json::Value, ContractVerifierError> {
+    let contracts = output["contracts"]
+        .get(file_name)
+        .ok_or_else(|| ContractVerifierError::MissingSource(file_name.to_owned()))?;
+    contracts
+        .get(contract_name)
+        .ok_or_else(|| ContractVerifierError::MissingContract(contract_name.to_owned()))
+}
+
+/// Checks the compiler output submitted with the request against the artifacts reproduced by the verifier
+/// from the submitted sources using the requested compiler versions. Submitted artifacts are never trusted
+/// on their own; the verifier only stores the reproduced ones. Only Solidity sources are supported.
+pub(crate) fn check_submitted_compiler_output(
+    request: &VerificationRequest,
+    output: &serde_json::Value,
+    reproduced: &CompilationArtifacts,
+) -> Result<(), ContractVerifierError> {
+    if request.req.source_code_data.compiler_type() != CompilerType::Solc {
+        return Err(ContractVerifierError::CompilerOutputNotSupported);
+    }
+    let (file_name, contract_name) = split_contract_name(&request.req.contract_name, "sol");
+    let submitted = parse_standard_json_output(output, file_name, contract_name)?;
+    if submitted.bytecode != reproduced.bytecode {
+        return Err(ContractVerifierError::CompilerOutputMismatch(
+            "bytecode differs from the one produced by the compiler".to_owned(),
+        ));
+    }
+    if submitted.abi != reproduced.abi {
+        return Err(ContractVerifierError::CompilerOutputMismatch(
+            "ABI differs from the one produced by the compiler".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{
+        contract_verification_api::{
+            CompilerVersions, SourceCodeData, VerificationIncomingRequest,
+        },
+        Address,
+    };
+
+    use super::*;
+
+    fn request(source_code_data: SourceCodeData) -> VerificationRequest {
+        VerificationRequest {
+            id: 1,
+            req: VerificationIncomingRequest {
+                contract_address: Address::repeat_byte(1),
+                source_code_data,
+                contract_name: "Counter".to_owned(),
+                compiler_versions: CompilerVersions::Solc {
+                    compiler_zksolc_version: "v1.5.0".to_owned(),
+                    compiler_solc_version: "0.8.24".to_owned(),
+                },
+                optimization_used: true,
+                optimizer_mode: None,
+                constructor_arguments: Default::default(),
+                is_system: false,
+                force_evmla: false,
+                compiler_output: None,
+            },
+        }
+    }
+
+    fn solidity_request() -> VerificationRequest {
+        request(SourceCodeData::SolSingleFile(
+            "contract Counter {}".to_owned(),
+        ))
+    }
+
+    fn compiler_output(artifacts: &CompilationArtifacts) -> serde_json::Value {
+        serde_json::json!({
+            "contracts": {
+                "Counter.sol": {
+                    "Counter": {
+                        "abi": artifacts.abi,
+                        "evm": { "bytecode": { "object": hex::encode(&artifacts.bytecode) } },
+                    }
+                }
+            }
+        })
+    }
+
+    fn reproduced_artifacts() -> CompilationArtifacts {
+        CompilationArtifacts {
+            bytecode: vec![1, 2, 3],
+            abi: serde_json::json!([]),
+        }
+    }
+
+    fn assert_mismatch(result: Result<(), ContractVerifierError>) {
+        let err = result.unwrap_err();
+        assert!(
+            matches!(err, ContractVerifierError::CompilerOutputMismatch(_)),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn splitting_contract_name() {
+        assert_eq!(
+            split_contract_name("contracts/Counter.sol:Counter", "sol"),
+            ("contracts/Counter.sol".to_owned(), "Counter".to_owned())
+        );
+        assert_eq!(
+            split_contract_name("Counter", "sol"),
+            ("Counter.sol".to_owned(), "Counter".to_owned())
+        );
+    }
+
+    #[test]
+    fn checking_submitted_output() {
+        let reproduced = reproduced_artifacts();
+        let output = compiler_output(&reproduced);
+        check_submitted_compiler_output(&solidity_request(), &output, &reproduced).unwrap();
+    }
+
+    #[test]
+    fn submitted_output_differing_from_reproduced_one_is_rejected() {
+        let reproduced = reproduced_artifacts();
+
+        // Bytecode copied from the deployed contract cannot be passed off as the compilation result
+        // if the submitted sources don't compile to it.
+        let mut forged = reproduced.clone();
+        forged.bytecode = vec![1, 2, 4];
+        assert_mismatch(check_submitted_compiler_output(
+            &solidity_request(),
+            &compiler_output(&forged),
+            &reproduced,
+        ));
+
+        let mut forged = reproduced.clone();
+        forged.abi = serde_json::json!([{ "type": "fallback" }]);
+        assert_mismatch(check_submitted_compiler_output(
+            &solidity_request(),
+            &compiler_output(&forged),
+            &reproduced,
+        ));
+    }
+
+    #[test]
+    fn submitted_output_for_vyper_is_not_supported() {
+        let reproduced = reproduced_artifacts();
+        let request = request(SourceCodeData::VyperMultiFile(Default::default()));
+        let err =
+            check_submitted_compiler_output(&request, &compiler_output(&reproduced), &reproduced)
+                .unwrap_err();
+        assert!(
+            matches!(err, ContractVerifierError::CompilerOutputNotSupported),
+            "{err:?}"
+        );
+    }
+}
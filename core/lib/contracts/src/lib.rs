@@ -305,7 +305,9 @@ impl PartialEq for BaseSystemContracts {
 }
 
 impl BaseSystemContracts {
-    fn load_with_bootloader(bootloader_bytecode: Vec<u8>) -> Self {
+    /// Loads base system contracts with the provided bootloader bytecode and the default account
+    /// from the current system contracts.
+    pub fn load_with_bootloader(bootloader_bytecode: Vec<u8>) -> Self {
         let hash = hash_bytecode(&bootloader_bytecode);
 
         let bootloader = SystemContractCode {
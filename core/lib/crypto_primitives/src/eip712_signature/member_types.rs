@@ -1,3 +1,4 @@
+use serde_json::Value;
 use zksync_basic_types::{web3::keccak256, Address, H256, U256};
 
 use crate::eip712_signature::typed_structure::{EncodedStructureMember, StructMember};
@@ -13,6 +14,10 @@ impl StructMember for String {
     fn encode_member_data(&self) -> H256 {
         keccak256(self.as_bytes()).into()
     }
+
+    fn member_json_value(&self) -> Value {
+        Value::String(self.clone())
+    }
 }
 
 impl StructMember for Address {
@@ -26,6 +31,10 @@ impl StructMember for Address {
     fn encode_member_data(&self) -> H256 {
         H256::from(*self)
     }
+
+    fn member_json_value(&self) -> Value {
+        serde_json::to_value(self).expect("serialization fail")
+    }
 }
 
 impl StructMember for &[u8] {
@@ -39,6 +48,10 @@ impl StructMember for &[u8] {
     fn encode_member_data(&self) -> H256 {
         keccak256(self).into()
     }
+
+    fn member_json_value(&self) -> Value {
+        Value::String(format!("0x{}", hex::encode(self)))
+    }
 }
 
 impl StructMember for &[H256] {
@@ -56,6 +69,10 @@ impl StructMember for &[H256] {
             .collect();
         keccak256(&bytes).into()
     }
+
+    fn member_json_value(&self) -> Value {
+        serde_json::to_value(self).expect("serialization fail")
+    }
 }
 
 impl StructMember for U256 {
@@ -72,6 +89,10 @@ impl StructMember for U256 {
 
         bytes.into()
     }
+
+    fn member_json_value(&self) -> Value {
+        serde_json::to_value(self).expect("serialization fail")
+    }
 }
 
 impl StructMember for H256 {
@@ -85,6 +106,11 @@ impl StructMember for H256 {
     fn encode_member_data(&self) -> H256 {
         *self
     }
+
+    fn member_json_value(&self) -> Value {
+        // The hash is encoded as `uint256`, so its JSON representation must be numeric as well.
+        U256::from_big_endian(self.as_bytes()).member_json_value()
+    }
 }
 
 macro_rules! impl_primitive {
@@ -102,6 +128,9 @@ macro_rules! impl_primitive {
 
                 bytes.into()
             }
+            fn member_json_value(&self) -> Value {
+                U256::from(*self).member_json_value()
+            }
         }
     };
 }
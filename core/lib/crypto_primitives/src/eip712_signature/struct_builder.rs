@@ -1,6 +1,6 @@
 use std::collections::{BTreeMap, VecDeque};
 
-use serde_json::Value;
+use serde_json::{Map, Value};
 use zksync_basic_types::H256;
 
 use crate::eip712_signature::typed_structure::{EncodedStructureMember, StructMember};
@@ -181,3 +181,27 @@ impl StructBuilder for EncodeBuilder {
             .push((EncodedStructureMember::encode(name, member), encoded_data));
     }
 }
+
+/// Builder collecting member values of the structure into a JSON object.
+pub(crate) struct JsonMessageBuilder {
+    members: Map<String, Value>,
+}
+
+impl JsonMessageBuilder {
+    pub fn into_json(self) -> Value {
+        Value::Object(self.members)
+    }
+}
+
+impl StructBuilder for JsonMessageBuilder {
+    fn new() -> Self {
+        Self {
+            members: Map::new(),
+        }
+    }
+
+    fn add_member<MEMBER: StructMember>(&mut self, name: &str, member: &MEMBER) {
+        self.members
+            .insert(name.to_owned(), member.member_json_value());
+    }
+}
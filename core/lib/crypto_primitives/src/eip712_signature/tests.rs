@@ -8,7 +8,7 @@ use crate::{
     eip712_signature::{
         struct_builder::StructBuilder,
         typed_structure::{EIP712TypedStructure, Eip712Domain},
-        utils::get_eip712_json,
+        utils::{get_eip712_json, get_eip712_typed_data_json},
     },
     PackedEthSignature,
 };
@@ -204,4 +204,10 @@ fn test_get_eip712_json() {
         get_eip712_json(&domain, &message),
         serde_json::from_str::<serde_json::Value>(expected_value).unwrap()
     );
+    // The `serde` representation of the structures coincides with the EIP-712 one,
+    // so the typed data built from the structure definition must be the same.
+    assert_eq!(
+        get_eip712_typed_data_json(&domain, &message),
+        serde_json::from_str::<serde_json::Value>(expected_value).unwrap()
+    );
 }
@@ -7,6 +7,33 @@ use crate::eip712_signature::typed_structure::{EIP712TypedStructure, Eip712Domai
 pub fn get_eip712_json<T: EIP712TypedStructure + serde::Serialize>(
     eip712_domain: &Eip712Domain,
     typed_struct: &T,
+) -> Value {
+    serde_json::json!({
+        "primaryType": T::TYPE_NAME,
+        "domain": serde_json::to_value(eip712_domain).expect("serialization fail"),
+        "message": serde_json::to_value(typed_struct).expect("serialization fail"),
+        "types": get_json_types(eip712_domain, typed_struct),
+    })
+}
+
+/// Same as [`get_eip712_json()`], but takes the message from the EIP-712 structure definition
+/// rather than from the `serde` representation of the structure. Thus, it works for structures
+/// whose `serde` representation differs from the signed one (e.g., transaction requests).
+pub fn get_eip712_typed_data_json<T: EIP712TypedStructure>(
+    eip712_domain: &Eip712Domain,
+    typed_struct: &T,
+) -> Value {
+    serde_json::json!({
+        "primaryType": T::TYPE_NAME,
+        "domain": eip712_domain.get_json_message(),
+        "message": typed_struct.get_json_message(),
+        "types": get_json_types(eip712_domain, typed_struct),
+    })
+}
+
+fn get_json_types<T: EIP712TypedStructure>(
+    eip712_domain: &Eip712Domain,
+    typed_struct: &T,
 ) -> Value {
     let types = {
         let mut res = Map::new();
@@ -21,11 +48,5 @@ pub fn get_eip712_json<T: EIP712TypedStructure + serde::Serialize>(
         }
         res
     };
-
-    serde_json::json!({
-        "primaryType": T::TYPE_NAME,
-        "domain": serde_json::to_value(eip712_domain).expect("serialization fail"),
-        "message": serde_json::to_value(typed_struct).expect("serialization fail"),
-        "types": serde_json::to_value(types).expect("serialization fail"),
-    })
+    serde_json::to_value(types).expect("serialization fail")
 }
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS eth_txs_receipt_prices (
+    eth_tx_id INT PRIMARY KEY REFERENCES eth_txs (id) ON DELETE CASCADE,
+    effective_gas_price BIGINT,
+    blob_gas_used BIGINT,
+    blob_gas_price BIGINT,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1 @@
+ALTER TABLE contracts_verification_info DROP COLUMN IF EXISTS match_type;
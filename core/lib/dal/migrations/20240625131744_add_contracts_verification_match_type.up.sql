@@ -0,0 +1,2 @@
+ALTER TABLE contracts_verification_info
+    ADD COLUMN IF NOT EXISTS match_type TEXT NOT NULL DEFAULT 'full';
@@ -0,0 +1 @@
+ALTER TABLE contract_verification_requests DROP COLUMN IF EXISTS compiler_output;
@@ -0,0 +1 @@
+ALTER TABLE contract_verification_requests ADD COLUMN IF NOT EXISTS compiler_output JSONB;
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS snapshot_diffs (
+    l1_batch_number BIGINT PRIMARY KEY,
+    base_l1_batch_number BIGINT NOT NULL,
+    storage_logs_chunk_count INT NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
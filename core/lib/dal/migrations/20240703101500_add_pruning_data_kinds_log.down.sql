@@ -0,0 +1 @@
+DROP TABLE IF EXISTS pruning_data_kinds_log;
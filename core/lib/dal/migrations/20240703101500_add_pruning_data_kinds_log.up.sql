@@ -0,0 +1,12 @@
+-- Progress of pruning data kinds retained for longer than the rest of L1 batch data.
+CREATE TABLE IF NOT EXISTS pruning_data_kinds_log (
+    data_kind TEXT NOT NULL,
+    type prune_type NOT NULL,
+    -- `NULL` values mean that no data of the kind was pruned yet.
+    pruned_l1_batch BIGINT,
+    pruned_miniblock BIGINT,
+
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (data_kind, type)
+);
@@ -0,0 +1,11 @@
+-- L1 batches archived to the object store before being pruned.
+CREATE TABLE IF NOT EXISTS pruned_data_archive (
+    l1_batch_number BIGINT PRIMARY KEY,
+    first_miniblock BIGINT NOT NULL,
+    last_miniblock BIGINT NOT NULL,
+
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS pruned_data_archive_last_miniblock_idx ON pruned_data_archive (last_miniblock);
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS eth_watcher_processed_blocks (
+    block_number BIGINT PRIMARY KEY,
+    block_hash BYTEA NOT NULL,
+    event_digests BYTEA[] NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
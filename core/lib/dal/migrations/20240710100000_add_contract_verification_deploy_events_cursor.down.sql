@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS events_contract_deployed_bytecode_hash;
+DROP TABLE IF EXISTS contract_verification_deploy_events_cursor;
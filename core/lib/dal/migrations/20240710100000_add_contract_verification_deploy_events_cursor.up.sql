@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS contract_verification_deploy_events_cursor (
+    -- artificial primary key ensuring that the table contains at most 1 row.
+    fake_key BOOLEAN PRIMARY KEY,
+    miniblock_number BIGINT NOT NULL,
+    event_index_in_block INT NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    CHECK (fake_key)
+);
+
+-- Allows looking up contract deployments by the deployed bytecode hash.
+CREATE INDEX IF NOT EXISTS events_contract_deployed_bytecode_hash
+    ON events (topic3, miniblock_number, event_index_in_block)
+    WHERE address = '\x0000000000000000000000000000000000008006'
+    AND topic1 = '\x290afdae231a3fc0bbae8b1af63698b0a1d79b21ad17df0342dfb952fe74f8e5';
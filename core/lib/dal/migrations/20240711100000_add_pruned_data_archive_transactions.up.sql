@@ -0,0 +1,8 @@
+-- Archived L1 batches containing transactions, so that transactions can be looked up by hash after being pruned.
+CREATE TABLE IF NOT EXISTS pruned_data_archive_transactions (
+    tx_hash BYTEA PRIMARY KEY,
+    l1_batch_number BIGINT NOT NULL,
+
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
@@ -717,7 +717,8 @@ impl BlocksWeb3Dal<'_, '_> {
                 mb.l2_fair_gas_price,
                 mb.fair_pubdata_price,
                 l1_batches.bootloader_code_hash,
-                l1_batches.default_aa_code_hash
+                l1_batches.default_aa_code_hash,
+                l1_batches.rollup_last_leaf_index
             FROM
                 l1_batches
                 INNER JOIN mb ON TRUE
@@ -1,5 +1,6 @@
 #![doc = include_str!("../doc/ContractVerificationDal.md")]
 use std::{
+    collections::HashMap,
     fmt::{Display, Formatter},
     time::Duration,
 };
@@ -13,12 +14,29 @@ use zksync_types::{
         VerificationRequestStatus,
     },
     event::DEPLOY_EVENT_SIGNATURE,
-    Address, CONTRACT_DEPLOYER_ADDRESS,
+    Address, L2BlockNumber, CONTRACT_DEPLOYER_ADDRESS, H256,
 };
-use zksync_utils::address_to_h256;
+use zksync_utils::{address_to_h256, h256_to_account_address};
 
 use crate::{models::storage_verification_request::StorageVerificationRequest, Core};
 
+/// Position of a contract deployment event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DeployEventCursor {
+    pub l2_block_number: L2BlockNumber,
+    pub event_index_in_block: u32,
+}
+
+/// Contract deployment returned by [`ContractVerificationDal::get_deployed_contracts_with_verified_bytecode()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeployedContract {
+    pub cursor: DeployEventCursor,
+    pub address: Address,
+    /// Address of a verified contract with the same bytecode. Always `None` if the deployed contract
+    /// is verified itself.
+    pub verified_address: Option<Address>,
+}
+
 #[derive(Debug)]
 pub struct ContractVerificationDal<'a, 'c> {
     pub(crate) storage: &'a mut Connection<'c, Core>,
@@ -78,12 +96,13 @@ impl ContractVerificationDal<'_, '_> {
                     constructor_arguments,
                     is_system,
                     force_evmla,
+                    compiler_output,
                     status,
                     created_at,
                     updated_at
                 )
             VALUES
-                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, 'queued', NOW(), NOW())
+                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, 'queued', NOW(), NOW())
             RETURNING
                 id
             "#,
@@ -98,6 +117,7 @@ impl ContractVerificationDal<'_, '_> {
             query.constructor_arguments.0,
             query.is_system,
             query.force_evmla,
+            query.compiler_output,
         )
         .fetch_one(self.storage.conn())
         .await
@@ -156,7 +176,8 @@ impl ContractVerificationDal<'_, '_> {
                 optimizer_mode,
                 constructor_arguments,
                 is_system,
-                force_evmla
+                force_evmla,
+                compiler_output
             "#,
             &processing_timeout
         )
@@ -167,6 +188,7 @@ impl ContractVerificationDal<'_, '_> {
     }
 
     /// Updates the verification request status and inserts the verification info upon successful verification.
+    /// A full match is never overwritten by a partial one.
     pub async fn save_verification_info(
         &mut self,
         verification_info: VerificationInfo,
@@ -192,22 +214,28 @@ impl ContractVerificationDal<'_, '_> {
         .await?;
 
         let address = verification_info.request.req.contract_address;
+        let match_type = verification_info.match_type.as_str();
         // Serialization should always succeed.
         let verification_info_json = serde_json::to_value(verification_info)
             .expect("Failed to serialize verification info into serde_json");
         sqlx::query!(
             r#"
             INSERT INTO
-                contracts_verification_info (address, verification_info)
+                contracts_verification_info (address, verification_info, match_type)
             VALUES
-                ($1, $2)
+                ($1, $2, $3)
             ON CONFLICT (address) DO
             UPDATE
             SET
-                verification_info = $2
+                verification_info = $2,
+                match_type = $3
+            WHERE
+                contracts_verification_info.match_type = 'partial'
+                OR excluded.match_type = 'full'
             "#,
             address.as_bytes(),
-            &verification_info_json
+            &verification_info_json,
+            match_type
         )
         .execute(transaction.conn())
         .await?;
@@ -216,6 +244,159 @@ impl ContractVerificationDal<'_, '_> {
         Ok(())
     }
 
+    /// Inserts verification info for a contract verified by reusing artifacts of another contract
+    /// with the same bytecode. Returns `false` if the contract is already verified.
+    pub async fn save_reused_verification_info(
+        &mut self,
+        verification_info: VerificationInfo,
+    ) -> sqlx::Result<bool> {
+        let address = verification_info.request.req.contract_address;
+        let match_type = verification_info.match_type.as_str();
+        // Serialization should always succeed.
+        let verification_info_json = serde_json::to_value(verification_info)
+            .expect("Failed to serialize verification info into serde_json");
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO
+                contracts_verification_info (address, verification_info, match_type)
+            VALUES
+                ($1, $2, $3)
+            ON CONFLICT (address) DO NOTHING
+            "#,
+            address.as_bytes(),
+            &verification_info_json,
+            match_type
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Returns the position of the last contract deployment event processed by the verifier
+    /// of contracts with known bytecode.
+    pub async fn get_deploy_events_cursor(&mut self) -> sqlx::Result<Option<DeployEventCursor>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                miniblock_number,
+                event_index_in_block
+            FROM
+                contract_verification_deploy_events_cursor
+            "#
+        )
+        .fetch_optional(self.storage.conn())
+        .await?;
+        Ok(row.map(|row| DeployEventCursor {
+            l2_block_number: L2BlockNumber(row.miniblock_number as u32),
+            event_index_in_block: row.event_index_in_block as u32,
+        }))
+    }
+
+    /// Sets the position of the last contract deployment event processed by the verifier
+    /// of contracts with known bytecode.
+    pub async fn set_deploy_events_cursor(
+        &mut self,
+        cursor: DeployEventCursor,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                contract_verification_deploy_events_cursor (fake_key, miniblock_number, event_index_in_block, updated_at)
+            VALUES
+                (TRUE, $1, $2, NOW())
+            ON CONFLICT (fake_key) DO
+            UPDATE
+            SET
+                miniblock_number = excluded.miniblock_number,
+                event_index_in_block = excluded.event_index_in_block,
+                updated_at = NOW()
+            "#,
+            i64::from(cursor.l2_block_number.0),
+            cursor.event_index_in_block as i32
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Returns up to `limit` contract deployments following the `after` position, ordered by their position.
+    /// For each deployed contract that is not verified, the address of a verified contract with the same bytecode
+    /// hash is returned if there is one.
+    pub async fn get_deployed_contracts_with_verified_bytecode(
+        &mut self,
+        after: Option<DeployEventCursor>,
+        limit: usize,
+    ) -> sqlx::Result<Vec<DeployedContract>> {
+        let (after_l2_block, after_event_index) = after.map_or((-1, -1), |cursor| {
+            (
+                i64::from(cursor.l2_block_number.0),
+                cursor.event_index_in_block as i32,
+            )
+        });
+        // Contract deployer address and the deploy event signature are inlined so that the partial index
+        // on deploy events is used.
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                deploy_event.miniblock_number,
+                deploy_event.event_index_in_block,
+                deploy_event.topic4 AS contract_address,
+                EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        contracts_verification_info
+                    WHERE
+                        contracts_verification_info.address = SUBSTRING(deploy_event.topic4 FROM 13)
+                ) AS "is_verified!",
+                (
+                    SELECT
+                        contracts_verification_info.address
+                    FROM
+                        events verified_deploy_event
+                        JOIN contracts_verification_info ON contracts_verification_info.address = SUBSTRING(verified_deploy_event.topic4 FROM 13)
+                    WHERE
+                        verified_deploy_event.address = '\x0000000000000000000000000000000000008006'
+                        AND verified_deploy_event.topic1 = '\x290afdae231a3fc0bbae8b1af63698b0a1d79b21ad17df0342dfb952fe74f8e5'
+                        AND verified_deploy_event.topic3 = deploy_event.topic3
+                    LIMIT
+                        1
+                ) AS verified_address
+            FROM
+                events deploy_event
+            WHERE
+                deploy_event.address = '\x0000000000000000000000000000000000008006'
+                AND deploy_event.topic1 = '\x290afdae231a3fc0bbae8b1af63698b0a1d79b21ad17df0342dfb952fe74f8e5'
+                AND (deploy_event.miniblock_number, deploy_event.event_index_in_block) > ($1, $2)
+            ORDER BY
+                deploy_event.miniblock_number,
+                deploy_event.event_index_in_block
+            LIMIT
+                $3
+            "#,
+            after_l2_block,
+            after_event_index,
+            limit as i64
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| DeployedContract {
+                cursor: DeployEventCursor {
+                    l2_block_number: L2BlockNumber(row.miniblock_number as u32),
+                    event_index_in_block: row.event_index_in_block as u32,
+                },
+                address: h256_to_account_address(&H256::from_slice(&row.contract_address)),
+                verified_address: row
+                    .verified_address
+                    .filter(|_| !row.is_verified)
+                    .map(|address| Address::from_slice(&address)),
+            })
+            .collect())
+    }
+
     pub async fn save_verification_error(
         &mut self,
         id: usize,
@@ -484,7 +665,8 @@ impl ContractVerificationDal<'_, '_> {
                 optimizer_mode,
                 constructor_arguments,
                 is_system,
-                force_evmla
+                force_evmla,
+                compiler_output
             FROM
                 contract_verification_requests
             WHERE
@@ -526,4 +708,200 @@ impl ContractVerificationDal<'_, '_> {
         };
         Ok(Some(serde_json::from_value(info).context("invalid info")?))
     }
+
+    /// Returns ABIs of verified contracts among the provided addresses. Unverified contracts are omitted.
+    pub async fn get_contract_abis(
+        &mut self,
+        addresses: &[Address],
+    ) -> anyhow::Result<HashMap<Address, serde_json::Value>> {
+        let addresses: Vec<_> = addresses.iter().map(Address::as_bytes).collect();
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                address,
+                verification_info -> 'artifacts' -> 'abi' AS abi
+            FROM
+                contracts_verification_info
+            WHERE
+                address = ANY ($1)
+            "#,
+            &addresses as &[&[u8]],
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| Some((Address::from_slice(&row.address), row.abi?)))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{
+        contract_verification_api::{
+            CompilationArtifacts, CompilerVersions, SourceCodeData, VerificationMatch,
+        },
+        tx::IncludedTxLocation,
+        L1BatchNumber, ProtocolVersion, VmEvent,
+    };
+
+    use super::*;
+    use crate::{tests::create_l2_block_header, ConnectionPool, CoreDal};
+
+    fn deploy_event(index: u32, bytecode_hash: H256, contract_address: Address) -> VmEvent {
+        VmEvent {
+            location: (L1BatchNumber(1), index),
+            address: CONTRACT_DEPLOYER_ADDRESS,
+            indexed_topics: vec![
+                *DEPLOY_EVENT_SIGNATURE,
+                address_to_h256(&Address::repeat_byte(0xff)),
+                bytecode_hash,
+                address_to_h256(&contract_address),
+            ],
+            value: vec![],
+        }
+    }
+
+    fn verification_info(contract_address: Address) -> VerificationInfo {
+        VerificationInfo {
+            request: VerificationRequest {
+                id: 1,
+                req: VerificationIncomingRequest {
+                    contract_address,
+                    source_code_data: SourceCodeData::SolSingleFile("contract Counter {}".into()),
+                    contract_name: "Counter".to_owned(),
+                    compiler_versions: CompilerVersions::Solc {
+                        compiler_zksolc_version: "v1.5.0".to_owned(),
+                        compiler_solc_version: "0.8.24".to_owned(),
+                    },
+                    optimization_used: true,
+                    optimizer_mode: None,
+                    constructor_arguments: Default::default(),
+                    is_system: false,
+                    force_evmla: false,
+                    compiler_output: None,
+                },
+            },
+            artifacts: CompilationArtifacts {
+                bytecode: vec![0; 32],
+                abi: serde_json::json!([]),
+            },
+            verified_at: chrono::Utc::now(),
+            match_type: VerificationMatch::Full,
+        }
+    }
+
+    #[tokio::test]
+    async fn getting_deployed_contracts_with_verified_bytecode() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .insert_l2_block(&create_l2_block_header(1))
+            .await
+            .unwrap();
+
+        let verified_address = Address::repeat_byte(1);
+        let clone_address = Address::repeat_byte(2);
+        let other_address = Address::repeat_byte(3);
+        let events = [
+            deploy_event(0, H256::repeat_byte(1), verified_address),
+            deploy_event(1, H256::repeat_byte(1), clone_address),
+            deploy_event(2, H256::repeat_byte(2), other_address),
+        ];
+        let location = IncludedTxLocation {
+            tx_hash: H256::repeat_byte(1),
+            tx_index_in_l2_block: 0,
+            tx_initiator_address: Address::default(),
+        };
+        conn.events_dal()
+            .save_events(L2BlockNumber(1), &[(location, events.iter().collect())])
+            .await
+            .unwrap();
+
+        let dal = &mut conn.contract_verification_dal();
+        let contracts = dal
+            .get_deployed_contracts_with_verified_bytecode(None, 10)
+            .await
+            .unwrap();
+        assert_eq!(contracts.len(), 3);
+        assert!(
+            contracts
+                .iter()
+                .all(|contract| contract.verified_address.is_none()),
+            "{contracts:?}"
+        );
+
+        dal.save_verification_info(verification_info(verified_address))
+            .await
+            .unwrap();
+        let contracts = dal
+            .get_deployed_contracts_with_verified_bytecode(None, 10)
+            .await
+            .unwrap();
+        let addresses: Vec<_> = contracts
+            .iter()
+            .map(|contract| (contract.address, contract.verified_address))
+            .collect();
+        assert_eq!(
+            addresses,
+            [
+                (verified_address, None),
+                (clone_address, Some(verified_address)),
+                (other_address, None),
+            ]
+        );
+
+        let cursor = contracts[0].cursor;
+        assert_eq!(
+            cursor,
+            DeployEventCursor {
+                l2_block_number: L2BlockNumber(1),
+                event_index_in_block: 0,
+            }
+        );
+        assert_eq!(dal.get_deploy_events_cursor().await.unwrap(), None);
+        dal.set_deploy_events_cursor(cursor).await.unwrap();
+        assert_eq!(dal.get_deploy_events_cursor().await.unwrap(), Some(cursor));
+        let contracts = dal
+            .get_deployed_contracts_with_verified_bytecode(Some(cursor), 1)
+            .await
+            .unwrap();
+        assert_eq!(contracts.len(), 1);
+        assert_eq!(contracts[0].address, clone_address);
+
+        let reused = dal
+            .save_reused_verification_info(verification_info(clone_address))
+            .await
+            .unwrap();
+        assert!(reused);
+        let contracts = dal
+            .get_deployed_contracts_with_verified_bytecode(Some(cursor), 10)
+            .await
+            .unwrap();
+        assert_eq!(contracts.len(), 2);
+        assert!(
+            contracts
+                .iter()
+                .all(|contract| contract.verified_address.is_none()),
+            "{contracts:?}"
+        );
+
+        let last_cursor = contracts[1].cursor;
+        dal.set_deploy_events_cursor(last_cursor).await.unwrap();
+        assert_eq!(
+            dal.get_deploy_events_cursor().await.unwrap(),
+            Some(last_cursor)
+        );
+        let contracts = dal
+            .get_deployed_contracts_with_verified_bytecode(Some(last_cursor), 10)
+            .await
+            .unwrap();
+        assert!(contracts.is_empty(), "{contracts:?}");
+    }
 }
@@ -5,7 +5,8 @@ use sqlx::types::chrono::{DateTime, Utc};
 use zksync_db_connection::{connection::Connection, interpolate_query, match_query_as};
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
-    eth_sender::{EthTx, EthTxBlobSidecar, TxHistory, TxHistoryToSend},
+    api::{L1BatchSubmissionDetails, L1SubmissionAttempt, L1SubmissionStageDetails},
+    eth_sender::{EthTx, EthTxBlobSidecar, EthTxReceiptPrices, TxHistory, TxHistoryToSend},
     Address, L1BatchNumber, H256, U256,
 };
 
@@ -355,6 +356,201 @@ impl EthSenderDal<'_, '_> {
         Ok(())
     }
 
+    /// Saves gas prices reported in the receipt of a mined L1 transaction.
+    pub async fn save_receipt_prices(
+        &mut self,
+        eth_tx_id: u32,
+        prices: &EthTxReceiptPrices,
+    ) -> anyhow::Result<()> {
+        let to_i64 = |value: Option<U256>, name: &str| {
+            value
+                .map(|value| {
+                    i64::try_from(value)
+                        .map_err(|err| anyhow::anyhow!("Can't convert `{name}` to i64: {err}"))
+                })
+                .transpose()
+        };
+        let effective_gas_price = to_i64(prices.effective_gas_price, "effective_gas_price")?;
+        let blob_gas_used = to_i64(prices.blob_gas_used, "blob_gas_used")?;
+        let blob_gas_price = to_i64(prices.blob_gas_price, "blob_gas_price")?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                eth_txs_receipt_prices (
+                    eth_tx_id,
+                    effective_gas_price,
+                    blob_gas_used,
+                    blob_gas_price,
+                    created_at,
+                    updated_at
+                )
+            VALUES
+                ($1, $2, $3, $4, NOW(), NOW())
+            ON CONFLICT (eth_tx_id) DO
+            UPDATE
+            SET
+                effective_gas_price = excluded.effective_gas_price,
+                blob_gas_used = excluded.blob_gas_used,
+                blob_gas_price = excluded.blob_gas_price,
+                updated_at = NOW()
+            "#,
+            eth_tx_id as i32,
+            effective_gas_price,
+            blob_gas_used,
+            blob_gas_price
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the L1 submission timeline and costs for the specified L1 batch, or `None`
+    /// if the batch doesn't exist.
+    pub async fn get_l1_batch_submission_details(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Option<L1BatchSubmissionDetails>> {
+        let Some(row) = sqlx::query!(
+            r#"
+            SELECT
+                eth_commit_tx_id,
+                eth_prove_tx_id,
+                eth_execute_tx_id
+            FROM
+                l1_batches
+            WHERE
+                number = $1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .fetch_optional(self.storage.conn())
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let mut stages = [None, None, None];
+        let eth_tx_ids = [
+            row.eth_commit_tx_id,
+            row.eth_prove_tx_id,
+            row.eth_execute_tx_id,
+        ];
+        for (stage, eth_tx_id) in stages.iter_mut().zip(eth_tx_ids) {
+            if let Some(eth_tx_id) = eth_tx_id {
+                *stage = self.get_l1_submission_stage_details(eth_tx_id).await?;
+            }
+        }
+        let [commit, prove, execute] = stages;
+        let total_cost = [&commit, &prove, &execute]
+            .into_iter()
+            .flatten()
+            .filter_map(L1SubmissionStageDetails::cost_per_l1_batch)
+            .fold(U256::zero(), |acc, cost| acc + cost);
+        Ok(Some(L1BatchSubmissionDetails {
+            number: l1_batch_number,
+            commit,
+            prove,
+            execute,
+            total_cost,
+        }))
+    }
+
+    async fn get_l1_submission_stage_details(
+        &mut self,
+        eth_tx_id: i32,
+    ) -> anyhow::Result<Option<L1SubmissionStageDetails>> {
+        let Some(tx) = sqlx::query!(
+            r#"
+            SELECT
+                eth_txs.created_at,
+                eth_txs.gas_used,
+                eth_txs_history.tx_hash AS "confirmed_tx_hash?",
+                eth_txs_history.confirmed_at AS "confirmed_at?",
+                eth_txs_receipt_prices.effective_gas_price AS "effective_gas_price?",
+                eth_txs_receipt_prices.blob_gas_used AS "blob_gas_used?",
+                eth_txs_receipt_prices.blob_gas_price AS "blob_gas_price?",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        l1_batches
+                    WHERE
+                        eth_commit_tx_id = $1
+                        OR eth_prove_tx_id = $1
+                        OR eth_execute_tx_id = $1
+                ) AS "l1_batch_count!"
+            FROM
+                eth_txs
+                LEFT JOIN eth_txs_history ON eth_txs.confirmed_eth_tx_history_id = eth_txs_history.id
+                LEFT JOIN eth_txs_receipt_prices ON eth_txs_receipt_prices.eth_tx_id = eth_txs.id
+            WHERE
+                eth_txs.id = $1
+            "#,
+            eth_tx_id
+        )
+        .fetch_optional(self.storage.conn())
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let attempts = sqlx::query!(
+            r#"
+            SELECT
+                tx_hash,
+                base_fee_per_gas,
+                priority_fee_per_gas,
+                blob_base_fee_per_gas,
+                sent_at_block,
+                sent_at,
+                confirmed_at
+            FROM
+                eth_txs_history
+            WHERE
+                eth_tx_id = $1
+            ORDER BY
+                created_at
+            "#,
+            eth_tx_id
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        let parse_hash = |hash: &str| H256::from_str(hash).context("invalid tx_hash");
+        let attempts = attempts
+            .into_iter()
+            .map(|attempt| {
+                anyhow::Ok(L1SubmissionAttempt {
+                    tx_hash: parse_hash(&attempt.tx_hash)?,
+                    base_fee_per_gas: attempt.base_fee_per_gas as u64,
+                    priority_fee_per_gas: attempt.priority_fee_per_gas as u64,
+                    blob_base_fee_per_gas: attempt.blob_base_fee_per_gas.map(|fee| fee as u64),
+                    sent_at_block: attempt.sent_at_block.map(|block| block as u32),
+                    sent_at: attempt.sent_at.map(|time| time.and_utc()),
+                    confirmed_at: attempt.confirmed_at.map(|time| time.and_utc()),
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok(Some(L1SubmissionStageDetails {
+            eth_tx_id: eth_tx_id as u32,
+            l1_batch_count: tx.l1_batch_count as u32,
+            created_at: tx.created_at.and_utc(),
+            confirmed_tx_hash: tx
+                .confirmed_tx_hash
+                .as_deref()
+                .map(parse_hash)
+                .transpose()?,
+            confirmed_at: tx.confirmed_at.map(|time| time.and_utc()),
+            gas_used: tx.gas_used.map(|gas| gas as u64),
+            effective_gas_price: tx.effective_gas_price.map(|price| price as u64),
+            blob_gas_used: tx.blob_gas_used.map(|gas| gas as u64),
+            blob_gas_price: tx.blob_gas_price.map(|price| price as u64),
+            attempts,
+        }))
+    }
+
     pub async fn get_confirmed_tx_hash_by_eth_tx_id(
         &mut self,
         eth_tx_id: u32,
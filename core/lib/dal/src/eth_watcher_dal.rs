@@ -0,0 +1,248 @@
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::H256;
+
+use crate::Core;
+
+/// L1 block processed by the Ethereum watcher. Processed blocks are used to detect L1 reorgs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessedL1Block {
+    pub number: u64,
+    pub hash: H256,
+    /// Digests of the events ingested from this block.
+    pub event_digests: Vec<H256>,
+}
+
+/// DAL persisting cursors of L1 event processors and processed L1 blocks used by the Ethereum watcher.
+#[derive(Debug)]
+pub struct EthWatcherDal<'c, 'a> {
+    pub(crate) storage: &'c mut Connection<'a, Core>,
+}
+
+impl EthWatcherDal<'_, '_> {
+    /// Returns the last L1 block processed by the specified event processor, or `None`
+    /// if the processor has never persisted its cursor.
+    pub async fn get_last_processed_l1_block(
+        &mut self,
+        processor_name: &str,
+    ) -> DalResult<Option<u64>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                last_processed_l1_block
+            FROM
+                eth_watcher_cursors
+            WHERE
+                processor_name = $1
+            "#,
+            processor_name
+        )
+        .instrument("get_last_processed_l1_block")
+        .with_arg("processor_name", &processor_name)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(|row| row.last_processed_l1_block as u64))
+    }
+
+    /// Sets the last L1 block processed by the specified event processor.
+    pub async fn set_last_processed_l1_block(
+        &mut self,
+        processor_name: &str,
+        l1_block_number: u64,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                eth_watcher_cursors (processor_name, last_processed_l1_block, created_at, updated_at)
+            VALUES
+                ($1, $2, NOW(), NOW())
+            ON CONFLICT (processor_name) DO
+            UPDATE
+            SET
+                last_processed_l1_block = excluded.last_processed_l1_block,
+                updated_at = NOW()
+            "#,
+            processor_name,
+            l1_block_number as i64
+        )
+        .instrument("set_last_processed_l1_block")
+        .with_arg("processor_name", &processor_name)
+        .with_arg("l1_block_number", &l1_block_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns all processed L1 blocks ordered by their number.
+    pub async fn get_processed_l1_blocks(&mut self) -> DalResult<Vec<ProcessedL1Block>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                block_number,
+                block_hash,
+                event_digests
+            FROM
+                eth_watcher_processed_blocks
+            ORDER BY
+                block_number
+            "#
+        )
+        .instrument("get_processed_l1_blocks")
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ProcessedL1Block {
+                number: row.block_number as u64,
+                hash: H256::from_slice(&row.block_hash),
+                event_digests: row
+                    .event_digests
+                    .iter()
+                    .map(|digest| H256::from_slice(digest))
+                    .collect(),
+            })
+            .collect())
+    }
+
+    /// Inserts or replaces a processed L1 block.
+    pub async fn save_processed_l1_block(&mut self, block: &ProcessedL1Block) -> DalResult<()> {
+        let event_digests: Vec<_> = block.event_digests.iter().map(H256::as_bytes).collect();
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                eth_watcher_processed_blocks (block_number, block_hash, event_digests, created_at, updated_at)
+            VALUES
+                ($1, $2, $3, NOW(), NOW())
+            ON CONFLICT (block_number) DO
+            UPDATE
+            SET
+                block_hash = excluded.block_hash,
+                event_digests = excluded.event_digests,
+                updated_at = NOW()
+            "#,
+            block.number as i64,
+            block.hash.as_bytes(),
+            &event_digests as &[&[u8]]
+        )
+        .instrument("save_processed_l1_block")
+        .with_arg("block.number", &block.number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Removes processed L1 blocks with numbers greater than `last_retained_block` (e.g., orphaned by an L1 reorg).
+    pub async fn delete_processed_l1_blocks_after(
+        &mut self,
+        last_retained_block: u64,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM eth_watcher_processed_blocks
+            WHERE
+                block_number > $1
+            "#,
+            last_retained_block as i64
+        )
+        .instrument("delete_processed_l1_blocks_after")
+        .with_arg("last_retained_block", &last_retained_block)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Removes processed L1 blocks with numbers less than `first_retained_block`.
+    pub async fn delete_processed_l1_blocks_before(
+        &mut self,
+        first_retained_block: u64,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM eth_watcher_processed_blocks
+            WHERE
+                block_number < $1
+            "#,
+            first_retained_block as i64
+        )
+        .instrument("delete_processed_l1_blocks_before")
+        .with_arg("first_retained_block", &first_retained_block)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn persisting_eth_watcher_cursors() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.eth_watcher_dal();
+
+        assert_eq!(
+            dal.get_last_processed_l1_block("priority_ops")
+                .await
+                .unwrap(),
+            None
+        );
+        dal.set_last_processed_l1_block("priority_ops", 10)
+            .await
+            .unwrap();
+        dal.set_last_processed_l1_block("governance_upgrades", 5)
+            .await
+            .unwrap();
+        dal.set_last_processed_l1_block("priority_ops", 20)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            dal.get_last_processed_l1_block("priority_ops")
+                .await
+                .unwrap(),
+            Some(20)
+        );
+        assert_eq!(
+            dal.get_last_processed_l1_block("governance_upgrades")
+                .await
+                .unwrap(),
+            Some(5)
+        );
+    }
+
+    #[tokio::test]
+    async fn persisting_processed_l1_blocks() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.eth_watcher_dal();
+        assert!(dal.get_processed_l1_blocks().await.unwrap().is_empty());
+
+        let blocks: Vec<_> = (10..15)
+            .map(|number| ProcessedL1Block {
+                number,
+                hash: H256::from_low_u64_be(number),
+                event_digests: (0..number % 3).map(H256::from_low_u64_be).collect(),
+            })
+            .collect();
+        for block in &blocks {
+            dal.save_processed_l1_block(block).await.unwrap();
+        }
+        assert_eq!(dal.get_processed_l1_blocks().await.unwrap(), blocks);
+
+        let replaced_block = ProcessedL1Block {
+            number: 12,
+            hash: H256::repeat_byte(0xff),
+            event_digests: vec![H256::repeat_byte(0xee)],
+        };
+        dal.save_processed_l1_block(&replaced_block).await.unwrap();
+        dal.delete_processed_l1_blocks_after(12).await.unwrap();
+        dal.delete_processed_l1_blocks_before(11).await.unwrap();
+        assert_eq!(
+            dal.get_processed_l1_blocks().await.unwrap(),
+            [blocks[1].clone(), replaced_block]
+        );
+    }
+}
@@ -14,8 +14,9 @@ pub use zksync_db_connection::{
 use crate::{
     blocks_dal::BlocksDal, blocks_web3_dal::BlocksWeb3Dal, consensus_dal::ConsensusDal,
     contract_verification_dal::ContractVerificationDal, eth_sender_dal::EthSenderDal,
-    events_dal::EventsDal, events_web3_dal::EventsWeb3Dal, factory_deps_dal::FactoryDepsDal,
-    proof_generation_dal::ProofGenerationDal, protocol_versions_dal::ProtocolVersionsDal,
+    eth_watcher_dal::EthWatcherDal, events_dal::EventsDal, events_web3_dal::EventsWeb3Dal,
+    factory_deps_dal::FactoryDepsDal, proof_generation_dal::ProofGenerationDal,
+    protocol_versions_dal::ProtocolVersionsDal,
     protocol_versions_web3_dal::ProtocolVersionsWeb3Dal, pruning_dal::PruningDal,
     snapshot_recovery_dal::SnapshotRecoveryDal, snapshots_creator_dal::SnapshotsCreatorDal,
     snapshots_dal::SnapshotsDal, storage_logs_dal::StorageLogsDal,
@@ -32,6 +33,7 @@ pub mod consensus;
 pub mod consensus_dal;
 pub mod contract_verification_dal;
 pub mod eth_sender_dal;
+pub mod eth_watcher_dal;
 pub mod events_dal;
 pub mod events_web3_dal;
 pub mod factory_deps_dal;
@@ -86,6 +88,8 @@ where
 
     fn eth_sender_dal(&mut self) -> EthSenderDal<'_, 'a>;
 
+    fn eth_watcher_dal(&mut self) -> EthWatcherDal<'_, 'a>;
+
     fn events_dal(&mut self) -> EventsDal<'_, 'a>;
 
     fn events_web3_dal(&mut self) -> EventsWeb3Dal<'_, 'a>;
@@ -164,6 +168,10 @@ impl<'a> CoreDal<'a> for Connection<'a, Core> {
         EthSenderDal { storage: self }
     }
 
+    fn eth_watcher_dal(&mut self) -> EthWatcherDal<'_, 'a> {
+        EthWatcherDal { storage: self }
+    }
+
     fn events_dal(&mut self) -> EventsDal<'_, 'a> {
         EventsDal { storage: self }
     }
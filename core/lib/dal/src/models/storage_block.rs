@@ -350,6 +350,7 @@ pub(crate) struct StorageL1BatchDetails {
     pub fair_pubdata_price: Option<i64>,
     pub bootloader_code_hash: Option<Vec<u8>>,
     pub default_aa_code_hash: Option<Vec<u8>>,
+    pub rollup_last_leaf_index: Option<i64>,
 }
 
 impl From<StorageL1BatchDetails> for api::L1BatchDetails {
@@ -398,6 +399,7 @@ impl From<StorageL1BatchDetails> for api::L1BatchDetails {
         api::L1BatchDetails {
             base,
             number: L1BatchNumber(details.number as u32),
+            rollup_last_leaf_index: details.rollup_last_leaf_index.map(|index| index as u64),
         }
     }
 }
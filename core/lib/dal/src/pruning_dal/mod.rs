@@ -1,7 +1,7 @@
-use std::ops;
+use std::{collections::HashSet, ops};
 
 use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
-use zksync_types::{L1BatchNumber, L2BlockNumber};
+use zksync_types::{L1BatchNumber, L2BlockNumber, H256};
 
 use crate::Core;
 
@@ -33,6 +33,26 @@ pub struct HardPruningStats {
     pub deleted_l2_to_l1_logs: u64,
 }
 
+/// Kind of data that can be pruned separately from the rest of L1 batch data, e.g. in order to retain it for longer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PrunedDataKind {
+    /// Events and L2-to-L1 logs.
+    Events,
+    /// Transaction data (including data used in transaction receipts) and call traces.
+    Transactions,
+}
+
+impl PrunedDataKind {
+    pub const ALL: [Self; 2] = [Self::Events, Self::Transactions];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Events => "events",
+            Self::Transactions => "transactions",
+        }
+    }
+}
+
 #[derive(Debug, sqlx::Type)]
 #[sqlx(type_name = "prune_type")]
 enum PruneType {
@@ -135,11 +155,330 @@ impl PruningDal<'_, '_> {
         Ok(())
     }
 
+    /// Returns pruning info for data of the specified kind if this data is pruned separately from the rest
+    /// of L1 batch data (e.g., because it is retained for longer). `None` means that the data is pruned together
+    /// with the rest of L1 batch data, i.e., its pruning info is the one returned by [`Self::get_pruning_info()`].
+    pub async fn get_data_kind_pruning_info(
+        &mut self,
+        kind: PrunedDataKind,
+    ) -> DalResult<Option<PruningInfo>> {
+        let pruning_info = sqlx::query!(
+            r#"
+            SELECT
+                soft.pruned_l1_batch AS last_soft_pruned_l1_batch,
+                soft.pruned_miniblock AS last_soft_pruned_miniblock,
+                hard.pruned_l1_batch AS last_hard_pruned_l1_batch,
+                hard.pruned_miniblock AS last_hard_pruned_miniblock
+            FROM
+                pruning_data_kinds_log AS soft
+                INNER JOIN pruning_data_kinds_log AS hard ON hard.data_kind = soft.data_kind
+                AND hard.type = 'Hard'
+            WHERE
+                soft.data_kind = $1
+                AND soft.type = 'Soft'
+            "#,
+            kind.as_str()
+        )
+        .map(|row| PruningInfo {
+            last_soft_pruned_l1_batch: row
+                .last_soft_pruned_l1_batch
+                .map(|num| L1BatchNumber(num as u32)),
+            last_soft_pruned_l2_block: row
+                .last_soft_pruned_miniblock
+                .map(|num| L2BlockNumber(num as u32)),
+            last_hard_pruned_l1_batch: row
+                .last_hard_pruned_l1_batch
+                .map(|num| L1BatchNumber(num as u32)),
+            last_hard_pruned_l2_block: row
+                .last_hard_pruned_miniblock
+                .map(|num| L2BlockNumber(num as u32)),
+        })
+        .instrument("get_data_kind_pruning_info")
+        .with_arg("kind", &kind)
+        .report_latency()
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(pruning_info)
+    }
+
+    /// Starts pruning data of the specified kind separately from the rest of L1 batch data. Initial pruning info
+    /// for the data is copied from the current [`PruningInfo`]. No-op if the data is already pruned separately.
+    pub async fn start_pruning_data_kind_separately(
+        &mut self,
+        kind: PrunedDataKind,
+    ) -> DalResult<()> {
+        let pruning_info = self.get_pruning_info().await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                pruning_data_kinds_log (
+                    data_kind,
+                    TYPE,
+                    pruned_l1_batch,
+                    pruned_miniblock,
+                    created_at,
+                    updated_at
+                )
+            VALUES
+                ($1, 'Soft', $2, $3, NOW(), NOW()),
+                ($1, 'Hard', $4, $5, NOW(), NOW())
+            ON CONFLICT (data_kind, TYPE) DO NOTHING
+            "#,
+            kind.as_str(),
+            pruning_info
+                .last_soft_pruned_l1_batch
+                .map(|number| i64::from(number.0)),
+            pruning_info
+                .last_soft_pruned_l2_block
+                .map(|number| i64::from(number.0)),
+            pruning_info
+                .last_hard_pruned_l1_batch
+                .map(|number| i64::from(number.0)),
+            pruning_info
+                .last_hard_pruned_l2_block
+                .map(|number| i64::from(number.0)),
+        )
+        .instrument("start_pruning_data_kind_separately")
+        .with_arg("kind", &kind)
+        .with_arg("pruning_info", &pruning_info)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Stops pruning data of the specified kind separately. The caller is responsible for ensuring that the data kind
+    /// has caught up with the rest of L1 batch data (i.e., its pruning info is equal to [`Self::get_pruning_info()`]).
+    pub async fn stop_pruning_data_kind_separately(
+        &mut self,
+        kind: PrunedDataKind,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM pruning_data_kinds_log
+            WHERE
+                data_kind = $1
+            "#,
+            kind.as_str()
+        )
+        .instrument("stop_pruning_data_kind_separately")
+        .with_arg("kind", &kind)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Soft-prunes data of the specified kind, which must be pruned separately (see [`Self::start_pruning_data_kind_separately()`]).
+    pub async fn soft_prune_data_kind(
+        &mut self,
+        kind: PrunedDataKind,
+        last_l1_batch_to_prune: L1BatchNumber,
+        last_l2_block_to_prune: L2BlockNumber,
+    ) -> DalResult<()> {
+        self.update_data_kind_pruning_log(
+            kind,
+            PruneType::Soft,
+            last_l1_batch_to_prune,
+            last_l2_block_to_prune,
+        )
+        .await
+    }
+
+    async fn update_data_kind_pruning_log(
+        &mut self,
+        kind: PrunedDataKind,
+        prune_type: PruneType,
+        last_l1_batch_to_prune: L1BatchNumber,
+        last_l2_block_to_prune: L2BlockNumber,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE pruning_data_kinds_log
+            SET
+                pruned_l1_batch = $3,
+                pruned_miniblock = $4,
+                updated_at = NOW()
+            WHERE
+                data_kind = $1
+                AND TYPE = $2
+            "#,
+            kind.as_str(),
+            prune_type as PruneType,
+            i64::from(last_l1_batch_to_prune.0),
+            i64::from(last_l2_block_to_prune.0),
+        )
+        .instrument("update_data_kind_pruning_log")
+        .with_arg("kind", &kind)
+        .with_arg("last_l1_batch_to_prune", &last_l1_batch_to_prune)
+        .with_arg("last_l2_block_to_prune", &last_l2_block_to_prune)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Records that data for the specified L1 batch was archived to the object store, together with hashes
+    /// of the archived transactions. Should be called before the L1 batch is hard-pruned.
+    pub async fn insert_archived_l1_batch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+        tx_hashes: &[H256],
+    ) -> DalResult<()> {
+        let mut transaction = self.storage.start_transaction().await?;
+        let tx_hashes: Vec<_> = tx_hashes.iter().map(H256::as_bytes).collect();
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                pruned_data_archive_transactions (tx_hash, l1_batch_number, created_at, updated_at)
+            SELECT
+                u.tx_hash,
+                $2,
+                NOW(),
+                NOW()
+            FROM
+                UNNEST($1::bytea[]) AS u (tx_hash)
+            ON CONFLICT (tx_hash) DO NOTHING
+            "#,
+            &tx_hashes as &[&[u8]],
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("insert_archived_l1_batch#transactions")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("tx_hashes.len", &tx_hashes.len())
+        .report_latency()
+        .execute(&mut transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                pruned_data_archive (
+                    l1_batch_number,
+                    first_miniblock,
+                    last_miniblock,
+                    created_at,
+                    updated_at
+                )
+            VALUES
+                ($1, $2, $3, NOW(), NOW())
+            ON CONFLICT (l1_batch_number) DO NOTHING
+            "#,
+            i64::from(l1_batch_number.0),
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0),
+        )
+        .instrument("insert_archived_l1_batch")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("l2_blocks", &l2_blocks)
+        .report_latency()
+        .execute(&mut transaction)
+        .await?;
+        transaction.commit().await
+    }
+
+    /// Returns the archived L1 batch containing the transaction with the specified hash, or `None`
+    /// if the transaction is not archived.
+    pub async fn get_archived_l1_batch_for_transaction(
+        &mut self,
+        tx_hash: H256,
+    ) -> DalResult<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                pruned_data_archive.l1_batch_number
+            FROM
+                pruned_data_archive_transactions
+                JOIN pruned_data_archive ON pruned_data_archive.l1_batch_number = pruned_data_archive_transactions.l1_batch_number
+            WHERE
+                pruned_data_archive_transactions.tx_hash = $1
+            "#,
+            tx_hash.as_bytes()
+        )
+        .instrument("get_archived_l1_batch_for_transaction")
+        .with_arg("tx_hash", &tx_hash)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(|row| L1BatchNumber(row.l1_batch_number as u32)))
+    }
+
+    /// Returns the archived L1 batch containing the specified L2 block, or `None` if the block is not archived.
+    pub async fn get_archived_l1_batch_for_l2_block(
+        &mut self,
+        l2_block_number: L2BlockNumber,
+    ) -> DalResult<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number
+            FROM
+                pruned_data_archive
+            WHERE
+                last_miniblock >= $1
+                AND first_miniblock <= $1
+            ORDER BY
+                last_miniblock
+            LIMIT
+                1
+            "#,
+            i64::from(l2_block_number.0)
+        )
+        .instrument("get_archived_l1_batch_for_l2_block")
+        .with_arg("l2_block_number", &l2_block_number)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(|row| L1BatchNumber(row.l1_batch_number as u32)))
+    }
+
+    /// Returns archived L1 batches containing L2 blocks in the specified range together with their L2 block ranges,
+    /// ordered by the batch number. Returns at most `limit` batches.
+    pub async fn get_archived_l1_batches(
+        &mut self,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+        limit: usize,
+    ) -> DalResult<Vec<(L1BatchNumber, ops::RangeInclusive<L2BlockNumber>)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number,
+                first_miniblock,
+                last_miniblock
+            FROM
+                pruned_data_archive
+            WHERE
+                last_miniblock >= $1
+                AND first_miniblock <= $2
+            ORDER BY
+                l1_batch_number
+            LIMIT
+                $3
+            "#,
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0),
+            limit as i64
+        )
+        .instrument("get_archived_l1_batches")
+        .with_arg("l2_blocks", &l2_blocks)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let l2_blocks = L2BlockNumber(row.first_miniblock as u32)
+                    ..=L2BlockNumber(row.last_miniblock as u32);
+                (L1BatchNumber(row.l1_batch_number as u32), l2_blocks)
+            })
+            .collect())
+    }
+
     pub async fn hard_prune_batches_range(
         &mut self,
         last_l1_batch_to_prune: L1BatchNumber,
         last_l2_block_to_prune: L2BlockNumber,
     ) -> DalResult<HardPruningStats> {
+        let last_hard_pruned_l2_block = self.get_pruning_info().await?.last_hard_pruned_l2_block;
         let row = sqlx::query!(
             r#"
             SELECT
@@ -157,45 +496,115 @@ impl PruningDal<'_, '_> {
         .fetch_one(self.storage)
         .await?;
 
+        let mut separately_pruned_kinds = HashSet::new();
+        for kind in PrunedDataKind::ALL {
+            if self.get_data_kind_pruning_info(kind).await?.is_some() {
+                separately_pruned_kinds.insert(kind);
+            }
+        }
+
         // We don't have any L2 blocks available when recovering from a snapshot
-        let stats = if let Some(first_l2_block_to_prune) = row.first_miniblock_to_prune {
-            let first_l2_block_to_prune = L2BlockNumber(first_l2_block_to_prune as u32);
-
-            let deleted_events = self
-                .delete_events(first_l2_block_to_prune..=last_l2_block_to_prune)
-                .await?;
-            let deleted_l2_to_l1_logs = self
-                .delete_l2_to_l1_logs(first_l2_block_to_prune..=last_l2_block_to_prune)
-                .await?;
-            let deleted_call_traces = self
-                .delete_call_traces(first_l2_block_to_prune..=last_l2_block_to_prune)
-                .await?;
-            self.clear_transaction_fields(first_l2_block_to_prune..=last_l2_block_to_prune)
-                .await?;
-
-            let deleted_storage_logs = self
-                .prune_storage_logs(first_l2_block_to_prune..=last_l2_block_to_prune)
-                .await?;
-            let deleted_l1_batches = self.delete_l1_batches(last_l1_batch_to_prune).await?;
-            let deleted_l2_blocks = self.delete_l2_blocks(last_l2_block_to_prune).await?;
-
-            HardPruningStats {
-                deleted_l1_batches,
-                deleted_l2_blocks,
-                deleted_events,
-                deleted_l2_to_l1_logs,
-                deleted_call_traces,
-                deleted_storage_logs,
+        let mut stats = HardPruningStats::default();
+        if let Some(first_l2_block_to_prune) = row.first_miniblock_to_prune {
+            let mut first_l2_block_to_prune = L2BlockNumber(first_l2_block_to_prune as u32);
+            // L2 blocks preceding the hard-pruned one may be retained if some kinds of data are pruned separately.
+            if let Some(last_hard_pruned_l2_block) = last_hard_pruned_l2_block {
+                first_l2_block_to_prune =
+                    first_l2_block_to_prune.max(last_hard_pruned_l2_block + 1);
             }
-        } else {
-            HardPruningStats::default()
-        };
+            let l2_blocks_to_prune = first_l2_block_to_prune..=last_l2_block_to_prune;
+
+            for kind in PrunedDataKind::ALL {
+                if !separately_pruned_kinds.contains(&kind) {
+                    self.prune_data_kind(kind, l2_blocks_to_prune.clone(), &mut stats)
+                        .await?;
+                }
+            }
+            stats.deleted_storage_logs = self.prune_storage_logs(l2_blocks_to_prune).await?;
+        }
 
         self.insert_hard_pruning_log(last_l1_batch_to_prune, last_l2_block_to_prune)
             .await?;
+        self.hard_prune_data_kinds_inner(&mut stats).await?;
         Ok(stats)
     }
 
+    /// Hard-prunes separately pruned data kinds up to their soft-pruned L1 batch, and L1 batch / L2 block headers
+    /// no longer needed by any kind of data. Unlike [`Self::hard_prune_batches_range()`], doesn't touch other data.
+    pub async fn hard_prune_data_kinds(&mut self) -> DalResult<HardPruningStats> {
+        let mut stats = HardPruningStats::default();
+        self.hard_prune_data_kinds_inner(&mut stats).await?;
+        Ok(stats)
+    }
+
+    async fn hard_prune_data_kinds_inner(&mut self, stats: &mut HardPruningStats) -> DalResult<()> {
+        let pruning_info = self.get_pruning_info().await?;
+        // Headers are retained as long as they are needed for any kind of data (e.g., to return events via the API).
+        let mut last_l1_batch_to_prune = pruning_info.last_hard_pruned_l1_batch;
+        let mut last_l2_block_to_prune = pruning_info.last_hard_pruned_l2_block;
+
+        for kind in PrunedDataKind::ALL {
+            let Some(info) = self.get_data_kind_pruning_info(kind).await? else {
+                continue;
+            };
+            if let (Some(soft_pruned_l1_batch), Some(soft_pruned_l2_block)) = (
+                info.last_soft_pruned_l1_batch,
+                info.last_soft_pruned_l2_block,
+            ) {
+                if info.last_hard_pruned_l2_block != Some(soft_pruned_l2_block) {
+                    let first_l2_block_to_prune = info
+                        .last_hard_pruned_l2_block
+                        .map_or(L2BlockNumber(0), |number| number + 1);
+                    self.prune_data_kind(
+                        kind,
+                        first_l2_block_to_prune..=soft_pruned_l2_block,
+                        stats,
+                    )
+                    .await?;
+                    self.update_data_kind_pruning_log(
+                        kind,
+                        PruneType::Hard,
+                        soft_pruned_l1_batch,
+                        soft_pruned_l2_block,
+                    )
+                    .await?;
+                }
+            }
+            // `None` is less than any `Some(_)`, so headers won't be pruned if any data kind wasn't pruned yet.
+            last_l1_batch_to_prune = last_l1_batch_to_prune.min(info.last_soft_pruned_l1_batch);
+            last_l2_block_to_prune = last_l2_block_to_prune.min(info.last_soft_pruned_l2_block);
+        }
+
+        if let (Some(last_l1_batch_to_prune), Some(last_l2_block_to_prune)) =
+            (last_l1_batch_to_prune, last_l2_block_to_prune)
+        {
+            stats.deleted_l1_batches += self.delete_l1_batches(last_l1_batch_to_prune).await?;
+            stats.deleted_l2_blocks += self.delete_l2_blocks(last_l2_block_to_prune).await?;
+        }
+        Ok(())
+    }
+
+    async fn prune_data_kind(
+        &mut self,
+        kind: PrunedDataKind,
+        l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+        stats: &mut HardPruningStats,
+    ) -> DalResult<()> {
+        match kind {
+            PrunedDataKind::Events => {
+                stats.deleted_events += self.delete_events(l2_blocks_to_prune.clone()).await?;
+                stats.deleted_l2_to_l1_logs +=
+                    self.delete_l2_to_l1_logs(l2_blocks_to_prune).await?;
+            }
+            PrunedDataKind::Transactions => {
+                stats.deleted_call_traces +=
+                    self.delete_call_traces(l2_blocks_to_prune.clone()).await?;
+                self.clear_transaction_fields(l2_blocks_to_prune).await?;
+            }
+        }
+        Ok(())
+    }
+
     async fn delete_events(
         &mut self,
         l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
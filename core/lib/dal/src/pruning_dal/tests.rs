@@ -452,6 +452,88 @@ async fn l1_batches_can_be_hard_pruned() {
     );
 }
 
+#[tokio::test]
+async fn separately_pruned_data_is_retained() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    insert_realistic_l1_batches(&mut conn, 10).await;
+
+    let mut dal = conn.pruning_dal();
+    assert_eq!(
+        dal.get_data_kind_pruning_info(PrunedDataKind::Events)
+            .await
+            .unwrap(),
+        None
+    );
+    dal.start_pruning_data_kind_separately(PrunedDataKind::Events)
+        .await
+        .unwrap();
+    assert_eq!(
+        dal.get_data_kind_pruning_info(PrunedDataKind::Events)
+            .await
+            .unwrap(),
+        Some(PruningInfo::default())
+    );
+
+    dal.soft_prune_batches_range(L1BatchNumber(5), L2BlockNumber(11))
+        .await
+        .unwrap();
+    let stats = dal
+        .hard_prune_batches_range(L1BatchNumber(5), L2BlockNumber(11))
+        .await
+        .unwrap();
+    assert_eq!(stats.deleted_events, 0);
+    assert_eq!(stats.deleted_l2_to_l1_logs, 0);
+    // Headers must be retained since they are required to access events.
+    assert_eq!(stats.deleted_l1_batches, 0);
+    assert_eq!(stats.deleted_l2_blocks, 0);
+    assert_l1_batch_objects_exists(&mut conn, L1BatchNumber(0)..=L1BatchNumber(10)).await;
+
+    let mut dal = conn.pruning_dal();
+    dal.soft_prune_data_kind(PrunedDataKind::Events, L1BatchNumber(3), L2BlockNumber(7))
+        .await
+        .unwrap();
+    let stats = dal.hard_prune_data_kinds().await.unwrap();
+    assert_eq!(stats.deleted_events, 40);
+    assert_eq!(stats.deleted_l2_to_l1_logs, 40);
+    assert_eq!(stats.deleted_l1_batches, 4);
+    assert_eq!(stats.deleted_l2_blocks, 8);
+    assert_eq!(stats.deleted_storage_logs, 0);
+    assert_eq!(
+        dal.get_data_kind_pruning_info(PrunedDataKind::Events)
+            .await
+            .unwrap(),
+        Some(PruningInfo {
+            last_soft_pruned_l1_batch: Some(L1BatchNumber(3)),
+            last_soft_pruned_l2_block: Some(L2BlockNumber(7)),
+            last_hard_pruned_l1_batch: Some(L1BatchNumber(3)),
+            last_hard_pruned_l2_block: Some(L2BlockNumber(7)),
+        })
+    );
+    assert_l1_batch_objects_dont_exist(&mut conn, L1BatchNumber(0)..=L1BatchNumber(3)).await;
+    assert_l1_batch_objects_exists(&mut conn, L1BatchNumber(4)..=L1BatchNumber(10)).await;
+
+    // Transactions are pruned together with the rest of the data.
+    assert_eq!(
+        conn.pruning_dal()
+            .get_data_kind_pruning_info(PrunedDataKind::Transactions)
+            .await
+            .unwrap(),
+        None
+    );
+    conn.pruning_dal()
+        .stop_pruning_data_kind_separately(PrunedDataKind::Events)
+        .await
+        .unwrap();
+    assert_eq!(
+        conn.pruning_dal()
+            .get_data_kind_pruning_info(PrunedDataKind::Events)
+            .await
+            .unwrap(),
+        None
+    );
+}
+
 #[tokio::test]
 async fn transactions_are_handled_correctly_after_pruning() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -512,3 +594,66 @@ async fn transactions_are_handled_correctly_after_pruning() {
         .unwrap();
     assert!(transaction_details.is_none(), "{transaction_details:?}");
 }
+
+#[tokio::test]
+async fn archived_l1_batches_are_looked_up_by_l2_block() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+
+    for (l1_batch_number, l2_blocks) in [(1, 1..=2), (2, 3..=3), (3, 4..=6)] {
+        let l2_blocks = L2BlockNumber(*l2_blocks.start())..=L2BlockNumber(*l2_blocks.end());
+        let tx_hashes = [H256::from_low_u64_be(l1_batch_number.into())];
+        conn.pruning_dal()
+            .insert_archived_l1_batch(L1BatchNumber(l1_batch_number), l2_blocks, &tx_hashes)
+            .await
+            .unwrap();
+    }
+
+    for (tx_hash, expected_l1_batch) in [(0, None), (1, Some(1)), (3, Some(3)), (4, None)] {
+        let l1_batch_number = conn
+            .pruning_dal()
+            .get_archived_l1_batch_for_transaction(H256::from_low_u64_be(tx_hash))
+            .await
+            .unwrap();
+        assert_eq!(l1_batch_number, expected_l1_batch.map(L1BatchNumber));
+    }
+
+    for (l2_block_number, expected_l1_batch) in [
+        (0, None),
+        (1, Some(1)),
+        (2, Some(1)),
+        (3, Some(2)),
+        (5, Some(3)),
+        (7, None),
+    ] {
+        let l1_batch_number = conn
+            .pruning_dal()
+            .get_archived_l1_batch_for_l2_block(L2BlockNumber(l2_block_number))
+            .await
+            .unwrap();
+        assert_eq!(l1_batch_number, expected_l1_batch.map(L1BatchNumber));
+    }
+
+    let l1_batches = conn
+        .pruning_dal()
+        .get_archived_l1_batches(L2BlockNumber(2)..=L2BlockNumber(5), 10)
+        .await
+        .unwrap();
+    assert_eq!(
+        l1_batches,
+        [
+            (L1BatchNumber(1), L2BlockNumber(1)..=L2BlockNumber(2)),
+            (L1BatchNumber(2), L2BlockNumber(3)..=L2BlockNumber(3)),
+            (L1BatchNumber(3), L2BlockNumber(4)..=L2BlockNumber(6)),
+        ]
+    );
+    let l1_batches = conn
+        .pruning_dal()
+        .get_archived_l1_batches(L2BlockNumber(3)..=L2BlockNumber(100), 1)
+        .await
+        .unwrap();
+    assert_eq!(
+        l1_batches,
+        [(L1BatchNumber(2), L2BlockNumber(3)..=L2BlockNumber(3))]
+    );
+}
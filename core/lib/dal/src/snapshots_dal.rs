@@ -4,7 +4,9 @@ use zksync_db_connection::{
     instrument::InstrumentExt,
 };
 use zksync_types::{
-    snapshots::{AllSnapshots, SnapshotMetadata, SnapshotVersion},
+    snapshots::{
+        AllSnapshots, SnapshotDiffManifest, SnapshotDiffMetadata, SnapshotMetadata, SnapshotVersion,
+    },
     L1BatchNumber,
 };
 
@@ -186,6 +188,145 @@ impl SnapshotsDal<'_, '_> {
         .await
     }
 
+    /// Records a differential snapshot. Should be called after all snapshot data is persisted.
+    pub async fn add_snapshot_diff(&mut self, manifest: &SnapshotDiffManifest) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                snapshot_diffs (
+                    l1_batch_number,
+                    base_l1_batch_number,
+                    storage_logs_chunk_count,
+                    created_at
+                )
+            VALUES
+                ($1, $2, $3, NOW())
+            "#,
+            i64::from(manifest.l1_batch_number.0),
+            i64::from(manifest.base_l1_batch_number.0),
+            manifest.storage_logs_chunk_count as i32
+        )
+        .instrument("add_snapshot_diff")
+        .with_arg("l1_batch_number", &manifest.l1_batch_number)
+        .with_arg("base_l1_batch_number", &manifest.base_l1_batch_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the L1 batch number of the oldest snapshot that is not fully persisted yet (i.e., has missing
+    /// storage log chunks).
+    pub async fn get_oldest_incomplete_snapshot_l1_batch_number(
+        &mut self,
+    ) -> DalResult<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MIN(l1_batch_number) AS "l1_batch_number"
+            FROM
+                snapshots
+            WHERE
+                ''::TEXT = ANY (storage_logs_filepaths)
+            "#
+        )
+        .instrument("get_oldest_incomplete_snapshot_l1_batch_number")
+        .fetch_one(self.storage)
+        .await?;
+        Ok(row
+            .l1_batch_number
+            .map(|number| L1BatchNumber(number as u32)))
+    }
+
+    /// Returns the L1 batch number of the newest differential snapshot.
+    pub async fn get_newest_snapshot_diff_l1_batch_number(
+        &mut self,
+    ) -> DalResult<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MAX(l1_batch_number) AS "l1_batch_number"
+            FROM
+                snapshot_diffs
+            "#
+        )
+        .instrument("get_newest_snapshot_diff_l1_batch_number")
+        .fetch_one(self.storage)
+        .await?;
+        Ok(row
+            .l1_batch_number
+            .map(|number| L1BatchNumber(number as u32)))
+    }
+
+    /// Returns metadata for all snapshots before the specified L1 batch number.
+    pub async fn get_snapshots_before(
+        &mut self,
+        first_retained_l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Vec<SnapshotMetadata>> {
+        sqlx::query_as!(
+            StorageSnapshotMetadata,
+            r#"
+            SELECT
+                VERSION,
+                l1_batch_number,
+                factory_deps_filepath,
+                storage_logs_filepaths
+            FROM
+                snapshots
+            WHERE
+                l1_batch_number < $1
+            ORDER BY
+                l1_batch_number
+            "#,
+            first_retained_l1_batch_number.0 as i32
+        )
+        .try_map(SnapshotMetadata::try_from)
+        .instrument("get_snapshots_before")
+        .with_arg(
+            "first_retained_l1_batch_number",
+            &first_retained_l1_batch_number,
+        )
+        .fetch_all(self.storage)
+        .await
+    }
+
+    /// Returns metadata for all differential snapshots before the specified L1 batch number.
+    pub async fn get_snapshot_diffs_before(
+        &mut self,
+        first_retained_l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Vec<SnapshotDiffMetadata>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number,
+                base_l1_batch_number,
+                storage_logs_chunk_count
+            FROM
+                snapshot_diffs
+            WHERE
+                l1_batch_number < $1
+            ORDER BY
+                l1_batch_number
+            "#,
+            i64::from(first_retained_l1_batch_number.0)
+        )
+        .instrument("get_snapshot_diffs_before")
+        .with_arg(
+            "first_retained_l1_batch_number",
+            &first_retained_l1_batch_number,
+        )
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| SnapshotDiffMetadata {
+                l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+                base_l1_batch_number: L1BatchNumber(row.base_l1_batch_number as u32),
+                storage_logs_chunk_count: row.storage_logs_chunk_count as u64,
+            })
+            .collect())
+    }
+
     /// Deletes all snapshots after the specified L1 batch number and returns their metadata.
     pub async fn delete_snapshots_after(
         &mut self,
@@ -214,11 +355,78 @@ impl SnapshotsDal<'_, '_> {
         .fetch_all(self.storage)
         .await
     }
+
+    /// Deletes all snapshots before the specified L1 batch number and returns their metadata.
+    pub async fn delete_snapshots_before(
+        &mut self,
+        first_retained_l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Vec<SnapshotMetadata>> {
+        sqlx::query_as!(
+            StorageSnapshotMetadata,
+            r#"
+            DELETE FROM snapshots
+            WHERE
+                l1_batch_number < $1
+            RETURNING
+                VERSION,
+                l1_batch_number,
+                factory_deps_filepath,
+                storage_logs_filepaths
+            "#,
+            first_retained_l1_batch_number.0 as i32
+        )
+        .try_map(SnapshotMetadata::try_from)
+        .instrument("delete_snapshots_before")
+        .with_arg(
+            "first_retained_l1_batch_number",
+            &first_retained_l1_batch_number,
+        )
+        .fetch_all(self.storage)
+        .await
+    }
+
+    /// Deletes all differential snapshots before the specified L1 batch number and returns their metadata.
+    pub async fn delete_snapshot_diffs_before(
+        &mut self,
+        first_retained_l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Vec<SnapshotDiffMetadata>> {
+        let rows = sqlx::query!(
+            r#"
+            DELETE FROM snapshot_diffs
+            WHERE
+                l1_batch_number < $1
+            RETURNING
+                l1_batch_number,
+                base_l1_batch_number,
+                storage_logs_chunk_count
+            "#,
+            i64::from(first_retained_l1_batch_number.0)
+        )
+        .instrument("delete_snapshot_diffs_before")
+        .with_arg(
+            "first_retained_l1_batch_number",
+            &first_retained_l1_batch_number,
+        )
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| SnapshotDiffMetadata {
+                l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+                base_l1_batch_number: L1BatchNumber(row.base_l1_batch_number as u32),
+                storage_logs_chunk_count: row.storage_logs_chunk_count as u64,
+            })
+            .collect())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use zksync_types::{snapshots::SnapshotVersion, L1BatchNumber};
+    use zksync_types::{
+        snapshots::{SnapshotDiffManifest, SnapshotDiffMetadata, SnapshotVersion},
+        L1BatchNumber, L2BlockNumber,
+    };
 
     use crate::{ConnectionPool, Core, CoreDal};
 
@@ -242,6 +450,11 @@ mod tests {
             .await
             .expect("Failed to retrieve snapshots");
         assert_eq!(snapshots.snapshots_l1_batch_numbers, []);
+        let incomplete_snapshot = dal
+            .get_oldest_incomplete_snapshot_l1_batch_number()
+            .await
+            .unwrap();
+        assert_eq!(incomplete_snapshot, Some(l1_batch_number));
 
         for i in 0..2 {
             dal.add_storage_logs_filepath_for_snapshot(
@@ -258,6 +471,11 @@ mod tests {
             .await
             .expect("Failed to retrieve snapshots");
         assert_eq!(snapshots.snapshots_l1_batch_numbers, [l1_batch_number]);
+        let incomplete_snapshot = dal
+            .get_oldest_incomplete_snapshot_l1_batch_number()
+            .await
+            .unwrap();
+        assert_eq!(incomplete_snapshot, None);
 
         let snapshot_metadata = dal
             .get_snapshot_metadata(l1_batch_number)
@@ -267,6 +485,59 @@ mod tests {
         assert_eq!(snapshot_metadata.l1_batch_number, l1_batch_number);
     }
 
+    #[tokio::test]
+    async fn adding_snapshot_diffs() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.snapshots_dal();
+        let newest_diff = dal
+            .get_newest_snapshot_diff_l1_batch_number()
+            .await
+            .unwrap();
+        assert_eq!(newest_diff, None);
+
+        for (base_l1_batch_number, l1_batch_number) in [(100, 110), (110, 125)] {
+            let manifest = SnapshotDiffManifest {
+                version: SnapshotVersion::Version0.into(),
+                base_l1_batch_number: L1BatchNumber(base_l1_batch_number),
+                l1_batch_number: L1BatchNumber(l1_batch_number),
+                l2_block_number: L2BlockNumber(l1_batch_number * 2),
+                storage_logs_chunk_count: 1,
+            };
+            dal.add_snapshot_diff(&manifest).await.unwrap();
+        }
+        let newest_diff = dal
+            .get_newest_snapshot_diff_l1_batch_number()
+            .await
+            .unwrap();
+        assert_eq!(newest_diff, Some(L1BatchNumber(125)));
+
+        // Diffs must not be returned as full snapshots.
+        let snapshots = dal.get_all_complete_snapshots().await.unwrap();
+        assert_eq!(snapshots.snapshots_l1_batch_numbers, []);
+
+        let expected_diffs = [SnapshotDiffMetadata {
+            l1_batch_number: L1BatchNumber(110),
+            base_l1_batch_number: L1BatchNumber(100),
+            storage_logs_chunk_count: 1,
+        }];
+        let diffs = dal
+            .get_snapshot_diffs_before(L1BatchNumber(125))
+            .await
+            .unwrap();
+        assert_eq!(diffs, expected_diffs);
+        let deleted_diffs = dal
+            .delete_snapshot_diffs_before(L1BatchNumber(125))
+            .await
+            .unwrap();
+        assert_eq!(deleted_diffs, expected_diffs);
+        let newest_diff = dal
+            .get_newest_snapshot_diff_l1_batch_number()
+            .await
+            .unwrap();
+        assert_eq!(newest_diff, Some(L1BatchNumber(125)));
+    }
+
     #[tokio::test]
     async fn deleting_snapshots() {
         let pool = ConnectionPool::<Core>::test_pool().await;
@@ -330,6 +601,62 @@ mod tests {
         assert_eq!(complete_snapshots.snapshots_l1_batch_numbers, []);
     }
 
+    #[tokio::test]
+    async fn deleting_old_snapshots() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.snapshots_dal();
+        for l1_batch_number in [100, 200, 300] {
+            dal.add_snapshot(
+                SnapshotVersion::Version0,
+                L1BatchNumber(l1_batch_number),
+                1,
+                "gs:///bucket/factory_deps.bin",
+            )
+            .await
+            .unwrap();
+            dal.add_storage_logs_filepath_for_snapshot(
+                L1BatchNumber(l1_batch_number),
+                0,
+                "gs:///bucket/chunk.bin",
+            )
+            .await
+            .unwrap();
+        }
+
+        let deleted_snapshots = dal
+            .delete_snapshots_before(L1BatchNumber(100))
+            .await
+            .unwrap();
+        assert!(deleted_snapshots.is_empty(), "{deleted_snapshots:?}");
+        let snapshots = dal.get_snapshots_before(L1BatchNumber(300)).await.unwrap();
+        let l1_batch_numbers: Vec<_> = snapshots
+            .iter()
+            .map(|snapshot| snapshot.l1_batch_number)
+            .collect();
+        assert_eq!(l1_batch_numbers, [L1BatchNumber(100), L1BatchNumber(200)]);
+
+        let deleted_snapshots = dal
+            .delete_snapshots_before(L1BatchNumber(300))
+            .await
+            .unwrap();
+        let mut deleted_l1_batch_numbers: Vec<_> = deleted_snapshots
+            .iter()
+            .map(|snapshot| snapshot.l1_batch_number)
+            .collect();
+        deleted_l1_batch_numbers.sort_unstable();
+        assert_eq!(
+            deleted_l1_batch_numbers,
+            [L1BatchNumber(100), L1BatchNumber(200)]
+        );
+
+        let complete_snapshots = dal.get_all_complete_snapshots().await.unwrap();
+        assert_eq!(
+            complete_snapshots.snapshots_l1_batch_numbers,
+            [L1BatchNumber(300)]
+        );
+    }
+
     #[tokio::test]
     async fn adding_files() {
         let pool = ConnectionPool::<Core>::test_pool().await;
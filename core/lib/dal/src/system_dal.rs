@@ -44,6 +44,45 @@ impl SystemDal<'_, '_> {
         })
     }
 
+    /// Estimates the size of live data in the database, including indexes, in bytes.
+    ///
+    /// Unlike `pg_database_size()`, the estimate decreases after data is deleted (e.g., by pruning) even if
+    /// Postgres doesn't return the freed disk space to the OS. The estimate is based on table statistics (the number
+    /// of live rows and average column widths, plus 24 bytes of row header), and thus may lag behind the actual data;
+    /// tables without collected statistics are accounted using their on-disk size.
+    pub async fn estimate_database_size(&mut self) -> DalResult<u64> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                SUM(
+                    COALESCE(
+                        stats.n_live_tup * (widths.row_width + 24) * PG_TOTAL_RELATION_SIZE(stats.relid)::FLOAT8 / GREATEST(PG_TABLE_SIZE(stats.relid), 1),
+                        PG_TOTAL_RELATION_SIZE(stats.relid)
+                    )
+                )::BIGINT AS "estimated_size"
+            FROM
+                pg_stat_user_tables AS stats
+                LEFT JOIN (
+                    SELECT
+                        schemaname,
+                        tablename,
+                        SUM(avg_width) AS row_width
+                    FROM
+                        pg_stats
+                    GROUP BY
+                        schemaname,
+                        tablename
+                ) AS widths ON widths.schemaname = stats.schemaname
+                AND widths.tablename = stats.relname
+            "#
+        )
+        .instrument("estimate_database_size")
+        .report_latency()
+        .fetch_one(self.storage)
+        .await?;
+        Ok(row.estimated_size.unwrap_or(0) as u64)
+    }
+
     pub(crate) async fn get_table_sizes(&mut self) -> DalResult<HashMap<String, TableSize>> {
         let rows = sqlx::query!(
             r#"
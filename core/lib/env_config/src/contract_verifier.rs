@@ -23,6 +23,12 @@ mod tests {
             threads_per_server: Some(128),
             port: 3070,
             url: "127.0.0.1:3070".to_string(),
+            compilers_dir: None,
+            compilers_mirror: Some("https://compilers.example.com/".to_string()),
+            compilers_checksums: Some("/etc/compilers/checksums.json".to_string()),
+            max_concurrent_compilations: Some(4),
+            zksolc_compilation_timeout: Some(60),
+            zkvyper_compilation_timeout: None,
         }
     }
 
@@ -36,6 +42,10 @@ mod tests {
             CONTRACT_VERIFIER_PORT=3070
             CONTRACT_VERIFIER_URL=127.0.0.1:3070
             CONTRACT_VERIFIER_THREADS_PER_SERVER=128
+            CONTRACT_VERIFIER_COMPILERS_MIRROR=https://compilers.example.com/
+            CONTRACT_VERIFIER_COMPILERS_CHECKSUMS=/etc/compilers/checksums.json
+            CONTRACT_VERIFIER_MAX_CONCURRENT_COMPILATIONS=4
+            CONTRACT_VERIFIER_ZKSOLC_COMPILATION_TIMEOUT=60
 
         "#;
         lock.set_env(config);
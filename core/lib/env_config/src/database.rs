@@ -103,6 +103,7 @@ mod tests {
             DATABASE_MERKLE_TREE_MAX_L1_BATCHES_PER_ITER=50
             DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_BLOCK_CACHE_CAPACITY_MB=64
             DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_MAX_OPEN_FILES=100
+            DATABASE_EXPERIMENTAL_ROCKSDB_CHECKPOINTS_INTERVAL_SEC=600
         "#;
         lock.set_env(config);
 
@@ -124,6 +125,10 @@ mod tests {
             db_config.experimental.state_keeper_db_max_open_files,
             NonZeroU32::new(100)
         );
+        assert_eq!(
+            db_config.experimental.rocksdb_checkpoints_interval_sec,
+            Some(600)
+        );
     }
 
     #[test]
@@ -133,6 +138,7 @@ mod tests {
             "DATABASE_STATE_KEEPER_DB_PATH",
             "DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_MAX_OPEN_FILES",
             "DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_BLOCK_CACHE_CAPACITY_MB",
+            "DATABASE_EXPERIMENTAL_ROCKSDB_CHECKPOINTS_INTERVAL_SEC",
             "DATABASE_MERKLE_TREE_BACKUP_PATH",
             "DATABASE_MERKLE_TREE_PATH",
             "DATABASE_MERKLE_TREE_MODE",
@@ -159,6 +165,10 @@ mod tests {
             128
         );
         assert_eq!(db_config.experimental.state_keeper_db_max_open_files, None);
+        assert_eq!(
+            db_config.experimental.rocksdb_checkpoints_interval_sec,
+            None
+        );
 
         // Check that new env variable for Merkle tree path is supported
         lock.set_env("DATABASE_MERKLE_TREE_PATH=/db/tree/main");
@@ -41,7 +41,9 @@ impl FromEnv for GasAdjusterConfig {
 
 #[cfg(test)]
 mod tests {
-    use zksync_config::configs::eth_sender::{ProofSendingMode, PubdataSendingMode};
+    use zksync_config::configs::eth_sender::{
+        FeeEscalationStrategy, ProofSendingMode, PubdataSendingMode,
+    };
 
     use super::*;
     use crate::test_utils::{hash, EnvMutex};
@@ -70,6 +72,10 @@ mod tests {
                     l1_batch_min_age_before_execute_seconds: Some(1000),
                     max_acceptable_priority_fee_in_gwei: 100_000_000_000,
                     pubdata_sending_mode: PubdataSendingMode::Calldata,
+                    fee_escalation_strategy: FeeEscalationStrategy::Exponential,
+                    fee_escalation_bump_percent: 30,
+                    fee_escalation_max_multiplier: Some(2.5),
+                    max_l1_spend_per_batch_gwei: None,
                 }),
                 gas_adjuster: Some(GasAdjusterConfig {
                     default_priority_fee_per_gas: 20000000000,
@@ -131,6 +137,9 @@ mod tests {
             ETH_SENDER_SENDER_L1_BATCH_MIN_AGE_BEFORE_EXECUTE_SECONDS="1000"
             ETH_SENDER_SENDER_MAX_ACCEPTABLE_PRIORITY_FEE_IN_GWEI="100000000000"
             ETH_SENDER_SENDER_PUBDATA_SENDING_MODE="Calldata"
+            ETH_SENDER_SENDER_FEE_ESCALATION_STRATEGY="Exponential"
+            ETH_SENDER_SENDER_FEE_ESCALATION_BUMP_PERCENT="30"
+            ETH_SENDER_SENDER_FEE_ESCALATION_MAX_MULTIPLIER="2.5"
             ETH_CLIENT_WEB3_URL="http://127.0.0.1:8545"
 
         "#;
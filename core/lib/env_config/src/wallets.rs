@@ -1,36 +1,67 @@
 use std::str::FromStr;
 
 use anyhow::Context;
-use zksync_basic_types::{Address, H256};
-use zksync_config::configs::wallets::{AddressWallet, EthSender, StateKeeper, Wallet, Wallets};
+use zksync_basic_types::{url::SensitiveUrl, Address, H256};
+use zksync_config::configs::wallets::{
+    AddressWallet, EthSender, RemoteSignerWallet, SignerWallet, StateKeeper, Wallet, Wallets,
+};
 
 use crate::FromEnv;
 
+/// Loads a signer wallet either from a private key, or from a remote signer URL and the wallet address.
+fn signer_wallet_from_env(
+    private_key_var: &str,
+    remote_signer_url_var: &str,
+    address_var: &str,
+) -> anyhow::Result<Option<SignerWallet>> {
+    let private_key = std::env::var(private_key_var)
+        .ok()
+        .map(|pk| pk.parse::<H256>().context("Malformed pk"))
+        .transpose()?;
+    let remote_signer_url = std::env::var(remote_signer_url_var)
+        .ok()
+        .map(|url| {
+            url.parse::<SensitiveUrl>()
+                .context("Malformed remote signer URL")
+        })
+        .transpose()?;
+
+    Ok(match (private_key, remote_signer_url) {
+        (Some(_), Some(_)) => {
+            anyhow::bail!(
+                "`{private_key_var}` and `{remote_signer_url_var}` cannot be set simultaneously"
+            );
+        }
+        (Some(private_key), None) => {
+            Some(Wallet::from_private_key_bytes(private_key, None)?.into())
+        }
+        (None, Some(url)) => {
+            let address = std::env::var(address_var)
+                .with_context(|| format!("`{address_var}` must be set for a remote signer"))?;
+            let address = Address::from_str(&address).context("Malformed address")?;
+            Some(RemoteSignerWallet::new(address, url).into())
+        }
+        (None, None) => None,
+    })
+}
+
 impl FromEnv for Wallets {
     fn from_env() -> anyhow::Result<Self> {
-        let operator = std::env::var("ETH_SENDER_SENDER_OPERATOR_PRIVATE_KEY")
-            .ok()
-            .map(|pk| pk.parse::<H256>().context("Malformed pk"))
-            .transpose()?;
-        let blob_operator = std::env::var("ETH_SENDER_SENDER_OPERATOR_BLOBS_PRIVATE_KEY")
-            .ok()
-            .map(|pk| pk.parse::<H256>().context("Malformed pk"))
-            .transpose()?;
-
-        let eth_sender = if let Some(operator) = operator {
-            let operator = Wallet::from_private_key_bytes(operator, None)?;
-            let blob_operator = if let Some(blob_operator) = blob_operator {
-                Some(Wallet::from_private_key_bytes(blob_operator, None)?)
-            } else {
-                None
-            };
-            Some(EthSender {
-                operator,
-                blob_operator,
-            })
-        } else {
-            None
-        };
+        let operator = signer_wallet_from_env(
+            "ETH_SENDER_SENDER_OPERATOR_PRIVATE_KEY",
+            "ETH_SENDER_SENDER_OPERATOR_REMOTE_SIGNER_URL",
+            "ETH_SENDER_SENDER_OPERATOR_COMMIT_ETH_ADDR",
+        )?;
+        let blob_operator = signer_wallet_from_env(
+            "ETH_SENDER_SENDER_OPERATOR_BLOBS_PRIVATE_KEY",
+            "ETH_SENDER_SENDER_OPERATOR_BLOBS_REMOTE_SIGNER_URL",
+            "ETH_SENDER_SENDER_OPERATOR_BLOBS_ETH_ADDR",
+        )?;
+
+        let eth_sender = operator.map(|operator| EthSender {
+            operator,
+            blob_operator,
+        });
 
         let fee_account = std::env::var("CHAIN_STATE_KEEPER_FEE_ACCOUNT_ADDR").ok();
         let state_keeper = if let Some(fee_account) = fee_account {
@@ -46,3 +77,101 @@ impl FromEnv for Wallets {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{addr, hash, EnvMutex};
+
+    static MUTEX: EnvMutex = EnvMutex::new();
+
+    const PRIVATE_KEY_VAR: &str = "TEST_WALLET_PRIVATE_KEY";
+    const REMOTE_SIGNER_URL_VAR: &str = "TEST_WALLET_REMOTE_SIGNER_URL";
+    const ADDRESS_VAR: &str = "TEST_WALLET_ADDR";
+    const ALL_VARS: [&str; 3] = [PRIVATE_KEY_VAR, REMOTE_SIGNER_URL_VAR, ADDRESS_VAR];
+
+    fn load_test_wallet() -> anyhow::Result<Option<SignerWallet>> {
+        signer_wallet_from_env(PRIVATE_KEY_VAR, REMOTE_SIGNER_URL_VAR, ADDRESS_VAR)
+    }
+
+    #[test]
+    fn signer_wallet_from_private_key() {
+        let mut lock = MUTEX.lock();
+        lock.remove_env(&ALL_VARS);
+        lock.set_env(
+            r#"
+            TEST_WALLET_PRIVATE_KEY="0x27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be"
+            "#,
+        );
+
+        let wallet = load_test_wallet().unwrap().expect("no wallet");
+        let SignerWallet::PrivateKey(wallet) = wallet else {
+            panic!("Unexpected wallet: {wallet:?}");
+        };
+        assert_eq!(
+            wallet.private_key().expose_secret().secret_bytes(),
+            hash("27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be").as_bytes()
+        );
+    }
+
+    #[test]
+    fn signer_wallet_from_remote_signer() {
+        let mut lock = MUTEX.lock();
+        lock.remove_env(&ALL_VARS);
+        lock.set_env(
+            r#"
+            TEST_WALLET_REMOTE_SIGNER_URL="http://127.0.0.1:9000/"
+            TEST_WALLET_ADDR="0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7"
+            "#,
+        );
+
+        let wallet = load_test_wallet().unwrap().expect("no wallet");
+        let SignerWallet::Remote(wallet) = wallet else {
+            panic!("Unexpected wallet: {wallet:?}");
+        };
+        assert_eq!(
+            wallet.address(),
+            addr("de03a0B5963f75f1C8485B355fF6D30f3093BDE7")
+        );
+        assert_eq!(wallet.url().expose_str(), "http://127.0.0.1:9000/");
+    }
+
+    #[test]
+    fn signer_wallet_is_not_set() {
+        let mut lock = MUTEX.lock();
+        lock.remove_env(&ALL_VARS);
+        assert!(load_test_wallet().unwrap().is_none());
+
+        // The address alone doesn't define a signer wallet.
+        lock.set_env(r#"TEST_WALLET_ADDR="0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7""#);
+        assert!(load_test_wallet().unwrap().is_none());
+    }
+
+    #[test]
+    fn errors_loading_signer_wallet() {
+        let mut lock = MUTEX.lock();
+        lock.remove_env(&ALL_VARS);
+        lock.set_env(
+            r#"
+            TEST_WALLET_PRIVATE_KEY="0x27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be"
+            TEST_WALLET_REMOTE_SIGNER_URL="http://127.0.0.1:9000/"
+            TEST_WALLET_ADDR="0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7"
+            "#,
+        );
+        let err = load_test_wallet().unwrap_err().to_string();
+        assert!(err.contains("cannot be set simultaneously"), "{err}");
+
+        lock.remove_env(&[PRIVATE_KEY_VAR, ADDRESS_VAR]);
+        let err = load_test_wallet().unwrap_err().to_string();
+        assert!(err.contains(ADDRESS_VAR), "{err}");
+
+        lock.set_env(r#"TEST_WALLET_ADDR="not an address""#);
+        let err = load_test_wallet().unwrap_err().to_string();
+        assert!(err.contains("Malformed address"), "{err}");
+
+        lock.remove_env(&[REMOTE_SIGNER_URL_VAR, ADDRESS_VAR]);
+        lock.set_env(r#"TEST_WALLET_PRIVATE_KEY="0x1234""#);
+        let err = load_test_wallet().unwrap_err().to_string();
+        assert!(err.contains("Malformed pk"), "{err}");
+    }
+}
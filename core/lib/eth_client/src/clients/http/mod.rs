@@ -4,7 +4,9 @@ use vise::{
     Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Histogram, LabeledFamily, Metrics,
 };
 
-pub use self::signing::{PKSigningClient, SigningClient};
+pub use self::signing::{
+    signing_client_for_wallet, PKSigningClient, RemoteSigningClient, SigningClient,
+};
 
 mod decl;
 mod query;
@@ -1,8 +1,11 @@
 use std::{fmt, sync::Arc};
 
 use async_trait::async_trait;
+use zksync_config::configs::wallets::SignerWallet;
 use zksync_contracts::hyperchain_contract;
-use zksync_eth_signer::{EthereumSigner, PrivateKeySigner, TransactionParameters};
+use zksync_eth_signer::{
+    EthereumSigner, PrivateKeySigner, RemoteSigner, SignerError, TransactionParameters,
+};
 use zksync_types::{
     ethabi, web3, Address, K256PrivateKey, L1ChainId, EIP_4844_TX_TYPE, H160, U256,
 };
@@ -40,6 +43,61 @@ impl PKSigningClient {
     }
 }
 
+/// HTTP-based Ethereum client, backed by a remote signer (e.g., Web3Signer) to sign transactions.
+pub type RemoteSigningClient = SigningClient<RemoteSigner>;
+
+impl RemoteSigningClient {
+    pub fn new_raw(
+        signer: RemoteSigner,
+        diamond_proxy_addr: Address,
+        default_priority_fee_per_gas: u64,
+        l1_chain_id: L1ChainId,
+        query_client: Box<DynClient<L1>>,
+    ) -> Self {
+        let operator_address = signer.address();
+        tracing::info!("Operator address (remote signer): {operator_address:?}");
+        SigningClient::new(
+            query_client,
+            hyperchain_contract(),
+            operator_address,
+            signer,
+            diamond_proxy_addr,
+            default_priority_fee_per_gas.into(),
+            l1_chain_id,
+        )
+    }
+}
+
+/// Creates a signing client for the provided wallet, which may be backed either by a private key
+/// or by a remote signer.
+pub fn signing_client_for_wallet(
+    wallet: &SignerWallet,
+    diamond_proxy_addr: Address,
+    default_priority_fee_per_gas: u64,
+    l1_chain_id: L1ChainId,
+    query_client: Box<DynClient<L1>>,
+) -> Result<Box<dyn BoundEthInterface>, SignerError> {
+    Ok(match wallet {
+        SignerWallet::PrivateKey(wallet) => Box::new(PKSigningClient::new_raw(
+            wallet.private_key().clone(),
+            diamond_proxy_addr,
+            default_priority_fee_per_gas,
+            l1_chain_id,
+            query_client,
+        )),
+        SignerWallet::Remote(wallet) => {
+            let signer = RemoteSigner::new(wallet.url(), wallet.address())?;
+            Box::new(RemoteSigningClient::new_raw(
+                signer,
+                diamond_proxy_addr,
+                default_priority_fee_per_gas,
+                l1_chain_id,
+                query_client,
+            ))
+        }
+    })
+}
+
 /// Gas limit value to be used in transaction if for some reason
 /// gas limit was not set for it.
 ///
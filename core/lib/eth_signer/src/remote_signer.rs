@@ -0,0 +1,275 @@
+//! Signer delegating to a remote signing service over HTTP JSON-RPC (e.g., [Web3Signer]).
+//!
+//! [Web3Signer]: https://docs.web3signer.consensys.io/
+
+use jsonrpsee::{
+    core::client::ClientT,
+    http_client::{HttpClient, HttpClientBuilder},
+    rpc_params,
+};
+use serde::{Deserialize, Serialize};
+use zksync_types::{
+    eip712_signature::utils::get_eip712_typed_data_json,
+    url::SensitiveUrl,
+    web3::{AccessList, Bytes},
+    Address, EIP712TypedStructure, Eip712Domain, PackedEthSignature, H256, U256, U64,
+};
+
+use crate::{raw_ethereum_tx::TransactionParameters, EthereumSigner, SignerError};
+
+/// Transaction object accepted by the `eth_signTransaction` method of the remote signer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RemoteTransactionRequest {
+    pub from: Address,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<Address>,
+    pub gas: U256,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<U256>,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    pub value: U256,
+    pub data: Bytes,
+    pub nonce: U256,
+    pub chain_id: U64,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub transaction_type: Option<U64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_list: Option<AccessList>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_blob_gas: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_versioned_hashes: Option<Vec<H256>>,
+}
+
+impl RemoteTransactionRequest {
+    fn new(from: Address, params: TransactionParameters) -> Self {
+        Self {
+            from,
+            to: params.to,
+            gas: params.gas,
+            gas_price: params.gas_price,
+            max_fee_per_gas: params.max_fee_per_gas,
+            max_priority_fee_per_gas: params.max_priority_fee_per_gas,
+            value: params.value,
+            data: Bytes(params.data),
+            nonce: params.nonce,
+            chain_id: params.chain_id.into(),
+            transaction_type: params.transaction_type,
+            access_list: params.access_list,
+            max_fee_per_blob_gas: params.max_fee_per_blob_gas,
+            blob_versioned_hashes: params.blob_versioned_hashes,
+        }
+    }
+
+    #[cfg(test)]
+    fn into_params(self) -> TransactionParameters {
+        TransactionParameters {
+            nonce: self.nonce,
+            to: self.to,
+            gas: self.gas,
+            gas_price: self.gas_price,
+            value: self.value,
+            data: self.data.0,
+            chain_id: self.chain_id.as_u64(),
+            transaction_type: self.transaction_type,
+            access_list: self.access_list,
+            max_fee_per_gas: self.max_fee_per_gas,
+            max_priority_fee_per_gas: self.max_priority_fee_per_gas,
+            max_fee_per_blob_gas: self.max_fee_per_blob_gas,
+            blob_versioned_hashes: self.blob_versioned_hashes,
+        }
+    }
+}
+
+/// Signer that holds no key material and delegates signing to a remote service implementing
+/// the `eth_signTransaction` and `eth_signTypedData` JSON-RPC methods.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    client: HttpClient,
+    address: Address,
+}
+
+impl RemoteSigner {
+    /// Creates a signer for the `address` account managed by the remote signer at `url`.
+    pub fn new(url: &SensitiveUrl, address: Address) -> Result<Self, SignerError> {
+        let client = HttpClientBuilder::default()
+            .build(url.expose_str())
+            .map_err(|err| SignerError::InvalidRemoteSigner(err.to_string()))?;
+        Ok(Self { client, address })
+    }
+
+    /// Returns the address of the account managed by the remote signer.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+}
+
+#[async_trait::async_trait]
+impl EthereumSigner for RemoteSigner {
+    /// Returns the address of the account managed by the remote signer.
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        Ok(self.address)
+    }
+
+    /// Signs typed struct using the remote `eth_signTypedData` method.
+    async fn sign_typed_data<S: EIP712TypedStructure + Sync>(
+        &self,
+        domain: &Eip712Domain,
+        typed_struct: &S,
+    ) -> Result<PackedEthSignature, SignerError> {
+        let typed_data = get_eip712_typed_data_json(domain, typed_struct);
+        let signature: Bytes = self
+            .client
+            .request("eth_signTypedData", rpc_params![self.address, typed_data])
+            .await
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        PackedEthSignature::deserialize_packed(&signature.0)
+            .map_err(|err| SignerError::SigningFailed(format!("malformed signature: {err}")))
+    }
+
+    /// Signs the transaction using the remote `eth_signTransaction` method and returns it RLP-encoded.
+    async fn sign_transaction(
+        &self,
+        raw_tx: TransactionParameters,
+    ) -> Result<Vec<u8>, SignerError> {
+        let request = RemoteTransactionRequest::new(self.address, raw_tx);
+        let signed: Bytes = self
+            .client
+            .request("eth_signTransaction", rpc_params![request])
+            .await
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        Ok(signed.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use jsonrpsee::{
+        server::{ServerBuilder, ServerHandle},
+        types::{error::ErrorCode, ErrorObjectOwned},
+        RpcModule,
+    };
+    use zksync_types::{
+        transaction_request::{Eip712Meta, TransactionRequest},
+        K256PrivateKey, L2ChainId, H160, U256,
+    };
+
+    use super::*;
+    use crate::PrivateKeySigner;
+
+    /// Mock Web3Signer-like server backed by a private key.
+    async fn spawn_mock_signer(
+        signer: PrivateKeySigner,
+        expected_typed_data: serde_json::Value,
+        typed_data_signature: PackedEthSignature,
+    ) -> (SensitiveUrl, ServerHandle) {
+        let address = signer.get_address().await.unwrap();
+        let mut rpc_module = RpcModule::new(signer);
+        rpc_module
+            .register_async_method("eth_signTransaction", move |params, signer| async move {
+                let request: RemoteTransactionRequest = params.one()?;
+                if request.from != address {
+                    return Err(ErrorObjectOwned::from(ErrorCode::InvalidParams));
+                }
+                let signed = signer
+                    .sign_transaction(request.into_params())
+                    .await
+                    .unwrap();
+                Ok::<_, ErrorObjectOwned>(Bytes(signed))
+            })
+            .unwrap();
+        rpc_module
+            .register_method("eth_signTypedData", move |params, _| {
+                let (from, typed_data): (Address, serde_json::Value) = params.parse()?;
+                if from != address || typed_data != expected_typed_data {
+                    return Err(ErrorObjectOwned::from(ErrorCode::InvalidParams));
+                }
+                Ok::<_, ErrorObjectOwned>(Bytes(typed_data_signature.serialize_packed().to_vec()))
+            })
+            .unwrap();
+
+        let server = ServerBuilder::default()
+            .http_only()
+            .build((Ipv4Addr::LOCALHOST, 0))
+            .await
+            .unwrap();
+        let local_addr = server.local_addr().unwrap();
+        let server_handle = server.start(rpc_module);
+        let url = format!("http://{local_addr}/").parse().unwrap();
+        (url, server_handle)
+    }
+
+    #[tokio::test]
+    async fn remote_signer_basics() {
+        let private_key = K256PrivateKey::from_bytes(H256::from([5; 32])).unwrap();
+        let address = private_key.address();
+        let local_signer = PrivateKeySigner::new(private_key.clone());
+
+        let domain = Eip712Domain::new(L2ChainId::from(270));
+        let typed_struct = TransactionRequest {
+            nonce: U256::from(1),
+            to: Some(H160::repeat_byte(1)),
+            from: Some(address),
+            value: U256::from(10),
+            gas_price: U256::from(11),
+            gas: U256::from(12),
+            input: vec![1, 2, 3].into(),
+            chain_id: Some(270),
+            eip712_meta: Some(Eip712Meta {
+                gas_per_pubdata: U256::from(800),
+                ..Eip712Meta::default()
+            }),
+            ..TransactionRequest::default()
+        };
+        let expected_signature =
+            PackedEthSignature::sign_typed_data(&private_key, &domain, &typed_struct).unwrap();
+        let (url, server_handle) = spawn_mock_signer(
+            local_signer.clone(),
+            get_eip712_typed_data_json(&domain, &typed_struct),
+            expected_signature.clone(),
+        )
+        .await;
+
+        let remote_signer = RemoteSigner::new(&url, address).unwrap();
+        assert_eq!(remote_signer.get_address().await.unwrap(), address);
+
+        let signature = remote_signer
+            .sign_typed_data(&domain, &typed_struct)
+            .await
+            .unwrap();
+        assert_eq!(signature, expected_signature);
+
+        let tx = TransactionParameters {
+            nonce: U256::from(1u32),
+            to: Some(H160::default()),
+            gas: U256::from(100_000),
+            gas_price: None,
+            max_fee_per_gas: U256::from(2u32),
+            max_priority_fee_per_gas: U256::from(1u32),
+            value: Default::default(),
+            data: vec![1, 2, 3],
+            chain_id: 9,
+            transaction_type: Some(U64::from(2u32)),
+            access_list: None,
+            blob_versioned_hashes: None,
+            max_fee_per_blob_gas: None,
+        };
+        let signed_tx = remote_signer.sign_transaction(tx.clone()).await.unwrap();
+        let expected_tx = local_signer.sign_transaction(tx).await.unwrap();
+        assert_eq!(signed_tx, expected_tx);
+
+        // A signer for an account not managed by the remote service should fail.
+        let wrong_signer = RemoteSigner::new(&url, Address::repeat_byte(0xff)).unwrap();
+        let err = wrong_signer
+            .sign_transaction(TransactionParameters::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SignerError::SigningFailed(_)), "{err:?}");
+
+        server_handle.stop().ok();
+    }
+}
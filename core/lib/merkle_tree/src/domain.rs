@@ -9,8 +9,8 @@ use crate::{
     consistency::ConsistencyError,
     storage::{PatchSet, Patched, RocksDBWrapper},
     types::{
-        Key, Root, TreeEntry, TreeEntryWithProof, TreeInstruction, TreeLogEntry, ValueHash,
-        TREE_DEPTH,
+        Key, Root, TreeEntriesWithMultiProof, TreeEntry, TreeEntryWithProof, TreeInstruction,
+        TreeLogEntry, ValueHash, TREE_DEPTH,
     },
     BlockOutput, HashTree, MerkleTree, MerkleTreePruner, MerkleTreePrunerHandle, NoVersionError,
 };
@@ -432,6 +432,22 @@ impl ZkSyncTreeReader {
         })
     }
 
+    /// Reads entries with the specified keys from the tree. The entries are returned in the same order
+    /// as requested. If a certain key is not present in the tree, the corresponding returned entry
+    /// will be [empty](TreeEntry::is_empty()).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree `version` is missing.
+    pub fn entries(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        keys: &[Key],
+    ) -> Result<Vec<TreeEntry>, NoVersionError> {
+        let version = u64::from(l1_batch_number.0);
+        self.0.entries(version, keys)
+    }
+
     /// Reads entries together with Merkle proofs with the specified keys from the tree. The entries are returned
     /// in the same order as requested.
     ///
@@ -447,6 +463,21 @@ impl ZkSyncTreeReader {
         self.0.entries_with_proofs(version, keys)
     }
 
+    /// Reads entries together with a compact multi-proof for the specified keys from the tree.
+    /// Entries in the returned proof are sorted by key and deduplicated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree `version` is missing.
+    pub fn entries_with_multi_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        keys: &[Key],
+    ) -> Result<TreeEntriesWithMultiProof, NoVersionError> {
+        let version = u64::from(l1_batch_number.0);
+        self.0.entries_with_multi_proof(version, keys)
+    }
+
     /// Verifies consistency of the tree at the specified L1 batch number.
     ///
     /// # Errors
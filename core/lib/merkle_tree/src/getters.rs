@@ -4,7 +4,10 @@ use crate::{
     hasher::HasherWithStats,
     recovery::MerkleTreeRecovery,
     storage::{LoadAncestorsResult, SortedKeys, WorkingPatchSet},
-    types::{Nibbles, Node, ProfiledTreeOperation, TreeEntry, TreeEntryWithProof},
+    types::{
+        Nibbles, Node, ProfiledTreeOperation, TreeEntriesWithMultiProof, TreeEntry,
+        TreeEntryWithProof,
+    },
     Database, HashTree, Key, MerkleTree, NoVersionError, PruneDatabase, ValueHash,
 };
 
@@ -59,6 +62,22 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
             },
         )
     }
+
+    /// Reads entries with the specified keys from the tree together with a compact multi-proof
+    /// of their authenticity. Unlike [`Self::entries_with_proofs()`], entries are returned sorted by key,
+    /// and duplicate keys are removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree `version` is missing.
+    pub fn entries_with_multi_proof(
+        &self,
+        version: u64,
+        leaf_keys: &[Key],
+    ) -> Result<TreeEntriesWithMultiProof, NoVersionError> {
+        let proofs = self.entries_with_proofs(version, leaf_keys)?;
+        Ok(TreeEntriesWithMultiProof::new(proofs))
+    }
 }
 
 fn load_and_transform_entries<T>(
@@ -1,14 +1,14 @@
 //! Merkle proof-related hashing logic.
 
-use std::mem;
+use std::{mem, slice};
 
-use anyhow::ensure;
+use anyhow::{ensure, Context as _};
 
 use crate::{
     hasher::{HashTree, HasherWithStats},
     types::{
-        BlockOutputWithProofs, Key, LeafNode, TreeEntry, TreeEntryWithProof, TreeInstruction,
-        TreeLogEntry, ValueHash, TREE_DEPTH,
+        BlockOutputWithProofs, Key, LeafNode, TreeEntriesWithMultiProof, TreeEntry,
+        TreeEntryWithProof, TreeInstruction, TreeLogEntry, ValueHash, TREE_DEPTH,
     },
     utils,
 };
@@ -104,6 +104,192 @@ impl TreeEntryWithProof {
     }
 }
 
+/// Returns the hash adjacent to the Merkle path at the specified `depth`, or `None` if the hash was trimmed
+/// from the path (i.e., it corresponds to an empty subtree).
+fn adjacent_hash(merkle_path: &[ValueHash], depth: usize) -> Option<ValueHash> {
+    let trimmed_hash_count = TREE_DEPTH - merkle_path.len();
+    let idx = depth.checked_sub(trimmed_hash_count)?;
+    Some(merkle_path[idx])
+}
+
+/// Splits sorted `keys` in a subtree into ones belonging to the left and right child of the subtree root,
+/// which has children at the specified `depth`.
+fn split_keys(keys: impl Iterator<Item = Key>, depth: usize) -> usize {
+    let mut split = 0;
+    for key in keys {
+        if key.bit(depth) {
+            break;
+        }
+        split += 1;
+    }
+    split
+}
+
+impl TreeEntriesWithMultiProof {
+    /// Creates a multi-proof from individual entry proofs, e.g. ones returned by `entries_with_proofs()`.
+    /// Proofs are sorted by key and deduplicated, so they can be supplied in any order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the proofs has a Merkle path longer than the tree depth.
+    pub fn new(mut proofs: Vec<TreeEntryWithProof>) -> Self {
+        proofs.sort_unstable_by_key(|proof| proof.base.key);
+        proofs.dedup_by_key(|proof| proof.base.key);
+        assert!(
+            proofs
+                .iter()
+                .all(|proof| proof.merkle_path.len() <= TREE_DEPTH),
+            "Merkle path is too long"
+        );
+
+        let mut hashes = vec![];
+        if !proofs.is_empty() {
+            Self::collect_hashes(&proofs, TREE_DEPTH, &mut hashes);
+        }
+        Self {
+            entries: proofs.iter().map(|proof| proof.base).collect(),
+            path_lengths: proofs
+                .iter()
+                .map(|proof| proof.merkle_path.len() as u16)
+                .collect(),
+            hashes,
+        }
+    }
+
+    /// Collects hashes for a subtree with the specified `height` containing the (non-empty) `proofs`.
+    /// Must traverse the subtree in the same order as [`Self::fold()`].
+    fn collect_hashes(proofs: &[TreeEntryWithProof], height: usize, hashes: &mut Vec<ValueHash>) {
+        if height == 0 {
+            return;
+        }
+        let depth = height - 1;
+        let split = split_keys(proofs.iter().map(|proof| proof.base.key), depth);
+        let (left, right) = proofs.split_at(split);
+        if left.is_empty() || right.is_empty() {
+            // The adjacent subtree doesn't contain proven entries, so its hash must be supplied
+            // unless it's trimmed from all Merkle paths (i.e., corresponds to an empty subtree).
+            let hash = proofs
+                .iter()
+                .find_map(|proof| adjacent_hash(&proof.merkle_path, depth));
+            hashes.extend(hash);
+            Self::collect_hashes(proofs, depth, hashes);
+        } else {
+            Self::collect_hashes(left, depth, hashes);
+            Self::collect_hashes(right, depth, hashes);
+        }
+    }
+
+    /// Computes the root hash of the tree based on this proof.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the proof is malformed (e.g., entries are not sorted, or there are too few
+    /// or too many hashes).
+    pub fn root_hash(&self, hasher: &dyn HashTree) -> anyhow::Result<ValueHash> {
+        ensure!(!self.entries.is_empty(), "Multi-proof contains no entries");
+        ensure!(
+            self.entries.len() == self.path_lengths.len(),
+            "Mismatch between number of entries ({}) and path lengths ({})",
+            self.entries.len(),
+            self.path_lengths.len()
+        );
+        for window in self.entries.windows(2) {
+            ensure!(
+                window[0].key < window[1].key,
+                "Entries are not sorted by key or contain duplicates: {:?}, {:?}",
+                window[0].key,
+                window[1].key
+            );
+        }
+        for (entry, &path_length) in self.entries.iter().zip(&self.path_lengths) {
+            ensure!(
+                usize::from(path_length) <= TREE_DEPTH,
+                "Merkle path for key {:?} is too long: {path_length}",
+                entry.key
+            );
+            if entry.leaf_index == 0 {
+                ensure!(
+                    entry.value.is_zero(),
+                    "Invalid missing value specification for key {:?}: leaf index is zero, but value is non-default",
+                    entry.key
+                );
+            }
+        }
+
+        let mut hashes = self.hashes.iter();
+        let root_hash = Self::fold(
+            hasher,
+            &self.entries,
+            &self.path_lengths,
+            TREE_DEPTH,
+            &mut hashes,
+        )?;
+        ensure!(
+            hashes.next().is_none(),
+            "Multi-proof contains {} unused hashes",
+            hashes.len() + 1
+        );
+        Ok(root_hash)
+    }
+
+    fn fold(
+        hasher: &dyn HashTree,
+        entries: &[TreeEntry],
+        path_lengths: &[u16],
+        height: usize,
+        hashes: &mut slice::Iter<'_, ValueHash>,
+    ) -> anyhow::Result<ValueHash> {
+        if height == 0 {
+            let entry = &entries[0];
+            return Ok(hasher.hash_leaf(&entry.value, entry.leaf_index));
+        }
+
+        let depth = height - 1;
+        let split = split_keys(entries.iter().map(|entry| entry.key), depth);
+        if split == 0 || split == entries.len() {
+            let max_path_length = path_lengths.iter().copied().max().unwrap_or(0);
+            let trimmed_hash_count = TREE_DEPTH - usize::from(max_path_length);
+            let adjacent_hash = if depth >= trimmed_hash_count {
+                *hashes
+                    .next()
+                    .context("Multi-proof doesn't contain enough hashes")?
+            } else {
+                hasher.empty_subtree_hash(depth)
+            };
+            let hash = Self::fold(hasher, entries, path_lengths, depth, hashes)?;
+            Ok(if split == 0 {
+                hasher.hash_branch(&adjacent_hash, &hash)
+            } else {
+                hasher.hash_branch(&hash, &adjacent_hash)
+            })
+        } else {
+            let (left_entries, right_entries) = entries.split_at(split);
+            let (left_lengths, right_lengths) = path_lengths.split_at(split);
+            let left_hash = Self::fold(hasher, left_entries, left_lengths, depth, hashes)?;
+            let right_hash = Self::fold(hasher, right_entries, right_lengths, depth, hashes)?;
+            Ok(hasher.hash_branch(&left_hash, &right_hash))
+        }
+    }
+
+    /// Verifies this proof.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error <=> proof is invalid.
+    pub fn verify(
+        &self,
+        hasher: &dyn HashTree,
+        trusted_root_hash: ValueHash,
+    ) -> anyhow::Result<()> {
+        let root_hash = self.root_hash(hasher)?;
+        ensure!(
+            root_hash == trusted_root_hash,
+            "Root hash mismatch: got {root_hash}, want {trusted_root_hash}"
+        );
+        Ok(())
+    }
+}
+
 /// Range digest in a Merkle tree allowing to compute its root hash based on the provided entries.
 ///
 /// - The entries must be ordered by key. I.e., the first entry must have the numerically smallest key,
@@ -57,8 +57,8 @@ pub use crate::{
         RocksDBWrapper,
     },
     types::{
-        BlockOutput, BlockOutputWithProofs, Key, TreeEntry, TreeEntryWithProof, TreeInstruction,
-        TreeLogEntry, TreeLogEntryWithProof, ValueHash,
+        BlockOutput, BlockOutputWithProofs, Key, TreeEntriesWithMultiProof, TreeEntry,
+        TreeEntryWithProof, TreeInstruction, TreeLogEntry, TreeLogEntryWithProof, ValueHash,
     },
 };
 use crate::{storage::Storage, types::Root};
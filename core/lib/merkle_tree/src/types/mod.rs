@@ -127,6 +127,24 @@ pub struct TreeEntryWithProof {
     pub merkle_path: Vec<ValueHash>,
 }
 
+/// Entries in a Merkle tree together with a compact proof of their authenticity.
+///
+/// Unlike a collection of [`TreeEntryWithProof`]s, the proof doesn't duplicate hashes shared by Merkle paths
+/// of different entries. Hashes that can be computed from the proven entries themselves are omitted as well.
+/// A multi-proof can be created from individual proofs using [`Self::new()`] and verified using [`Self::verify()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeEntriesWithMultiProof {
+    /// Proven entries ordered by key in the ascending order. Keys are unique.
+    pub entries: Vec<TreeEntry>,
+    /// Lengths of [Merkle paths](TreeEntryWithProof::merkle_path) for each of `entries`. These lengths determine
+    /// which adjacent hashes correspond to empty subtrees and are thus skipped from `hashes`.
+    pub path_lengths: Vec<u16>,
+    /// Hashes adjacent to the Merkle paths of `entries` that cannot be computed from the entries, ordered as encountered
+    /// during the depth-first traversal of the proven part of the tree (root to leaves, left subtrees before right ones).
+    /// Hashes of empty subtrees are skipped.
+    pub hashes: Vec<ValueHash>,
+}
+
 /// Output of inserting a block of entries into a Merkle tree.
 #[derive(Debug, PartialEq, Eq)]
 pub struct BlockOutput {
@@ -6,8 +6,8 @@ use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
 use test_casing::test_casing;
 use zksync_crypto::hasher::blake2::Blake2Hasher;
 use zksync_merkle_tree::{
-    Database, HashTree, MerkleTree, PatchSet, Patched, TreeEntry, TreeInstruction, TreeLogEntry,
-    TreeRangeDigest,
+    Database, HashTree, MerkleTree, PatchSet, Patched, TreeEntriesWithMultiProof, TreeEntry,
+    TreeInstruction, TreeLogEntry, TreeRangeDigest,
 };
 use zksync_types::{AccountTreeId, Address, StorageKey, H256, U256};
 
@@ -543,6 +543,101 @@ fn range_proofs_with_random_ranges() {
     }
 }
 
+#[test_casing(4, [1, 10, 50, 100])]
+fn multi_proofs_for_existing_and_missing_keys(key_count: usize) {
+    const RNG_SEED: u64 = 42;
+
+    let mut rng = StdRng::seed_from_u64(RNG_SEED);
+    let (kvs, expected_hash) = &*ENTRIES_AND_HASH;
+    let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+    tree.extend(kvs.clone()).unwrap();
+
+    let existing_keys: Vec<_> = kvs
+        .choose_multiple(&mut rng, key_count)
+        .map(|entry| entry.key)
+        .collect();
+    let adjacent_keys: Vec<_> = kvs
+        .choose_multiple(&mut rng, key_count)
+        .map(|entry| entry.key ^ (U256::one() << rng.gen_range(0..256)))
+        .collect();
+    let mut keys: Vec<_> = existing_keys.into_iter().chain(adjacent_keys).collect();
+    keys.push(keys[0]); // duplicate keys must be handled
+    keys.shuffle(&mut rng);
+
+    let multi_proof = tree.entries_with_multi_proof(0, &keys).unwrap();
+    multi_proof.verify(&Blake2Hasher, *expected_hash).unwrap();
+
+    let mut sorted_keys = keys.clone();
+    sorted_keys.sort_unstable();
+    sorted_keys.dedup();
+    let proven_keys: Vec<_> = multi_proof.entries.iter().map(|entry| entry.key).collect();
+    assert_eq!(proven_keys, sorted_keys);
+    let expected_entries = tree.entries(0, &sorted_keys).unwrap();
+    assert_eq!(multi_proof.entries, expected_entries);
+
+    // The multi-proof must be more compact than separate proofs.
+    let proofs = tree.entries_with_proofs(0, &sorted_keys).unwrap();
+    let separate_hash_count: usize = proofs.iter().map(|proof| proof.merkle_path.len()).sum();
+    assert!(
+        multi_proof.hashes.len() <= separate_hash_count,
+        "{} > {separate_hash_count}",
+        multi_proof.hashes.len()
+    );
+    assert_eq!(TreeEntriesWithMultiProof::new(proofs), multi_proof);
+}
+
+#[test]
+fn multi_proof_on_empty_tree() {
+    let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+    let empty_tree_hash = tree.extend(vec![]).unwrap().root_hash;
+    assert_eq!(empty_tree_hash, Blake2Hasher.empty_subtree_hash(256));
+    let keys: Vec<_> = generate_key_value_pairs(0..10)
+        .into_iter()
+        .map(|entry| entry.key)
+        .collect();
+    let multi_proof = tree.entries_with_multi_proof(0, &keys).unwrap();
+
+    assert!(multi_proof.entries.iter().all(TreeEntry::is_empty));
+    assert!(multi_proof.hashes.is_empty());
+    multi_proof.verify(&Blake2Hasher, empty_tree_hash).unwrap();
+}
+
+#[test]
+fn tampered_multi_proofs_are_rejected() {
+    let (kvs, expected_hash) = &*ENTRIES_AND_HASH;
+    let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+    tree.extend(kvs.clone()).unwrap();
+
+    let keys: Vec<_> = kvs.iter().step_by(10).map(|entry| entry.key).collect();
+    let multi_proof = tree.entries_with_multi_proof(0, &keys).unwrap();
+    multi_proof.verify(&Blake2Hasher, *expected_hash).unwrap();
+
+    let mut tampered = multi_proof.clone();
+    tampered.entries[1].value = H256::repeat_byte(0xff);
+    let err = tampered.verify(&Blake2Hasher, *expected_hash).unwrap_err();
+    assert!(err.to_string().contains("Root hash mismatch"), "{err}");
+
+    let mut tampered = multi_proof.clone();
+    tampered.hashes[0] = H256::zero();
+    tampered.verify(&Blake2Hasher, *expected_hash).unwrap_err();
+
+    let mut tampered = multi_proof.clone();
+    tampered.hashes.pop();
+    let err = tampered.verify(&Blake2Hasher, *expected_hash).unwrap_err();
+    assert!(err.to_string().contains("enough hashes"), "{err}");
+
+    let mut tampered = multi_proof.clone();
+    tampered.hashes.push(H256::zero());
+    let err = tampered.verify(&Blake2Hasher, *expected_hash).unwrap_err();
+    assert!(err.to_string().contains("unused hashes"), "{err}");
+
+    let mut tampered = multi_proof;
+    tampered.entries.swap(0, 1);
+    tampered.path_lengths.swap(0, 1);
+    let err = tampered.verify(&Blake2Hasher, *expected_hash).unwrap_err();
+    assert!(err.to_string().contains("not sorted"), "{err}");
+}
+
 /// RocksDB-specific tests.
 mod rocksdb {
     use std::collections::BTreeMap;
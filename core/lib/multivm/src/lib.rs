@@ -13,7 +13,7 @@ pub use self::versions::{
 pub use crate::{
     glue::{
         history_mode::HistoryMode,
-        tracers::{MultiVMTracer, MultiVmTracerPointer},
+        tracers::{IntoOldVmTracer, MultiVMTracer, MultiVmTracerPointer},
     },
     vm_instance::VmInstance,
 };
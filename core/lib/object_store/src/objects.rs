@@ -7,8 +7,10 @@ use flate2::{read::GzDecoder, write::GzEncoder, Compression};
 use prost::Message;
 use zksync_protobuf::{decode, ProtoFmt};
 use zksync_types::{
+    pruning::ArchivedL1Batch,
     snapshots::{
-        SnapshotFactoryDependencies, SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey,
+        SnapshotDiffManifest, SnapshotDiffPart, SnapshotFactoryDependencies,
+        SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey,
     },
     L1BatchNumber,
 };
@@ -117,6 +119,80 @@ impl StoredObject for SnapshotStorageLogsChunk {
     }
 }
 
+impl StoredObject for SnapshotDiffPart<SnapshotFactoryDependencies> {
+    const BUCKET: Bucket = Bucket::StorageSnapshot;
+    type Key<'a> = L1BatchNumber;
+
+    fn encode_key(key: Self::Key<'_>) -> String {
+        format!("snapshot_diff_l1_batch_{key}_factory_deps.proto.gzip")
+    }
+
+    fn serialize(&self) -> Result<Vec<u8>, BoxedError> {
+        self.0.serialize()
+    }
+
+    fn deserialize(bytes: Vec<u8>) -> Result<Self, BoxedError> {
+        SnapshotFactoryDependencies::deserialize(bytes).map(Self)
+    }
+}
+
+impl StoredObject for SnapshotDiffPart<SnapshotStorageLogsChunk> {
+    const BUCKET: Bucket = Bucket::StorageSnapshot;
+    type Key<'a> = SnapshotStorageLogsStorageKey;
+
+    fn encode_key(key: Self::Key<'_>) -> String {
+        format!(
+            "snapshot_diff_l1_batch_{}_storage_logs_part_{:0>4}.proto.gzip",
+            key.l1_batch_number, key.chunk_id
+        )
+    }
+
+    fn serialize(&self) -> Result<Vec<u8>, BoxedError> {
+        self.0.serialize()
+    }
+
+    fn deserialize(bytes: Vec<u8>) -> Result<Self, BoxedError> {
+        SnapshotStorageLogsChunk::deserialize(bytes).map(Self)
+    }
+}
+
+impl StoredObject for SnapshotDiffManifest {
+    const BUCKET: Bucket = Bucket::StorageSnapshot;
+    type Key<'a> = L1BatchNumber;
+
+    fn encode_key(key: Self::Key<'_>) -> String {
+        format!("snapshot_diff_l1_batch_{key}_manifest.json")
+    }
+
+    fn serialize(&self) -> Result<Vec<u8>, BoxedError> {
+        serde_json::to_vec(self).map_err(From::from)
+    }
+
+    fn deserialize(bytes: Vec<u8>) -> Result<Self, BoxedError> {
+        serde_json::from_slice(&bytes).map_err(From::from)
+    }
+}
+
+impl StoredObject for ArchivedL1Batch {
+    const BUCKET: Bucket = Bucket::PrunedDataArchive;
+    type Key<'a> = L1BatchNumber;
+
+    fn encode_key(key: Self::Key<'_>) -> String {
+        format!("l1_batch_{key}_archive.json.gzip")
+    }
+
+    fn serialize(&self) -> Result<Vec<u8>, BoxedError> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        serde_json::to_writer(&mut encoder, self)?;
+        encoder.finish().map_err(From::from)
+    }
+
+    fn deserialize(bytes: Vec<u8>) -> Result<Self, BoxedError> {
+        let decoder = GzDecoder::new(&bytes[..]);
+        serde_json::from_reader(decoder).map_err(From::from)
+    }
+}
+
 impl dyn ObjectStore + '_ {
     /// Fetches the value for the given key if it exists.
     ///
@@ -181,7 +257,7 @@ mod tests {
     use zksync_types::{
         snapshots::{SnapshotFactoryDependency, SnapshotStorageLog},
         web3::Bytes,
-        AccountTreeId, StorageKey, H160, H256,
+        AccountTreeId, L2BlockNumber, StorageKey, H160, H256,
     };
 
     use super::*;
@@ -261,4 +337,92 @@ mod tests {
         let reconstructed_factory_deps = store.get(key).await.unwrap();
         assert_eq!(factory_deps, reconstructed_factory_deps);
     }
+
+    #[tokio::test]
+    async fn snapshot_diff_parts_are_stored_separately_from_full_snapshots() {
+        let store = MockObjectStore::arc();
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: L1BatchNumber(567),
+            chunk_id: 5,
+        };
+        let log = SnapshotStorageLog {
+            key: StorageKey::new(AccountTreeId::new(H160::random()), H256::random()),
+            value: H256::random(),
+            l1_batch_number_of_initial_write: L1BatchNumber(123),
+            enumeration_index: 234,
+        };
+        let full_chunk = SnapshotStorageLogsChunk {
+            storage_logs: vec![log.clone()],
+        };
+        let diff_chunk = SnapshotDiffPart(SnapshotStorageLogsChunk {
+            storage_logs: vec![SnapshotStorageLog {
+                value: H256::random(),
+                ..log
+            }],
+        });
+        store.put(key, &full_chunk).await.unwrap();
+        store.put(key, &diff_chunk).await.unwrap();
+
+        let restored_full_chunk: SnapshotStorageLogsChunk = store.get(key).await.unwrap();
+        assert_eq!(restored_full_chunk, full_chunk);
+        let restored_diff_chunk: SnapshotDiffPart<SnapshotStorageLogsChunk> =
+            store.get(key).await.unwrap();
+        assert_eq!(restored_diff_chunk, diff_chunk);
+
+        let manifest = SnapshotDiffManifest {
+            version: 0,
+            base_l1_batch_number: L1BatchNumber(500),
+            l1_batch_number: L1BatchNumber(567),
+            l2_block_number: L2BlockNumber(1_000),
+            storage_logs_chunk_count: 10,
+        };
+        store.put(L1BatchNumber(567), &manifest).await.unwrap();
+        let restored_manifest: SnapshotDiffManifest = store.get(L1BatchNumber(567)).await.unwrap();
+        assert_eq!(restored_manifest, manifest);
+    }
+
+    #[tokio::test]
+    async fn archived_l1_batch_roundtrip() {
+        let store = MockObjectStore::arc();
+        let tx = zksync_types::api::Transaction {
+            hash: H256::repeat_byte(1),
+            ..zksync_types::api::Transaction::default()
+        };
+        let receipt = zksync_types::api::TransactionReceipt {
+            transaction_hash: tx.hash,
+            logs: vec![zksync_types::api::Log {
+                address: H160::repeat_byte(2),
+                topics: vec![H256::repeat_byte(3)],
+                data: Bytes(vec![1, 2, 3]),
+                block_hash: None,
+                block_number: Some(10.into()),
+                l1_batch_number: Some(5.into()),
+                transaction_hash: Some(tx.hash),
+                transaction_index: Some(0.into()),
+                log_index: Some(0.into()),
+                transaction_log_index: Some(0.into()),
+                log_type: None,
+                removed: Some(false),
+            }],
+            ..zksync_types::api::TransactionReceipt::default()
+        };
+        let block = zksync_types::api::Block {
+            number: 10.into(),
+            transactions: vec![zksync_types::api::TransactionVariant::Full(tx)],
+            ..zksync_types::api::Block::default()
+        };
+        let archive = ArchivedL1Batch {
+            l1_batch_number: L1BatchNumber(5),
+            l2_blocks: vec![zksync_types::pruning::ArchivedL2Block {
+                block,
+                receipts: vec![receipt],
+            }],
+        };
+        store.put(L1BatchNumber(5), &archive).await.unwrap();
+
+        let restored_archive: ArchivedL1Batch = store.get(L1BatchNumber(5)).await.unwrap();
+        assert_eq!(restored_archive, archive);
+        assert!(restored_archive.l2_block(L2BlockNumber(10)).is_some());
+        assert!(restored_archive.l2_block(L2BlockNumber(11)).is_none());
+    }
 }
@@ -19,6 +19,8 @@ pub enum Bucket {
     ProofsTee,
     StorageSnapshot,
     TeeVerifierInput,
+    RocksdbCheckpoints,
+    PrunedDataArchive,
 }
 
 impl Bucket {
@@ -37,6 +39,8 @@ impl Bucket {
             Self::ProofsTee => "proofs_tee",
             Self::StorageSnapshot => "storage_logs_snapshots",
             Self::TeeVerifierInput => "tee_verifier_inputs",
+            Self::RocksdbCheckpoints => "rocksdb_checkpoints",
+            Self::PrunedDataArchive => "pruned_data_archive",
         }
     }
 }
@@ -159,6 +159,7 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .collect::<Result<Vec<_>, _>>()
                 .context("account_pks")?,
             extended_api_tracing: self.extended_api_tracing.unwrap_or_default(),
+            vm_storage_from_merkle_tree: self.vm_storage_from_merkle_tree.unwrap_or_default(),
             api_namespaces,
         })
     }
@@ -229,6 +230,7 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .map(|k| format!("{:?}", k))
                 .collect(),
             extended_api_tracing: Some(this.extended_api_tracing),
+            vm_storage_from_merkle_tree: Some(this.vm_storage_from_merkle_tree),
             api_namespaces: this.api_namespaces.clone().unwrap_or_default(),
         }
     }
@@ -23,6 +23,16 @@ impl ProtoRepr for proto::ContractVerifier {
                 .map(|a| a.try_into())
                 .transpose()
                 .context("threads_per_server")?,
+            compilers_dir: self.compilers_dir.clone(),
+            compilers_mirror: self.compilers_mirror.clone(),
+            compilers_checksums: self.compilers_checksums.clone(),
+            max_concurrent_compilations: self
+                .max_concurrent_compilations
+                .map(|x| x.try_into())
+                .transpose()
+                .context("max_concurrent_compilations")?,
+            zksolc_compilation_timeout: self.zksolc_compilation_timeout,
+            zkvyper_compilation_timeout: self.zkvyper_compilation_timeout,
         })
     }
 
@@ -34,6 +44,12 @@ impl ProtoRepr for proto::ContractVerifier {
             polling_interval: this.polling_interval,
             threads_per_server: this.threads_per_server.map(|a| a as u32),
             prometheus_port: Some(this.prometheus_port.into()),
+            compilers_dir: this.compilers_dir.clone(),
+            compilers_mirror: this.compilers_mirror.clone(),
+            compilers_checksums: this.compilers_checksums.clone(),
+            max_concurrent_compilations: this.max_concurrent_compilations.map(|x| x as u64),
+            zksolc_compilation_timeout: this.zksolc_compilation_timeout,
+            zkvyper_compilation_timeout: this.zkvyper_compilation_timeout,
         }
     }
 }
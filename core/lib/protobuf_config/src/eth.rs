@@ -42,6 +42,28 @@ impl proto::PubdataSendingMode {
     }
 }
 
+impl proto::FeeEscalationStrategy {
+    fn new(x: &configs::eth_sender::FeeEscalationStrategy) -> Self {
+        use configs::eth_sender::FeeEscalationStrategy as From;
+        match x {
+            From::Minimal => Self::Minimal,
+            From::Linear => Self::Linear,
+            From::Exponential => Self::Exponential,
+            From::DeadlineAware => Self::DeadlineAware,
+        }
+    }
+
+    fn parse(&self) -> configs::eth_sender::FeeEscalationStrategy {
+        use configs::eth_sender::FeeEscalationStrategy as To;
+        match self {
+            Self::Minimal => To::Minimal,
+            Self::Linear => To::Linear,
+            Self::Exponential => To::Exponential,
+            Self::DeadlineAware => To::DeadlineAware,
+        }
+    }
+}
+
 impl ProtoRepr for proto::Eth {
     type Type = configs::eth_sender::EthConfig;
 
@@ -109,6 +131,18 @@ impl ProtoRepr for proto::Sender {
                 .and_then(|x| Ok(proto::PubdataSendingMode::try_from(*x)?))
                 .context("pubdata_sending_mode")?
                 .parse(),
+            fee_escalation_strategy: self
+                .fee_escalation_strategy
+                .map(proto::FeeEscalationStrategy::try_from)
+                .transpose()
+                .context("fee_escalation_strategy")?
+                .map(|x| x.parse())
+                .unwrap_or_default(),
+            fee_escalation_bump_percent: self.fee_escalation_bump_percent.unwrap_or(
+                configs::eth_sender::SenderConfig::default_fee_escalation_bump_percent(),
+            ),
+            fee_escalation_max_multiplier: self.fee_escalation_max_multiplier,
+            max_l1_spend_per_batch_gwei: self.max_l1_spend_per_batch_gwei,
         })
     }
 
@@ -139,6 +173,12 @@ impl ProtoRepr for proto::Sender {
             pubdata_sending_mode: Some(
                 proto::PubdataSendingMode::new(&this.pubdata_sending_mode).into(),
             ),
+            fee_escalation_strategy: Some(
+                proto::FeeEscalationStrategy::new(&this.fee_escalation_strategy).into(),
+            ),
+            fee_escalation_bump_percent: Some(this.fee_escalation_bump_percent),
+            fee_escalation_max_multiplier: this.fee_escalation_max_multiplier,
+            max_l1_spend_per_batch_gwei: this.max_l1_spend_per_batch_gwei,
         }
     }
 }
@@ -28,6 +28,7 @@ impl ProtoRepr for proto::Db {
             include_indices_and_filters_in_block_cache: self
                 .include_indices_and_filters_in_block_cache
                 .unwrap_or_default(),
+            rocksdb_checkpoints_interval_sec: self.rocksdb_checkpoints_interval_sec,
         })
     }
 
@@ -46,6 +47,7 @@ impl ProtoRepr for proto::Db {
             include_indices_and_filters_in_block_cache: Some(
                 this.include_indices_and_filters_in_block_cache,
             ),
+            rocksdb_checkpoints_interval_sec: this.rocksdb_checkpoints_interval_sec,
         }
     }
 }
@@ -1,9 +1,10 @@
-use std::num::NonZeroU64;
+use std::num::{NonZeroU32, NonZeroU64};
 
+use anyhow::Context as _;
 use zksync_config::configs::PruningConfig;
 use zksync_protobuf::ProtoRepr;
 
-use crate::proto::pruning as proto;
+use crate::{proto::pruning as proto, read_optional_repr};
 
 impl ProtoRepr for proto::Pruning {
     type Type = PruningConfig;
@@ -14,6 +15,13 @@ impl ProtoRepr for proto::Pruning {
             chunk_size: self.chunk_size,
             removal_delay_sec: self.removal_delay_sec.and_then(NonZeroU64::new),
             data_retention_sec: self.data_retention_sec,
+            db_size_budget_mb: self.db_size_budget_mb,
+            events_retention_sec: self.events_retention_sec,
+            transactions_retention_sec: self.transactions_retention_sec,
+            archive_object_store: read_optional_repr(&self.archive_object_store)
+                .context("archive_object_store")?,
+            tree_retained_l1_batches: self.tree_retained_l1_batches.and_then(NonZeroU32::new),
+            tree_retain_for_snapshots: self.tree_retain_for_snapshots,
         })
     }
 
@@ -23,6 +31,12 @@ impl ProtoRepr for proto::Pruning {
             chunk_size: this.chunk_size,
             removal_delay_sec: this.removal_delay_sec.map(|a| a.get()),
             data_retention_sec: this.data_retention_sec,
+            db_size_budget_mb: this.db_size_budget_mb,
+            events_retention_sec: this.events_retention_sec,
+            transactions_retention_sec: this.transactions_retention_sec,
+            archive_object_store: this.archive_object_store.as_ref().map(ProtoRepr::build),
+            tree_retained_l1_batches: this.tree_retained_l1_batches.map(NonZeroU32::get),
+            tree_retain_for_snapshots: this.tree_retain_for_snapshots,
         }
     }
 }
@@ -59,6 +59,8 @@ impl ProtoRepr for proto::SnapshotRecovery {
                 .context("postgres")?
                 .unwrap_or_default(),
             l1_batch: self.l1_batch.map(L1BatchNumber),
+            diff_l1_batch: self.diff_l1_batch.map(L1BatchNumber),
+            archive_path: self.archive_path.clone(),
             object_store: read_optional_repr(&self.object_store).context("object store")?,
         })
     }
@@ -90,6 +92,8 @@ impl ProtoRepr for proto::SnapshotRecovery {
             tree,
             experimental,
             l1_batch: this.l1_batch.map(|a| a.0),
+            diff_l1_batch: this.diff_l1_batch.map(|a| a.0),
+            archive_path: this.archive_path.clone(),
             object_store: this.object_store.as_ref().map(ProtoRepr::build),
         }
     }
@@ -18,6 +18,19 @@ impl ProtoRepr for proto::SnapshotsCreator {
             concurrent_queries_count: *required(&self.concurrent_queries_count)
                 .context("concurrent_queries_count")?,
             object_store,
+            diff_snapshots: self.diff_snapshots.unwrap_or_default(),
+            full_snapshot_l1_batch_interval: self
+                .full_snapshot_l1_batch_interval
+                .unwrap_or(Self::Type::default_full_snapshot_l1_batch_interval()),
+            l1_batch_interval: self
+                .l1_batch_interval
+                .unwrap_or(Self::Type::default_l1_batch_interval()),
+            retained_snapshots_count: self
+                .retained_snapshots_count
+                .unwrap_or(Self::Type::default_retained_snapshots_count()),
+            poll_interval_ms: self
+                .poll_interval_ms
+                .unwrap_or(Self::Type::default_poll_interval_ms()),
         })
     }
 
@@ -26,6 +39,11 @@ impl ProtoRepr for proto::SnapshotsCreator {
             storage_logs_chunk_size: Some(this.storage_logs_chunk_size),
             concurrent_queries_count: Some(this.concurrent_queries_count),
             object_store: this.object_store.as_ref().map(ProtoRepr::build),
+            diff_snapshots: Some(this.diff_snapshots),
+            full_snapshot_l1_batch_interval: Some(this.full_snapshot_l1_batch_interval),
+            l1_batch_interval: Some(this.l1_batch_interval),
+            retained_snapshots_count: Some(this.retained_snapshots_count),
+            poll_interval_ms: Some(this.poll_interval_ms),
         }
     }
 }
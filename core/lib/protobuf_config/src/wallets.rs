@@ -1,44 +1,81 @@
 use anyhow::Context;
+use zksync_basic_types::url::SensitiveUrl;
 use zksync_config::configs::{
     self,
-    wallets::{AddressWallet, EthSender, StateKeeper, Wallet},
+    wallets::{AddressWallet, EthSender, RemoteSignerWallet, SignerWallet, StateKeeper, Wallet},
 };
 use zksync_protobuf::{required, ProtoRepr};
 
 use crate::{parse_h160, parse_h256, proto::wallets as proto};
 
+fn read_signer_wallet(
+    private_key_wallet: Option<&proto::PrivateKeyWallet>,
+    remote_wallet: Option<&proto::RemoteSignerWallet>,
+) -> anyhow::Result<Option<SignerWallet>> {
+    match (private_key_wallet, remote_wallet) {
+        (Some(_), Some(_)) => {
+            anyhow::bail!("private key and remote signer cannot be specified simultaneously")
+        }
+        (Some(wallet), None) => {
+            let wallet = Wallet::from_private_key_bytes(
+                parse_h256(required(&wallet.private_key).context("private_key")?)?,
+                wallet.address.as_ref().and_then(|a| parse_h160(a).ok()),
+            )?;
+            Ok(Some(wallet.into()))
+        }
+        (None, Some(wallet)) => {
+            let address =
+                parse_h160(required(&wallet.address).context("address")?).context("address")?;
+            let url = required(&wallet.url)
+                .context("url")?
+                .parse::<SensitiveUrl>()
+                .context("url")?;
+            Ok(Some(RemoteSignerWallet::new(address, url).into()))
+        }
+        (None, None) => Ok(None),
+    }
+}
+
+fn build_signer_wallet(
+    wallet: &SignerWallet,
+) -> (
+    Option<proto::PrivateKeyWallet>,
+    Option<proto::RemoteSignerWallet>,
+) {
+    match wallet {
+        SignerWallet::PrivateKey(wallet) => (
+            Some(proto::PrivateKeyWallet {
+                address: Some(format!("{:?}", wallet.address())),
+                private_key: Some(format!("{:?}", wallet.private_key())),
+            }),
+            None,
+        ),
+        SignerWallet::Remote(wallet) => (
+            None,
+            Some(proto::RemoteSignerWallet {
+                address: Some(format!("{:?}", wallet.address())),
+                url: Some(wallet.url().expose_str().to_string()),
+            }),
+        ),
+    }
+}
+
 impl ProtoRepr for proto::Wallets {
     type Type = configs::wallets::Wallets;
     fn read(&self) -> anyhow::Result<Self::Type> {
-        let eth_sender = if self.operator.is_some() && self.blob_operator.is_some() {
-            let blob_operator = if let Some(blob_operator) = &self.blob_operator {
-                Some(Wallet::from_private_key_bytes(
-                    parse_h256(required(&blob_operator.private_key).context("blob operator")?)?,
-                    blob_operator
-                        .address
-                        .as_ref()
-                        .and_then(|a| parse_h160(a).ok()),
-                )?)
-            } else {
-                None
-            };
-
-            let operator_wallet = &self.operator.clone().context("Operator private key")?;
-
-            let operator = Wallet::from_private_key_bytes(
-                parse_h256(required(&operator_wallet.private_key).context("operator")?)?,
-                operator_wallet
-                    .address
-                    .as_ref()
-                    .and_then(|a| parse_h160(a).ok()),
-            )?;
-
-            Some(EthSender {
+        let operator = read_signer_wallet(self.operator.as_ref(), self.remote_operator.as_ref())
+            .context("operator")?;
+        let blob_operator = read_signer_wallet(
+            self.blob_operator.as_ref(),
+            self.remote_blob_operator.as_ref(),
+        )
+        .context("blob_operator")?;
+        let eth_sender = match (operator, blob_operator) {
+            (Some(operator), Some(blob_operator)) => Some(EthSender {
                 operator,
-                blob_operator,
-            })
-        } else {
-            None
+                blob_operator: Some(blob_operator),
+            }),
+            _ => None,
         };
 
         let state_keeper = if let Some(fee_account) = &self.fee_account {
@@ -60,24 +97,17 @@ impl ProtoRepr for proto::Wallets {
     }
 
     fn build(this: &Self::Type) -> Self {
-        let (operator, blob_operator) = if let Some(eth_sender) = &this.eth_sender {
-            let blob = eth_sender
-                .blob_operator
-                .as_ref()
-                .map(|blob| proto::PrivateKeyWallet {
-                    address: Some(format!("{:?}", blob.address())),
-                    private_key: Some(format!("{:?}", blob.private_key())),
-                });
-            (
-                Some(proto::PrivateKeyWallet {
-                    address: Some(format!("{:?}", eth_sender.operator.address())),
-                    private_key: Some(format!("{:?}", eth_sender.operator.private_key())),
-                }),
-                blob,
-            )
-        } else {
-            (None, None)
-        };
+        let ((operator, remote_operator), (blob_operator, remote_blob_operator)) =
+            if let Some(eth_sender) = &this.eth_sender {
+                let blob = eth_sender
+                    .blob_operator
+                    .as_ref()
+                    .map(build_signer_wallet)
+                    .unwrap_or_default();
+                (build_signer_wallet(&eth_sender.operator), blob)
+            } else {
+                Default::default()
+            };
 
         let fee_account = this
             .state_keeper
@@ -89,6 +119,8 @@ impl ProtoRepr for proto::Wallets {
             blob_operator,
             operator,
             fee_account,
+            remote_operator,
+            remote_blob_operator,
         }
     }
 }
@@ -0,0 +1,565 @@
+//! Online backups of [`RocksDB`] instances (such as the Merkle tree or the state keeper cache) to an object store.
+//!
+//! A backup is a [RocksDB checkpoint](RocksDB::create_checkpoint()) uploaded file by file, followed by
+//! a [`CheckpointManifest`] listing the uploaded files. The manifest is uploaded last and replaces the manifest
+//! of the previous checkpoint, so that readers only ever observe complete checkpoints.
+//!
+//! Files are uploaded and downloaded in chunks of bounded size, so that large SST files are never loaded into memory
+//! in full. Since SST files are immutable, SST files already uploaded as a part of the previous checkpoint
+//! are reused by the next checkpoint instead of being uploaded again.
+//!
+//! Only a single writer per DB and object store is supported, since a writer reuses and removes files
+//! of its previous checkpoint. A [`RocksdbCheckpointer`] refuses to upload checkpoints once it detects that
+//! its latest checkpoint was replaced by another writer.
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    sync::watch,
+};
+use zksync_object_store::{
+    serialize_using_bincode, Bucket, ObjectStore, ObjectStoreError, StoredObject,
+};
+use zksync_storage::{db::NamedColumnFamily, RocksDB};
+
+use crate::metrics::{CheckpointStage, METRICS};
+
+mod metrics;
+#[cfg(test)]
+mod tests;
+
+/// Information about a single file in a [`CheckpointManifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointFile {
+    /// Name of the file relative to the checkpoint directory.
+    pub name: String,
+    /// File size in bytes.
+    pub size: u64,
+    /// ID of the checkpoint as a part of which the file was uploaded. May be less than the ID of the containing
+    /// checkpoint if the file is reused from a previous checkpoint.
+    pub checkpoint_id: u64,
+}
+
+/// Manifest of a RocksDB checkpoint stored in an object store.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointManifest {
+    /// Name of the database as specified by [`NamedColumnFamily::DB_NAME`].
+    pub db_name: String,
+    /// Checkpoint ID. Equals to the checkpoint creation timestamp in milliseconds since UNIX epoch.
+    pub id: u64,
+    /// Size of chunks that checkpoint files are split into in the object store, in bytes.
+    pub chunk_size: u64,
+    /// Files constituting the checkpoint.
+    pub files: Vec<CheckpointFile>,
+}
+
+impl StoredObject for CheckpointManifest {
+    const BUCKET: Bucket = Bucket::RocksdbCheckpoints;
+    /// Name of the database.
+    type Key<'a> = &'a str;
+
+    fn encode_key(key: Self::Key<'_>) -> String {
+        format!("{key}_latest_checkpoint.bin")
+    }
+
+    serialize_using_bincode!();
+}
+
+impl CheckpointManifest {
+    fn chunk_key(&self, file: &CheckpointFile, chunk_index: u64) -> String {
+        format!(
+            "{}_checkpoint_{}_{}_{chunk_index}",
+            self.db_name, file.checkpoint_id, file.name
+        )
+    }
+
+    fn chunk_count(&self, file: &CheckpointFile) -> u64 {
+        file.size.div_ceil(self.chunk_size)
+    }
+
+    /// Returns files uploaded as a part of this checkpoint (i.e., not reused from a previous checkpoint).
+    fn new_files(&self) -> impl Iterator<Item = &CheckpointFile> + '_ {
+        self.files
+            .iter()
+            .filter(|file| file.checkpoint_id == self.id)
+    }
+
+    /// Returns object store keys of all chunks of all checkpoint files.
+    fn chunk_keys(&self) -> impl Iterator<Item = String> + '_ {
+        self.files.iter().flat_map(move |file| {
+            (0..self.chunk_count(file)).map(move |chunk_index| self.chunk_key(file, chunk_index))
+        })
+    }
+
+    /// Checks whether the specified file can be reused from this checkpoint, i.e., it is an immutable SST file
+    /// present in this checkpoint.
+    fn reusable_file(&self, name: &str, size: u64) -> Option<&CheckpointFile> {
+        if !name.ends_with(".sst") {
+            return None;
+        }
+        self.files
+            .iter()
+            .find(|file| file.name == name && file.size == size)
+    }
+
+    /// Returns the total size of checkpoint files in bytes.
+    pub fn total_size(&self) -> u64 {
+        self.files.iter().map(|file| file.size).sum()
+    }
+}
+
+/// Configuration for [`RocksdbCheckpointTask`].
+#[derive(Debug, Clone)]
+pub struct RocksdbCheckpointConfig {
+    /// Interval between uploading checkpoints.
+    pub interval: Duration,
+    /// Directory to create checkpoints in before uploading them. Should be located on the same filesystem
+    /// as the backed up RocksDB instance; otherwise, creating a checkpoint will copy all DB files.
+    /// The directory is cleared when [`RocksdbCheckpointTask`] starts.
+    pub scratch_dir: PathBuf,
+}
+
+impl RocksdbCheckpointConfig {
+    /// Creates a config with the scratch directory located next to the DB at `db_path`.
+    pub fn for_db_path(db_path: &Path, interval: Duration) -> Self {
+        let mut scratch_dir = db_path.as_os_str().to_owned();
+        scratch_dir.push(".checkpoints");
+        Self {
+            interval,
+            scratch_dir: scratch_dir.into(),
+        }
+    }
+}
+
+/// Default size of chunks that checkpoint files are split into: 64 MiB.
+const DEFAULT_CHUNK_SIZE: u64 = 64 << 20;
+
+/// Uploads checkpoints of a RocksDB instance to an object store.
+#[derive(Debug)]
+pub struct RocksdbCheckpointer<CF> {
+    db: RocksDB<CF>,
+    object_store: Arc<dyn ObjectStore>,
+    scratch_dir: PathBuf,
+    chunk_size: u64,
+    /// Manifest of the last checkpoint uploaded by this checkpointer. Only files uploaded by this checkpointer
+    /// are reused; SST file names are only guaranteed to be unique within a single RocksDB instance.
+    last_manifest: Mutex<Option<CheckpointManifest>>,
+}
+
+impl<CF: NamedColumnFamily> RocksdbCheckpointer<CF> {
+    pub fn new(db: RocksDB<CF>, object_store: Arc<dyn ObjectStore>, scratch_dir: PathBuf) -> Self {
+        Self {
+            db,
+            object_store,
+            scratch_dir,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            last_manifest: Mutex::new(None),
+        }
+    }
+
+    /// Sets the size of chunks that checkpoint files are split into in the object store. The default value is 64 MiB.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn with_chunk_size(mut self, chunk_size: u64) -> Self {
+        assert!(chunk_size > 0, "chunk size must be positive");
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Creates a checkpoint of the database and uploads it to the object store. SST files uploaded as a part
+    /// of the previous checkpoint are not uploaded again. On success, files of the previously uploaded checkpoint
+    /// not referenced by the new checkpoint are removed from the store.
+    ///
+    /// # Errors
+    ///
+    /// Propagates RocksDB, I/O and object store errors. Returns an error if the checkpoint previously uploaded
+    /// by this checkpointer was replaced by another writer.
+    pub async fn upload_checkpoint(&self) -> anyhow::Result<CheckpointManifest> {
+        let previous_manifest = self.latest_manifest().await?;
+        let last_manifest = self.last_manifest.lock().unwrap().clone();
+        if let Some(last_manifest) = &last_manifest {
+            let previous_id = previous_manifest.as_ref().map(|manifest| manifest.id);
+            anyhow::ensure!(
+                previous_id == Some(last_manifest.id),
+                "checkpoint #{} of RocksDB `{}` was replaced by another writer (latest checkpoint: {previous_id:?}); \
+                 only a single checkpoint writer is supported",
+                last_manifest.id,
+                CF::DB_NAME
+            );
+        }
+
+        let id = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .context("system time is before UNIX epoch")?
+            .as_millis()
+            .try_into()
+            .context("timestamp overflow")?;
+        let checkpoint_dir = self.scratch_dir.join(format!("{}_{id}", CF::DB_NAME));
+
+        let stage_latency = METRICS.stage_latency[&CheckpointStage::Create].start();
+        let db = self.db.clone();
+        let scratch_dir = self.scratch_dir.clone();
+        let files = tokio::task::spawn_blocking({
+            let checkpoint_dir = checkpoint_dir.clone();
+            move || create_checkpoint(&db, &scratch_dir, &checkpoint_dir)
+        })
+        .await
+        .context("panicked creating RocksDB checkpoint")??;
+        stage_latency.observe();
+
+        let last_manifest = last_manifest.filter(|manifest| manifest.chunk_size == self.chunk_size);
+        let files = files
+            .into_iter()
+            .map(|(name, size)| {
+                let reused_file = last_manifest
+                    .as_ref()
+                    .and_then(|manifest| manifest.reusable_file(&name, size));
+                reused_file.cloned().unwrap_or(CheckpointFile {
+                    name,
+                    size,
+                    checkpoint_id: id,
+                })
+            })
+            .collect();
+        let manifest = CheckpointManifest {
+            db_name: CF::DB_NAME.to_owned(),
+            id,
+            chunk_size: self.chunk_size,
+            files,
+        };
+        let upload_result = self.upload_new_files(&checkpoint_dir, &manifest).await;
+        // The checkpoint directory is no longer needed regardless of the upload outcome.
+        if let Err(err) = tokio::fs::remove_dir_all(&checkpoint_dir).await {
+            tracing::warn!(
+                "Failed removing RocksDB checkpoint directory `{}`: {err}",
+                checkpoint_dir.display()
+            );
+        }
+        let uploaded_size = upload_result?;
+
+        self.object_store
+            .put(CF::DB_NAME, &manifest)
+            .await
+            .context("failed uploading checkpoint manifest")?;
+        *self.last_manifest.lock().unwrap() = Some(manifest.clone());
+        let total_size = manifest.total_size();
+        METRICS.latest_checkpoint_size.set(total_size);
+        tracing::info!(
+            "Uploaded checkpoint #{id} of RocksDB `{}` ({} files, {total_size} bytes; {} new files, \
+             {uploaded_size} bytes uploaded)",
+            CF::DB_NAME,
+            manifest.files.len(),
+            manifest.new_files().count()
+        );
+
+        if let Some(previous_manifest) = previous_manifest {
+            self.remove_obsolete_files(&previous_manifest, &manifest)
+                .await;
+        }
+        Ok(manifest)
+    }
+
+    async fn latest_manifest(&self) -> anyhow::Result<Option<CheckpointManifest>> {
+        match self.object_store.get(CF::DB_NAME).await {
+            Ok(manifest) => Ok(Some(manifest)),
+            Err(ObjectStoreError::KeyNotFound(_)) => Ok(None),
+            Err(err) => Err(anyhow::Error::from(err).context("failed getting checkpoint manifest")),
+        }
+    }
+
+    /// Uploads files of the checkpoint not reused from the previous checkpoint chunk by chunk.
+    /// Returns the number of uploaded bytes.
+    async fn upload_new_files(
+        &self,
+        checkpoint_dir: &Path,
+        manifest: &CheckpointManifest,
+    ) -> anyhow::Result<u64> {
+        let stage_latency = METRICS.stage_latency[&CheckpointStage::Upload].start();
+        let mut uploaded_size = 0;
+        for file in manifest.new_files() {
+            let path = checkpoint_dir.join(&file.name);
+            let mut reader = tokio::fs::File::open(&path)
+                .await
+                .with_context(|| format!("failed opening `{}`", path.display()))?;
+            for chunk_index in 0..manifest.chunk_count(file) {
+                let mut chunk = Vec::with_capacity(manifest.chunk_size.min(file.size) as usize);
+                (&mut reader)
+                    .take(manifest.chunk_size)
+                    .read_to_end(&mut chunk)
+                    .await
+                    .with_context(|| format!("failed reading `{}`", path.display()))?;
+                let chunk_len = chunk.len() as u64;
+                self.object_store
+                    .put_raw(
+                        Bucket::RocksdbCheckpoints,
+                        &manifest.chunk_key(file, chunk_index),
+                        chunk,
+                    )
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "failed uploading chunk #{chunk_index} of checkpoint file `{}`",
+                            file.name
+                        )
+                    })?;
+                METRICS.uploaded_bytes.inc_by(chunk_len);
+                uploaded_size += chunk_len;
+            }
+        }
+        stage_latency.observe();
+        Ok(uploaded_size)
+    }
+
+    async fn remove_obsolete_files(
+        &self,
+        previous_manifest: &CheckpointManifest,
+        manifest: &CheckpointManifest,
+    ) {
+        let retained_keys: HashSet<_> = manifest.chunk_keys().collect();
+        for key in previous_manifest.chunk_keys() {
+            if retained_keys.contains(&key) {
+                continue;
+            }
+            if let Err(err) = self
+                .object_store
+                .remove_raw(Bucket::RocksdbCheckpoints, &key)
+                .await
+            {
+                tracing::warn!("Failed removing obsolete checkpoint file `{key}`: {err}");
+            }
+        }
+    }
+}
+
+/// Creates a checkpoint and returns names and sizes of its files.
+fn create_checkpoint<CF: NamedColumnFamily>(
+    db: &RocksDB<CF>,
+    scratch_dir: &Path,
+    checkpoint_dir: &Path,
+) -> anyhow::Result<Vec<(String, u64)>> {
+    std::fs::create_dir_all(scratch_dir)
+        .with_context(|| format!("failed creating `{}`", scratch_dir.display()))?;
+    db.create_checkpoint(checkpoint_dir)
+        .context("failed creating RocksDB checkpoint")?;
+
+    let mut files = vec![];
+    for entry in std::fs::read_dir(checkpoint_dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        anyhow::ensure!(
+            metadata.is_file(),
+            "unexpected non-file entry in RocksDB checkpoint: {:?}",
+            entry.path()
+        );
+        let name = entry
+            .file_name()
+            .into_string()
+            .map_err(|name| anyhow::anyhow!("non-UTF8 file name in checkpoint: {name:?}"))?;
+        files.push((name, metadata.len()));
+    }
+    files.sort_unstable();
+    Ok(files)
+}
+
+/// Maximum number of attempts to download the latest checkpoint. Another attempt is made if the checkpoint
+/// was replaced (and thus its files were removed) while being downloaded.
+const MAX_RESTORE_ATTEMPTS: usize = 3;
+
+/// Restores the latest checkpoint of a RocksDB instance with column families `CF` from the object store
+/// to the specified `path`, which must not exist.
+///
+/// # Return value
+///
+/// Returns the manifest of the restored checkpoint, or `None` if the store contains no checkpoints for the DB.
+///
+/// # Errors
+///
+/// Propagates I/O and object store errors.
+pub async fn restore_latest_checkpoint<CF: NamedColumnFamily>(
+    object_store: &dyn ObjectStore,
+    path: &Path,
+) -> anyhow::Result<Option<CheckpointManifest>> {
+    anyhow::ensure!(
+        !path.exists(),
+        "cannot restore RocksDB checkpoint to `{}`: path already exists",
+        path.display()
+    );
+    let mut restore_path = path.as_os_str().to_owned();
+    restore_path.push(".restoring");
+    let restore_path = PathBuf::from(restore_path);
+
+    let started_at = Instant::now();
+    for attempt in 1..=MAX_RESTORE_ATTEMPTS {
+        let manifest = match object_store.get::<CheckpointManifest>(CF::DB_NAME).await {
+            Ok(manifest) => manifest,
+            Err(ObjectStoreError::KeyNotFound(_)) => {
+                tracing::info!(
+                    "No checkpoints for RocksDB `{}` in object store",
+                    CF::DB_NAME
+                );
+                return Ok(None);
+            }
+            Err(err) => {
+                return Err(anyhow::Error::from(err).context("failed getting checkpoint manifest"));
+            }
+        };
+        tracing::info!(
+            "Restoring checkpoint #{} of RocksDB `{}` ({} files, {} bytes) to `{}`",
+            manifest.id,
+            CF::DB_NAME,
+            manifest.files.len(),
+            manifest.total_size(),
+            path.display()
+        );
+
+        if restore_path.exists() {
+            // Left over from an interrupted restore.
+            tokio::fs::remove_dir_all(&restore_path)
+                .await
+                .with_context(|| format!("failed removing `{}`", restore_path.display()))?;
+        }
+        tokio::fs::create_dir_all(&restore_path)
+            .await
+            .with_context(|| format!("failed creating `{}`", restore_path.display()))?;
+
+        match download_files(object_store, &manifest, &restore_path).await {
+            Ok(()) => {}
+            Err(ObjectStoreError::KeyNotFound(err)) if attempt < MAX_RESTORE_ATTEMPTS => {
+                tracing::info!(
+                    "Checkpoint #{} was replaced during download ({err}); retrying",
+                    manifest.id
+                );
+                continue;
+            }
+            Err(err) => {
+                return Err(anyhow::Error::from(err).context("failed downloading checkpoint file"));
+            }
+        }
+
+        for file in &manifest.files {
+            let file_path = restore_path.join(&file.name);
+            let size = tokio::fs::metadata(&file_path).await?.len();
+            anyhow::ensure!(
+                size == file.size,
+                "size mismatch for checkpoint file `{}`: expected {}, got {size}",
+                file.name,
+                file.size
+            );
+        }
+        tokio::fs::rename(&restore_path, path)
+            .await
+            .with_context(|| {
+                format!("failed moving restored checkpoint to `{}`", path.display())
+            })?;
+
+        let elapsed = started_at.elapsed();
+        METRICS.restore_latency.observe(elapsed);
+        tracing::info!(
+            "Restored checkpoint #{} of RocksDB `{}` in {elapsed:?}",
+            manifest.id,
+            CF::DB_NAME
+        );
+        return Ok(Some(manifest));
+    }
+    unreachable!("the last restore attempt always returns")
+}
+
+async fn download_files(
+    object_store: &dyn ObjectStore,
+    manifest: &CheckpointManifest,
+    restore_path: &Path,
+) -> Result<(), ObjectStoreError> {
+    let io_error = |err: std::io::Error| ObjectStoreError::Other {
+        source: err.into(),
+        is_transient: false,
+    };
+
+    for file in &manifest.files {
+        let file_path = restore_path.join(&file.name);
+        let mut writer = tokio::fs::File::create(&file_path)
+            .await
+            .map_err(io_error)?;
+        for chunk_index in 0..manifest.chunk_count(file) {
+            let chunk = object_store
+                .get_raw(
+                    Bucket::RocksdbCheckpoints,
+                    &manifest.chunk_key(file, chunk_index),
+                )
+                .await?;
+            writer.write_all(&chunk).await.map_err(io_error)?;
+        }
+        writer.sync_all().await.map_err(io_error)?;
+    }
+    Ok(())
+}
+
+/// Task periodically uploading checkpoints of a RocksDB instance to an object store.
+#[derive(Debug)]
+pub struct RocksdbCheckpointTask<CF> {
+    checkpointer: RocksdbCheckpointer<CF>,
+    interval: Duration,
+}
+
+impl<CF: NamedColumnFamily> RocksdbCheckpointTask<CF> {
+    pub fn new(
+        db: RocksDB<CF>,
+        object_store: Arc<dyn ObjectStore>,
+        config: RocksdbCheckpointConfig,
+    ) -> Self {
+        Self {
+            checkpointer: RocksdbCheckpointer::new(db, object_store, config.scratch_dir),
+            interval: config.interval,
+        }
+    }
+
+    /// Runs this task until a stop signal is received. Checkpoint upload errors are logged and do not stop the task.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the scratch directory cannot be cleared.
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        tracing::info!(
+            "Starting uploading checkpoints of RocksDB `{}` every {:?}",
+            CF::DB_NAME,
+            self.interval
+        );
+        let scratch_dir = &self.checkpointer.scratch_dir;
+        if scratch_dir.exists() {
+            // Remove checkpoints left over after an abnormal termination.
+            tokio::fs::remove_dir_all(scratch_dir)
+                .await
+                .with_context(|| format!("failed clearing `{}`", scratch_dir.display()))?;
+        }
+
+        while !*stop_receiver.borrow_and_update() {
+            if let Err(err) = self.checkpointer.upload_checkpoint().await {
+                METRICS.errors.inc();
+                tracing::warn!(
+                    "Failed uploading checkpoint of RocksDB `{}`: {err:#}",
+                    CF::DB_NAME
+                );
+            }
+            if tokio::time::timeout(self.interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!(
+            "Stop signal received, RocksDB `{}` checkpoint task is shutting down",
+            CF::DB_NAME
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+//! Metrics for RocksDB checkpoints.
+
+use std::time::Duration;
+
+use vise::{
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "stage", rename_all = "snake_case")]
+pub(crate) enum CheckpointStage {
+    Create,
+    Upload,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "rocksdb_checkpoints")]
+pub(crate) struct CheckpointMetrics {
+    /// Latency of checkpoint creation stages.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub stage_latency: Family<CheckpointStage, Histogram<Duration>>,
+    /// Total number of bytes uploaded to the object store.
+    pub uploaded_bytes: Counter,
+    /// Size of the latest uploaded checkpoint in bytes.
+    pub latest_checkpoint_size: Gauge<u64>,
+    /// Number of errors encountered when uploading checkpoints.
+    pub errors: Counter,
+    /// Latency of restoring a checkpoint from the object store.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub restore_latency: Histogram<Duration>,
+}
+
+#[vise::register]
+pub(crate) static METRICS: vise::Global<CheckpointMetrics> = vise::Global::new();
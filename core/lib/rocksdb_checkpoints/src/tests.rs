@@ -0,0 +1,266 @@
+//! Tests for RocksDB checkpoints.
+
+use tempfile::TempDir;
+use zksync_object_store::MockObjectStore;
+
+use super::*;
+
+#[derive(Debug, Clone, Copy)]
+struct TestColumnFamily;
+
+impl NamedColumnFamily for TestColumnFamily {
+    const DB_NAME: &'static str = "test";
+    const ALL: &'static [Self] = &[Self];
+
+    fn name(&self) -> &'static str {
+        "default"
+    }
+}
+
+fn create_db(path: &Path, entries: impl Iterator<Item = u64>) -> RocksDB<TestColumnFamily> {
+    let db = RocksDB::new(path).unwrap().with_sync_writes();
+    let mut batch = db.new_write_batch();
+    for i in entries {
+        batch.put_cf(TestColumnFamily, &i.to_be_bytes(), &i.to_le_bytes());
+    }
+    db.write(batch).unwrap();
+    db
+}
+
+fn assert_entries(db: &RocksDB<TestColumnFamily>, entries: impl Iterator<Item = u64>) {
+    for i in entries {
+        let value = db.get_cf(TestColumnFamily, &i.to_be_bytes()).unwrap();
+        assert_eq!(value.unwrap(), i.to_le_bytes(), "{i}");
+    }
+}
+
+#[tokio::test]
+async fn restoring_without_checkpoints() {
+    let temp_dir = TempDir::new().unwrap();
+    let object_store = MockObjectStore::arc();
+    let db_path = temp_dir.path().join("restored");
+
+    let manifest = restore_latest_checkpoint::<TestColumnFamily>(&*object_store, &db_path)
+        .await
+        .unwrap();
+    assert!(manifest.is_none());
+    assert!(!db_path.exists());
+}
+
+#[tokio::test]
+async fn uploading_and_restoring_checkpoint() {
+    let temp_dir = TempDir::new().unwrap();
+    let object_store = MockObjectStore::arc();
+    let db = create_db(&temp_dir.path().join("db"), 0..100);
+
+    let checkpointer = RocksdbCheckpointer::new(
+        db.clone(),
+        object_store.clone(),
+        temp_dir.path().join("scratch"),
+    );
+    let manifest = checkpointer.upload_checkpoint().await.unwrap();
+    assert_eq!(manifest.db_name, "test");
+    assert!(!manifest.files.is_empty());
+    assert!(manifest.files.iter().any(|file| file.name == "CURRENT"));
+    let scratch_entries = std::fs::read_dir(temp_dir.path().join("scratch")).unwrap();
+    assert_eq!(scratch_entries.count(), 0);
+
+    // Changes after the checkpoint must not be restored.
+    let mut batch = db.new_write_batch();
+    batch.put_cf(TestColumnFamily, b"new", b"value");
+    db.write(batch).unwrap();
+
+    let restored_path = temp_dir.path().join("restored");
+    let restored_manifest =
+        restore_latest_checkpoint::<TestColumnFamily>(&*object_store, &restored_path)
+            .await
+            .unwrap()
+            .expect("no checkpoint");
+    assert_eq!(restored_manifest, manifest);
+
+    let restored_db = RocksDB::<TestColumnFamily>::new(&restored_path).unwrap();
+    assert_entries(&restored_db, 0..100);
+    assert_eq!(restored_db.get_cf(TestColumnFamily, b"new").unwrap(), None);
+
+    // Restoring to an existing path should fail.
+    let err = restore_latest_checkpoint::<TestColumnFamily>(&*object_store, &restored_path)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("already exists"), "{err}");
+}
+
+#[tokio::test]
+async fn uploading_checkpoint_removes_previous_one() {
+    let temp_dir = TempDir::new().unwrap();
+    let object_store = MockObjectStore::arc();
+    let db = create_db(&temp_dir.path().join("db"), 0..10);
+    let checkpointer = RocksdbCheckpointer::new(
+        db.clone(),
+        object_store.clone(),
+        temp_dir.path().join("scratch"),
+    );
+    let first_manifest = checkpointer.upload_checkpoint().await.unwrap();
+
+    let mut batch = db.new_write_batch();
+    for i in 10..20_u64 {
+        batch.put_cf(TestColumnFamily, &i.to_be_bytes(), &i.to_le_bytes());
+    }
+    db.write(batch).unwrap();
+    // Ensure that the second checkpoint gets a different ID.
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    let second_manifest = checkpointer.upload_checkpoint().await.unwrap();
+    assert!(second_manifest.id > first_manifest.id);
+
+    // SST files of the first checkpoint must be reused.
+    let first_sst_files: Vec<_> = first_manifest
+        .files
+        .iter()
+        .filter(|file| file.name.ends_with(".sst"))
+        .collect();
+    assert!(!first_sst_files.is_empty());
+    for file in first_sst_files {
+        assert!(second_manifest.files.contains(file), "{file:?}");
+    }
+    assert!(second_manifest
+        .new_files()
+        .all(|file| file.checkpoint_id == second_manifest.id));
+
+    // Other files of the first checkpoint must be removed.
+    let second_keys: HashSet<_> = second_manifest.chunk_keys().collect();
+    for key in first_manifest.chunk_keys() {
+        let result = object_store.get_raw(Bucket::RocksdbCheckpoints, &key).await;
+        if second_keys.contains(&key) {
+            result.unwrap();
+        } else {
+            let err = result.unwrap_err();
+            assert!(matches!(err, ObjectStoreError::KeyNotFound(_)), "{err}");
+        }
+    }
+
+    let restored_path = temp_dir.path().join("restored");
+    let restored_manifest =
+        restore_latest_checkpoint::<TestColumnFamily>(&*object_store, &restored_path)
+            .await
+            .unwrap()
+            .expect("no checkpoint");
+    assert_eq!(restored_manifest, second_manifest);
+    let restored_db = RocksDB::<TestColumnFamily>::new(&restored_path).unwrap();
+    assert_entries(&restored_db, 0..20);
+}
+
+#[tokio::test]
+async fn concurrent_checkpoint_writer_is_detected() {
+    let temp_dir = TempDir::new().unwrap();
+    let object_store = MockObjectStore::arc();
+    let db = create_db(&temp_dir.path().join("db"), 0..10);
+    let checkpointer = RocksdbCheckpointer::new(
+        db.clone(),
+        object_store.clone(),
+        temp_dir.path().join("scratch"),
+    );
+    checkpointer.upload_checkpoint().await.unwrap();
+
+    let other_db = create_db(&temp_dir.path().join("other_db"), 0..20);
+    let other_checkpointer = RocksdbCheckpointer::new(
+        other_db,
+        object_store.clone(),
+        temp_dir.path().join("other_scratch"),
+    );
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    let other_manifest = other_checkpointer.upload_checkpoint().await.unwrap();
+
+    // The first checkpointer must not reuse or remove files referenced by the other writer's checkpoint.
+    let err = checkpointer.upload_checkpoint().await.unwrap_err();
+    assert!(err.to_string().contains("another writer"), "{err}");
+    for key in other_manifest.chunk_keys() {
+        object_store
+            .get_raw(Bucket::RocksdbCheckpoints, &key)
+            .await
+            .unwrap();
+    }
+
+    let restored_path = temp_dir.path().join("restored");
+    let restored_manifest =
+        restore_latest_checkpoint::<TestColumnFamily>(&*object_store, &restored_path)
+            .await
+            .unwrap()
+            .expect("no checkpoint");
+    assert_eq!(restored_manifest, other_manifest);
+    let restored_db = RocksDB::<TestColumnFamily>::new(&restored_path).unwrap();
+    assert_entries(&restored_db, 0..20);
+}
+
+#[tokio::test]
+async fn uploading_and_restoring_checkpoint_in_chunks() {
+    let temp_dir = TempDir::new().unwrap();
+    let object_store = MockObjectStore::arc();
+    let db = create_db(&temp_dir.path().join("db"), 0..1_000);
+
+    let checkpointer = RocksdbCheckpointer::new(
+        db.clone(),
+        object_store.clone(),
+        temp_dir.path().join("scratch"),
+    )
+    .with_chunk_size(100);
+    let manifest = checkpointer.upload_checkpoint().await.unwrap();
+    assert_eq!(manifest.chunk_size, 100);
+    assert!(manifest.files.iter().any(|file| file.size > 100));
+
+    for file in &manifest.files {
+        let chunk_count = manifest.chunk_count(file);
+        for chunk_index in 0..chunk_count {
+            let chunk = object_store
+                .get_raw(
+                    Bucket::RocksdbCheckpoints,
+                    &manifest.chunk_key(file, chunk_index),
+                )
+                .await
+                .unwrap();
+            if chunk_index + 1 < chunk_count {
+                assert_eq!(chunk.len(), 100);
+            } else {
+                assert!(!chunk.is_empty() && chunk.len() <= 100);
+            }
+        }
+    }
+
+    let restored_path = temp_dir.path().join("restored");
+    let restored_manifest =
+        restore_latest_checkpoint::<TestColumnFamily>(&*object_store, &restored_path)
+            .await
+            .unwrap()
+            .expect("no checkpoint");
+    assert_eq!(restored_manifest, manifest);
+    let restored_db = RocksDB::<TestColumnFamily>::new(&restored_path).unwrap();
+    assert_entries(&restored_db, 0..1_000);
+}
+
+#[tokio::test]
+async fn checkpoint_task_basics() {
+    let temp_dir = TempDir::new().unwrap();
+    let object_store = MockObjectStore::arc();
+    let db = create_db(&temp_dir.path().join("db"), 0..10);
+    let config = RocksdbCheckpointConfig::for_db_path(
+        &temp_dir.path().join("db"),
+        Duration::from_secs(3_600),
+    );
+    assert_eq!(config.scratch_dir, temp_dir.path().join("db.checkpoints"));
+
+    let task = RocksdbCheckpointTask::new(db, object_store.clone(), config);
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let task_handle = tokio::spawn(task.run(stop_receiver));
+
+    let manifest = loop {
+        match object_store.get::<CheckpointManifest>("test").await {
+            Ok(manifest) => break manifest,
+            Err(ObjectStoreError::KeyNotFound(_)) => {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            Err(err) => panic!("{err}"),
+        }
+    };
+    assert!(!manifest.files.is_empty());
+
+    stop_sender.send_replace(true);
+    task_handle.await.unwrap().unwrap();
+}
@@ -0,0 +1,384 @@
+//! Local snapshot archives.
+//!
+//! A snapshot archive is a single self-describing file containing all data necessary to recover a node
+//! from a snapshot: the snapshot header, the information about the snapshot L1 batch and L2 block, tokens,
+//! factory deps and storage log chunks. Archives allow recovering a node without access to the main node API
+//! or the object store holding snapshots, e.g. in air-gapped setups.
+//!
+//! # Layout
+//!
+//! - 8-byte magic ([`SnapshotArchive::MAGIC`])
+//! - Factory deps and storage log chunks in the same (compressed) format as they are stored in the object store
+//! - JSON-serialized manifest
+//! - Manifest length in bytes (little-endian `u64`)
+//! - 8-byte magic
+//!
+//! The manifest specifies the location of each object in the archive together with its Keccak-256 hash,
+//! which is checked each time the object is read.
+
+use std::{
+    collections::HashMap,
+    io::SeekFrom,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::{
+    fs,
+    io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt},
+};
+use zksync_object_store::{Bucket, ObjectStore, ObjectStoreError, StoredObject};
+use zksync_types::{
+    api,
+    snapshots::{
+        SnapshotFactoryDependencies, SnapshotHeader, SnapshotStorageLogsChunk,
+        SnapshotStorageLogsStorageKey,
+    },
+    tokens::TokenInfo,
+    web3::keccak256,
+    L1BatchNumber, L2BlockNumber, H256,
+};
+use zksync_web3_decl::error::EnrichedClientResult;
+
+use crate::SnapshotsApplierMainNodeClient;
+
+/// Location of an object store object in the archive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ArchiveEntry {
+    /// Object store key of the object.
+    key: String,
+    /// Offset of the object from the start of the archive file.
+    offset: u64,
+    len: u64,
+    /// Keccak-256 hash of the object bytes.
+    hash: H256,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ArchiveManifest {
+    version: u16,
+    header: SnapshotHeader,
+    l1_batch_details: api::L1BatchDetails,
+    l2_block_details: api::BlockDetails,
+    tokens: Vec<TokenInfo>,
+    factory_deps: ArchiveEntry,
+    storage_logs_chunks: Vec<ArchiveEntry>,
+}
+
+/// Local snapshot archive. Can be used both as a main node client and as an object store
+/// for [`SnapshotsApplierTask`](crate::SnapshotsApplierTask).
+#[derive(Debug, Clone)]
+pub struct SnapshotArchive {
+    path: PathBuf,
+    manifest: Arc<ArchiveManifest>,
+    entries: Arc<HashMap<String, ArchiveEntry>>,
+}
+
+impl SnapshotArchive {
+    /// Magic bytes at the start and at the end of the archive.
+    pub const MAGIC: [u8; 8] = *b"ZKSNAPSH";
+    /// Current version of the archive format.
+    const VERSION: u16 = 0;
+
+    /// Exports a snapshot into an archive at the specified path. If the L1 batch is not specified,
+    /// the newest snapshot is exported.
+    pub async fn export(
+        main_node_client: &dyn SnapshotsApplierMainNodeClient,
+        blob_store: &dyn ObjectStore,
+        l1_batch_number: Option<L1BatchNumber>,
+        path: &Path,
+    ) -> anyhow::Result<()> {
+        let l1_batch_number = match l1_batch_number {
+            Some(number) => number,
+            None => main_node_client
+                .fetch_newest_snapshot_l1_batch_number()
+                .await?
+                .context("no snapshots on main node")?,
+        };
+        let header = main_node_client
+            .fetch_snapshot(l1_batch_number)
+            .await?
+            .with_context(|| {
+                format!("snapshot for L1 batch #{l1_batch_number} is not present on main node")
+            })?;
+        let l2_block_number = header.l2_block_number;
+        let l1_batch_details = main_node_client
+            .fetch_l1_batch_details(l1_batch_number)
+            .await?
+            .with_context(|| format!("L1 batch #{l1_batch_number} is missing on main node"))?;
+        let l2_block_details = main_node_client
+            .fetch_l2_block_details(l2_block_number)
+            .await?
+            .with_context(|| format!("L2 block #{l2_block_number} is missing on main node"))?;
+        let tokens = main_node_client.fetch_tokens(l2_block_number).await?;
+        tracing::info!(
+            "Exporting snapshot for L1 batch #{l1_batch_number} with {} storage log chunks to {}",
+            header.storage_logs_chunks.len(),
+            path.display()
+        );
+
+        // The archive is written to a temporary file first, so that a partially written archive is never observed.
+        let tmp_path = path.with_extension("partial");
+        let mut file = fs::File::create(&tmp_path)
+            .await
+            .with_context(|| format!("failed creating {}", tmp_path.display()))?;
+        let mut writer = ArchiveWriter {
+            file: &mut file,
+            offset: 0,
+        };
+        writer.write(&Self::MAGIC).await?;
+
+        let factory_deps_key = SnapshotFactoryDependencies::encode_key(l1_batch_number);
+        let factory_deps = writer.copy_object(blob_store, factory_deps_key).await?;
+        let mut storage_logs_chunks = Vec::with_capacity(header.storage_logs_chunks.len());
+        for chunk in &header.storage_logs_chunks {
+            let key = SnapshotStorageLogsChunk::encode_key(SnapshotStorageLogsStorageKey {
+                l1_batch_number,
+                chunk_id: chunk.chunk_id,
+            });
+            storage_logs_chunks.push(writer.copy_object(blob_store, key).await?);
+            tracing::info!("Exported storage logs chunk {}", chunk.chunk_id);
+        }
+
+        let manifest = ArchiveManifest {
+            version: Self::VERSION,
+            header,
+            l1_batch_details,
+            l2_block_details,
+            tokens,
+            factory_deps,
+            storage_logs_chunks,
+        };
+        let manifest = serde_json::to_vec(&manifest).context("failed serializing manifest")?;
+        writer.write(&manifest).await?;
+        writer.write(&(manifest.len() as u64).to_le_bytes()).await?;
+        writer.write(&Self::MAGIC).await?;
+        file.sync_all().await?;
+        drop(file);
+
+        fs::rename(&tmp_path, path)
+            .await
+            .with_context(|| format!("failed moving archive to {}", path.display()))?;
+        tracing::info!("Exported snapshot to {}", path.display());
+        Ok(())
+    }
+
+    /// Opens an archive at the specified path. Only the manifest is read; objects are read lazily.
+    pub async fn open(path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        const TRAILER_LEN: u64 = 16;
+
+        let path = path.into();
+        let mut file = fs::File::open(&path)
+            .await
+            .with_context(|| format!("failed opening snapshot archive {}", path.display()))?;
+        let mut magic = [0_u8; 8];
+        file.read_exact(&mut magic).await?;
+        anyhow::ensure!(magic == Self::MAGIC, "file is not a snapshot archive");
+
+        let file_len = file.metadata().await?.len();
+        anyhow::ensure!(file_len >= TRAILER_LEN + 8, "snapshot archive is truncated");
+        file.seek(SeekFrom::Start(file_len - TRAILER_LEN)).await?;
+        let mut trailer = [0_u8; TRAILER_LEN as usize];
+        file.read_exact(&mut trailer).await?;
+        anyhow::ensure!(
+            trailer[8..] == Self::MAGIC,
+            "snapshot archive is truncated or corrupted"
+        );
+        let manifest_len = u64::from_le_bytes(trailer[..8].try_into().unwrap());
+        let manifest_offset = (file_len - TRAILER_LEN)
+            .checked_sub(manifest_len)
+            .context("snapshot archive manifest has invalid length")?;
+
+        file.seek(SeekFrom::Start(manifest_offset)).await?;
+        let mut manifest = vec![0_u8; manifest_len as usize];
+        file.read_exact(&mut manifest).await?;
+        let manifest: ArchiveManifest =
+            serde_json::from_slice(&manifest).context("failed deserializing manifest")?;
+        anyhow::ensure!(
+            manifest.version == Self::VERSION,
+            "unsupported snapshot archive version: {}",
+            manifest.version
+        );
+        anyhow::ensure!(
+            manifest.storage_logs_chunks.len() == manifest.header.storage_logs_chunks.len(),
+            "number of storage log chunks in the archive doesn't match the snapshot header"
+        );
+
+        let entries = manifest
+            .storage_logs_chunks
+            .iter()
+            .chain([&manifest.factory_deps])
+            .map(|entry| {
+                anyhow::ensure!(
+                    entry.offset.saturating_add(entry.len) <= manifest_offset,
+                    "entry {entry:?} is out of bounds"
+                );
+                Ok((entry.key.clone(), entry.clone()))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        tracing::info!(
+            "Opened archive {} with snapshot for L1 batch #{}",
+            path.display(),
+            manifest.header.l1_batch_number
+        );
+        Ok(Self {
+            path,
+            manifest: Arc::new(manifest),
+            entries: Arc::new(entries),
+        })
+    }
+
+    /// Returns the L1 batch number of the archived snapshot.
+    pub fn l1_batch_number(&self) -> L1BatchNumber {
+        self.manifest.header.l1_batch_number
+    }
+
+    async fn read_entry(&self, entry: &ArchiveEntry) -> Result<Vec<u8>, ObjectStoreError> {
+        let io_error = |err: std::io::Error| ObjectStoreError::Other {
+            source: err.into(),
+            is_transient: false,
+        };
+
+        let mut file = fs::File::open(&self.path).await.map_err(io_error)?;
+        file.seek(SeekFrom::Start(entry.offset))
+            .await
+            .map_err(io_error)?;
+        let mut buffer = vec![0_u8; entry.len as usize];
+        file.read_exact(&mut buffer).await.map_err(io_error)?;
+
+        let actual_hash = H256(keccak256(&buffer));
+        if actual_hash != entry.hash {
+            return Err(ObjectStoreError::Other {
+                source: format!(
+                    "hash mismatch for object `{}` in snapshot archive: expected {:?}, got {actual_hash:?}",
+                    entry.key, entry.hash
+                )
+                .into(),
+                is_transient: false,
+            });
+        }
+        Ok(buffer)
+    }
+}
+
+#[derive(Debug)]
+struct ArchiveWriter<'a> {
+    file: &'a mut fs::File,
+    offset: u64,
+}
+
+impl ArchiveWriter<'_> {
+    async fn write(&mut self, bytes: &[u8]) -> anyhow::Result<()> {
+        self.file
+            .write_all(bytes)
+            .await
+            .context("failed writing archive")?;
+        self.offset += bytes.len() as u64;
+        Ok(())
+    }
+
+    async fn copy_object(
+        &mut self,
+        blob_store: &dyn ObjectStore,
+        key: String,
+    ) -> anyhow::Result<ArchiveEntry> {
+        let bytes = blob_store
+            .get_raw(Bucket::StorageSnapshot, &key)
+            .await
+            .with_context(|| format!("failed fetching `{key}` from object store"))?;
+        let entry = ArchiveEntry {
+            key,
+            offset: self.offset,
+            len: bytes.len() as u64,
+            hash: H256(keccak256(&bytes)),
+        };
+        self.write(&bytes).await?;
+        Ok(entry)
+    }
+}
+
+#[async_trait]
+impl SnapshotsApplierMainNodeClient for SnapshotArchive {
+    async fn fetch_l1_batch_details(
+        &self,
+        number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<api::L1BatchDetails>> {
+        let details = &self.manifest.l1_batch_details;
+        Ok((details.number == number).then(|| details.clone()))
+    }
+
+    async fn fetch_l2_block_details(
+        &self,
+        number: L2BlockNumber,
+    ) -> EnrichedClientResult<Option<api::BlockDetails>> {
+        let details = &self.manifest.l2_block_details;
+        Ok((details.number == number).then(|| details.clone()))
+    }
+
+    async fn fetch_newest_snapshot_l1_batch_number(
+        &self,
+    ) -> EnrichedClientResult<Option<L1BatchNumber>> {
+        Ok(Some(self.l1_batch_number()))
+    }
+
+    async fn fetch_snapshot(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<SnapshotHeader>> {
+        let header = &self.manifest.header;
+        Ok((header.l1_batch_number == l1_batch_number).then(|| header.clone()))
+    }
+
+    async fn fetch_tokens(
+        &self,
+        at_l2_block: L2BlockNumber,
+    ) -> EnrichedClientResult<Vec<TokenInfo>> {
+        if at_l2_block == self.manifest.header.l2_block_number {
+            Ok(self.manifest.tokens.clone())
+        } else {
+            Ok(vec![])
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectStore for SnapshotArchive {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        let entry = (bucket == Bucket::StorageSnapshot)
+            .then(|| self.entries.get(key))
+            .flatten()
+            .ok_or_else(|| {
+                let err = format!("object `{key}` in bucket `{bucket}` is not in snapshot archive");
+                ObjectStoreError::KeyNotFound(err.into())
+            })?;
+        self.read_entry(entry).await
+    }
+
+    async fn put_raw(
+        &self,
+        _bucket: Bucket,
+        _key: &str,
+        _value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        Err(ObjectStoreError::Other {
+            source: "snapshot archive is read-only".into(),
+            is_transient: false,
+        })
+    }
+
+    async fn remove_raw(&self, _bucket: Bucket, _key: &str) -> Result<(), ObjectStoreError> {
+        Err(ObjectStoreError::Other {
+            source: "snapshot archive is read-only".into(),
+            is_transient: false,
+        })
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        format!("{}/{bucket}", self.path.display())
+    }
+}
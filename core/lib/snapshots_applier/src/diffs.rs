@@ -0,0 +1,189 @@
+//! Support of differential snapshots.
+
+use std::collections::HashMap;
+
+use zksync_object_store::ObjectStore;
+use zksync_types::{
+    snapshots::{
+        uniform_hashed_keys_chunk, SnapshotDiffManifest, SnapshotDiffPart,
+        SnapshotFactoryDependencies, SnapshotFactoryDependency, SnapshotHeader, SnapshotStorageLog,
+        SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey,
+    },
+    L1BatchNumber, L2BlockNumber, H256,
+};
+
+use crate::{SnapshotRecoveryStrategy, SnapshotsApplierError, SnapshotsApplierMainNodeClient};
+
+/// Chain of differential snapshots applied on top of a full snapshot.
+#[derive(Debug)]
+pub(crate) struct SnapshotDiffChain {
+    pub base: SnapshotHeader,
+    /// Ordered by ascending L1 batch number.
+    pub diffs: Vec<SnapshotDiffManifest>,
+}
+
+impl SnapshotDiffChain {
+    /// Resolves the chain ending at the specified L1 batch by following links to base snapshots
+    /// until a full snapshot known to the main node is encountered.
+    pub async fn resolve(
+        main_node_client: &dyn SnapshotsApplierMainNodeClient,
+        blob_store: &dyn ObjectStore,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<Self, SnapshotsApplierError> {
+        let mut diffs = vec![];
+        let mut current_l1_batch = l1_batch_number;
+        let base = loop {
+            if let Some(header) = main_node_client.fetch_snapshot(current_l1_batch).await? {
+                break header;
+            }
+
+            let manifest: SnapshotDiffManifest =
+                blob_store.get(current_l1_batch).await.map_err(|err| {
+                    let context = format!(
+                        "snapshot for L1 batch #{current_l1_batch} is not present on main node, and its differential \
+                         snapshot manifest cannot be fetched from object store"
+                    );
+                    SnapshotsApplierError::object_store(err, context)
+                })?;
+            tracing::info!("Fetched differential snapshot manifest: {manifest:?}");
+            SnapshotRecoveryStrategy::check_snapshot_version(manifest.version)?;
+            if manifest.l1_batch_number != current_l1_batch
+                || manifest.base_l1_batch_number >= current_l1_batch
+            {
+                let err = anyhow::anyhow!(
+                    "invalid differential snapshot manifest for L1 batch #{current_l1_batch}: {manifest:?}"
+                );
+                return Err(err.into());
+            }
+            current_l1_batch = manifest.base_l1_batch_number;
+            diffs.push(manifest);
+        };
+
+        diffs.reverse();
+        Ok(Self { base, diffs })
+    }
+
+    /// Returns the L1 batch of the state produced by applying the chain.
+    pub fn l1_batch_number(&self) -> L1BatchNumber {
+        self.diffs
+            .last()
+            .map_or(self.base.l1_batch_number, |diff| diff.l1_batch_number)
+    }
+
+    /// Returns the L2 block of the state produced by applying the chain.
+    pub fn l2_block_number(&self) -> L2BlockNumber {
+        self.diffs
+            .last()
+            .map_or(self.base.l2_block_number, |diff| diff.l2_block_number)
+    }
+}
+
+/// Merged data of all differential snapshots in a [`SnapshotDiffChain`]. The data is held in memory, which is acceptable
+/// since differential snapshots are expected to be much smaller than full ones.
+#[derive(Debug)]
+pub(crate) struct MergedSnapshotDiff {
+    pub base_l1_batch_number: L1BatchNumber,
+    /// Latest logs for storage slots present in the base snapshot, keyed by the hashed key.
+    updated_logs: HashMap<H256, SnapshotStorageLog>,
+    /// Logs for storage slots not present in the base snapshot, ordered by the hashed key.
+    new_logs: Vec<(H256, SnapshotStorageLog)>,
+    pub factory_deps: Vec<SnapshotFactoryDependency>,
+}
+
+impl MergedSnapshotDiff {
+    pub async fn load(
+        blob_store: &dyn ObjectStore,
+        chain: &SnapshotDiffChain,
+    ) -> Result<Self, SnapshotsApplierError> {
+        let base_l1_batch_number = chain.base.l1_batch_number;
+        let mut logs = HashMap::new();
+        let mut factory_deps = vec![];
+        for manifest in &chain.diffs {
+            let l1_batch_number = manifest.l1_batch_number;
+            let SnapshotDiffPart(diff_deps): SnapshotDiffPart<SnapshotFactoryDependencies> =
+                blob_store.get(l1_batch_number).await.map_err(|err| {
+                    let context = format!(
+                        "cannot fetch factory deps diff for L1 batch #{l1_batch_number} from object store"
+                    );
+                    SnapshotsApplierError::object_store(err, context)
+                })?;
+            factory_deps.extend(diff_deps.factory_deps);
+
+            for chunk_id in 0..manifest.storage_logs_chunk_count {
+                let storage_key = SnapshotStorageLogsStorageKey {
+                    l1_batch_number,
+                    chunk_id,
+                };
+                let SnapshotDiffPart(chunk): SnapshotDiffPart<SnapshotStorageLogsChunk> =
+                    blob_store.get(storage_key).await.map_err(|err| {
+                        let context = format!(
+                            "cannot fetch storage logs diff {storage_key:?} from object store"
+                        );
+                        SnapshotsApplierError::object_store(err, context)
+                    })?;
+                for log in chunk.storage_logs {
+                    if log.enumeration_index == 0
+                        || log.l1_batch_number_of_initial_write > l1_batch_number
+                    {
+                        let err = anyhow::anyhow!(
+                            "invalid storage log in differential snapshot for L1 batch #{l1_batch_number}: {log:?}"
+                        );
+                        return Err(err.into());
+                    }
+                    // Later diffs override earlier ones.
+                    logs.insert(log.key.hashed_key(), log);
+                }
+            }
+            tracing::info!(
+                "Loaded differential snapshot for L1 batch #{l1_batch_number}; {} storage logs are changed in total",
+                logs.len()
+            );
+        }
+
+        let (new_logs, updated_logs): (HashMap<_, _>, _) = logs
+            .into_iter()
+            .partition(|(_, log)| log.l1_batch_number_of_initial_write > base_l1_batch_number);
+        let mut new_logs: Vec<_> = new_logs.into_iter().collect();
+        new_logs.sort_unstable_by_key(|(hashed_key, _)| *hashed_key);
+        Ok(Self {
+            base_l1_batch_number,
+            updated_logs,
+            new_logs,
+            factory_deps,
+        })
+    }
+
+    /// Applies this diff to a chunk of storage logs from the base snapshot. Updated storage slots are changed in place,
+    /// and new slots are assigned to chunks based on their hashed keys.
+    pub fn apply_to_chunk(
+        &self,
+        storage_logs: &mut Vec<SnapshotStorageLog>,
+        chunk_id: u64,
+        chunk_count: u64,
+    ) -> anyhow::Result<()> {
+        for log in storage_logs.iter_mut() {
+            let Some(updated_log) = self.updated_logs.get(&log.key.hashed_key()) else {
+                continue;
+            };
+            anyhow::ensure!(
+                updated_log.key == log.key
+                    && updated_log.enumeration_index == log.enumeration_index
+                    && updated_log.l1_batch_number_of_initial_write
+                        == log.l1_batch_number_of_initial_write,
+                "storage log in differential snapshot {updated_log:?} is inconsistent with the base snapshot log {log:?}"
+            );
+            log.value = updated_log.value;
+        }
+
+        let hashed_keys_range = uniform_hashed_keys_chunk(chunk_id, chunk_count);
+        let start = self
+            .new_logs
+            .partition_point(|(hashed_key, _)| hashed_key < hashed_keys_range.start());
+        let end = self
+            .new_logs
+            .partition_point(|(hashed_key, _)| hashed_key <= hashed_keys_range.end());
+        let new_logs = self.new_logs[start..end].iter().map(|(_, log)| log.clone());
+        storage_logs.extend(new_logs);
+        Ok(())
+    }
+}
@@ -26,8 +26,14 @@ use zksync_web3_decl::{
     namespaces::{EnNamespaceClient, SnapshotsNamespaceClient, ZksNamespaceClient},
 };
 
-use self::metrics::{InitialStage, StorageLogsChunksStage, METRICS};
+pub use self::archive::SnapshotArchive;
+use self::{
+    diffs::{MergedSnapshotDiff, SnapshotDiffChain},
+    metrics::{InitialStage, StorageLogsChunksStage, METRICS},
+};
 
+mod archive;
+mod diffs;
 mod metrics;
 #[cfg(test)]
 mod tests;
@@ -237,6 +243,7 @@ pub struct SnapshotApplierTaskStats {
 #[derive(Debug)]
 pub struct SnapshotsApplierTask {
     snapshot_l1_batch: Option<L1BatchNumber>,
+    snapshot_diff_l1_batch: Option<L1BatchNumber>,
     config: SnapshotsApplierConfig,
     health_updater: HealthUpdater,
     connection_pool: ConnectionPool<Core>,
@@ -253,6 +260,7 @@ impl SnapshotsApplierTask {
     ) -> Self {
         Self {
             snapshot_l1_batch: None,
+            snapshot_diff_l1_batch: None,
             config,
             health_updater: ReactiveHealthCheck::new("snapshot_recovery").1,
             connection_pool,
@@ -266,6 +274,18 @@ impl SnapshotsApplierTask {
         self.snapshot_l1_batch = Some(number);
     }
 
+    /// Specifies the L1 batch to recover to using differential snapshots. Differential snapshots are resolved
+    /// from the object store by following links to base snapshots, until a full snapshot known to the main node
+    /// is encountered. The resulting state is applied to Postgres in a single recovery pass; it is then verified
+    /// by Merkle tree recovery against the root hash of this L1 batch.
+    ///
+    /// This setting is ignored if recovery is complete; it overrides [`Self::set_snapshot_l1_batch()`].
+    /// If recovery from differential snapshots is resumed, this setting must be specified again (its value
+    /// is ignored in favor of the L1 batch from the recovery status).
+    pub fn set_snapshot_diff_l1_batch(&mut self, number: L1BatchNumber) {
+        self.snapshot_diff_l1_batch = Some(number);
+    }
+
     /// Returns the health check for snapshot recovery.
     pub fn health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
@@ -291,6 +311,7 @@ impl SnapshotsApplierTask {
                 self.blob_store.as_ref(),
                 &self.health_updater,
                 self.snapshot_l1_batch,
+                self.snapshot_diff_l1_batch,
                 self.config.max_concurrency.get(),
             )
             .await;
@@ -345,8 +366,11 @@ impl SnapshotRecoveryStrategy {
     async fn new(
         storage: &mut Connection<'_, Core>,
         main_node_client: &dyn SnapshotsApplierMainNodeClient,
+        blob_store: &dyn ObjectStore,
         snapshot_l1_batch: Option<L1BatchNumber>,
-    ) -> Result<(Self, SnapshotRecoveryStatus), SnapshotsApplierError> {
+        snapshot_diff_l1_batch: Option<L1BatchNumber>,
+    ) -> Result<(Self, SnapshotRecoveryStatus, Option<SnapshotDiffChain>), SnapshotsApplierError>
+    {
         let latency =
             METRICS.initial_stage_duration[&InitialStage::FetchMetadataFromMainNode].start();
         let applied_snapshot_status = storage
@@ -357,12 +381,28 @@ impl SnapshotRecoveryStrategy {
         if let Some(applied_snapshot_status) = applied_snapshot_status {
             let sealed_l2_block_number = storage.blocks_dal().get_sealed_l2_block_number().await?;
             if sealed_l2_block_number.is_some() {
-                return Ok((Self::Completed, applied_snapshot_status));
+                return Ok((Self::Completed, applied_snapshot_status, None));
             }
 
+            let diff_chain = if let Some(diff_l1_batch) = snapshot_diff_l1_batch {
+                if diff_l1_batch != applied_snapshot_status.l1_batch_number {
+                    tracing::warn!(
+                        "Requested L1 batch #{diff_l1_batch} for differential snapshot recovery differs from \
+                         the L1 batch in the recovery status; continuing recovery from the status"
+                    );
+                }
+                let l1_batch_number = applied_snapshot_status.l1_batch_number;
+                Some(
+                    SnapshotDiffChain::resolve(main_node_client, blob_store, l1_batch_number)
+                        .await?,
+                )
+            } else {
+                None
+            };
+
             let latency = latency.observe();
             tracing::info!("Re-initialized snapshots applier after reset/failure in {latency:?}");
-            Ok((Self::Resumed, applied_snapshot_status))
+            Ok((Self::Resumed, applied_snapshot_status, diff_chain))
         } else {
             let is_genesis_needed = storage.blocks_dal().is_genesis_needed().await?;
             if !is_genesis_needed {
@@ -372,8 +412,17 @@ impl SnapshotRecoveryStrategy {
                 return Err(SnapshotsApplierError::Fatal(err));
             }
 
-            let recovery_status =
-                Self::create_fresh_recovery_status(main_node_client, snapshot_l1_batch).await?;
+            let diff_chain = if let Some(diff_l1_batch) = snapshot_diff_l1_batch {
+                Some(SnapshotDiffChain::resolve(main_node_client, blob_store, diff_l1_batch).await?)
+            } else {
+                None
+            };
+            let recovery_status = Self::create_fresh_recovery_status(
+                main_node_client,
+                snapshot_l1_batch,
+                diff_chain.as_ref(),
+            )
+            .await?;
 
             let storage_logs_count = storage
                 .storage_logs_dal()
@@ -390,34 +439,60 @@ impl SnapshotRecoveryStrategy {
 
             let latency = latency.observe();
             tracing::info!("Initialized fresh snapshots applier in {latency:?}");
-            Ok((Self::New, recovery_status))
+            Ok((Self::New, recovery_status, diff_chain))
         }
     }
 
     async fn create_fresh_recovery_status(
         main_node_client: &dyn SnapshotsApplierMainNodeClient,
         snapshot_l1_batch: Option<L1BatchNumber>,
+        diff_chain: Option<&SnapshotDiffChain>,
     ) -> Result<SnapshotRecoveryStatus, SnapshotsApplierError> {
-        let l1_batch_number = match snapshot_l1_batch {
-            Some(num) => num,
-            None => main_node_client
-                .fetch_newest_snapshot_l1_batch_number()
-                .await?
-                .context("no snapshots on main node; snapshot recovery is impossible")?,
+        let (l1_batch_number, l2_block_number, chunk_count) = if let Some(chain) = diff_chain {
+            let l1_batch_number = chain.l1_batch_number();
+            let l2_block_number = chain.l2_block_number();
+            tracing::info!(
+                "Found {diff_count} differential snapshot(s) with data up to L1 batch #{l1_batch_number}, \
+                 L2 block #{l2_block_number} on top of snapshot for L1 batch #{base_l1_batch}, version {version}, \
+                 storage logs are divided into {chunk_count} chunk(s)",
+                diff_count = chain.diffs.len(),
+                base_l1_batch = chain.base.l1_batch_number,
+                version = chain.base.version,
+                chunk_count = chain.base.storage_logs_chunks.len()
+            );
+            Self::check_snapshot_version(chain.base.version)?;
+            (
+                l1_batch_number,
+                l2_block_number,
+                chain.base.storage_logs_chunks.len(),
+            )
+        } else {
+            let l1_batch_number = match snapshot_l1_batch {
+                Some(num) => num,
+                None => main_node_client
+                    .fetch_newest_snapshot_l1_batch_number()
+                    .await?
+                    .context("no snapshots on main node; snapshot recovery is impossible")?,
+            };
+            let snapshot_response = main_node_client.fetch_snapshot(l1_batch_number).await?;
+
+            let snapshot = snapshot_response.with_context(|| {
+                format!("snapshot for L1 batch #{l1_batch_number} is not present on main node")
+            })?;
+            let l2_block_number = snapshot.l2_block_number;
+            tracing::info!(
+                "Found snapshot with data up to L1 batch #{l1_batch_number}, L2 block #{l2_block_number}, \
+                version {version}, storage logs are divided into {chunk_count} chunk(s)",
+                version = snapshot.version,
+                chunk_count = snapshot.storage_logs_chunks.len()
+            );
+            Self::check_snapshot_version(snapshot.version)?;
+            (
+                l1_batch_number,
+                l2_block_number,
+                snapshot.storage_logs_chunks.len(),
+            )
         };
-        let snapshot_response = main_node_client.fetch_snapshot(l1_batch_number).await?;
-
-        let snapshot = snapshot_response.with_context(|| {
-            format!("snapshot for L1 batch #{l1_batch_number} is not present on main node")
-        })?;
-        let l2_block_number = snapshot.l2_block_number;
-        tracing::info!(
-            "Found snapshot with data up to L1 batch #{l1_batch_number}, L2 block #{l2_block_number}, \
-            version {version}, storage logs are divided into {chunk_count} chunk(s)",
-            version = snapshot.version,
-            chunk_count = snapshot.storage_logs_chunks.len()
-        );
-        Self::check_snapshot_version(snapshot.version)?;
 
         let l1_batch = main_node_client
             .fetch_l1_batch_details(l1_batch_number)
@@ -449,11 +524,11 @@ impl SnapshotRecoveryStrategy {
             l1_batch_number,
             l1_batch_timestamp: l1_batch.base.timestamp,
             l1_batch_root_hash,
-            l2_block_number: snapshot.l2_block_number,
+            l2_block_number,
             l2_block_timestamp: l2_block.base.timestamp,
             l2_block_hash,
             protocol_version,
-            storage_logs_chunks_processed: vec![false; snapshot.storage_logs_chunks.len()],
+            storage_logs_chunks_processed: vec![false; chunk_count],
         })
     }
 
@@ -479,6 +554,8 @@ struct SnapshotsApplier<'a> {
     main_node_client: &'a dyn SnapshotsApplierMainNodeClient,
     blob_store: &'a dyn ObjectStore,
     applied_snapshot_status: SnapshotRecoveryStatus,
+    /// Merged differential snapshots applied on top of the base snapshot, if any.
+    diff: Option<MergedSnapshotDiff>,
     health_updater: &'a HealthUpdater,
     max_concurrency: usize,
     factory_deps_recovered: bool,
@@ -493,6 +570,7 @@ impl<'a> SnapshotsApplier<'a> {
         blob_store: &'a dyn ObjectStore,
         health_updater: &'a HealthUpdater,
         snapshot_l1_batch: Option<L1BatchNumber>,
+        snapshot_diff_l1_batch: Option<L1BatchNumber>,
         max_concurrency: usize,
     ) -> Result<(SnapshotRecoveryStrategy, SnapshotRecoveryStatus), SnapshotsApplierError> {
         // While the recovery is in progress, the node is healthy (no error has occurred),
@@ -504,10 +582,12 @@ impl<'a> SnapshotsApplier<'a> {
             .await?;
         let mut storage_transaction = storage.start_transaction().await?;
 
-        let (strategy, applied_snapshot_status) = SnapshotRecoveryStrategy::new(
+        let (strategy, applied_snapshot_status, diff_chain) = SnapshotRecoveryStrategy::new(
             &mut storage_transaction,
             main_node_client,
+            blob_store,
             snapshot_l1_batch,
+            snapshot_diff_l1_batch,
         )
         .await?;
         tracing::info!("Chosen snapshot recovery strategy: {strategy:?} with status: {applied_snapshot_status:?}");
@@ -516,12 +596,17 @@ impl<'a> SnapshotsApplier<'a> {
             SnapshotRecoveryStrategy::New => true,
             SnapshotRecoveryStrategy::Resumed => false,
         };
+        let diff = match &diff_chain {
+            Some(chain) => Some(MergedSnapshotDiff::load(blob_store, chain).await?),
+            None => None,
+        };
 
         let mut this = Self {
             connection_pool,
             main_node_client,
             blob_store,
             applied_snapshot_status,
+            diff,
             health_updater,
             max_concurrency,
             factory_deps_recovered: !created_from_scratch,
@@ -601,6 +686,16 @@ impl<'a> SnapshotsApplier<'a> {
             .update(Health::from(status).with_details(details));
     }
 
+    /// Returns the L1 batch of the full snapshot used for recovery. May differ from the L1 batch in the recovery status
+    /// if differential snapshots are applied.
+    fn base_l1_batch_number(&self) -> L1BatchNumber {
+        self.diff
+            .as_ref()
+            .map_or(self.applied_snapshot_status.l1_batch_number, |diff| {
+                diff.base_l1_batch_number
+            })
+    }
+
     async fn recover_factory_deps(
         &mut self,
         storage: &mut Connection<'_, Core>,
@@ -608,8 +703,8 @@ impl<'a> SnapshotsApplier<'a> {
         let latency = METRICS.initial_stage_duration[&InitialStage::ApplyFactoryDeps].start();
 
         tracing::debug!("Fetching factory dependencies from object store");
-        let l1_batch_number = self.applied_snapshot_status.l1_batch_number;
-        let factory_deps: SnapshotFactoryDependencies =
+        let l1_batch_number = self.base_l1_batch_number();
+        let mut factory_deps: SnapshotFactoryDependencies =
             self.blob_store.get(l1_batch_number).await.map_err(|err| {
                 let context = format!(
                     "cannot fetch factory deps for L1 batch #{l1_batch_number} from object store"
@@ -620,6 +715,15 @@ impl<'a> SnapshotsApplier<'a> {
             "Fetched {} factory dependencies from object store",
             factory_deps.factory_deps.len()
         );
+        if let Some(diff) = &self.diff {
+            tracing::debug!(
+                "Adding {} factory dependencies from differential snapshots",
+                diff.factory_deps.len()
+            );
+            factory_deps
+                .factory_deps
+                .extend(diff.factory_deps.iter().cloned());
+        }
 
         // we cannot insert all factory deps because of field size limit triggered by UNNEST
         // in underlying query, see `https://www.postgresql.org/docs/current/limits.html`
@@ -686,7 +790,7 @@ impl<'a> SnapshotsApplier<'a> {
 
         let storage_key = SnapshotStorageLogsStorageKey {
             chunk_id,
-            l1_batch_number: self.applied_snapshot_status.l1_batch_number,
+            l1_batch_number: self.base_l1_batch_number(),
         };
         let storage_snapshot_chunk: SnapshotStorageLogsChunk =
             self.blob_store.get(storage_key).await.map_err(|err| {
@@ -694,7 +798,15 @@ impl<'a> SnapshotsApplier<'a> {
                     format!("cannot fetch storage logs {storage_key:?} from object store");
                 SnapshotsApplierError::object_store(err, context)
             })?;
-        let storage_logs = &storage_snapshot_chunk.storage_logs;
+        let mut storage_logs = storage_snapshot_chunk.storage_logs;
+        if let Some(diff) = &self.diff {
+            let chunk_count = self
+                .applied_snapshot_status
+                .storage_logs_chunks_processed
+                .len();
+            diff.apply_to_chunk(&mut storage_logs, chunk_id, chunk_count as u64)?;
+        }
+        let storage_logs = &storage_logs;
         self.validate_storage_logs_chunk(storage_logs)?;
         let latency = latency.observe();
         tracing::info!(
@@ -18,7 +18,7 @@ use zksync_types::{
 
 use self::utils::{
     mock_l2_block_header, mock_recovery_status, mock_snapshot_header, mock_tokens, prepare_clients,
-    random_storage_logs, MockMainNodeClient, ObjectStoreWithErrors,
+    prepare_snapshot_diff, random_storage_logs, MockMainNodeClient, ObjectStoreWithErrors,
 };
 use super::*;
 use crate::tests::utils::HangingObjectStore;
@@ -190,6 +190,99 @@ async fn applier_error_for_missing_explicitly_specified_snapshot() {
     );
 }
 
+#[tokio::test]
+async fn applier_recovers_from_snapshot_diff() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let base_status = mock_recovery_status();
+    let base_logs = random_storage_logs(base_status.l1_batch_number, 200);
+    let (object_store, mut client) = prepare_clients(&base_status, &base_logs).await;
+
+    let expected_status = SnapshotRecoveryStatus {
+        l1_batch_number: base_status.l1_batch_number + 5,
+        l1_batch_root_hash: H256::random(),
+        l2_block_number: base_status.l2_block_number + 20,
+        l2_block_hash: H256::random(),
+        storage_logs_chunks_processed: vec![true; 2],
+        ..base_status.clone()
+    };
+    let mut diff_logs: Vec<_> = base_logs[..20]
+        .iter()
+        .map(|log| SnapshotStorageLog {
+            value: H256::random(),
+            ..log.clone()
+        })
+        .collect();
+    let new_logs = random_storage_logs(expected_status.l1_batch_number, 30);
+    diff_logs.extend(new_logs.into_iter().map(|log| SnapshotStorageLog {
+        enumeration_index: log.enumeration_index + 200,
+        ..log
+    }));
+    prepare_snapshot_diff(
+        &*object_store,
+        &mut client,
+        &base_status,
+        &expected_status,
+        &diff_logs,
+    )
+    .await;
+
+    let mut task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool.clone(),
+        Box::new(client),
+        object_store,
+    );
+    task.set_snapshot_diff_l1_batch(expected_status.l1_batch_number);
+    let stats = task.run().await.unwrap();
+    assert!(stats.done_work);
+
+    let mut storage = pool.connection().await.unwrap();
+    let applied_status = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await
+        .unwrap()
+        .expect("no recovery status");
+    assert_eq!(applied_status, expected_status);
+
+    let mut expected_logs: HashMap<_, _> = base_logs
+        .into_iter()
+        .map(|log| (log.key.hashed_key(), log))
+        .collect();
+    expected_logs.extend(diff_logs.into_iter().map(|log| (log.key.hashed_key(), log)));
+    assert_eq!(expected_logs.len(), 230);
+
+    let all_storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert_eq!(all_storage_logs.len(), expected_logs.len());
+    for db_log in all_storage_logs {
+        let expected_log = &expected_logs[&db_log.hashed_key];
+        assert_eq!(db_log.value, expected_log.value);
+        assert_eq!(db_log.l2_block_number, expected_status.l2_block_number);
+    }
+
+    let all_initial_writes = storage
+        .storage_logs_dedup_dal()
+        .dump_all_initial_writes_for_tests()
+        .await;
+    for initial_write in all_initial_writes {
+        let log = &expected_logs[&initial_write.hashed_key];
+        assert_eq!(
+            initial_write.l1_batch_number,
+            log.l1_batch_number_of_initial_write
+        );
+        assert_eq!(initial_write.index, log.enumeration_index);
+    }
+
+    let all_factory_deps = storage
+        .factory_deps_dal()
+        .dump_all_factory_deps_for_tests()
+        .await;
+    assert_eq!(all_factory_deps.len(), 2);
+}
+
 #[tokio::test]
 async fn snapshot_applier_recovers_after_stopping() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -528,3 +621,80 @@ async fn recovering_tokens() {
     );
     task.run().await.unwrap();
 }
+
+#[tokio::test]
+async fn recovering_from_snapshot_archive() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let archive_path = temp_dir.path().join("snapshot.bin");
+    let expected_status = mock_recovery_status();
+    let storage_logs = random_storage_logs(expected_status.l1_batch_number, 200);
+    let (object_store, mut client) = prepare_clients(&expected_status, &storage_logs).await;
+    client.tokens_response = mock_tokens();
+
+    SnapshotArchive::export(&client, &*object_store, None, &archive_path)
+        .await
+        .unwrap();
+    let archive = SnapshotArchive::open(&archive_path).await.unwrap();
+    assert_eq!(archive.l1_batch_number(), expected_status.l1_batch_number);
+
+    let task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool.clone(),
+        Box::new(archive.clone()),
+        Arc::new(archive),
+    );
+    let stats = task.run().await.unwrap();
+    assert!(stats.done_work);
+
+    let mut storage = pool.connection().await.unwrap();
+    let applied_status = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await
+        .unwrap()
+        .expect("no recovery status");
+    assert_eq!(applied_status, expected_status);
+    let all_storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert_eq!(all_storage_logs.len(), storage_logs.len());
+    let recovered_tokens = storage
+        .tokens_web3_dal()
+        .get_all_tokens(None)
+        .await
+        .unwrap();
+    assert_eq!(recovered_tokens.len(), client.tokens_response.len());
+}
+
+#[tokio::test]
+async fn snapshot_archive_detects_corrupted_objects() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let archive_path = temp_dir.path().join("snapshot.bin");
+    let expected_status = mock_recovery_status();
+    let storage_logs = random_storage_logs(expected_status.l1_batch_number, 100);
+    let (object_store, client) = prepare_clients(&expected_status, &storage_logs).await;
+    SnapshotArchive::export(&client, &*object_store, None, &archive_path)
+        .await
+        .unwrap();
+
+    // Corrupt the first object in the archive (factory deps), which immediately follows the magic bytes.
+    let mut archive_bytes = std::fs::read(&archive_path).unwrap();
+    archive_bytes[SnapshotArchive::MAGIC.len()] ^= 1;
+    std::fs::write(&archive_path, archive_bytes).unwrap();
+
+    let archive = SnapshotArchive::open(&archive_path).await.unwrap();
+    let task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool,
+        Box::new(archive.clone()),
+        Arc::new(archive),
+    );
+    let err = task.run().await.unwrap_err();
+    assert!(
+        format!("{err:#}").contains("hash mismatch"),
+        "unexpected error: {err:#}"
+    );
+}
@@ -9,9 +9,10 @@ use zksync_types::{
     api,
     block::L2BlockHeader,
     snapshots::{
-        SnapshotFactoryDependencies, SnapshotFactoryDependency, SnapshotHeader,
-        SnapshotRecoveryStatus, SnapshotStorageLog, SnapshotStorageLogsChunk,
-        SnapshotStorageLogsChunkMetadata, SnapshotStorageLogsStorageKey, SnapshotVersion,
+        SnapshotDiffManifest, SnapshotDiffPart, SnapshotFactoryDependencies,
+        SnapshotFactoryDependency, SnapshotHeader, SnapshotRecoveryStatus, SnapshotStorageLog,
+        SnapshotStorageLogsChunk, SnapshotStorageLogsChunkMetadata, SnapshotStorageLogsStorageKey,
+        SnapshotVersion,
     },
     tokens::{TokenInfo, TokenMetadata},
     web3::Bytes,
@@ -179,6 +180,7 @@ fn l1_batch_details(number: L1BatchNumber, root_hash: H256) -> api::L1BatchDetai
     api::L1BatchDetails {
         number,
         base: block_details_base(root_hash),
+        rollup_last_leaf_index: None,
     }
 }
 
@@ -302,6 +304,63 @@ pub(super) async fn prepare_clients(
     (object_store, client)
 }
 
+/// Puts a differential snapshot on top of the snapshot for `base_status` to the object store, and adds
+/// the corresponding L1 batch and L2 block info to the main node client.
+pub(super) async fn prepare_snapshot_diff(
+    object_store: &dyn ObjectStore,
+    client: &mut MockMainNodeClient,
+    base_status: &SnapshotRecoveryStatus,
+    status: &SnapshotRecoveryStatus,
+    logs: &[SnapshotStorageLog],
+) {
+    let factory_deps = SnapshotFactoryDependencies {
+        factory_deps: vec![SnapshotFactoryDependency {
+            bytecode: Bytes::from(vec![1; 32]),
+        }],
+    };
+    object_store
+        .put(status.l1_batch_number, &SnapshotDiffPart(factory_deps))
+        .await
+        .unwrap();
+
+    let chunk_key = SnapshotStorageLogsStorageKey {
+        l1_batch_number: status.l1_batch_number,
+        chunk_id: 0,
+    };
+    let chunk = SnapshotStorageLogsChunk {
+        storage_logs: logs.to_vec(),
+    };
+    object_store
+        .put(chunk_key, &SnapshotDiffPart(chunk))
+        .await
+        .unwrap();
+
+    let manifest = SnapshotDiffManifest {
+        version: SnapshotVersion::Version0.into(),
+        base_l1_batch_number: base_status.l1_batch_number,
+        l1_batch_number: status.l1_batch_number,
+        l2_block_number: status.l2_block_number,
+        storage_logs_chunk_count: 1,
+    };
+    object_store
+        .put(status.l1_batch_number, &manifest)
+        .await
+        .unwrap();
+
+    client.fetch_l1_batch_responses.insert(
+        status.l1_batch_number,
+        l1_batch_details(status.l1_batch_number, status.l1_batch_root_hash),
+    );
+    client.fetch_l2_block_responses.insert(
+        status.l2_block_number,
+        l2_block_details(
+            status.l2_block_number,
+            status.l1_batch_number,
+            status.l2_block_hash,
+        ),
+    );
+}
+
 /// Object store wrapper that hangs up after processing the specified number of requests.
 /// Used to emulate the snapshot applier being restarted since, if it's configured to have concurrency 1,
 /// the applier will request an object from the store strictly after fully processing all previously requested objects.
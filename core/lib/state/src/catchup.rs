@@ -1,8 +1,9 @@
-use std::{error, fmt, time::Instant};
+use std::{error, fmt, path::Path, sync::Arc, time::Instant};
 
 use anyhow::Context;
 use tokio::sync::watch;
 use zksync_dal::{ConnectionPool, Core};
+use zksync_object_store::ObjectStore;
 use zksync_shared_metrics::{SnapshotRecoveryStage, APP_METRICS};
 use zksync_storage::RocksDB;
 use zksync_types::L1BatchNumber;
@@ -42,7 +43,7 @@ impl error::Error for AsyncCatchupFailed {}
 type AsyncOnceCell<T> = watch::Receiver<Option<T>>;
 
 /// A lazily initialized handle to RocksDB cache returned from [`AsyncCatchupTask::new()`].
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RocksdbCell {
     initial_state: AsyncOnceCell<InitialRocksdbState>,
     db: AsyncOnceCell<RocksDB<StateKeeperColumnFamily>>,
@@ -100,6 +101,7 @@ pub struct AsyncCatchupTask {
     initial_state_sender: watch::Sender<Option<InitialRocksdbState>>,
     db_sender: watch::Sender<Option<RocksDB<StateKeeperColumnFamily>>>,
     to_l1_batch_number: Option<L1BatchNumber>,
+    checkpoint_store: Option<Arc<dyn ObjectStore>>,
 }
 
 impl AsyncCatchupTask {
@@ -115,6 +117,7 @@ impl AsyncCatchupTask {
             initial_state_sender,
             db_sender,
             to_l1_batch_number: None,
+            checkpoint_store: None,
         };
         (this, RocksdbCell { initial_state, db })
     }
@@ -134,6 +137,14 @@ impl AsyncCatchupTask {
         self
     }
 
+    /// Sets an object store with RocksDB checkpoints. If the RocksDB cache doesn't exist when the task starts,
+    /// it will be bootstrapped from the latest checkpoint in the store (if any).
+    #[must_use]
+    pub fn with_checkpoint_store(mut self, checkpoint_store: Arc<dyn ObjectStore>) -> Self {
+        self.checkpoint_store = Some(checkpoint_store);
+        self
+    }
+
     /// Block until RocksDB cache instance is caught up with Postgres.
     ///
     /// # Errors
@@ -144,12 +155,21 @@ impl AsyncCatchupTask {
         let started_at = Instant::now();
         tracing::info!("Catching up RocksDB asynchronously");
 
-        let mut rocksdb_builder = RocksdbStorage::builder_with_options(
-            self.state_keeper_db_path.as_ref(),
-            self.state_keeper_db_options,
-        )
-        .await
-        .context("Failed creating RocksDB storage builder")?;
+        let db_path = Path::new(&self.state_keeper_db_path);
+        // Restoring from a checkpoint can take a long time, so a Postgres connection is not held while it's in progress.
+        let rocksdb_builder = if let Some(checkpoint_store) = &self.checkpoint_store {
+            RocksdbStorage::builder_from_checkpoint(
+                db_path,
+                self.state_keeper_db_options,
+                checkpoint_store.as_ref(),
+                &self.pool,
+            )
+            .await
+        } else {
+            RocksdbStorage::builder_with_options(db_path, self.state_keeper_db_options).await
+        };
+        let mut rocksdb_builder =
+            rocksdb_builder.context("Failed creating RocksDB storage builder")?;
 
         let initial_state = InitialRocksdbState {
             l1_batch_number: rocksdb_builder.l1_batch_number().await,
@@ -187,6 +207,8 @@ impl AsyncCatchupTask {
 mod tests {
     use tempfile::TempDir;
     use test_casing::test_casing;
+    use zksync_object_store::MockObjectStore;
+    use zksync_rocksdb_checkpoints::RocksdbCheckpointer;
     use zksync_types::L2BlockNumber;
 
     use super::*;
@@ -236,6 +258,58 @@ mod tests {
         rocksdb_cell.get().unwrap(); // RocksDB must be caught up at this point
     }
 
+    #[tokio::test]
+    async fn catching_up_from_checkpoint() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        prepare_postgres(&mut conn).await;
+        let storage_logs = gen_storage_logs(20..40);
+        create_l2_block(&mut conn, L2BlockNumber(1), storage_logs.clone()).await;
+        create_l1_batch(&mut conn, L1BatchNumber(1), &storage_logs).await;
+
+        let temp_dir = TempDir::new().unwrap();
+        let (task, rocksdb_cell) =
+            AsyncCatchupTask::new(pool.clone(), temp_dir.path().to_str().unwrap().to_owned());
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        task.run(stop_receiver).await.unwrap();
+        let db = rocksdb_cell.wait().await.unwrap();
+
+        let checkpoint_store = MockObjectStore::arc();
+        RocksdbCheckpointer::new(
+            db,
+            checkpoint_store.clone(),
+            temp_dir.path().join("checkpoints"),
+        )
+        .upload_checkpoint()
+        .await
+        .unwrap();
+
+        let storage_logs = gen_storage_logs(40..60);
+        create_l2_block(&mut conn, L2BlockNumber(2), storage_logs.clone()).await;
+        create_l1_batch(&mut conn, L1BatchNumber(2), &storage_logs).await;
+        drop(conn);
+
+        let restored_dir = TempDir::new().unwrap();
+        let restored_path = restored_dir.path().join("state_keeper");
+        let (task, rocksdb_cell) =
+            AsyncCatchupTask::new(pool, restored_path.to_str().unwrap().to_owned());
+        let task = task.with_checkpoint_store(checkpoint_store);
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let task_handle = tokio::spawn(task.run(stop_receiver));
+
+        let initial_state = rocksdb_cell.ensure_initialized().await.unwrap();
+        assert_eq!(initial_state.l1_batch_number, Some(L1BatchNumber(2)));
+
+        task_handle.await.unwrap().unwrap();
+        let db = rocksdb_cell.get().unwrap();
+        assert_eq!(
+            RocksdbStorageBuilder::from_rocksdb(db)
+                .l1_batch_number()
+                .await,
+            Some(L1BatchNumber(3))
+        );
+    }
+
     #[derive(Debug)]
     enum CancellationScenario {
         DropTask,
@@ -30,7 +30,9 @@ use std::{
 use anyhow::Context as _;
 use itertools::{Either, Itertools};
 use tokio::sync::watch;
-use zksync_dal::{Connection, Core, CoreDal, DalError};
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalError};
+use zksync_object_store::ObjectStore;
+use zksync_rocksdb_checkpoints::restore_latest_checkpoint;
 use zksync_storage::{db::NamedColumnFamily, RocksDB, RocksDBOptions};
 use zksync_types::{L1BatchNumber, StorageKey, StorageValue, H256};
 
@@ -126,7 +128,7 @@ impl From<anyhow::Error> for RocksdbSyncError {
 }
 
 /// Options for [`RocksdbStorage`].
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct RocksdbStorageOptions {
     /// Size of the RocksDB block cache in bytes. The default value is 128 MiB.
     pub block_cache_capacity: usize,
@@ -297,6 +299,64 @@ impl RocksdbStorage {
             .map(RocksdbStorageBuilder)
     }
 
+    /// Creates a new storage builder with the provided RocksDB `path` and custom options. If there is no RocksDB instance
+    /// at `path`, it is bootstrapped from the latest checkpoint in `checkpoint_store` (if any). The restored instance
+    /// can then be caught up with Postgres using [`RocksdbStorageBuilder::synchronize()`].
+    ///
+    /// If the restored checkpoint is ahead of Postgres (e.g., because Postgres was restored from an older backup),
+    /// it is discarded, and an empty storage is created instead. A Postgres connection is only acquired
+    /// for this check, i.e., after the checkpoint is restored.
+    ///
+    /// # Errors
+    ///
+    /// Propagates RocksDB I/O, object store and Postgres errors.
+    pub async fn builder_from_checkpoint(
+        path: &Path,
+        options: RocksdbStorageOptions,
+        checkpoint_store: &dyn ObjectStore,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<RocksdbStorageBuilder> {
+        if path.exists() {
+            return Self::builder_with_options(path, options).await;
+        }
+
+        let manifest = restore_latest_checkpoint::<StateKeeperColumnFamily>(checkpoint_store, path)
+            .await
+            .context("failed restoring state keeper RocksDB from checkpoint")?;
+        let builder = Self::builder_with_options(path, options).await?;
+        let Some(manifest) = manifest else {
+            return Ok(builder);
+        };
+
+        let cache_l1_batch_number = builder.l1_batch_number().await;
+        let sealed_l1_batch_number = pool
+            .connection_tagged("state_keeper")
+            .await?
+            .blocks_dal()
+            .get_sealed_l1_batch_number()
+            .await?;
+        let next_sealed_l1_batch_number =
+            sealed_l1_batch_number.map_or(L1BatchNumber(0), |number| number + 1);
+        if cache_l1_batch_number > Some(next_sealed_l1_batch_number) {
+            tracing::warn!(
+                "State keeper RocksDB restored from checkpoint #{} is ahead of Postgres \
+                 (next L1 batch: {cache_l1_batch_number:?} vs {next_sealed_l1_batch_number}); discarding it",
+                manifest.id
+            );
+            drop(builder);
+            tokio::fs::remove_dir_all(path)
+                .await
+                .with_context(|| format!("failed removing `{}`", path.display()))?;
+            return Self::builder_with_options(path, options).await;
+        }
+
+        tracing::info!(
+            "Restored state keeper RocksDB from checkpoint #{}; next L1 batch: {cache_l1_batch_number:?}",
+            manifest.id
+        );
+        Ok(builder)
+    }
+
     async fn new(path: PathBuf, options: RocksdbStorageOptions) -> anyhow::Result<Self> {
         tokio::task::spawn_blocking(move || {
             let db = RocksDB::with_options(&path, options.into_generic())
@@ -618,6 +618,62 @@ pub struct DebugCall {
     pub error: Option<String>,
     pub revert_reason: Option<String>,
     pub calls: Vec<DebugCall>,
+    /// Decoded input and output of the call. Only present if decoding was requested and the callee is verified.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decoded: Option<DecodedCall>,
+}
+
+/// Function argument, return value or event field decoded using the ABI of a verified contract.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedParam {
+    /// Parameter name as specified in the ABI; may be empty.
+    pub name: String,
+    /// Solidity type of the parameter, e.g. `uint256` or `(address,bytes)[]`.
+    pub r#type: String,
+    /// Decoded value. Integers are encoded as decimal strings, addresses and bytes as 0x-prefixed hex strings.
+    pub value: serde_json::Value,
+}
+
+/// Function call decoded using the ABI of a verified contract.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedCall {
+    /// Function name, e.g. `transfer`.
+    pub name: String,
+    /// Function signature, e.g. `transfer(address,uint256)`.
+    pub signature: String,
+    pub inputs: Vec<DecodedParam>,
+    /// Decoded return values. `None` if the call has reverted or the output cannot be decoded.
+    pub outputs: Option<Vec<DecodedParam>>,
+}
+
+/// Event decoded using the ABI of the verified contract that emitted it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedEvent {
+    pub address: Address,
+    pub log_index: U256,
+    /// Event name, e.g. `Transfer`.
+    pub name: String,
+    /// Event signature, e.g. `Transfer(address,address,uint256)`.
+    pub signature: String,
+    pub params: Vec<DecodedParam>,
+}
+
+/// Transaction decoded using ABIs of verified contracts, as returned by `zks_decodeTransaction`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedTransaction {
+    pub transaction_hash: H256,
+    pub from: Address,
+    pub to: Option<Address>,
+    /// Decoded top-level call. `None` if the transaction recipient is not verified or calldata doesn't match its ABI.
+    pub call: Option<DecodedCall>,
+    /// Events emitted by verified contracts. Events that cannot be decoded are omitted.
+    pub events: Vec<DecodedEvent>,
+    /// Call trace with decoded calls to verified contracts, if the trace is available.
+    pub trace: Option<DebugCall>,
 }
 
 impl From<Call> for DebugCall {
@@ -640,6 +696,7 @@ impl From<Call> for DebugCall {
             error: value.error.clone(),
             revert_reason: value.revert_reason,
             calls,
+            decoded: None,
         }
     }
 }
@@ -731,6 +788,9 @@ pub enum SupportedTracers {
 #[serde(rename_all = "camelCase")]
 pub struct CallTracerConfig {
     pub only_top_call: bool,
+    /// Whether to decode calls to verified contracts using their ABIs.
+    #[serde(default)]
+    pub decode: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -787,6 +847,72 @@ pub struct L1BatchDetails {
     pub number: L1BatchNumber,
     #[serde(flatten)]
     pub base: BlockDetailsBase,
+    /// Enumeration index for the next leaf inserted into the Merkle tree, i.e. the number of tree leaves
+    /// after the batch plus 1. `None` if the batch isn't processed by the tree yet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rollup_last_leaf_index: Option<u64>,
+}
+
+/// Single attempt to send an L1 transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1SubmissionAttempt {
+    pub tx_hash: H256,
+    pub base_fee_per_gas: u64,
+    pub priority_fee_per_gas: u64,
+    pub blob_base_fee_per_gas: Option<u64>,
+    pub sent_at_block: Option<u32>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+}
+
+/// Information about the L1 transaction performing a certain operation (commit, prove or execute)
+/// for an L1 batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1SubmissionStageDetails {
+    pub eth_tx_id: u32,
+    /// Number of L1 batches covered by the L1 transaction. The transaction cost is split evenly among them.
+    pub l1_batch_count: u32,
+    pub created_at: DateTime<Utc>,
+    pub confirmed_tx_hash: Option<H256>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    pub gas_used: Option<u64>,
+    pub effective_gas_price: Option<u64>,
+    pub blob_gas_used: Option<u64>,
+    pub blob_gas_price: Option<u64>,
+    /// All sending attempts ordered by creation.
+    pub attempts: Vec<L1SubmissionAttempt>,
+}
+
+impl L1SubmissionStageDetails {
+    /// Returns the total cost of the L1 transaction in wei, or `None` if the transaction
+    /// is not confirmed or its receipt prices are unknown.
+    pub fn cost(&self) -> Option<U256> {
+        let execution_cost = U256::from(self.gas_used?) * U256::from(self.effective_gas_price?);
+        let blob_cost = match (self.blob_gas_used, self.blob_gas_price) {
+            (Some(gas_used), Some(price)) => U256::from(gas_used) * U256::from(price),
+            _ => U256::zero(),
+        };
+        Some(execution_cost + blob_cost)
+    }
+
+    /// Returns the share of the L1 transaction cost attributed to a single L1 batch.
+    pub fn cost_per_l1_batch(&self) -> Option<U256> {
+        Some(self.cost()? / U256::from(self.l1_batch_count.max(1)))
+    }
+}
+
+/// L1 submission timeline and costs for an L1 batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1BatchSubmissionDetails {
+    pub number: L1BatchNumber,
+    pub commit: Option<L1SubmissionStageDetails>,
+    pub prove: Option<L1SubmissionStageDetails>,
+    pub execute: Option<L1SubmissionStageDetails>,
+    /// Total L1 cost attributed to the batch in wei, summed over all confirmed stages with known costs.
+    pub total_cost: U256,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
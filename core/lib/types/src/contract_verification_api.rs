@@ -144,6 +144,10 @@ pub struct VerificationIncomingRequest {
     pub is_system: bool,
     #[serde(default)]
     pub force_evmla: bool,
+    /// Compiler output in the standard JSON format. If provided, the verifier checks that it's identical
+    /// to the output it reproduces by compiling the submitted sources with the requested compiler versions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compiler_output: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
@@ -217,12 +221,36 @@ pub struct CompilationArtifacts {
     pub abi: serde_json::Value,
 }
 
+/// How closely the compiled bytecode matches the deployed one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VerificationMatch {
+    /// Bytecodes are identical, including the metadata suffix.
+    #[default]
+    Full,
+    /// Bytecodes are identical except for the CBOR metadata suffix (e.g., because of different
+    /// metadata hash or source paths).
+    Partial,
+}
+
+impl VerificationMatch {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Full => "full",
+            Self::Partial => "partial",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct VerificationInfo {
     pub request: VerificationRequest,
     pub artifacts: CompilationArtifacts,
     pub verified_at: DateTime<Utc>,
+    /// Verification info saved before partial matches were supported always corresponds to a full match.
+    #[serde(default)]
+    pub match_type: VerificationMatch,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -116,6 +116,7 @@ mod test {
             error: None,
             revert_reason: None,
             calls: new_testing_trace(),
+            decoded: None,
         }
     }
 
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{aggregated_operations::AggregatedActionType, Address, Nonce, H256};
+use crate::{aggregated_operations::AggregatedActionType, Address, Nonce, H256, U256};
 
 /// A forward-compatible `enum` describing a EIP4844 sidecar
 ///
@@ -89,3 +89,11 @@ pub struct TxHistoryToSend {
     pub signed_raw_tx: Vec<u8>,
     pub nonce: Nonce,
 }
+
+/// Gas prices paid by a mined L1 transaction, as reported in its receipt.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct EthTxReceiptPrices {
+    pub effective_gas_price: Option<U256>,
+    pub blob_gas_used: Option<U256>,
+    pub blob_gas_price: Option<U256>,
+}
@@ -0,0 +1,76 @@
+//! Types related to archiving pruned node data.
+
+use serde::{Deserialize, Serialize};
+use zksync_basic_types::{L1BatchNumber, L2BlockNumber, H256};
+
+use crate::api;
+
+/// L2 block data archived before the block is pruned from the node storage.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchivedL2Block {
+    /// Block in the API format, with full transactions.
+    pub block: api::Block<api::TransactionVariant>,
+    /// Receipts for all block transactions ordered by the transaction index. Receipts include events
+    /// and L2-to-L1 logs emitted by the transactions.
+    pub receipts: Vec<api::TransactionReceipt>,
+}
+
+impl ArchivedL2Block {
+    pub fn number(&self) -> L2BlockNumber {
+        L2BlockNumber(self.block.number.as_u32())
+    }
+
+    /// Returns the archived block with transaction hashes instead of full transactions.
+    pub fn block_with_tx_hashes(&self) -> api::Block<api::TransactionVariant> {
+        let transactions = self
+            .block
+            .transactions
+            .iter()
+            .map(|tx| match tx {
+                api::TransactionVariant::Full(tx) => api::TransactionVariant::Hash(tx.hash),
+                api::TransactionVariant::Hash(hash) => api::TransactionVariant::Hash(*hash),
+            })
+            .collect();
+        self.block.clone().with_transactions(transactions)
+    }
+
+    /// Iterates over all events emitted in this block.
+    pub fn logs(&self) -> impl Iterator<Item = &api::Log> + '_ {
+        self.receipts.iter().flat_map(|receipt| &receipt.logs)
+    }
+}
+
+/// Archive of all L2 blocks in an L1 batch written to the object store before the batch is pruned.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchivedL1Batch {
+    pub l1_batch_number: L1BatchNumber,
+    /// L2 blocks in the batch ordered by the block number.
+    pub l2_blocks: Vec<ArchivedL2Block>,
+}
+
+impl ArchivedL1Batch {
+    /// Gets an archived L2 block with the specified number.
+    pub fn l2_block(&self, number: L2BlockNumber) -> Option<&ArchivedL2Block> {
+        let first_block_number = self.l2_blocks.first()?.number();
+        let idx = number.0.checked_sub(first_block_number.0)?;
+        self.l2_blocks
+            .get(idx as usize)
+            .filter(|block| block.number() == number)
+    }
+
+    /// Iterates over hashes of all transactions in this batch.
+    pub fn transaction_hashes(&self) -> impl Iterator<Item = H256> + '_ {
+        self.l2_blocks
+            .iter()
+            .flat_map(|block| &block.receipts)
+            .map(|receipt| receipt.transaction_hash)
+    }
+
+    /// Gets an archived receipt for the transaction with the specified hash.
+    pub fn transaction_receipt(&self, tx_hash: H256) -> Option<&api::TransactionReceipt> {
+        self.l2_blocks
+            .iter()
+            .flat_map(|block| &block.receipts)
+            .find(|receipt| receipt.transaction_hash == tx_hash)
+    }
+}
@@ -101,6 +101,41 @@ pub struct SnapshotFactoryDependency {
     pub bytecode: Bytes,
 }
 
+/// Manifest of a differential snapshot. A differential snapshot contains storage logs and factory dependencies
+/// changed after the end of the base snapshot L1 batch, up to and including its own L1 batch. Thus, applying it
+/// on top of the base snapshot produces the storage state at the end of [`Self::l1_batch_number`].
+///
+/// The base snapshot may be either a full snapshot or another differential snapshot, so differential snapshots
+/// form chains ending in a full snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotDiffManifest {
+    // Not a `SnapshotVersion` for the same reasons as in `SnapshotHeader`.
+    pub version: u16,
+    /// L1 batch of the base snapshot.
+    pub base_l1_batch_number: L1BatchNumber,
+    /// L1 batch of the snapshot. The data in the snapshot captures node storage at the end of this batch.
+    pub l1_batch_number: L1BatchNumber,
+    /// Last L2 block in [`Self::l1_batch_number`].
+    pub l2_block_number: L2BlockNumber,
+    /// Number of storage log chunks. Chunks are keyed by [`SnapshotStorageLogsStorageKey`] with the L1 batch
+    /// of the differential snapshot.
+    pub storage_logs_chunk_count: u64,
+}
+
+/// Metadata of a differential snapshot persisted in Postgres.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotDiffMetadata {
+    pub l1_batch_number: L1BatchNumber,
+    pub base_l1_batch_number: L1BatchNumber,
+    pub storage_logs_chunk_count: u64,
+}
+
+/// Part of a differential snapshot (i.e., [`SnapshotStorageLogsChunk`] or [`SnapshotFactoryDependencies`]).
+/// This wrapper allows to store differential snapshot data separately from full snapshots for the same L1 batch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotDiffPart<T>(pub T);
+
 impl ProtoFmt for SnapshotFactoryDependency {
     type Proto = crate::proto::SnapshotFactoryDependency;
 
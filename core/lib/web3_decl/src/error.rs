@@ -25,6 +25,10 @@ pub enum Web3Error {
     PrunedBlock(L2BlockNumber),
     #[error("L1 batch with such an ID is pruned; the first retained L1 batch is {0}")]
     PrunedL1Batch(L1BatchNumber),
+    #[error(
+        "Merkle tree data for such an L1 batch is pruned; the first L1 batch with proofs is {0}"
+    )]
+    PrunedProofs(L1BatchNumber),
     #[error("{}", _0.as_ref())]
     ProxyError(#[from] EnrichedClientError),
     #[error("{0}")]
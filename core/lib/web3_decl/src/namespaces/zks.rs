@@ -5,7 +5,8 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion,
+        BlockDetails, BridgeAddresses, DecodedTransaction, L1BatchDetails,
+        L1BatchSubmissionDetails, L2ToL1LogProof, Proof, ProtocolVersion,
         TransactionDetailedResult, TransactionDetails,
     },
     fee::Fee,
@@ -90,6 +91,9 @@ pub trait ZksNamespace {
     #[method(name = "getTransactionDetails")]
     async fn get_transaction_details(&self, hash: H256) -> RpcResult<Option<TransactionDetails>>;
 
+    #[method(name = "decodeTransaction")]
+    async fn decode_transaction(&self, hash: H256) -> RpcResult<Option<DecodedTransaction>>;
+
     #[method(name = "getRawBlockTransactions")]
     async fn get_raw_block_transactions(
         &self,
@@ -100,6 +104,12 @@ pub trait ZksNamespace {
     async fn get_l1_batch_details(&self, batch: L1BatchNumber)
         -> RpcResult<Option<L1BatchDetails>>;
 
+    #[method(name = "getL1BatchSubmissionDetails")]
+    async fn get_l1_batch_submission_details(
+        &self,
+        batch: L1BatchNumber,
+    ) -> RpcResult<Option<L1BatchSubmissionDetails>>;
+
     #[method(name = "getBytecodeByHash")]
     async fn get_bytecode_by_hash(&self, hash: H256) -> RpcResult<Option<Vec<u8>>>;
 
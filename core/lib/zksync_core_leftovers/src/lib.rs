@@ -88,6 +88,8 @@ pub enum Component {
     CommitmentGenerator,
     /// VM runner-based component that saves protective reads to Postgres.
     VmRunnerProtectiveReads,
+    /// Component periodically creating storage snapshots.
+    SnapshotsCreator,
 }
 
 #[derive(Debug)]
@@ -127,6 +129,7 @@ impl FromStr for Components {
             "vm_runner_protective_reads" => {
                 Ok(Components(vec![Component::VmRunnerProtectiveReads]))
             }
+            "snapshots_creator" => Ok(Components(vec![Component::SnapshotsCreator])),
             other => Err(format!("{} is not a valid component name", other)),
         }
     }
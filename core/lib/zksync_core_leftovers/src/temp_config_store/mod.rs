@@ -106,10 +106,11 @@ impl TempConfigStore {
         let eth_sender = self.eth_sender_config.as_ref().and_then(|config| {
             let sender = config.sender.as_ref()?;
             let operator_private_key = sender.private_key().ok()??;
-            let operator = Wallet::new(operator_private_key);
+            let operator = Wallet::new(operator_private_key).into();
             let blob_operator = sender
                 .private_key_blobs()
-                .and_then(|operator| Wallet::from_private_key_bytes(operator, None).ok());
+                .and_then(|operator| Wallet::from_private_key_bytes(operator, None).ok())
+                .map(Into::into);
             Some(EthSender {
                 operator,
                 blob_operator,
@@ -34,11 +34,12 @@ use zksync_types::{
 use zksync_utils::{h256_to_u256, time::seconds_since_epoch, u256_to_h256};
 
 use super::{
+    storage::SandboxStorage,
     vm_metrics::{self, SandboxStage, SANDBOX_METRICS},
     BlockArgs, TxExecutionArgs, TxSharedArgs, VmPermit,
 };
 
-type BoxedVm<'a> = Box<VmInstance<StorageView<PostgresStorage<'a>>, HistoryDisabled>>;
+type BoxedVm<'a> = Box<VmInstance<StorageView<SandboxStorage<'a>>, HistoryDisabled>>;
 
 #[derive(Debug)]
 struct Sandbox<'a> {
@@ -46,7 +47,7 @@ struct Sandbox<'a> {
     l1_batch_env: L1BatchEnv,
     execution_args: &'a TxExecutionArgs,
     l2_block_info_to_reset: Option<StoredL2BlockInfo>,
-    storage_view: StorageView<PostgresStorage<'a>>,
+    storage_view: StorageView<SandboxStorage<'a>>,
 }
 
 impl<'a> Sandbox<'a> {
@@ -80,6 +81,22 @@ impl<'a> Sandbox<'a> {
         )
         .await?;
 
+        let tree_state = if let Some(tree) = shared_args.storage_tree.clone() {
+            let overlay = tree
+                .load_overlay(&mut connection, resolved_block_info.state_l2_block_number)
+                .await
+                .context("cannot load tree state overlay")?;
+            if overlay.is_none() {
+                tracing::debug!(
+                    "Merkle tree doesn't retain state for L2 block #{}; falling back to Postgres storage",
+                    resolved_block_info.state_l2_block_number
+                );
+            }
+            overlay.map(|overlay| (tree, overlay))
+        } else {
+            None
+        };
+
         let storage = PostgresStorage::new_async(
             Handle::current(),
             connection,
@@ -89,6 +106,10 @@ impl<'a> Sandbox<'a> {
         .await
         .context("cannot create `PostgresStorage`")?
         .with_caches(shared_args.caches.clone());
+        let storage = match tree_state {
+            Some((tree, overlay)) => SandboxStorage::Tree(tree.create_storage(overlay, storage)),
+            None => SandboxStorage::Postgres(storage),
+        };
 
         let storage_view = StorageView::new(storage);
         let (system_env, l1_batch_env) = Self::prepare_env(
@@ -259,7 +280,7 @@ impl<'a> Sandbox<'a> {
         mut self,
         tx: &Transaction,
         adjust_pubdata_price: bool,
-    ) -> (BoxedVm<'a>, StoragePtr<StorageView<PostgresStorage<'a>>>) {
+    ) -> (BoxedVm<'a>, StoragePtr<StorageView<SandboxStorage<'a>>>) {
         self.setup_storage_view(tx);
         let protocol_version = self.system_env.version;
         if adjust_pubdata_price {
@@ -296,7 +317,7 @@ pub(super) fn apply_vm_in_sandbox<T>(
     tx: Transaction,
     block_args: BlockArgs,
     apply: impl FnOnce(
-        &mut VmInstance<StorageView<PostgresStorage<'_>>, HistoryDisabled>,
+        &mut VmInstance<StorageView<SandboxStorage<'_>>, HistoryDisabled>,
         Transaction,
         ProtocolVersionId,
     ) -> T,
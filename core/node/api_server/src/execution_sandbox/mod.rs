@@ -6,7 +6,10 @@ use std::{
 use anyhow::Context as _;
 use rand::{thread_rng, Rng};
 use tokio::runtime::Handle;
-use zksync_dal::{pruning_dal::PruningInfo, Connection, Core, CoreDal, DalError};
+use zksync_dal::{
+    pruning_dal::{PrunedDataKind, PruningInfo},
+    Connection, Core, CoreDal, DalError,
+};
 use zksync_state::PostgresStorageCaches;
 use zksync_types::{
     api, fee_model::BatchFeeInput, AccountTreeId, Address, L1BatchNumber, L2BlockNumber, L2ChainId,
@@ -16,6 +19,7 @@ use self::vm_metrics::SandboxStage;
 pub(super) use self::{
     error::SandboxExecutionError,
     execute::{TransactionExecutor, TxExecutionArgs},
+    storage::{TreeStateOverlayCache, VmStorageTree},
     tracers::ApiTracer,
     validate::ValidationError,
     vm_metrics::{SubmitTxStage, SANDBOX_METRICS},
@@ -26,6 +30,7 @@ use super::tx_sender::MultiVMBaseSystemContracts;
 mod apply;
 mod error;
 mod execute;
+mod storage;
 pub mod testonly;
 #[cfg(test)]
 mod tests;
@@ -169,6 +174,9 @@ pub(crate) struct TxSharedArgs {
     pub fee_input: BatchFeeInput,
     pub base_system_contracts: MultiVMBaseSystemContracts,
     pub caches: PostgresStorageCaches,
+    /// Merkle tree used to read VM storage. If not set or if the tree doesn't retain the necessary state,
+    /// storage is read from Postgres.
+    pub storage_tree: Option<VmStorageTree>,
     pub validation_computational_gas_limit: u32,
     pub chain_id: L2ChainId,
     pub whitelisted_tokens_for_aa: Vec<Address>,
@@ -182,6 +190,7 @@ impl TxSharedArgs {
             fee_input: BatchFeeInput::l1_pegged(55, 555),
             base_system_contracts,
             caches: PostgresStorageCaches::new(1, 1),
+            storage_tree: None,
             validation_computational_gas_limit: u32::MAX,
             chain_id: L2ChainId::default(),
             whitelisted_tokens_for_aa: Vec::new(),
@@ -189,9 +198,45 @@ impl TxSharedArgs {
     }
 }
 
+/// Pruning info for the main L1 batch data and for data kinds that can be pruned separately.
+#[derive(Debug, Clone, Copy)]
+struct FullPruningInfo {
+    main: PruningInfo,
+    events: PruningInfo,
+    transactions: PruningInfo,
+}
+
+impl FullPruningInfo {
+    async fn load(storage: &mut Connection<'_, Core>) -> anyhow::Result<Self> {
+        let mut dal = storage.pruning_dal();
+        let main = dal.get_pruning_info().await?;
+        // Data kinds not pruned separately are pruned together with the main data.
+        let events = dal
+            .get_data_kind_pruning_info(PrunedDataKind::Events)
+            .await?
+            .unwrap_or(main);
+        let transactions = dal
+            .get_data_kind_pruning_info(PrunedDataKind::Transactions)
+            .await?
+            .unwrap_or(main);
+        Ok(Self {
+            main,
+            events,
+            transactions,
+        })
+    }
+
+    fn for_data_kind(&self, kind: PrunedDataKind) -> PruningInfo {
+        match kind {
+            PrunedDataKind::Events => self.events,
+            PrunedDataKind::Transactions => self.transactions,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 struct BlockStartInfoInner {
-    info: PruningInfo,
+    info: FullPruningInfo,
     cached_at: Instant,
 }
 
@@ -224,7 +269,7 @@ impl BlockStartInfo {
         storage: &mut Connection<'_, Core>,
         max_cache_age: Duration,
     ) -> anyhow::Result<Self> {
-        let info = storage.pruning_dal().get_pruning_info().await?;
+        let info = FullPruningInfo::load(storage).await?;
         Ok(Self {
             cached_pruning_info: Arc::new(RwLock::new(BlockStartInfoInner {
                 info,
@@ -245,8 +290,8 @@ impl BlockStartInfo {
         &self,
         storage: &mut Connection<'_, Core>,
         now: Instant,
-    ) -> anyhow::Result<PruningInfo> {
-        let info = storage.pruning_dal().get_pruning_info().await?;
+    ) -> anyhow::Result<FullPruningInfo> {
+        let info = FullPruningInfo::load(storage).await?;
 
         let mut new_cached_pruning_info = self
             .cached_pruning_info
@@ -267,7 +312,7 @@ impl BlockStartInfo {
     async fn get_pruning_info(
         &self,
         storage: &mut Connection<'_, Core>,
-    ) -> anyhow::Result<PruningInfo> {
+    ) -> anyhow::Result<FullPruningInfo> {
         let inner = self.copy_inner();
         let now = Instant::now();
         if inner.is_expired(now, self.max_cache_age) {
@@ -283,7 +328,24 @@ impl BlockStartInfo {
         storage: &mut Connection<'_, Core>,
     ) -> anyhow::Result<L2BlockNumber> {
         let cached_pruning_info = self.get_pruning_info(storage).await?;
-        let last_block = cached_pruning_info.last_soft_pruned_l2_block;
+        let last_block = cached_pruning_info.main.last_soft_pruned_l2_block;
+        if let Some(L2BlockNumber(last_block)) = last_block {
+            return Ok(L2BlockNumber(last_block + 1));
+        }
+        Ok(L2BlockNumber(0))
+    }
+
+    /// Returns the first L2 block for which data of the specified kind is retained. This may be less than
+    /// [`Self::first_l2_block()`] if the data kind is pruned separately (it can only be retained for longer).
+    pub async fn first_l2_block_with_data(
+        &self,
+        kind: PrunedDataKind,
+        storage: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<L2BlockNumber> {
+        let cached_pruning_info = self.get_pruning_info(storage).await?;
+        let last_block = cached_pruning_info
+            .for_data_kind(kind)
+            .last_soft_pruned_l2_block;
         if let Some(L2BlockNumber(last_block)) = last_block {
             return Ok(L2BlockNumber(last_block + 1));
         }
@@ -295,7 +357,23 @@ impl BlockStartInfo {
         storage: &mut Connection<'_, Core>,
     ) -> anyhow::Result<L1BatchNumber> {
         let cached_pruning_info = self.get_pruning_info(storage).await?;
-        let last_batch = cached_pruning_info.last_soft_pruned_l1_batch;
+        let last_batch = cached_pruning_info.main.last_soft_pruned_l1_batch;
+        if let Some(L1BatchNumber(last_block)) = last_batch {
+            return Ok(L1BatchNumber(last_block + 1));
+        }
+        Ok(L1BatchNumber(0))
+    }
+
+    /// Returns the first L1 batch for which data of the specified kind is retained.
+    pub async fn first_l1_batch_with_data(
+        &self,
+        kind: PrunedDataKind,
+        storage: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<L1BatchNumber> {
+        let cached_pruning_info = self.get_pruning_info(storage).await?;
+        let last_batch = cached_pruning_info
+            .for_data_kind(kind)
+            .last_soft_pruned_l1_batch;
         if let Some(L1BatchNumber(last_block)) = last_batch {
             return Ok(L1BatchNumber(last_block + 1));
         }
@@ -313,6 +391,28 @@ impl BlockStartInfo {
             .first_l2_block(storage)
             .await
             .map_err(BlockArgsError::Database)?;
+        Self::check_pruned_block(block, first_l2_block)
+    }
+
+    /// Checks whether data of the specified kind is pruned for a block with the specified ID. The data may be retained
+    /// for a block even if the block itself is considered pruned by [`Self::ensure_not_pruned_block()`].
+    pub async fn ensure_data_not_pruned_block(
+        &self,
+        kind: PrunedDataKind,
+        block: api::BlockId,
+        storage: &mut Connection<'_, Core>,
+    ) -> Result<(), BlockArgsError> {
+        let first_l2_block = self
+            .first_l2_block_with_data(kind, storage)
+            .await
+            .map_err(BlockArgsError::Database)?;
+        Self::check_pruned_block(block, first_l2_block)
+    }
+
+    fn check_pruned_block(
+        block: api::BlockId,
+        first_l2_block: L2BlockNumber,
+    ) -> Result<(), BlockArgsError> {
         match block {
             api::BlockId::Number(api::BlockNumber::Number(number))
                 if number < first_l2_block.0.into() =>
@@ -0,0 +1,276 @@
+//! VM storage implementations used in the sandbox.
+
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Context as _;
+use lru::LruCache;
+use zksync_dal::{Connection, Core, CoreDal, DalError};
+use zksync_merkle_tree::{domain::ZkSyncTreeReader, TreeEntry};
+use zksync_state::{PostgresStorage, ReadStorage};
+use zksync_types::{L1BatchNumber, L2BlockNumber, StorageKey, StorageValue, H256};
+use zksync_utils::h256_to_u256;
+
+/// Storage changes in the L1 batch containing a certain L2 block, up to and including this block.
+/// These changes are not reflected in the Merkle tree (which operates on the L1 batch granularity),
+/// so they are loaded from Postgres and applied on top of the tree state.
+#[derive(Debug)]
+pub(super) struct TreeStateOverlay {
+    /// Tree version (= L1 batch number) the overlay is applied on top of.
+    tree_version: L1BatchNumber,
+    /// Values of storage slots modified in the L1 batch, keyed by the hashed storage key.
+    modified_values: HashMap<H256, StorageValue>,
+}
+
+impl TreeStateOverlay {
+    /// Loads the overlay for the state after the specified L2 block. Returns `Ok(None)` if the tree
+    /// doesn't retain the necessary state (e.g., it lags behind Postgres, or the necessary version is pruned).
+    pub async fn load(
+        tree: &ZkSyncTreeReader,
+        connection: &mut Connection<'_, Core>,
+        l2_block_number: L2BlockNumber,
+    ) -> anyhow::Result<Option<Self>> {
+        let resolved = connection
+            .storage_web3_dal()
+            .resolve_l1_batch_number_of_l2_block(l2_block_number)
+            .await
+            .with_context(|| {
+                format!("failed resolving L1 batch number for L2 block #{l2_block_number}")
+            })?;
+        let l1_batch_number = resolved.expected_l1_batch();
+        let Some(tree_version) = l1_batch_number.0.checked_sub(1).map(L1BatchNumber) else {
+            return Ok(None); // The tree cannot provide the state before genesis
+        };
+
+        if !is_version_retained(tree, tree_version) {
+            return Ok(None);
+        }
+
+        let prev_l2_block_range = connection
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(tree_version)
+            .await
+            .map_err(DalError::generalize)?;
+        let Some((_, prev_l2_block)) = prev_l2_block_range else {
+            // Can happen e.g. for the snapshot L1 batch, which has no L2 blocks in Postgres.
+            return Ok(None);
+        };
+        let first_l2_block = prev_l2_block + 1;
+
+        let modified_values = if first_l2_block <= l2_block_number {
+            let mut dal = connection.storage_logs_dal();
+            let modified_keys = dal
+                .modified_keys_in_l2_blocks(first_l2_block..=l2_block_number)
+                .await
+                .map_err(DalError::generalize)?;
+            let values = dal
+                .get_storage_values(&modified_keys, l2_block_number)
+                .await
+                .map_err(DalError::generalize)?;
+            values
+                .into_iter()
+                .map(|(hashed_key, value)| (hashed_key, value.unwrap_or_default()))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+
+        Ok(Some(Self {
+            tree_version,
+            modified_values,
+        }))
+    }
+}
+
+fn is_version_retained(tree: &ZkSyncTreeReader, version: L1BatchNumber) -> bool {
+    tree.next_l1_batch_number() > version
+        && tree
+            .min_l1_batch_number()
+            .map_or(false, |min_version| min_version <= version)
+}
+
+/// LRU cache of [`TreeStateOverlay`]s keyed by the L2 block number, so that overlays are not loaded from Postgres
+/// on each VM invocation (the vast majority of invocations target the latest sealed L2 block). Overlays
+/// for sealed L2 blocks never change, so cached overlays don't need to be invalidated.
+#[derive(Debug, Clone)]
+pub(crate) struct TreeStateOverlayCache(Arc<Mutex<LruCache<L2BlockNumber, Arc<TreeStateOverlay>>>>);
+
+impl Default for TreeStateOverlayCache {
+    fn default() -> Self {
+        const CAPACITY: NonZeroUsize = match NonZeroUsize::new(16) {
+            Some(capacity) => capacity,
+            None => unreachable!(),
+        };
+        Self(Arc::new(Mutex::new(LruCache::new(CAPACITY))))
+    }
+}
+
+/// Merkle tree used as VM storage together with the cache of overlays on top of it.
+#[derive(Debug, Clone)]
+pub(crate) struct VmStorageTree {
+    reader: ZkSyncTreeReader,
+    overlays: TreeStateOverlayCache,
+}
+
+impl VmStorageTree {
+    pub fn new(reader: ZkSyncTreeReader, overlays: TreeStateOverlayCache) -> Self {
+        Self { reader, overlays }
+    }
+
+    /// Loads the overlay for the state after the specified L2 block, or takes it from the cache.
+    /// Returns `Ok(None)` under the same conditions as [`TreeStateOverlay::load()`].
+    pub(super) async fn load_overlay(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        l2_block_number: L2BlockNumber,
+    ) -> anyhow::Result<Option<Arc<TreeStateOverlay>>> {
+        let cached = self
+            .overlays
+            .0
+            .lock()
+            .unwrap()
+            .get(&l2_block_number)
+            .cloned();
+        if let Some(overlay) = cached {
+            // The tree version may have been pruned since the overlay was cached.
+            return Ok(is_version_retained(&self.reader, overlay.tree_version).then_some(overlay));
+        }
+
+        let overlay = TreeStateOverlay::load(&self.reader, connection, l2_block_number)
+            .await?
+            .map(Arc::new);
+        if let Some(overlay) = &overlay {
+            self.overlays
+                .0
+                .lock()
+                .unwrap()
+                .put(l2_block_number, overlay.clone());
+        }
+        Ok(overlay)
+    }
+
+    pub(super) fn create_storage<'a>(
+        &self,
+        overlay: Arc<TreeStateOverlay>,
+        postgres: PostgresStorage<'a>,
+    ) -> TreeStorage<'a> {
+        TreeStorage::new(self.reader.clone(), overlay, postgres)
+    }
+}
+
+/// [`ReadStorage`] implementation reading storage values and enumeration indices from the Merkle tree.
+/// Changes in the L1 batch that are not yet reflected in the tree are taken from a [`TreeStateOverlay`].
+/// Factory dependencies are read from Postgres.
+///
+/// Reading from the tree is significantly faster than reading from Postgres, especially for old L2 blocks.
+/// If the tree version the storage is based on is pruned during VM execution, all subsequent reads fall back
+/// to Postgres. This doesn't change the observed state since Postgres storage is created for the same L2 block.
+#[derive(Debug)]
+pub(super) struct TreeStorage<'a> {
+    /// Set to `None` once the tree version is pruned.
+    tree: Option<ZkSyncTreeReader>,
+    overlay: Arc<TreeStateOverlay>,
+    postgres: PostgresStorage<'a>,
+}
+
+impl<'a> TreeStorage<'a> {
+    pub fn new(
+        tree: ZkSyncTreeReader,
+        overlay: Arc<TreeStateOverlay>,
+        postgres: PostgresStorage<'a>,
+    ) -> Self {
+        Self {
+            tree: Some(tree),
+            overlay,
+            postgres,
+        }
+    }
+
+    /// Reads the entry from the tree. Returns `None` if the tree no longer retains the necessary version,
+    /// in which case the entry should be read from Postgres.
+    fn read_tree_entry(&mut self, key: &StorageKey) -> Option<TreeEntry> {
+        let tree = self.tree.as_ref()?;
+        let tree_key = h256_to_u256(key.hashed_key());
+        match tree.entries(self.overlay.tree_version, &[tree_key]) {
+            Ok(entries) => Some(entries[0]),
+            Err(err) => {
+                tracing::info!(
+                    "Merkle tree version was pruned during VM execution ({err}); falling back to Postgres storage"
+                );
+                self.tree = None;
+                None
+            }
+        }
+    }
+}
+
+impl ReadStorage for TreeStorage<'_> {
+    fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+        if let Some(value) = self.overlay.modified_values.get(&key.hashed_key()) {
+            return *value;
+        }
+        match self.read_tree_entry(key) {
+            Some(entry) => entry.value,
+            None => self.postgres.read_value(key),
+        }
+    }
+
+    fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        // Writes in the L1 batch of the L2 block are not taken into account, consistently with `PostgresStorage`.
+        match self.read_tree_entry(key) {
+            Some(entry) => entry.is_empty(),
+            None => self.postgres.is_write_initial(key),
+        }
+    }
+
+    fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+        self.postgres.load_factory_dep(hash)
+    }
+
+    fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+        match self.read_tree_entry(key) {
+            Some(entry) => (!entry.is_empty()).then_some(entry.leaf_index),
+            None => self.postgres.get_enumeration_index(key),
+        }
+    }
+}
+
+/// VM storage used in the sandbox.
+#[derive(Debug)]
+pub(super) enum SandboxStorage<'a> {
+    Postgres(PostgresStorage<'a>),
+    Tree(TreeStorage<'a>),
+}
+
+impl ReadStorage for SandboxStorage<'_> {
+    fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+        match self {
+            Self::Postgres(postgres) => postgres.read_value(key),
+            Self::Tree(tree) => tree.read_value(key),
+        }
+    }
+
+    fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        match self {
+            Self::Postgres(postgres) => postgres.is_write_initial(key),
+            Self::Tree(tree) => tree.is_write_initial(key),
+        }
+    }
+
+    fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+        match self {
+            Self::Postgres(postgres) => postgres.load_factory_dep(hash),
+            Self::Tree(tree) => tree.load_factory_dep(hash),
+        }
+    }
+
+    fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+        match self {
+            Self::Postgres(postgres) => postgres.get_enumeration_index(key),
+            Self::Tree(tree) => tree.get_enumeration_index(key),
+        }
+    }
+}
@@ -1,12 +1,27 @@
 //! Tests for the VM execution sandbox.
 
+use std::{collections::BTreeMap, path::Path};
+
 use assert_matches::assert_matches;
+use tempfile::TempDir;
 use zksync_dal::ConnectionPool;
+use zksync_merkle_tree::{
+    domain::{ZkSyncTree, ZkSyncTreeReader},
+    RocksDBWrapper, TreeInstruction,
+};
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
 use zksync_node_test_utils::{create_l2_block, create_l2_transaction, prepare_recovery_snapshot};
+use zksync_state::{PostgresStorage, ReadStorage};
+use zksync_types::{StorageKey, StorageLog, H256};
 
 use super::*;
-use crate::{execution_sandbox::apply::apply_vm_in_sandbox, tx_sender::ApiContracts};
+use crate::{
+    execution_sandbox::{
+        apply::apply_vm_in_sandbox,
+        storage::{TreeStateOverlay, TreeStateOverlayCache, TreeStorage, VmStorageTree},
+    },
+    tx_sender::ApiContracts,
+};
 
 #[tokio::test]
 async fn creating_block_args() {
@@ -182,14 +197,28 @@ async fn instantiating_vm() {
 }
 
 async fn test_instantiating_vm(pool: ConnectionPool<Core>, block_args: BlockArgs) {
+    test_instantiating_vm_with_tree(pool, block_args, None).await;
+}
+
+async fn test_instantiating_vm_with_tree(
+    pool: ConnectionPool<Core>,
+    block_args: BlockArgs,
+    tree_reader: Option<ZkSyncTreeReader>,
+) {
+    let storage_tree =
+        tree_reader.map(|reader| VmStorageTree::new(reader, TreeStateOverlayCache::default()));
     let (vm_concurrency_limiter, _) = VmConcurrencyLimiter::new(1);
     let vm_permit = vm_concurrency_limiter.acquire().await.unwrap();
     let transaction = create_l2_transaction(10, 100).into();
     let estimate_gas_contracts = ApiContracts::load_from_disk().await.unwrap().estimate_gas;
+    let shared_args = TxSharedArgs {
+        storage_tree,
+        ..TxSharedArgs::mock(estimate_gas_contracts)
+    };
     tokio::task::spawn_blocking(move || {
         apply_vm_in_sandbox(
             vm_permit,
-            TxSharedArgs::mock(estimate_gas_contracts),
+            shared_args,
             true,
             &TxExecutionArgs::for_gas_estimate(None, &transaction, 123),
             &pool,
@@ -204,3 +233,265 @@ async fn test_instantiating_vm(pool: ConnectionPool<Core>, block_args: BlockArgs
     .expect("VM instantiation panicked")
     .expect("VM instantiation errored");
 }
+
+/// Creates a Merkle tree containing the genesis L1 batch persisted in Postgres.
+async fn create_genesis_tree(storage: &mut Connection<'_, Core>, path: &Path) -> ZkSyncTree {
+    let genesis_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    let hashed_keys: Vec<_> = genesis_logs.iter().map(|log| log.hashed_key).collect();
+    let indices = storage
+        .storage_logs_dal()
+        .get_l1_batches_and_indices_for_initial_writes(&hashed_keys)
+        .await
+        .unwrap();
+
+    // Later logs overwrite earlier ones for the same key.
+    let mut instructions = BTreeMap::new();
+    for log in &genesis_logs {
+        let key = StorageKey::new(AccountTreeId::new(log.address), log.key);
+        let (_, leaf_index) = indices[&log.hashed_key];
+        instructions.insert(
+            leaf_index,
+            TreeInstruction::write(key, leaf_index, log.value),
+        );
+    }
+    let instructions: Vec<_> = instructions.into_values().collect();
+
+    let db = RocksDBWrapper::new(path).unwrap();
+    let mut tree = ZkSyncTree::new(db).unwrap();
+    tree.process_l1_batch(&instructions).unwrap();
+    tree.save().unwrap();
+    tree
+}
+
+/// Inserts L2 block #1 with storage logs overwriting the first returned key (a genesis storage slot)
+/// and writing to the second to last returned key (a new slot). The last returned key is never written to.
+async fn insert_l2_block_with_storage_logs(storage: &mut Connection<'_, Core>) -> Vec<StorageKey> {
+    storage
+        .blocks_dal()
+        .insert_l2_block(&create_l2_block(1))
+        .await
+        .unwrap();
+    let genesis_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    let mut keys: Vec<_> = genesis_logs
+        .iter()
+        .take(20)
+        .map(|log| StorageKey::new(AccountTreeId::new(log.address), log.key))
+        .collect();
+    let new_key = StorageKey::new(AccountTreeId::new(Address::repeat_byte(1)), H256::zero());
+    let missing_key = StorageKey::new(AccountTreeId::new(Address::repeat_byte(2)), H256::zero());
+    let new_logs = [
+        StorageLog::new_write_log(keys[0], H256::repeat_byte(0x11)),
+        StorageLog::new_write_log(new_key, H256::repeat_byte(0x22)),
+    ];
+    storage
+        .storage_logs_dal()
+        .insert_storage_logs(L2BlockNumber(1), &new_logs)
+        .await
+        .unwrap();
+    keys.extend([new_key, missing_key]);
+    keys
+}
+
+#[tokio::test]
+async fn tree_storage_is_consistent_with_postgres() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    let temp_dir = TempDir::new().unwrap();
+    let tree_reader = create_genesis_tree(&mut storage, temp_dir.path())
+        .await
+        .reader();
+
+    // The tree cannot provide the state for the genesis L1 batch.
+    let overlay = TreeStateOverlay::load(&tree_reader, &mut storage, L2BlockNumber(0))
+        .await
+        .unwrap();
+    assert!(overlay.is_none());
+
+    // Add an L2 block in the pending L1 batch.
+    let keys = insert_l2_block_with_storage_logs(&mut storage).await;
+    let new_key = keys[keys.len() - 2];
+
+    let overlay = TreeStateOverlay::load(&tree_reader, &mut storage, L2BlockNumber(1))
+        .await
+        .unwrap()
+        .expect("no overlay");
+    let postgres_for_tree = PostgresStorage::new_async(
+        Handle::current(),
+        pool.connection().await.unwrap(),
+        L2BlockNumber(1),
+        false,
+    )
+    .await
+    .unwrap();
+    let mut tree_storage = TreeStorage::new(tree_reader, Arc::new(overlay), postgres_for_tree);
+    let mut postgres = PostgresStorage::new_async(
+        Handle::current(),
+        pool.connection().await.unwrap(),
+        L2BlockNumber(1),
+        false,
+    )
+    .await
+    .unwrap();
+
+    tokio::task::spawn_blocking(move || {
+        assert_eq!(tree_storage.read_value(&keys[0]), H256::repeat_byte(0x11));
+        assert_eq!(tree_storage.read_value(&new_key), H256::repeat_byte(0x22));
+        assert!(tree_storage.is_write_initial(&new_key));
+
+        for key in &keys {
+            assert_eq!(
+                tree_storage.read_value(key),
+                postgres.read_value(key),
+                "{key:?}"
+            );
+            assert_eq!(
+                tree_storage.is_write_initial(key),
+                postgres.is_write_initial(key),
+                "{key:?}"
+            );
+            assert_eq!(
+                tree_storage.get_enumeration_index(key),
+                postgres.get_enumeration_index(key),
+                "{key:?}"
+            );
+        }
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn tree_storage_falls_back_to_postgres_if_tree_version_is_missing() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    let temp_dir = TempDir::new().unwrap();
+    let tree_reader = create_genesis_tree(&mut storage, &temp_dir.path().join("tree"))
+        .await
+        .reader();
+    let keys = insert_l2_block_with_storage_logs(&mut storage).await;
+    let overlay = TreeStateOverlay::load(&tree_reader, &mut storage, L2BlockNumber(1))
+        .await
+        .unwrap()
+        .expect("no overlay");
+
+    // Simulate the tree version being pruned by using an empty tree.
+    let empty_tree_db = RocksDBWrapper::new(&temp_dir.path().join("empty")).unwrap();
+    let empty_tree_reader = ZkSyncTree::new(empty_tree_db).unwrap().reader();
+    let postgres_for_tree = PostgresStorage::new_async(
+        Handle::current(),
+        pool.connection().await.unwrap(),
+        L2BlockNumber(1),
+        false,
+    )
+    .await
+    .unwrap();
+    let mut tree_storage =
+        TreeStorage::new(empty_tree_reader, Arc::new(overlay), postgres_for_tree);
+    let mut postgres = PostgresStorage::new_async(
+        Handle::current(),
+        pool.connection().await.unwrap(),
+        L2BlockNumber(1),
+        false,
+    )
+    .await
+    .unwrap();
+
+    tokio::task::spawn_blocking(move || {
+        for key in &keys {
+            assert_eq!(
+                tree_storage.read_value(key),
+                postgres.read_value(key),
+                "{key:?}"
+            );
+            assert_eq!(
+                tree_storage.is_write_initial(key),
+                postgres.is_write_initial(key),
+                "{key:?}"
+            );
+            assert_eq!(
+                tree_storage.get_enumeration_index(key),
+                postgres.get_enumeration_index(key),
+                "{key:?}"
+            );
+        }
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn tree_state_overlays_are_cached() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    let temp_dir = TempDir::new().unwrap();
+    let tree_reader = create_genesis_tree(&mut storage, temp_dir.path())
+        .await
+        .reader();
+    insert_l2_block_with_storage_logs(&mut storage).await;
+
+    let storage_tree = VmStorageTree::new(tree_reader, TreeStateOverlayCache::default());
+    let overlay = storage_tree
+        .load_overlay(&mut storage, L2BlockNumber(1))
+        .await
+        .unwrap()
+        .expect("no overlay");
+    // The overlay should be taken from the cache; the cache is shared among clones.
+    let cached_overlay = storage_tree
+        .clone()
+        .load_overlay(&mut storage, L2BlockNumber(1))
+        .await
+        .unwrap()
+        .expect("no overlay");
+    assert!(Arc::ptr_eq(&overlay, &cached_overlay));
+
+    // Overlays that cannot be loaded are not cached.
+    let overlay = storage_tree
+        .load_overlay(&mut storage, L2BlockNumber(0))
+        .await
+        .unwrap();
+    assert!(overlay.is_none());
+}
+
+#[tokio::test]
+async fn instantiating_vm_with_tree_storage() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    let temp_dir = TempDir::new().unwrap();
+    let tree_reader = create_genesis_tree(&mut storage, temp_dir.path())
+        .await
+        .reader();
+    storage
+        .blocks_dal()
+        .insert_l2_block(&create_l2_block(1))
+        .await
+        .unwrap();
+
+    let block_args = BlockArgs::pending(&mut storage).await.unwrap();
+    test_instantiating_vm_with_tree(pool.clone(), block_args, Some(tree_reader.clone())).await;
+    let start_info = BlockStartInfo::new(&mut storage, Duration::MAX)
+        .await
+        .unwrap();
+    // The tree cannot serve this block, so storage should fall back to Postgres.
+    let block_args = BlockArgs::new(&mut storage, api::BlockId::Number(0.into()), &start_info)
+        .await
+        .unwrap();
+    test_instantiating_vm_with_tree(pool.clone(), block_args, Some(tree_reader)).await;
+}
@@ -9,6 +9,7 @@ use zksync_contracts::BaseSystemContracts;
 use zksync_dal::{
     transactions_dal::L2TxSubmissionResult, Connection, ConnectionPool, Core, CoreDal,
 };
+use zksync_metadata_calculator::LazyAsyncTreeReader;
 use zksync_multivm::{
     interface::VmExecutionResultAndLogs,
     utils::{
@@ -40,8 +41,9 @@ pub(super) use self::result::SubmitTxError;
 use self::{master_pool_sink::MasterPoolSink, tx_sink::TxSink};
 use crate::{
     execution_sandbox::{
-        BlockArgs, SubmitTxStage, TransactionExecutor, TxExecutionArgs, TxSharedArgs,
-        VmConcurrencyBarrier, VmConcurrencyLimiter, VmPermit, SANDBOX_METRICS,
+        BlockArgs, SubmitTxStage, TransactionExecutor, TreeStateOverlayCache, TxExecutionArgs,
+        TxSharedArgs, VmConcurrencyBarrier, VmConcurrencyLimiter, VmPermit, VmStorageTree,
+        SANDBOX_METRICS,
     },
     tx_sender::result::ApiCallResult,
 };
@@ -211,6 +213,8 @@ pub struct TxSenderBuilder {
     sealer: Option<Arc<dyn ConditionalSealer>>,
     /// Cache for tokens that are white-listed for AA.
     whitelisted_tokens_for_aa_cache: Option<Arc<RwLock<Vec<Address>>>>,
+    /// Merkle tree used to read VM storage.
+    tree_reader: Option<LazyAsyncTreeReader>,
 }
 
 impl TxSenderBuilder {
@@ -225,6 +229,7 @@ impl TxSenderBuilder {
             tx_sink,
             sealer: None,
             whitelisted_tokens_for_aa_cache: None,
+            tree_reader: None,
         }
     }
 
@@ -238,6 +243,13 @@ impl TxSenderBuilder {
         self
     }
 
+    /// Makes VM execution read storage from the provided Merkle tree if the tree retains the necessary state
+    /// (otherwise, storage is read from Postgres as usual).
+    pub fn with_tree_reader(mut self, tree_reader: LazyAsyncTreeReader) -> Self {
+        self.tree_reader = Some(tree_reader);
+        self
+    }
+
     pub fn build(
         self,
         batch_fee_input_provider: Arc<dyn BatchFeeModelInputProvider>,
@@ -262,6 +274,8 @@ impl TxSenderBuilder {
             storage_caches,
             whitelisted_tokens_for_aa_cache,
             sealer,
+            tree_reader: self.tree_reader,
+            tree_overlays: TreeStateOverlayCache::default(),
             executor: TransactionExecutor::Real,
         }))
     }
@@ -320,6 +334,10 @@ pub struct TxSenderInner {
     pub(super) whitelisted_tokens_for_aa_cache: Arc<RwLock<Vec<Address>>>,
     /// Batch sealer used to check whether transaction can be executed by the sequencer.
     sealer: Arc<dyn ConditionalSealer>,
+    /// Merkle tree used to read VM storage.
+    tree_reader: Option<LazyAsyncTreeReader>,
+    /// Cache of recently used state overlays on top of the Merkle tree.
+    tree_overlays: TreeStateOverlayCache,
     pub(super) executor: TransactionExecutor,
 }
 
@@ -341,6 +359,15 @@ impl TxSender {
         self.0.storage_caches.clone()
     }
 
+    /// Returns the Merkle tree to use for VM storage, or `None` if storage should be read from Postgres.
+    pub(crate) fn vm_storage_tree(&self) -> Option<VmStorageTree> {
+        let tree_reader = self.0.tree_reader.as_ref()?.read()?;
+        Some(VmStorageTree::new(
+            tree_reader.sync_reader(),
+            self.0.tree_overlays.clone(),
+        ))
+    }
+
     pub(crate) async fn read_whitelisted_tokens_for_aa_cache(&self) -> Vec<Address> {
         self.0.whitelisted_tokens_for_aa_cache.read().await.clone()
     }
@@ -472,6 +499,7 @@ impl TxSender {
             fee_input,
             base_system_contracts: self.0.api_contracts.eth_call.clone(),
             caches: self.storage_caches(),
+            storage_tree: self.vm_storage_tree(),
             validation_computational_gas_limit: self
                 .0
                 .sender_config
@@ -719,6 +747,7 @@ impl TxSender {
             validation_computational_gas_limit: BATCH_COMPUTATIONAL_GAS_LIMIT,
             base_system_contracts: self.0.api_contracts.estimate_gas.clone(),
             caches: self.storage_caches(),
+            storage_tree: self.vm_storage_tree(),
             chain_id: config.chain_id,
             whitelisted_tokens_for_aa: self.read_whitelisted_tokens_for_aa_cache().await,
         }
@@ -0,0 +1,373 @@
+//! Decoding of calldata, return values and events using ABIs of verified contracts.
+
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+use zksync_dal::{Connection, Core, CoreDal};
+use zksync_types::{
+    api::{DebugCall, DecodedCall, DecodedEvent, DecodedParam, Log},
+    ethabi::{self, Contract, EventParam, ParamType, Token},
+    Address, H256, U256,
+};
+use zksync_web3_decl::error::Web3Error;
+
+/// Decoder holding parsed ABIs for a set of verified contracts.
+#[derive(Debug, Default)]
+pub(crate) struct AbiDecoder {
+    contracts: HashMap<Address, Contract>,
+}
+
+impl AbiDecoder {
+    /// Loads ABIs for the provided addresses. Addresses of unverified contracts are ignored.
+    pub async fn load(
+        connection: &mut Connection<'_, Core>,
+        addresses: impl IntoIterator<Item = Address>,
+    ) -> Result<Self, Web3Error> {
+        let addresses: Vec<_> = addresses.into_iter().unique().collect();
+        if addresses.is_empty() {
+            return Ok(Self::default());
+        }
+        let abis = connection
+            .contract_verification_dal()
+            .get_contract_abis(&addresses)
+            .await?;
+        let contracts = abis
+            .into_iter()
+            .filter_map(|(address, abi)| match serde_json::from_value(abi) {
+                Ok(contract) => Some((address, contract)),
+                Err(err) => {
+                    tracing::warn!("Cannot parse ABI of verified contract {address:?}: {err}");
+                    None
+                }
+            })
+            .collect();
+        Ok(Self { contracts })
+    }
+
+    /// Decodes a call to the specified contract. Return values are only decoded if `output` is provided.
+    pub fn decode_call(
+        &self,
+        to: Address,
+        input: &[u8],
+        output: Option<&[u8]>,
+    ) -> Option<DecodedCall> {
+        let contract = self.contracts.get(&to)?;
+        let (selector, args) = (input.get(..4)?, &input[4..]);
+        let function = contract
+            .functions()
+            .find(|function| function.short_signature() == selector)?;
+        let inputs = function.decode_input(args).ok()?;
+        let outputs = output.and_then(|output| function.decode_output(output).ok());
+
+        Some(DecodedCall {
+            name: function.name.clone(),
+            signature: signature(
+                &function.name,
+                function.inputs.iter().map(|param| &param.kind),
+            ),
+            inputs: decoded_params(
+                function
+                    .inputs
+                    .iter()
+                    .map(|param| (&param.name, &param.kind)),
+                inputs,
+            ),
+            outputs: outputs.map(|outputs| {
+                decoded_params(
+                    function
+                        .outputs
+                        .iter()
+                        .map(|param| (&param.name, &param.kind)),
+                    outputs,
+                )
+            }),
+        })
+    }
+
+    /// Decodes an event emitted by a verified contract. Anonymous events are not decoded.
+    pub fn decode_log(&self, log: &Log) -> Option<DecodedEvent> {
+        let contract = self.contracts.get(&log.address)?;
+        let topic = *log.topics.first()?;
+        let event = contract
+            .events()
+            .find(|event| !event.anonymous && event.signature() == topic)?;
+        let tokens = decode_event_params(&event.inputs, &log.topics[1..], &log.data.0)?;
+        let params = decoded_params(
+            event.inputs.iter().map(|param| (&param.name, &param.kind)),
+            tokens,
+        );
+        Some(DecodedEvent {
+            address: log.address,
+            log_index: log.log_index.unwrap_or_default(),
+            name: event.name.clone(),
+            signature: signature(&event.name, event.inputs.iter().map(|param| &param.kind)),
+            params,
+        })
+    }
+
+    /// Decodes the call and all its subcalls in place. Return values are decoded only for successful calls.
+    pub fn decode_trace(&self, call: &mut DebugCall) {
+        let output =
+            (call.error.is_none() && call.revert_reason.is_none()).then_some(&call.output.0);
+        call.decoded = self.decode_call(call.to, &call.input.0, output.map(Vec::as_slice));
+        for subcall in &mut call.calls {
+            self.decode_trace(subcall);
+        }
+    }
+}
+
+/// Collects addresses of all callees in the trace.
+pub(crate) fn trace_callees(call: &DebugCall, addresses: &mut HashSet<Address>) {
+    addresses.insert(call.to);
+    for subcall in &call.calls {
+        trace_callees(subcall, addresses);
+    }
+}
+
+fn signature<'a>(name: &str, types: impl Iterator<Item = &'a ParamType>) -> String {
+    format!("{name}({})", types.map(ToString::to_string).join(","))
+}
+
+/// Decodes event params positionally, so that params with duplicate or empty names are handled correctly.
+/// Indexed params of reference types are represented by their hashes.
+fn decode_event_params(inputs: &[EventParam], topics: &[H256], data: &[u8]) -> Option<Vec<Token>> {
+    let indexed_count = inputs.iter().filter(|param| param.indexed).count();
+    if topics.len() != indexed_count {
+        return None;
+    }
+    let data_kinds: Vec<_> = inputs
+        .iter()
+        .filter(|param| !param.indexed)
+        .map(|param| param.kind.clone())
+        .collect();
+    let mut data_tokens = ethabi::decode(&data_kinds, data).ok()?.into_iter();
+    let mut topics = topics.iter();
+
+    inputs
+        .iter()
+        .map(|param| {
+            if !param.indexed {
+                return data_tokens.next();
+            }
+            let topic = topics.next()?;
+            let kind = match &param.kind {
+                ParamType::Bytes
+                | ParamType::String
+                | ParamType::Array(_)
+                | ParamType::FixedArray(..)
+                | ParamType::Tuple(_) => ParamType::FixedBytes(32),
+                kind => kind.clone(),
+            };
+            ethabi::decode(&[kind], topic.as_bytes()).ok()?.pop()
+        })
+        .collect()
+}
+
+fn decoded_params<'a>(
+    params: impl Iterator<Item = (&'a String, &'a ParamType)>,
+    tokens: Vec<Token>,
+) -> Vec<DecodedParam> {
+    params
+        .zip(tokens)
+        .map(|((name, kind), token)| DecodedParam {
+            name: name.clone(),
+            r#type: kind.to_string(),
+            value: token_to_json(token),
+        })
+        .collect()
+}
+
+fn token_to_json(token: Token) -> serde_json::Value {
+    match token {
+        Token::Address(address) => format!("{address:?}").into(),
+        Token::FixedBytes(bytes) | Token::Bytes(bytes) => {
+            format!("0x{}", hex::encode(bytes)).into()
+        }
+        Token::Uint(value) => value.to_string().into(),
+        Token::Int(value) => signed_to_string(value).into(),
+        Token::Bool(value) => value.into(),
+        Token::String(value) => value.into(),
+        Token::Array(tokens) | Token::FixedArray(tokens) | Token::Tuple(tokens) => {
+            tokens.into_iter().map(token_to_json).collect()
+        }
+    }
+}
+
+/// Formats a two's complement 256-bit integer as a signed decimal.
+fn signed_to_string(value: U256) -> String {
+    if value.bit(255) {
+        let abs = (!value).overflowing_add(U256::one()).0;
+        format!("-{abs}")
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::web3::Bytes;
+
+    use super::*;
+
+    fn erc20_decoder(address: Address) -> AbiDecoder {
+        let abi = serde_json::json!([
+            {
+                "type": "function",
+                "name": "transfer",
+                "stateMutability": "nonpayable",
+                "inputs": [
+                    { "name": "to", "type": "address" },
+                    { "name": "amount", "type": "uint256" }
+                ],
+                "outputs": [{ "name": "", "type": "bool" }]
+            },
+            {
+                "type": "event",
+                "name": "Transfer",
+                "anonymous": false,
+                "inputs": [
+                    { "name": "from", "type": "address", "indexed": true },
+                    { "name": "to", "type": "address", "indexed": true },
+                    { "name": "value", "type": "int256", "indexed": false }
+                ]
+            }
+        ]);
+        AbiDecoder {
+            contracts: HashMap::from([(address, serde_json::from_value(abi).unwrap())]),
+        }
+    }
+
+    fn test_log(address: Address, topics: Vec<H256>, data: Vec<u8>) -> Log {
+        Log {
+            address,
+            topics,
+            data: Bytes(data),
+            block_hash: None,
+            block_number: None,
+            l1_batch_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: Some(5.into()),
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        }
+    }
+
+    #[test]
+    fn decoding_call() {
+        let token = Address::repeat_byte(1);
+        let decoder = erc20_decoder(token);
+        let recipient = Address::repeat_byte(2);
+        let mut input =
+            ethabi::short_signature("transfer", &[ParamType::Address, ParamType::Uint(256)])
+                .to_vec();
+        input.extend(ethabi::encode(&[
+            Token::Address(recipient),
+            Token::Uint(100.into()),
+        ]));
+        let output = ethabi::encode(&[Token::Bool(true)]);
+
+        let call = decoder.decode_call(token, &input, Some(&output)).unwrap();
+        assert_eq!(call.name, "transfer");
+        assert_eq!(call.signature, "transfer(address,uint256)");
+        assert_eq!(call.inputs[0].name, "to");
+        assert_eq!(call.inputs[0].value, format!("{recipient:?}"));
+        assert_eq!(call.inputs[1].r#type, "uint256");
+        assert_eq!(call.inputs[1].value, "100");
+        assert_eq!(call.outputs.unwrap()[0].value, true);
+
+        assert!(decoder.decode_call(recipient, &input, None).is_none());
+        assert!(decoder.decode_call(token, &input[..3], None).is_none());
+    }
+
+    #[test]
+    fn decoding_log() {
+        let token = Address::repeat_byte(1);
+        let decoder = erc20_decoder(token);
+        let topic = ethabi::long_signature(
+            "Transfer",
+            &[ParamType::Address, ParamType::Address, ParamType::Int(256)],
+        );
+        let log = test_log(
+            token,
+            vec![
+                topic,
+                H256::from(Address::repeat_byte(2)),
+                H256::from(Address::repeat_byte(3)),
+            ],
+            ethabi::encode(&[Token::Int(U256::MAX)]),
+        );
+
+        let event = decoder.decode_log(&log).unwrap();
+        assert_eq!(event.name, "Transfer");
+        assert_eq!(event.signature, "Transfer(address,address,int256)");
+        assert_eq!(event.log_index, 5.into());
+        let values: Vec<_> = event.params.iter().map(|param| &param.value).collect();
+        assert_eq!(values[2], "-1");
+        assert_eq!(*values[1], format!("{:?}", Address::repeat_byte(3)));
+    }
+
+    #[test]
+    fn decoding_log_with_duplicate_and_empty_param_names() {
+        let address = Address::repeat_byte(1);
+        let abi = serde_json::json!([{
+            "type": "event",
+            "name": "Swap",
+            "anonymous": false,
+            "inputs": [
+                { "name": "", "type": "address", "indexed": true },
+                { "name": "", "type": "bool", "indexed": false },
+                { "name": "amount", "type": "string", "indexed": true },
+                { "name": "amount", "type": "uint256", "indexed": false }
+            ]
+        }]);
+        let decoder = AbiDecoder {
+            contracts: HashMap::from([(address, serde_json::from_value(abi).unwrap())]),
+        };
+        let topic = ethabi::long_signature(
+            "Swap",
+            &[
+                ParamType::Address,
+                ParamType::Bool,
+                ParamType::String,
+                ParamType::Uint(256),
+            ],
+        );
+        let string_hash = H256::repeat_byte(0xaa);
+        let log = test_log(
+            address,
+            vec![topic, H256::from(Address::repeat_byte(2)), string_hash],
+            ethabi::encode(&[Token::Bool(true), Token::Uint(42.into())]),
+        );
+
+        let event = decoder.decode_log(&log).unwrap();
+        assert_eq!(event.signature, "Swap(address,bool,string,uint256)");
+        let params: Vec<_> = event
+            .params
+            .iter()
+            .map(|param| (param.name.as_str(), param.r#type.as_str(), &param.value))
+            .collect();
+        assert_eq!(
+            params,
+            [
+                (
+                    "",
+                    "address",
+                    &serde_json::json!(format!("{:?}", Address::repeat_byte(2)))
+                ),
+                ("", "bool", &serde_json::json!(true)),
+                (
+                    "amount",
+                    "string",
+                    &serde_json::json!(format!("{string_hash:?}"))
+                ),
+                ("amount", "uint256", &serde_json::json!("42")),
+            ]
+        );
+
+        // Logs with an unexpected number of topics are not decoded.
+        let log = test_log(address, vec![topic], log.data.0);
+        assert!(decoder.decode_log(&log).is_none());
+    }
+}
@@ -3,8 +3,9 @@ use std::collections::HashMap;
 use itertools::Itertools;
 use zksync_types::{
     api::{
-        ApiStorageLog, BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Log, Proof,
-        ProtocolVersion, TransactionDetailedResult, TransactionDetails,
+        ApiStorageLog, BlockDetails, BridgeAddresses, DecodedTransaction, L1BatchDetails,
+        L1BatchSubmissionDetails, L2ToL1LogProof, Log, Proof, ProtocolVersion,
+        TransactionDetailedResult, TransactionDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -118,6 +119,12 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn decode_transaction(&self, hash: H256) -> RpcResult<Option<DecodedTransaction>> {
+        self.decode_transaction_impl(hash)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_raw_block_transactions(
         &self,
         block_number: L2BlockNumber,
@@ -136,6 +143,15 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_l1_batch_submission_details(
+        &self,
+        batch_number: L1BatchNumber,
+    ) -> RpcResult<Option<L1BatchSubmissionDetails>> {
+        self.get_l1_batch_submission_details_impl(batch_number)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_bytecode_by_hash(&self, hash: H256) -> RpcResult<Option<Vec<u8>>> {
         self.get_bytecode_by_hash_impl(hash)
             .await
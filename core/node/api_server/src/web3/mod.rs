@@ -14,6 +14,7 @@ use zksync_dal::{helpers::wait_for_l1_batch, ConnectionPool, Core};
 use zksync_health_check::{HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_metadata_calculator::api_server::TreeApiClient;
 use zksync_node_sync::SyncState;
+use zksync_object_store::ObjectStore;
 use zksync_types::L2BlockNumber;
 use zksync_web3_decl::{
     jsonrpsee::{
@@ -48,6 +49,7 @@ use crate::{
     tx_sender::TxSender,
 };
 
+mod abi_decoding;
 pub mod backend_jsonrpsee;
 pub mod mempool_cache;
 pub(super) mod metrics;
@@ -132,6 +134,7 @@ struct OptionalApiParams {
     websocket_requests_per_minute_limit: Option<NonZeroU32>,
     tree_api: Option<Arc<dyn TreeApiClient>>,
     mempool_cache: Option<MempoolCache>,
+    pruned_data_archive: Option<Arc<dyn ObjectStore>>,
     extended_tracing: bool,
     pub_sub_events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
@@ -278,6 +281,14 @@ impl ApiBuilder {
         self
     }
 
+    /// Enables serving blocks, receipts and logs for pruned L1 batches from the pruned data archive
+    /// in the specified object store.
+    pub fn with_pruned_data_archive(mut self, blob_store: Arc<dyn ObjectStore>) -> Self {
+        tracing::info!("Using pruned data archive in object store: {blob_store:?}");
+        self.optional.pruned_data_archive = Some(blob_store);
+        self
+    }
+
     pub fn with_extended_tracing(mut self, extended_tracing: bool) -> Self {
         self.optional.extended_tracing = extended_tracing;
         self
@@ -363,6 +374,7 @@ impl ApiServer {
             mempool_cache: self.optional.mempool_cache,
             last_sealed_l2_block,
             tree_api: self.optional.tree_api,
+            pruned_data_archive: self.optional.pruned_data_archive,
         })
     }
 
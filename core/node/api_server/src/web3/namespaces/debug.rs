@@ -1,8 +1,8 @@
-use std::sync::Arc;
+use std::{collections::HashSet, slice, sync::Arc};
 
 use anyhow::Context as _;
 use once_cell::sync::OnceCell;
-use zksync_dal::{CoreDal, DalError};
+use zksync_dal::{Connection, Core, CoreDal, DalError};
 use zksync_multivm::{
     interface::ExecutionResult, vm_latest::constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
 };
@@ -21,7 +21,11 @@ use zksync_web3_decl::error::Web3Error;
 use crate::{
     execution_sandbox::{ApiTracer, TxSharedArgs},
     tx_sender::{ApiContracts, TxSenderConfig},
-    web3::{backend_jsonrpsee::MethodTracer, state::RpcState},
+    web3::{
+        abi_decoding::{trace_callees, AbiDecoder},
+        backend_jsonrpsee::MethodTracer,
+        state::RpcState,
+    },
 };
 
 #[derive(Debug, Clone)]
@@ -71,8 +75,9 @@ impl DebugNamespace {
         }
 
         let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
+            .as_ref()
+            .map_or(false, |options| options.tracer_config.only_top_call);
+        let decode = options.map_or(false, |options| options.tracer_config.decode);
         let mut connection = self.state.acquire_connection().await?;
         let block_number = self.state.resolve_block(&mut connection, block_id).await?;
         self.current_method()
@@ -83,16 +88,23 @@ impl DebugNamespace {
             .get_traces_for_l2_block(block_number)
             .await
             .map_err(DalError::generalize)?;
-        let call_trace = call_traces
+        let mut call_traces: Vec<DebugCall> = call_traces
             .into_iter()
             .map(|call_trace| {
                 let mut result: DebugCall = call_trace.into();
                 if only_top_call {
                     result.calls = vec![];
                 }
-                ResultDebugCall { result }
+                result
             })
             .collect();
+        if decode {
+            Self::decode_traces(&mut connection, &mut call_traces).await?;
+        }
+        let call_trace = call_traces
+            .into_iter()
+            .map(|result| ResultDebugCall { result })
+            .collect();
         Ok(call_trace)
     }
 
@@ -112,21 +124,26 @@ impl DebugNamespace {
         options: Option<TracerConfig>,
     ) -> Result<Option<DebugCall>, Web3Error> {
         let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
+            .as_ref()
+            .map_or(false, |options| options.tracer_config.only_top_call);
+        let decode = options.map_or(false, |options| options.tracer_config.decode);
         let mut connection = self.state.acquire_connection().await?;
         let call_trace = connection
             .transactions_dal()
             .get_call_trace(tx_hash)
             .await
             .map_err(DalError::generalize)?;
-        Ok(call_trace.map(|call_trace| {
-            let mut result: DebugCall = call_trace.into();
-            if only_top_call {
-                result.calls = vec![];
-            }
-            result
-        }))
+        let Some(call_trace) = call_trace else {
+            return Ok(None);
+        };
+        let mut result: DebugCall = call_trace.into();
+        if only_top_call {
+            result.calls = vec![];
+        }
+        if decode {
+            Self::decode_traces(&mut connection, slice::from_mut(&mut result)).await?;
+        }
+        Ok(Some(result))
     }
 
     pub async fn debug_trace_call_impl(
@@ -139,8 +156,9 @@ impl DebugNamespace {
         self.current_method().set_block_id(block_id);
 
         let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
+            .as_ref()
+            .map_or(false, |options| options.tracer_config.only_top_call);
+        let decode = options.map_or(false, |options| options.tracer_config.decode);
 
         let mut connection = self.state.acquire_connection().await?;
         let block_args = self
@@ -225,7 +243,28 @@ impl DebugNamespace {
             revert_reason,
             trace,
         );
-        Ok(call.into())
+        let mut result: DebugCall = call.into();
+        if decode {
+            let mut connection = self.state.acquire_connection().await?;
+            Self::decode_traces(&mut connection, slice::from_mut(&mut result)).await?;
+        }
+        Ok(result)
+    }
+
+    /// Decodes calls to verified contracts in the provided traces.
+    async fn decode_traces(
+        connection: &mut Connection<'_, Core>,
+        traces: &mut [DebugCall],
+    ) -> Result<(), Web3Error> {
+        let mut callees = HashSet::new();
+        for trace in &*traces {
+            trace_callees(trace, &mut callees);
+        }
+        let decoder = AbiDecoder::load(connection, callees).await?;
+        for trace in traces {
+            decoder.decode_trace(trace);
+        }
+        Ok(())
     }
 
     async fn shared_args(&self) -> TxSharedArgs {
@@ -235,6 +274,7 @@ impl DebugNamespace {
             fee_input: self.batch_fee_input,
             base_system_contracts: self.api_contracts.eth_call.clone(),
             caches: self.state.tx_sender.storage_caches().clone(),
+            storage_tree: self.state.tx_sender.vm_storage_tree(),
             validation_computational_gas_limit: BATCH_COMPUTATIONAL_GAS_LIMIT,
             chain_id: sender_config.chain_id,
             whitelisted_tokens_for_aa: self
@@ -1,5 +1,5 @@
 use anyhow::Context as _;
-use zksync_dal::{CoreDal, DalError};
+use zksync_dal::{pruning_dal::PrunedDataKind, CoreDal, DalError};
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
@@ -15,7 +15,7 @@ use zksync_types::{
 use zksync_utils::u256_to_h256;
 use zksync_web3_decl::{
     error::Web3Error,
-    types::{Address, Block, Filter, FilterChanges, Log, U64},
+    types::{Address, Block, Filter, FilterChanges, Log, PubSubFilter, U64},
 };
 
 use crate::{
@@ -183,13 +183,135 @@ impl EthNamespace {
         let (from_block, to_block) = self.state.resolve_filter_block_range(&filter).await?;
 
         filter.to_block = Some(BlockNumber::Number(to_block.0.into()));
-        let changes = self
-            .filter_changes(&mut TypedFilter::Events(filter, from_block))
+        self.get_logs_from_block(filter, from_block).await
+    }
+
+    /// Gets logs matching the filter starting from `from_block`. Logs for pruned blocks are loaded
+    /// from the pruned data archive if it is enabled.
+    async fn get_logs_from_block(
+        &self,
+        filter: Filter,
+        from_block: L2BlockNumber,
+    ) -> Result<Vec<Log>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        let first_l2_block_with_events = self
+            .state
+            .start_info
+            .first_l2_block_with_data(PrunedDataKind::Events, &mut storage)
             .await?;
-        Ok(match changes {
-            FilterChanges::Logs(list) => list,
-            _ => unreachable!("Unexpected `FilterChanges` type, expected `Logs`"),
-        })
+        if from_block >= first_l2_block_with_events {
+            drop(storage);
+            let mut stored_from_block = from_block;
+            return self
+                .get_stored_logs(&filter, &mut stored_from_block, from_block, 0)
+                .await;
+        }
+
+        let to_block = self
+            .state
+            .resolve_filter_block_number(filter.to_block)
+            .await?;
+        let archived_blocks = from_block..=to_block.min(first_l2_block_with_events - 1);
+        let pub_sub_filter = PubSubFilter {
+            address: filter.address.clone(),
+            topics: filter.topics.clone(),
+        };
+        let Some(mut logs) = self
+            .state
+            .get_archived_logs(&mut storage, &pub_sub_filter, archived_blocks)
+            .await?
+        else {
+            return Err(Web3Error::PrunedBlock(first_l2_block_with_events));
+        };
+        drop(storage);
+
+        if to_block >= first_l2_block_with_events {
+            // Archived logs count towards the logs limit.
+            let mut stored_from_block = first_l2_block_with_events;
+            let stored_logs = self
+                .get_stored_logs(&filter, &mut stored_from_block, from_block, logs.len())
+                .await?;
+            logs.extend(stored_logs);
+        }
+        Ok(logs)
+    }
+
+    /// Gets logs stored in Postgres matching the filter starting from `from_block` and advances `from_block`
+    /// past the last processed block. `range_start` and `preceding_logs_count` describe logs preceding `from_block`
+    /// included into the same response (e.g., loaded from the pruned data archive); these logs count towards
+    /// the logs limit.
+    async fn get_stored_logs(
+        &self,
+        filter: &Filter,
+        from_block: &mut L2BlockNumber,
+        range_start: L2BlockNumber,
+        preceding_logs_count: usize,
+    ) -> Result<Vec<Log>, Web3Error> {
+        let addresses = if let Some(addresses) = &filter.address {
+            addresses.0.clone()
+        } else {
+            vec![]
+        };
+        let topics = if let Some(topics) = &filter.topics {
+            if topics.len() > EVENT_TOPIC_NUMBER_LIMIT {
+                return Err(Web3Error::TooManyTopics);
+            }
+            let topics_by_idx = topics
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, topics)| Some((idx as u32 + 1, topics.as_ref()?.0.clone())));
+            topics_by_idx.collect::<Vec<_>>()
+        } else {
+            vec![]
+        };
+
+        let mut to_block = self
+            .state
+            .resolve_filter_block_number(filter.to_block)
+            .await?;
+
+        if matches!(filter.to_block, Some(BlockNumber::Number(_))) {
+            to_block = to_block.min(
+                self.state
+                    .resolve_filter_block_number(Some(BlockNumber::Latest))
+                    .await?,
+            );
+        }
+
+        let get_logs_filter = GetLogsFilter {
+            from_block: *from_block,
+            to_block,
+            addresses,
+            topics,
+        };
+
+        let mut storage = self.state.acquire_connection().await?;
+
+        // Check if there is more than one block in range and there are more than `req_entities_limit` logs that satisfies filter.
+        // In this case we should return error and suggest requesting logs with smaller block range.
+        let limit = self.state.api_config.req_entities_limit;
+        if range_start != to_block {
+            if let Some(l2_block_number) = storage
+                .events_web3_dal()
+                .get_log_block_number(&get_logs_filter, limit.saturating_sub(preceding_logs_count))
+                .await
+                .map_err(DalError::generalize)?
+            {
+                return Err(Web3Error::LogsLimitExceeded(
+                    limit,
+                    range_start.0,
+                    range_start.0.max(l2_block_number.0 - 1),
+                ));
+            }
+        }
+
+        let logs = storage
+            .events_web3_dal()
+            .get_logs(get_logs_filter, i32::MAX as usize)
+            .await
+            .map_err(DalError::generalize)?;
+        *from_block = to_block + 1;
+        Ok(logs)
     }
 
     pub async fn get_filter_logs_impl(&self, idx: U256) -> Result<FilterChanges, Web3Error> {
@@ -209,13 +331,11 @@ impl EthNamespace {
             .state
             .resolve_filter_block_number(filter.from_block)
             .await?;
-        let logs = self
-            .filter_changes(&mut TypedFilter::Events(filter, from_block))
-            .await?;
+        let logs = self.get_logs_from_block(filter, from_block).await?;
 
         // We are not updating the filter, since that is the purpose of `get_filter_changes` method,
         // which is getting changes happened from the last poll and moving the cursor forward.
-        Ok(logs)
+        Ok(FilterChanges::Logs(logs))
     }
 
     pub async fn get_block_impl(
@@ -233,10 +353,25 @@ impl EthNamespace {
         }
 
         let mut storage = self.state.acquire_connection().await?;
-        self.state
+        let prune_check = self
+            .state
             .start_info
             .ensure_not_pruned(block_id, &mut storage)
-            .await?;
+            .await;
+        if let Err(Web3Error::PrunedBlock(_)) = &prune_check {
+            if let Some(archived_block) = self
+                .state
+                .get_archived_l2_block(&mut storage, block_id)
+                .await?
+            {
+                return Ok(Some(if full_transactions {
+                    archived_block.block
+                } else {
+                    archived_block.block_with_tx_hashes()
+                }));
+            }
+        }
+        prune_check?;
 
         let Some(block_number) = self
             .state
@@ -337,14 +472,26 @@ impl EthNamespace {
         }
 
         let mut storage = self.state.acquire_connection().await?;
-        self.state
+        let prune_check = self
+            .state
             .start_info
-            .ensure_not_pruned(block_id, &mut storage)
-            .await?;
+            .ensure_data_not_pruned(PrunedDataKind::Transactions, block_id, &mut storage)
+            .await;
+        if let Err(Web3Error::PrunedBlock(_)) = &prune_check {
+            if let Some(archived_block) = self
+                .state
+                .get_archived_l2_block(&mut storage, block_id)
+                .await?
+            {
+                return Ok(Some(archived_block.receipts));
+            }
+        }
+        prune_check?;
 
+        // Receipts may be retained for longer than the rest of block data.
         let Some(block_number) = self
             .state
-            .resolve_block_unchecked(&mut storage, block_id)
+            .resolve_block_with_data_unchecked(&mut storage, PrunedDataKind::Transactions, block_id)
             .await?
         else {
             return Ok(None);
@@ -516,7 +663,13 @@ impl EthNamespace {
             .get_transaction_receipts(&[hash])
             .await
             .context("get_transaction_receipts")?;
-        Ok(receipts.into_iter().next())
+        if let Some(receipt) = receipts.into_iter().next() {
+            return Ok(Some(receipt));
+        }
+        // The transaction may be pruned; try loading its receipt from the pruned data archive.
+        self.state
+            .get_archived_transaction_receipt(&mut storage, hash)
+            .await
     }
 
     pub async fn new_block_filter_impl(&self) -> Result<U256, Web3Error> {
@@ -753,71 +906,10 @@ impl EthNamespace {
             }
 
             TypedFilter::Events(filter, from_block) => {
-                let addresses = if let Some(addresses) = &filter.address {
-                    addresses.0.clone()
-                } else {
-                    vec![]
-                };
-                let topics = if let Some(topics) = &filter.topics {
-                    if topics.len() > EVENT_TOPIC_NUMBER_LIMIT {
-                        return Err(Web3Error::TooManyTopics);
-                    }
-                    let topics_by_idx = topics.iter().enumerate().filter_map(|(idx, topics)| {
-                        Some((idx as u32 + 1, topics.as_ref()?.0.clone()))
-                    });
-                    topics_by_idx.collect::<Vec<_>>()
-                } else {
-                    vec![]
-                };
-
-                let mut to_block = self
-                    .state
-                    .resolve_filter_block_number(filter.to_block)
+                let range_start = *from_block;
+                let logs = self
+                    .get_stored_logs(filter, from_block, range_start, 0)
                     .await?;
-
-                if matches!(filter.to_block, Some(BlockNumber::Number(_))) {
-                    to_block = to_block.min(
-                        self.state
-                            .resolve_filter_block_number(Some(BlockNumber::Latest))
-                            .await?,
-                    );
-                }
-
-                let get_logs_filter = GetLogsFilter {
-                    from_block: *from_block,
-                    to_block,
-                    addresses,
-                    topics,
-                };
-
-                let mut storage = self.state.acquire_connection().await?;
-
-                // Check if there is more than one block in range and there are more than `req_entities_limit` logs that satisfies filter.
-                // In this case we should return error and suggest requesting logs with smaller block range.
-                if *from_block != to_block {
-                    if let Some(l2_block_number) = storage
-                        .events_web3_dal()
-                        .get_log_block_number(
-                            &get_logs_filter,
-                            self.state.api_config.req_entities_limit,
-                        )
-                        .await
-                        .map_err(DalError::generalize)?
-                    {
-                        return Err(Web3Error::LogsLimitExceeded(
-                            self.state.api_config.req_entities_limit,
-                            from_block.0,
-                            from_block.0.max(l2_block_number.0 - 1),
-                        ));
-                    }
-                }
-
-                let logs = storage
-                    .events_web3_dal()
-                    .get_logs(get_logs_filter, i32::MAX as usize)
-                    .await
-                    .map_err(DalError::generalize)?;
-                *from_block = to_block + 1;
                 FilterChanges::Logs(logs)
             }
         })
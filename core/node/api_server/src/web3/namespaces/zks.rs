@@ -1,15 +1,19 @@
-use std::{collections::HashMap, convert::TryInto};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryInto,
+};
 
 use anyhow::Context as _;
-use zksync_dal::{Connection, Core, CoreDal, DalError};
-use zksync_metadata_calculator::api_server::TreeApiError;
+use zksync_dal::{pruning_dal::PrunedDataKind, Connection, Core, CoreDal, DalError};
+use zksync_metadata_calculator::api_server::{TreeApiClient, TreeApiError};
 use zksync_mini_merkle_tree::MiniMerkleTree;
 use zksync_multivm::interface::VmExecutionResultAndLogs;
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, GetLogsFilter, L1BatchDetails, L2ToL1LogProof, Proof,
-        ProtocolVersion, StorageProof, TransactionDetails,
+        BlockDetails, BridgeAddresses, DebugCall, DecodedTransaction, GetLogsFilter,
+        L1BatchDetails, L1BatchSubmissionDetails, L2ToL1LogProof, Proof, ProtocolVersion,
+        StorageProof, TransactionDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -31,7 +35,12 @@ use zksync_web3_decl::{
 
 use crate::{
     utils::open_readonly_transaction,
-    web3::{backend_jsonrpsee::MethodTracer, metrics::API_METRICS, RpcState},
+    web3::{
+        abi_decoding::{trace_callees, AbiDecoder},
+        backend_jsonrpsee::MethodTracer,
+        metrics::API_METRICS,
+        RpcState,
+    },
 };
 
 #[derive(Debug)]
@@ -202,7 +211,7 @@ impl ZksNamespace {
         let mut storage = self.state.acquire_connection().await?;
         self.state
             .start_info
-            .ensure_not_pruned(block_number, &mut storage)
+            .ensure_data_not_pruned(PrunedDataKind::Events, block_number, &mut storage)
             .await?;
 
         let Some(l1_batch_number) = storage
@@ -387,7 +396,7 @@ impl ZksNamespace {
         let mut storage = self.state.acquire_connection().await?;
         self.state
             .start_info
-            .ensure_not_pruned(block_number, &mut storage)
+            .ensure_data_not_pruned(PrunedDataKind::Transactions, block_number, &mut storage)
             .await?;
 
         Ok(storage
@@ -420,6 +429,61 @@ impl ZksNamespace {
         Ok(tx_details)
     }
 
+    pub async fn decode_transaction_impl(
+        &self,
+        hash: H256,
+    ) -> Result<Option<DecodedTransaction>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        // Open a readonly transaction to have a consistent view of Postgres
+        let mut storage = open_readonly_transaction(&mut storage).await?;
+        let Some(tx) = storage
+            .transactions_web3_dal()
+            .get_transaction_by_hash(hash, self.state.api_config.l2_chain_id)
+            .await
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(None);
+        };
+        let logs = storage
+            .transactions_web3_dal()
+            .get_transaction_receipts(&[hash])
+            .await
+            .map_err(DalError::generalize)?
+            .into_iter()
+            .next()
+            .map_or_else(Vec::new, |receipt| receipt.logs);
+        let mut trace: Option<DebugCall> = storage
+            .transactions_dal()
+            .get_call_trace(hash)
+            .await
+            .map_err(DalError::generalize)?
+            .map(Into::into);
+
+        let mut addresses: HashSet<_> = logs.iter().map(|log| log.address).collect();
+        addresses.extend(tx.to);
+        if let Some(trace) = &trace {
+            trace_callees(trace, &mut addresses);
+        }
+        let decoder = AbiDecoder::load(&mut storage, addresses).await?;
+        if let Some(trace) = &mut trace {
+            decoder.decode_trace(trace);
+        }
+
+        Ok(Some(DecodedTransaction {
+            transaction_hash: hash,
+            from: tx.from.unwrap_or_default(),
+            to: tx.to,
+            call: tx
+                .to
+                .and_then(|to| decoder.decode_call(to, &tx.input.0, None)),
+            events: logs
+                .iter()
+                .filter_map(|log| decoder.decode_log(log))
+                .collect(),
+            trace,
+        }))
+    }
+
     pub async fn get_l1_batch_details_impl(
         &self,
         batch_number: L1BatchNumber,
@@ -437,6 +501,22 @@ impl ZksNamespace {
             .map_err(DalError::generalize)?)
     }
 
+    pub async fn get_l1_batch_submission_details_impl(
+        &self,
+        batch_number: L1BatchNumber,
+    ) -> Result<Option<L1BatchSubmissionDetails>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        self.state
+            .start_info
+            .ensure_not_pruned(batch_number, &mut storage)
+            .await?;
+
+        Ok(storage
+            .eth_sender_dal()
+            .get_l1_batch_submission_details(batch_number)
+            .await?)
+    }
+
     pub async fn get_bytecode_by_hash_impl(
         &self,
         hash: H256,
@@ -487,31 +567,41 @@ impl ZksNamespace {
         keys: Vec<H256>,
         l1_batch_number: L1BatchNumber,
     ) -> Result<Option<Proof>, Web3Error> {
+        let tree_api = self
+            .state
+            .tree_api
+            .as_deref()
+            .ok_or(Web3Error::MethodNotImplemented)?;
         let mut storage = self.state.acquire_connection().await?;
-        self.state
+        let pruning_check = self
+            .state
             .start_info
             .ensure_not_pruned(l1_batch_number, &mut storage)
-            .await?;
+            .await;
+        drop(storage);
+        if let Err(err) = pruning_check {
+            return Err(match err {
+                Web3Error::PrunedL1Batch(first_retained_l1_batch) => {
+                    Self::pruned_proofs_error(tree_api, first_retained_l1_batch).await
+                }
+                err => err,
+            });
+        }
+
         let hashed_keys = keys
             .iter()
             .map(|key| StorageKey::new(AccountTreeId::new(address), *key).hashed_key_u256())
             .collect();
-        let tree_api = self
-            .state
-            .tree_api
-            .as_deref()
-            .ok_or(Web3Error::MethodNotImplemented)?;
         let proofs_result = tree_api.get_proofs(l1_batch_number, hashed_keys).await;
         let proofs = match proofs_result {
             Ok(proofs) => proofs,
             Err(TreeApiError::NotReady(_)) => return Err(Web3Error::TreeApiUnavailable),
             Err(TreeApiError::NoVersion(err)) => {
-                return if err.missing_version > err.version_count {
+                return if err.missing_version >= err.version_count {
                     Ok(None)
                 } else {
-                    Err(Web3Error::InternalError(anyhow::anyhow!(
-                        "L1 batch #{l1_batch_number} is pruned in Merkle tree, but not in Postgres"
-                    )))
+                    // The L1 batch is pruned in the Merkle tree, but not in Postgres.
+                    Err(Self::pruned_proofs_error(tree_api, l1_batch_number + 1).await)
                 };
             }
             Err(TreeApiError::Internal(err)) => return Err(Web3Error::InternalError(err)),
@@ -540,6 +630,28 @@ impl ZksNamespace {
         }))
     }
 
+    /// Returns an error specifying the first L1 batch for which proofs can be served, taking into account
+    /// both Postgres and Merkle tree pruning. `min_l1_batch_number` is the lower bound for this batch.
+    async fn pruned_proofs_error(
+        tree_api: &dyn TreeApiClient,
+        min_l1_batch_number: L1BatchNumber,
+    ) -> Web3Error {
+        match tree_api.get_info().await {
+            Ok(info) => {
+                let first_l1_batch_with_proofs = info
+                    .min_l1_batch_number
+                    .map_or(min_l1_batch_number, |number| {
+                        number.max(min_l1_batch_number)
+                    });
+                Web3Error::PrunedProofs(first_l1_batch_with_proofs)
+            }
+            Err(TreeApiError::NotReady(_)) => Web3Error::TreeApiUnavailable,
+            Err(err) => Web3Error::InternalError(
+                anyhow::Error::new(err).context("failed getting Merkle tree info"),
+            ),
+        }
+    }
+
     pub fn get_base_token_l1_address_impl(&self) -> Result<Address, Web3Error> {
         self.state
             .api_config
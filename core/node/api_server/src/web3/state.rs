@@ -1,5 +1,6 @@
 use std::{
     future::Future,
+    ops,
     sync::{
         atomic::{AtomicU32, Ordering},
         Arc,
@@ -16,14 +17,24 @@ use zksync_config::{
     configs::{api::Web3JsonRpcConfig, ContractsConfig},
     GenesisConfig,
 };
-use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalError};
+use zksync_dal::{
+    pruning_dal::PrunedDataKind, Connection, ConnectionPool, Core, CoreDal, DalError,
+};
 use zksync_metadata_calculator::api_server::TreeApiClient;
 use zksync_node_sync::SyncState;
+use zksync_object_store::ObjectStore;
 use zksync_types::{
-    api, commitment::L1BatchCommitmentMode, l2::L2Tx, transaction_request::CallRequest, Address,
-    L1BatchNumber, L1ChainId, L2BlockNumber, L2ChainId, H256, U256, U64,
+    api,
+    commitment::L1BatchCommitmentMode,
+    l2::L2Tx,
+    pruning::{ArchivedL1Batch, ArchivedL2Block},
+    transaction_request::CallRequest,
+    Address, L1BatchNumber, L1ChainId, L2BlockNumber, L2ChainId, H256, U256, U64,
+};
+use zksync_web3_decl::{
+    error::Web3Error,
+    types::{Filter, PubSubFilter},
 };
-use zksync_web3_decl::{error::Web3Error, types::Filter};
 
 use super::{
     backend_jsonrpsee::MethodTracer,
@@ -87,6 +98,28 @@ impl BlockStartInfo {
             }
         }
     }
+
+    /// Same as [`Self::ensure_not_pruned()`], but checks retention of data of the specified kind, which may be retained
+    /// for longer than the rest of block data.
+    pub(super) async fn ensure_data_not_pruned(
+        &self,
+        kind: PrunedDataKind,
+        query: impl Into<PruneQuery>,
+        storage: &mut Connection<'_, Core>,
+    ) -> Result<(), Web3Error> {
+        match query.into() {
+            PruneQuery::BlockId(id) => {
+                Ok(self.ensure_data_not_pruned_block(kind, id, storage).await?)
+            }
+            PruneQuery::L1Batch(number) => {
+                let first_l1_batch = self.first_l1_batch_with_data(kind, storage).await?;
+                if number < first_l1_batch {
+                    return Err(Web3Error::PrunedL1Batch(first_l1_batch));
+                }
+                Ok(())
+            }
+        }
+    }
 }
 
 /// Configuration values for the API.
@@ -247,6 +280,8 @@ pub(crate) struct RpcState {
     pub(super) start_info: BlockStartInfo,
     pub(super) mempool_cache: Option<MempoolCache>,
     pub(super) last_sealed_l2_block: SealedL2BlockNumber,
+    /// Object store with archived data for pruned L1 batches.
+    pub(super) pruned_data_archive: Option<Arc<dyn ObjectStore>>,
 }
 
 impl RpcState {
@@ -284,6 +319,140 @@ impl RpcState {
             .map_err(|err| err.generalize().into())
     }
 
+    /// Loads a pruned L2 block with the specified ID from the pruned data archive. Returns `Ok(None)` if the archive
+    /// is not enabled, or the block is not archived. Only numeric block IDs are supported.
+    pub(crate) async fn get_archived_l2_block(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        block: api::BlockId,
+    ) -> Result<Option<ArchivedL2Block>, Web3Error> {
+        let Some(blob_store) = &self.pruned_data_archive else {
+            return Ok(None);
+        };
+        let block_number = match block {
+            api::BlockId::Number(api::BlockNumber::Number(number)) => {
+                let Ok(number) = u32::try_from(number) else {
+                    return Ok(None);
+                };
+                L2BlockNumber(number)
+            }
+            api::BlockId::Number(api::BlockNumber::Earliest) => L2BlockNumber(0),
+            _ => return Ok(None),
+        };
+
+        let Some(l1_batch_number) = connection
+            .pruning_dal()
+            .get_archived_l1_batch_for_l2_block(block_number)
+            .await
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(None);
+        };
+        let archive = Self::load_archived_l1_batch(blob_store.as_ref(), l1_batch_number).await?;
+        Ok(archive.l2_block(block_number).cloned())
+    }
+
+    /// Loads a receipt for a pruned transaction with the specified hash from the pruned data archive.
+    /// Returns `Ok(None)` if the archive is not enabled, or the transaction is not archived.
+    pub(crate) async fn get_archived_transaction_receipt(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        tx_hash: H256,
+    ) -> Result<Option<api::TransactionReceipt>, Web3Error> {
+        let Some(blob_store) = &self.pruned_data_archive else {
+            return Ok(None);
+        };
+        let Some(l1_batch_number) = connection
+            .pruning_dal()
+            .get_archived_l1_batch_for_transaction(tx_hash)
+            .await
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(None);
+        };
+        let archive = Self::load_archived_l1_batch(blob_store.as_ref(), l1_batch_number).await?;
+        Ok(archive.transaction_receipt(tx_hash).cloned())
+    }
+
+    /// Loads logs matching the filter for the specified range of pruned L2 blocks from the pruned data archive.
+    /// Returns `Ok(None)` if the archive is not enabled, or if it doesn't cover the entire range.
+    pub(crate) async fn get_archived_logs(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        filter: &PubSubFilter,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+    ) -> Result<Option<Vec<api::Log>>, Web3Error> {
+        /// Maximum number of archived L1 batches loaded to serve a single request.
+        const MAX_L1_BATCHES_PER_REQUEST: usize = 100;
+
+        let Some(blob_store) = &self.pruned_data_archive else {
+            return Ok(None);
+        };
+        let mut l1_batches = connection
+            .pruning_dal()
+            .get_archived_l1_batches(l2_blocks.clone(), MAX_L1_BATCHES_PER_REQUEST + 1)
+            .await
+            .map_err(DalError::generalize)?;
+        let is_truncated = l1_batches.len() > MAX_L1_BATCHES_PER_REQUEST;
+        l1_batches.truncate(MAX_L1_BATCHES_PER_REQUEST);
+
+        // Check that archived L1 batches cover the requested range without gaps; e.g., the archive may have been
+        // enabled after some L1 batches were already pruned.
+        let mut next_block = *l2_blocks.start();
+        for (_, batch_blocks) in &l1_batches {
+            if *batch_blocks.start() > next_block {
+                return Ok(None);
+            }
+            next_block = next_block.max(*batch_blocks.end() + 1);
+        }
+        if !is_truncated && next_block <= *l2_blocks.end() {
+            return Ok(None);
+        }
+
+        let limit = self.api_config.req_entities_limit;
+        let from_block = *l2_blocks.start();
+        let mut last_processed_block = from_block;
+        let mut logs = vec![];
+        for (l1_batch_number, _) in l1_batches {
+            let archive =
+                Self::load_archived_l1_batch(blob_store.as_ref(), l1_batch_number).await?;
+            let blocks = archive
+                .l2_blocks
+                .iter()
+                .filter(|block| l2_blocks.contains(&block.number()));
+            for block in blocks {
+                logs.extend(block.logs().filter(|log| filter.matches(log)).cloned());
+                if logs.len() > limit {
+                    return Err(Web3Error::LogsLimitExceeded(
+                        limit,
+                        from_block.0,
+                        from_block.0.max(block.number().0.saturating_sub(1)),
+                    ));
+                }
+                last_processed_block = block.number();
+            }
+        }
+
+        if is_truncated {
+            return Err(Web3Error::LogsLimitExceeded(
+                limit,
+                from_block.0,
+                last_processed_block.0,
+            ));
+        }
+        Ok(Some(logs))
+    }
+
+    async fn load_archived_l1_batch(
+        blob_store: &dyn ObjectStore,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<ArchivedL1Batch> {
+        blob_store
+            .get(l1_batch_number)
+            .await
+            .with_context(|| format!("failed loading archived L1 batch #{l1_batch_number}"))
+    }
+
     /// Resolves the specified block ID to a block number, which is guaranteed to be present in the node storage.
     pub(crate) async fn resolve_block(
         &self,
@@ -312,6 +481,27 @@ impl RpcState {
         block: api::BlockId,
     ) -> Result<Option<L2BlockNumber>, Web3Error> {
         self.start_info.ensure_not_pruned(block, connection).await?;
+        Self::resolve_block_id_unchecked(connection, block).await
+    }
+
+    /// Same as [`Self::resolve_block_unchecked()`], but only checks that data of the specified kind is not pruned
+    /// for the block. Block data other than the specified kind may be missing for the returned block number.
+    pub(crate) async fn resolve_block_with_data_unchecked(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        kind: PrunedDataKind,
+        block: api::BlockId,
+    ) -> Result<Option<L2BlockNumber>, Web3Error> {
+        self.start_info
+            .ensure_data_not_pruned(kind, block, connection)
+            .await?;
+        Self::resolve_block_id_unchecked(connection, block).await
+    }
+
+    async fn resolve_block_id_unchecked(
+        connection: &mut Connection<'_, Core>,
+        block: api::BlockId,
+    ) -> Result<Option<L2BlockNumber>, Web3Error> {
         match block {
             api::BlockId::Number(api::BlockNumber::Number(number)) => {
                 Ok(u32::try_from(number).ok().map(L2BlockNumber))
@@ -350,9 +540,18 @@ impl RpcState {
         let block_number = block_number.unwrap_or(api::BlockNumber::Latest);
         let block_id = api::BlockId::Number(block_number);
         let mut conn = self.acquire_connection().await?;
-        Ok(self.resolve_block(&mut conn, block_id).await.unwrap())
-        // ^ `unwrap()` is safe: `resolve_block_id(api::BlockId::Number(_))` can only return `None`
-        // if called with an explicit number, and we've handled this case earlier.
+        // Filters only access events, which may be retained for longer than the rest of block data.
+        self.start_info
+            .ensure_data_not_pruned(PrunedDataKind::Events, block_id, &mut conn)
+            .await?;
+        let block_number = conn
+            .blocks_web3_dal()
+            .resolve_block_id(block_id)
+            .await
+            .map_err(DalError::generalize)?;
+        Ok(block_number.expect(
+            "`resolve_block_id(api::BlockId::Number(_))` can only return `None` if called with an explicit number",
+        ))
     }
 
     pub async fn resolve_filter_block_range(
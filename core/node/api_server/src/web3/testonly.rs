@@ -7,6 +7,7 @@ use zksync_config::configs::{api::Web3JsonRpcConfig, chain::StateKeeperConfig, w
 use zksync_dal::ConnectionPool;
 use zksync_health_check::CheckHealth;
 use zksync_node_fee_model::MockBatchFeeParamsProvider;
+use zksync_object_store::ObjectStore;
 use zksync_state::PostgresStorageCaches;
 use zksync_types::L2ChainId;
 
@@ -96,12 +97,38 @@ impl ApiServerHandles {
     }
 }
 
+/// Optional components of the API server spawned in tests.
+#[derive(Debug, Default)]
+pub(crate) struct TestServerOptions {
+    pub pruned_data_archive: Option<Arc<dyn ObjectStore>>,
+    pub tree_api: Option<Arc<dyn TreeApiClient>>,
+}
+
 pub async fn spawn_http_server(
     api_config: InternalApiConfig,
     pool: ConnectionPool<Core>,
     tx_executor: MockTransactionExecutor,
     method_tracer: Arc<MethodTracer>,
     stop_receiver: watch::Receiver<bool>,
+) -> ApiServerHandles {
+    spawn_http_server_with_options(
+        api_config,
+        pool,
+        tx_executor,
+        method_tracer,
+        TestServerOptions::default(),
+        stop_receiver,
+    )
+    .await
+}
+
+pub(crate) async fn spawn_http_server_with_options(
+    api_config: InternalApiConfig,
+    pool: ConnectionPool<Core>,
+    tx_executor: MockTransactionExecutor,
+    method_tracer: Arc<MethodTracer>,
+    options: TestServerOptions,
+    stop_receiver: watch::Receiver<bool>,
 ) -> ApiServerHandles {
     spawn_server(
         ApiTransportLabel::Http,
@@ -110,6 +137,7 @@ pub async fn spawn_http_server(
         None,
         tx_executor,
         method_tracer,
+        options,
         stop_receiver,
     )
     .await
@@ -129,11 +157,13 @@ pub async fn spawn_ws_server(
         websocket_requests_per_minute_limit,
         MockTransactionExecutor::default(),
         Arc::default(),
+        TestServerOptions::default(),
         stop_receiver,
     )
     .await
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn spawn_server(
     transport: ApiTransportLabel,
     api_config: InternalApiConfig,
@@ -141,6 +171,7 @@ async fn spawn_server(
     websocket_requests_per_minute_limit: Option<NonZeroU32>,
     tx_executor: MockTransactionExecutor,
     method_tracer: Arc<MethodTracer>,
+    options: TestServerOptions,
     stop_receiver: watch::Receiver<bool>,
 ) -> (ApiServerHandles, mpsc::UnboundedReceiver<PubSubEvent>) {
     let (tx_sender, vm_barrier) =
@@ -150,7 +181,7 @@ async fn spawn_server(
     let mut namespaces = Namespace::DEFAULT.to_vec();
     namespaces.extend([Namespace::Debug, Namespace::Snapshots]);
 
-    let server_builder = match transport {
+    let mut server_builder = match transport {
         ApiTransportLabel::Http => ApiBuilder::jsonrpsee_backend(api_config, pool).http(0),
         ApiTransportLabel::Ws => {
             let mut builder = ApiBuilder::jsonrpsee_backend(api_config, pool)
@@ -163,6 +194,12 @@ async fn spawn_server(
             builder
         }
     };
+    if let Some(pruned_data_archive) = options.pruned_data_archive {
+        server_builder = server_builder.with_pruned_data_archive(pruned_data_archive);
+    }
+    if let Some(tree_api) = options.tree_api {
+        server_builder = server_builder.with_tree_api(tree_api);
+    }
     let server_handles = server_builder
         .with_polling_interval(POLL_INTERVAL)
         .with_tx_sender(tx_sender)
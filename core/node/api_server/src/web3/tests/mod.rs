@@ -57,11 +57,13 @@ use zksync_web3_decl::{
 use super::*;
 use crate::{
     execution_sandbox::testonly::MockTransactionExecutor,
-    web3::testonly::{spawn_http_server, spawn_ws_server},
+    web3::testonly::{spawn_http_server_with_options, spawn_ws_server, TestServerOptions},
 };
 
 mod debug;
 mod filters;
+mod pruned_data_archive;
+mod pruned_proofs;
 mod snapshots;
 mod vm;
 mod ws;
@@ -151,6 +153,16 @@ trait HttpTest: Send + Sync {
     fn filters_disabled(&self) -> bool {
         false
     }
+
+    /// Overrides the `req_entities_limit` configuration parameter for HTTP server startup
+    fn req_entities_limit(&self) -> Option<usize> {
+        None
+    }
+
+    /// Optional components of the HTTP server
+    fn server_options(&self) -> TestServerOptions {
+        TestServerOptions::default()
+    }
 }
 
 /// Storage initialization strategy.
@@ -228,11 +240,15 @@ async fn test_http_server(test: impl HttpTest) {
     let genesis = GenesisConfig::for_tests();
     let mut api_config = InternalApiConfig::new(&web3_config, &contracts_config, &genesis);
     api_config.filters_disabled = test.filters_disabled();
-    let mut server_handles = spawn_http_server(
+    if let Some(limit) = test.req_entities_limit() {
+        api_config.req_entities_limit = limit;
+    }
+    let mut server_handles = spawn_http_server_with_options(
         api_config,
         pool.clone(),
         test.transaction_executor(),
         test.method_tracer(),
+        test.server_options(),
         stop_receiver,
     )
     .await;
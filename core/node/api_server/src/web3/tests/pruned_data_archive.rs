@@ -0,0 +1,276 @@
+//! Tests for serving pruned data from the pruned data archive.
+
+use zksync_object_store::{MockObjectStore, ObjectStore};
+use zksync_types::pruning::{ArchivedL1Batch, ArchivedL2Block};
+use zksync_web3_decl::types::Filter;
+
+use super::*;
+
+/// First L2 block retained in Postgres after snapshot recovery.
+const FIRST_RETAINED_BLOCK: L2BlockNumber =
+    L2BlockNumber(StorageInitialization::SNAPSHOT_RECOVERY_BLOCK.0 + 1);
+/// L2 block with events stored in Postgres.
+const STORED_EVENTS_BLOCK: L2BlockNumber = L2BlockNumber(FIRST_RETAINED_BLOCK.0 + 1);
+/// Archived L1 batches together with their L2 blocks. L2 blocks before the first archived block are pruned
+/// and not archived.
+const ARCHIVED_L1_BATCHES: [(u32, [u32; 2]); 2] = [(22, [21, 22]), (23, [23, 23])];
+
+fn archived_l2_block(number: u32) -> ArchivedL2Block {
+    let tx_hash = H256::from_low_u64_be(number.into());
+    let log = api::Log {
+        address: Address::repeat_byte(23),
+        topics: vec![H256::repeat_byte(42)],
+        data: number.to_le_bytes().to_vec().into(),
+        block_hash: Some(H256::zero()),
+        block_number: Some(number.into()),
+        l1_batch_number: None,
+        transaction_hash: Some(tx_hash),
+        transaction_index: Some(U64::zero()),
+        log_index: Some(U256::zero()),
+        transaction_log_index: Some(U256::zero()),
+        log_type: None,
+        removed: Some(false),
+    };
+    let transaction = api::Transaction {
+        hash: tx_hash,
+        block_number: Some(number.into()),
+        ..api::Transaction::default()
+    };
+    let block = api::Block {
+        number: number.into(),
+        transactions: vec![api::TransactionVariant::Full(transaction)],
+        ..api::Block::default()
+    };
+    let receipt = api::TransactionReceipt {
+        transaction_hash: tx_hash,
+        block_number: number.into(),
+        logs: vec![log],
+        ..api::TransactionReceipt::default()
+    };
+    ArchivedL2Block {
+        block,
+        receipts: vec![receipt],
+    }
+}
+
+#[derive(Debug)]
+struct PrunedDataArchiveTest {
+    blob_store: Arc<dyn ObjectStore>,
+}
+
+impl PrunedDataArchiveTest {
+    fn new() -> Self {
+        Self {
+            blob_store: MockObjectStore::arc(),
+        }
+    }
+
+    async fn prepare_archive(&self, storage: &mut Connection<'_, Core>) -> anyhow::Result<()> {
+        for (l1_batch_number, [first_block, last_block]) in ARCHIVED_L1_BATCHES {
+            let l1_batch_number = L1BatchNumber(l1_batch_number);
+            let archive = ArchivedL1Batch {
+                l1_batch_number,
+                l2_blocks: (first_block..=last_block).map(archived_l2_block).collect(),
+            };
+            self.blob_store.put(l1_batch_number, &archive).await?;
+            let tx_hashes: Vec<_> = archive.transaction_hashes().collect();
+            storage
+                .pruning_dal()
+                .insert_archived_l1_batch(
+                    l1_batch_number,
+                    L2BlockNumber(first_block)..=L2BlockNumber(last_block),
+                    &tx_hashes,
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+fn logs_filter(from_block: u32, to_block: u32) -> Filter {
+    Filter {
+        from_block: Some(api::BlockNumber::Number(from_block.into())),
+        to_block: Some(api::BlockNumber::Number(to_block.into())),
+        ..Filter::default()
+    }
+}
+
+fn assert_logs_limit_error(error: &ClientError, limit: usize) {
+    if let ClientError::Call(error) = error {
+        assert!(
+            error
+                .message()
+                .contains(&format!("Query returned more than {limit} results")),
+            "{error:?}"
+        );
+    } else {
+        panic!("Unexpected error: {error:?}");
+    }
+}
+
+#[async_trait]
+impl HttpTest for PrunedDataArchiveTest {
+    fn storage_initialization(&self) -> StorageInitialization {
+        StorageInitialization::empty_recovery()
+    }
+
+    fn req_entities_limit(&self) -> Option<usize> {
+        Some(6)
+    }
+
+    fn server_options(&self) -> TestServerOptions {
+        TestServerOptions {
+            pruned_data_archive: Some(self.blob_store.clone()),
+            ..TestServerOptions::default()
+        }
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut storage = pool.connection().await?;
+        self.prepare_archive(&mut storage).await?;
+        let (_, events) = store_events(&mut storage, STORED_EVENTS_BLOCK.0, 0).await?;
+        drop(storage);
+
+        // Logs are loaded from the archive for pruned blocks and from Postgres for the remaining blocks.
+        let logs = client
+            .get_logs(logs_filter(22, STORED_EVENTS_BLOCK.0))
+            .await?;
+        let log_block_numbers: Vec<_> = logs
+            .iter()
+            .map(|log| log.block_number.unwrap().as_u32())
+            .collect();
+        let expected_block_numbers = [22, 23].into_iter().chain([STORED_EVENTS_BLOCK.0; 4]);
+        assert_eq!(
+            log_block_numbers,
+            expected_block_numbers.collect::<Vec<_>>()
+        );
+        let stored_events: Vec<_> = events.iter().collect();
+        assert_logs_match(&logs[2..], &stored_events);
+
+        let logs = client.get_logs(logs_filter(21, 23)).await?;
+        assert_eq!(logs.len(), 3);
+        assert_eq!(logs[0], archived_l2_block(21).receipts[0].logs[0]);
+
+        // Filters are applied to archived logs.
+        let filter = Filter {
+            address: Some(Address::zero().into()),
+            ..logs_filter(21, 23)
+        };
+        let logs = client.get_logs(filter).await?;
+        assert!(logs.is_empty(), "{logs:?}");
+
+        // Archived logs count towards the logs limit: 3 archived + 4 stored logs > 6.
+        let error = client
+            .get_logs(logs_filter(21, STORED_EVENTS_BLOCK.0))
+            .await
+            .unwrap_err();
+        assert_logs_limit_error(&error, 6);
+
+        // The archive doesn't cover L2 block #20.
+        for from_block in [0, 20] {
+            let error = client
+                .get_logs(logs_filter(from_block, 23))
+                .await
+                .unwrap_err();
+            assert_pruned_block_error(&error, FIRST_RETAINED_BLOCK);
+            let error = client
+                .get_logs(logs_filter(from_block, STORED_EVENTS_BLOCK.0))
+                .await
+                .unwrap_err();
+            assert_pruned_block_error(&error, FIRST_RETAINED_BLOCK);
+        }
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_logs_from_pruned_data_archive() {
+    test_http_server(PrunedDataArchiveTest::new()).await;
+}
+
+#[derive(Debug)]
+struct PrunedDataArchiveBlocksTest(PrunedDataArchiveTest);
+
+#[async_trait]
+impl HttpTest for PrunedDataArchiveBlocksTest {
+    fn storage_initialization(&self) -> StorageInitialization {
+        StorageInitialization::empty_recovery()
+    }
+
+    fn server_options(&self) -> TestServerOptions {
+        self.0.server_options()
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut storage = pool.connection().await?;
+        self.0.prepare_archive(&mut storage).await?;
+        drop(storage);
+
+        for number in [21, 22, 23] {
+            let expected_block = archived_l2_block(number);
+            let block = client
+                .get_block_by_number(number.into(), true)
+                .await?
+                .context("no archived block")?;
+            assert_eq!(block, expected_block.block);
+            let block = client
+                .get_block_by_number(number.into(), false)
+                .await?
+                .context("no archived block")?;
+            assert_eq!(block, expected_block.block_with_tx_hashes());
+
+            let receipts = client
+                .get_block_receipts(api::BlockId::Number(number.into()))
+                .await?
+                .context("no archived receipts")?;
+            assert_eq!(receipts, expected_block.receipts);
+
+            let tx_hash = expected_block.receipts[0].transaction_hash;
+            let receipt = client
+                .get_transaction_receipt(tx_hash)
+                .await?
+                .context("no archived receipt")?;
+            assert_eq!(receipt, expected_block.receipts[0]);
+        }
+
+        let receipt = client
+            .get_transaction_receipt(H256::from_low_u64_be(20))
+            .await?;
+        assert!(receipt.is_none(), "{receipt:?}");
+
+        for number in [0_u32, 20] {
+            let error = client
+                .get_block_by_number(number.into(), false)
+                .await
+                .unwrap_err();
+            assert_pruned_block_error(&error, FIRST_RETAINED_BLOCK);
+            let error = client
+                .get_block_receipts(api::BlockId::Number(number.into()))
+                .await
+                .unwrap_err();
+            assert_pruned_block_error(&error, FIRST_RETAINED_BLOCK);
+        }
+
+        // Blocks retained in Postgres are still loaded from Postgres.
+        let block = client
+            .get_block_by_number(FIRST_RETAINED_BLOCK.0.into(), false)
+            .await?
+            .context("no retained block")?;
+        assert_eq!(block.number, FIRST_RETAINED_BLOCK.0.into());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_blocks_and_receipts_from_pruned_data_archive() {
+    test_http_server(PrunedDataArchiveBlocksTest(PrunedDataArchiveTest::new())).await;
+}
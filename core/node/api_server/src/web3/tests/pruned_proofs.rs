@@ -0,0 +1,149 @@
+//! Tests for serving Merkle proofs for L1 batches pruned in Postgres and / or the Merkle tree.
+
+use zksync_config::configs::database::MerkleTreeMode;
+use zksync_merkle_tree::NoVersionError;
+use zksync_metadata_calculator::{
+    api_server::{TreeApiClient, TreeApiError, TreeEntryWithProof},
+    MerkleTreeInfo,
+};
+use zksync_types::U256;
+
+use super::*;
+
+/// First L1 batch retained in Postgres after snapshot recovery.
+const FIRST_RETAINED_L1_BATCH: L1BatchNumber =
+    L1BatchNumber(StorageInitialization::SNAPSHOT_RECOVERY_BATCH.0 + 1);
+/// Next L1 batch to be processed by the mock Merkle tree.
+const NEXT_TREE_L1_BATCH: L1BatchNumber = L1BatchNumber(FIRST_RETAINED_L1_BATCH.0 + 5);
+
+/// Mock Merkle tree retaining versions starting from `min_l1_batch_number`.
+#[derive(Debug)]
+struct MockTreeApiClient {
+    min_l1_batch_number: L1BatchNumber,
+}
+
+#[async_trait]
+impl TreeApiClient for MockTreeApiClient {
+    async fn get_info(&self) -> Result<MerkleTreeInfo, TreeApiError> {
+        Ok(MerkleTreeInfo {
+            mode: MerkleTreeMode::Full,
+            root_hash: H256::zero(),
+            next_l1_batch_number: NEXT_TREE_L1_BATCH,
+            min_l1_batch_number: Some(self.min_l1_batch_number),
+            leaf_count: 0,
+        })
+    }
+
+    async fn get_proofs(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<Vec<TreeEntryWithProof>, TreeApiError> {
+        if l1_batch_number < self.min_l1_batch_number || l1_batch_number >= NEXT_TREE_L1_BATCH {
+            return Err(TreeApiError::NoVersion(NoVersionError {
+                missing_version: l1_batch_number.0.into(),
+                version_count: NEXT_TREE_L1_BATCH.0.into(),
+            }));
+        }
+        let entries = hashed_keys.into_iter().map(|_| TreeEntryWithProof {
+            value: H256::zero(),
+            index: 0,
+            merkle_path: vec![],
+        });
+        Ok(entries.collect())
+    }
+}
+
+fn assert_pruned_proofs_error(error: &ClientError, first_l1_batch_with_proofs: L1BatchNumber) {
+    if let ClientError::Call(error) = error {
+        assert_eq!(error.code(), ErrorCode::InvalidParams.code());
+        assert!(
+            error.message().contains(&format!(
+                "the first L1 batch with proofs is {first_l1_batch_with_proofs}"
+            )),
+            "{error:?}"
+        );
+        assert!(error.data().is_none(), "{error:?}");
+    } else {
+        panic!("Unexpected error: {error:?}");
+    }
+}
+
+#[derive(Debug)]
+struct PrunedProofsTest {
+    /// First L1 batch retained in the Merkle tree.
+    min_tree_l1_batch: L1BatchNumber,
+}
+
+#[async_trait]
+impl HttpTest for PrunedProofsTest {
+    fn storage_initialization(&self) -> StorageInitialization {
+        StorageInitialization::empty_recovery()
+    }
+
+    fn server_options(&self) -> TestServerOptions {
+        TestServerOptions {
+            tree_api: Some(Arc::new(MockTreeApiClient {
+                min_l1_batch_number: self.min_tree_l1_batch,
+            })),
+            ..TestServerOptions::default()
+        }
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let address = Address::repeat_byte(1);
+        let keys = vec![H256::zero(), H256::repeat_byte(1)];
+        let first_l1_batch_with_proofs = self.min_tree_l1_batch.max(FIRST_RETAINED_L1_BATCH);
+
+        // L1 batches pruned in Postgres.
+        for l1_batch_number in [0, StorageInitialization::SNAPSHOT_RECOVERY_BATCH.0] {
+            let error = client
+                .get_proof(address, keys.clone(), L1BatchNumber(l1_batch_number))
+                .await
+                .unwrap_err();
+            assert_pruned_proofs_error(&error, first_l1_batch_with_proofs);
+        }
+
+        // L1 batches retained in Postgres, but (potentially) pruned in the tree.
+        for l1_batch_number in FIRST_RETAINED_L1_BATCH.0..first_l1_batch_with_proofs.0 {
+            let error = client
+                .get_proof(address, keys.clone(), L1BatchNumber(l1_batch_number))
+                .await
+                .unwrap_err();
+            assert_pruned_proofs_error(&error, first_l1_batch_with_proofs);
+        }
+
+        // The first L1 batch with proofs is served.
+        let proof = client
+            .get_proof(address, keys.clone(), first_l1_batch_with_proofs)
+            .await?
+            .context("no proof for the first L1 batch with proofs")?;
+        assert_eq!(proof.address, address);
+        assert_eq!(proof.storage_proof.len(), keys.len());
+
+        // L1 batches not yet processed by the tree are not pruned.
+        let proof = client.get_proof(address, keys, NEXT_TREE_L1_BATCH).await?;
+        assert!(proof.is_none(), "{proof:?}");
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_proofs_for_l1_batches_pruned_in_postgres() {
+    test_http_server(PrunedProofsTest {
+        min_tree_l1_batch: L1BatchNumber(0),
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn getting_proofs_for_l1_batches_pruned_in_tree() {
+    test_http_server(PrunedProofsTest {
+        min_tree_l1_batch: FIRST_RETAINED_L1_BATCH + 3,
+    })
+    .await;
+}
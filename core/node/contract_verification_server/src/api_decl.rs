@@ -50,6 +50,10 @@ impl RestApi {
                 "/contract_verification/info/:address",
                 axum::routing::get(Self::verification_info),
             )
+            .route(
+                "/api",
+                axum::routing::get(Self::etherscan_api).post(Self::etherscan_api_form),
+            )
             .layer(CorsLayer::permissive())
             .with_state(Arc::new(self))
     }
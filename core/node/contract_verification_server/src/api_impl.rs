@@ -11,7 +11,7 @@ use zksync_types::{contract_verification_api::VerificationIncomingRequest, Addre
 
 use super::{api_decl::RestApi, metrics::METRICS};
 
-fn ok_json(data: impl Serialize) -> Response<String> {
+pub(super) fn ok_json(data: impl Serialize) -> Response<String> {
     Response::builder()
         .status(axum::http::StatusCode::OK)
         .body(serde_json::to_string(&data).expect("Failed to serialize"))
@@ -34,7 +34,7 @@ fn not_found() -> Response<String> {
 
 impl RestApi {
     #[tracing::instrument(skip(query))]
-    fn validate_contract_verification_query(
+    pub(super) fn validate_contract_verification_query(
         query: &VerificationIncomingRequest,
     ) -> Result<(), Response<String>> {
         if query.source_code_data.compiler_type() != query.compiler_versions.compiler_type() {
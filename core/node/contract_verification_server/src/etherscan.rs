@@ -0,0 +1,521 @@
+//! Etherscan-compatible facade for the contract verification API, so that tools speaking the Etherscan API
+//! (e.g., `forge verify-contract --verifier etherscan` or `hardhat-verify`) can be used with our chain.
+
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    extract::{Query, State},
+    response::Response,
+    Form,
+};
+use serde::Serialize;
+use zksync_dal::CoreDal;
+use zksync_types::{
+    contract_verification_api::{
+        CompilerVersions, SourceCodeData, VerificationIncomingRequest, VerificationInfo,
+        VerificationMatch, VerificationRequestStatus,
+    },
+    Address,
+};
+
+use super::{api_decl::RestApi, api_impl::ok_json, metrics::METRICS};
+
+/// Value of `ABI` returned for contracts without verified sources. Tools rely on this exact message.
+const NOT_VERIFIED_MESSAGE: &str = "Contract source code not verified";
+
+/// Response envelope used by all Etherscan API methods.
+#[derive(Debug, Serialize)]
+struct EtherscanResponse<T> {
+    status: &'static str,
+    message: &'static str,
+    result: T,
+}
+
+fn etherscan_ok(result: impl Serialize) -> Response<String> {
+    ok_json(EtherscanResponse {
+        status: "1",
+        message: "OK",
+        result,
+    })
+}
+
+fn etherscan_error(result: impl Into<String>) -> Response<String> {
+    ok_json(EtherscanResponse {
+        status: "0",
+        message: "NOTOK",
+        result: result.into(),
+    })
+}
+
+/// Item returned by the `getsourcecode` action.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct EtherscanSourceCode {
+    source_code: String,
+    #[serde(rename = "ABI")]
+    abi: String,
+    contract_name: String,
+    compiler_version: String,
+    optimization_used: String,
+    runs: String,
+    constructor_arguments: String,
+    #[serde(rename = "EVMVersion")]
+    evm_version: String,
+    library: String,
+    license_type: String,
+    proxy: String,
+    implementation: String,
+    swarm_source: String,
+    /// Non-standard field distinguishing full and partial matches.
+    match_type: String,
+}
+
+impl EtherscanSourceCode {
+    fn not_verified() -> Self {
+        Self {
+            abi: NOT_VERIFIED_MESSAGE.to_owned(),
+            proxy: "0".to_owned(),
+            ..Self::default()
+        }
+    }
+
+    fn new(info: VerificationInfo) -> Self {
+        let req = info.request.req;
+        let source_code = match req.source_code_data {
+            SourceCodeData::SolSingleFile(code) | SourceCodeData::YulSingleFile(code) => code,
+            SourceCodeData::StandardJsonInput(input) => {
+                // Etherscan wraps standard JSON input in double braces.
+                format!("{{{}}}", serde_json::Value::Object(input))
+            }
+            SourceCodeData::VyperMultiFile(sources) => {
+                serde_json::to_string(&sources).expect("failed serializing sources")
+            }
+        };
+        Self {
+            source_code,
+            abi: info.artifacts.abi.to_string(),
+            contract_name: req.contract_name,
+            compiler_version: req.compiler_versions.compiler_version(),
+            optimization_used: if req.optimization_used { "1" } else { "0" }.to_owned(),
+            constructor_arguments: hex::encode(&req.constructor_arguments.0),
+            proxy: "0".to_owned(),
+            match_type: info.match_type.as_str().to_owned(),
+            ..Self::default()
+        }
+    }
+}
+
+/// Parameters of Etherscan API calls. Keys are lowercased, since tools are inconsistent in their casing.
+#[derive(Debug)]
+struct EtherscanParams(HashMap<String, String>);
+
+impl EtherscanParams {
+    fn new(params: impl IntoIterator<Item = (String, String)>) -> Self {
+        Self(
+            params
+                .into_iter()
+                .map(|(key, value)| (key.to_ascii_lowercase(), value))
+                .collect(),
+        )
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0
+            .get(key)
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    }
+
+    fn require(&self, key: &str) -> Result<&str, String> {
+        self.get(key)
+            .ok_or_else(|| format!("Missing or empty `{key}` parameter"))
+    }
+
+    fn address(&self, key: &str) -> Result<Address, String> {
+        let address = self.require(key)?;
+        address
+            .parse()
+            .map_err(|_| format!("Invalid `{key}` parameter: {address}"))
+    }
+}
+
+/// Converts an Etherscan compiler version (e.g., `v0.8.24+commit.e11b9ed9`) to the format used by the verifier.
+fn normalize_compiler_version(version: &str) -> String {
+    if version.starts_with("zkVM-") {
+        return version.to_owned();
+    }
+    let version = version.strip_prefix('v').unwrap_or(version);
+    let version = version
+        .split_once('+')
+        .map_or(version, |(version, _)| version);
+    version.to_owned()
+}
+
+/// Converts a zk compiler version to the `v`-prefixed format used by the verifier.
+fn normalize_zk_compiler_version(version: &str) -> String {
+    if version.starts_with('v') {
+        version.to_owned()
+    } else {
+        format!("v{version}")
+    }
+}
+
+/// Returns the latest version from the list, comparing versions component-wise.
+fn latest_version(versions: Vec<String>) -> Option<String> {
+    versions.into_iter().max_by_key(|version| {
+        version
+            .trim_start_matches('v')
+            .split(|ch: char| !ch.is_ascii_digit())
+            .map(|part| part.parse::<u64>().unwrap_or(0))
+            .collect::<Vec<_>>()
+    })
+}
+
+/// Translates a `verifysourcecode` call into a verification request. If the zk compiler version
+/// is not specified, `default_zk_compiler_version` is used.
+fn parse_verification_request(
+    params: &EtherscanParams,
+    default_zk_compiler_version: Option<String>,
+) -> Result<VerificationIncomingRequest, String> {
+    let contract_address = params.address("contractaddress")?;
+    let source_code = params.require("sourcecode")?;
+    let code_format = params.get("codeformat").unwrap_or("solidity-single-file");
+    let is_vyper = code_format.starts_with("vyper");
+
+    let source_code_data = match code_format {
+        "solidity-single-file" => SourceCodeData::SolSingleFile(source_code.to_owned()),
+        "solidity-standard-json-input" => {
+            let input: serde_json::Value = serde_json::from_str(source_code)
+                .map_err(|err| format!("Invalid standard JSON input: {err}"))?;
+            match input {
+                serde_json::Value::Object(input) => SourceCodeData::StandardJsonInput(input),
+                _ => return Err("Standard JSON input must be an object".to_owned()),
+            }
+        }
+        "vyper-json" | "vyper-multi-file" => {
+            let input: serde_json::Value = serde_json::from_str(source_code)
+                .map_err(|err| format!("Invalid Vyper sources: {err}"))?;
+            let sources = input.get("sources").unwrap_or(&input);
+            let sources = sources
+                .as_object()
+                .ok_or("Vyper sources must be an object")?
+                .iter()
+                .map(|(path, source)| {
+                    let content = source
+                        .get("content")
+                        .unwrap_or(source)
+                        .as_str()
+                        .ok_or_else(|| format!("Invalid content for Vyper source `{path}`"))?;
+                    Ok((path.clone(), content.to_owned()))
+                })
+                .collect::<Result<_, String>>()?;
+            SourceCodeData::VyperMultiFile(sources)
+        }
+        _ => return Err(format!("Unsupported `codeformat`: {code_format}")),
+    };
+
+    let compiler_version = normalize_compiler_version(params.require("compilerversion")?);
+    let zk_compiler_key = if is_vyper {
+        "zkvyperversion"
+    } else {
+        "zksolcversion"
+    };
+    let zk_compiler_version = params
+        .get(zk_compiler_key)
+        .or_else(|| params.get("zkcompilerversion"))
+        .map(normalize_zk_compiler_version)
+        .or(default_zk_compiler_version)
+        .ok_or_else(|| format!("Missing `{zk_compiler_key}` parameter"))?;
+    let compiler_versions = if is_vyper {
+        CompilerVersions::Vyper {
+            compiler_zkvyper_version: zk_compiler_version,
+            compiler_vyper_version: compiler_version,
+        }
+    } else {
+        CompilerVersions::Solc {
+            compiler_zksolc_version: zk_compiler_version,
+            compiler_solc_version: compiler_version,
+        }
+    };
+
+    // Etherscan historically misspells this parameter; accept both spellings.
+    let constructor_arguments = params
+        .get("constructorarguements")
+        .or_else(|| params.get("constructorarguments"))
+        .unwrap_or_default();
+    let constructor_arguments = hex::decode(constructor_arguments.trim_start_matches("0x"))
+        .map_err(|err| format!("Invalid constructor arguments: {err}"))?;
+
+    Ok(VerificationIncomingRequest {
+        contract_address,
+        source_code_data,
+        contract_name: params.require("contractname")?.to_owned(),
+        compiler_versions,
+        optimization_used: matches!(params.get("optimizationused"), Some("1" | "true")),
+        optimizer_mode: params.get("optimizermode").map(str::to_owned),
+        constructor_arguments: constructor_arguments.into(),
+        is_system: matches!(params.get("enableeravmextensions"), Some("1" | "true")),
+        force_evmla: matches!(params.get("forceevmla"), Some("1" | "true")),
+        compiler_output: None,
+    })
+}
+
+/// Translates a verification request status into the `checkverifystatus` response.
+fn verification_status_response(status: VerificationRequestStatus) -> Response<String> {
+    match status.status.as_str() {
+        "successful" => etherscan_ok("Pass - Verified"),
+        "queued" | "in_progress" => etherscan_error("Pending in queue"),
+        _ => {
+            let mut message = "Fail - Unable to verify".to_owned();
+            if let Some(error) = status.error {
+                message = format!("{message}. {error}");
+            }
+            if let Some(errors) = status.compilation_errors {
+                message = format!("{message}: {}", errors.join("; "));
+            }
+            etherscan_error(message)
+        }
+    }
+}
+
+impl RestApi {
+    /// Handles Etherscan API calls with parameters passed in the query string.
+    #[tracing::instrument(skip(self_, query))]
+    pub async fn etherscan_api(
+        State(self_): State<Arc<Self>>,
+        Query(query): Query<HashMap<String, String>>,
+    ) -> Response<String> {
+        self_.etherscan_call(EtherscanParams::new(query)).await
+    }
+
+    /// Handles Etherscan API calls with parameters passed in the query string and / or a URL-encoded form.
+    #[tracing::instrument(skip(self_, query, form))]
+    pub async fn etherscan_api_form(
+        State(self_): State<Arc<Self>>,
+        Query(query): Query<HashMap<String, String>>,
+        Form(form): Form<HashMap<String, String>>,
+    ) -> Response<String> {
+        self_
+            .etherscan_call(EtherscanParams::new(query.into_iter().chain(form)))
+            .await
+    }
+
+    async fn etherscan_call(&self, params: EtherscanParams) -> Response<String> {
+        if params.get("module") != Some("contract") {
+            return etherscan_error("Only the `contract` module is supported");
+        }
+        let method = match params.get("action") {
+            Some("verifysourcecode") => "etherscan_verifysourcecode",
+            Some("checkverifystatus") => "etherscan_checkverifystatus",
+            Some("getsourcecode") => "etherscan_getsourcecode",
+            Some("getabi") => "etherscan_getabi",
+            action => {
+                return etherscan_error(format!("Unsupported action: {}", action.unwrap_or("")));
+            }
+        };
+
+        let method_latency = METRICS.call[&method].start();
+        let result = match method {
+            "etherscan_verifysourcecode" => self.etherscan_verify_source_code(&params).await,
+            "etherscan_checkverifystatus" => self.etherscan_check_verify_status(&params).await,
+            "etherscan_getsourcecode" => self.etherscan_get_source_code(&params).await,
+            _ => self.etherscan_get_abi(&params).await,
+        };
+        method_latency.observe();
+        result.unwrap_or_else(etherscan_error)
+    }
+
+    async fn etherscan_verify_source_code(
+        &self,
+        params: &EtherscanParams,
+    ) -> Result<Response<String>, String> {
+        let mut storage = self
+            .master_connection_pool
+            .connection_tagged("api")
+            .await
+            .unwrap();
+        let is_vyper = params
+            .get("codeformat")
+            .map_or(false, |format| format.starts_with("vyper"));
+        let zk_compiler_versions = if is_vyper {
+            storage
+                .contract_verification_dal()
+                .get_zkvyper_versions()
+                .await
+        } else {
+            storage
+                .contract_verification_dal()
+                .get_zksolc_versions()
+                .await
+        };
+        let default_zk_compiler_version = latest_version(zk_compiler_versions.unwrap());
+        let request = parse_verification_request(params, default_zk_compiler_version)?;
+        Self::validate_contract_verification_query(&request).map_err(|res| res.into_body())?;
+
+        if !storage
+            .storage_logs_dal()
+            .is_contract_deployed_at_address(request.contract_address)
+            .await
+        {
+            return Err("There is no deployed contract on this address".to_owned());
+        }
+        let existing_info = storage
+            .contract_verification_dal()
+            .get_contract_verification_info(request.contract_address)
+            .await
+            .unwrap();
+        if existing_info.map_or(false, |info| info.match_type == VerificationMatch::Full) {
+            return Err("Contract source code already verified".to_owned());
+        }
+
+        let request_id = storage
+            .contract_verification_dal()
+            .add_contract_verification_request(request)
+            .await
+            .unwrap();
+        // Etherscan returns a GUID for the submission; we use the verification request ID.
+        Ok(etherscan_ok(request_id.to_string()))
+    }
+
+    async fn etherscan_check_verify_status(
+        &self,
+        params: &EtherscanParams,
+    ) -> Result<Response<String>, String> {
+        let guid = params.require("guid")?;
+        let id = guid
+            .parse::<usize>()
+            .map_err(|_| format!("Invalid `guid` parameter: {guid}"))?;
+        let status = self
+            .replica_connection_pool
+            .connection_tagged("api")
+            .await
+            .unwrap()
+            .contract_verification_dal()
+            .get_verification_request_status(id)
+            .await
+            .unwrap()
+            .ok_or("Unknown GUID")?;
+        Ok(verification_status_response(status))
+    }
+
+    async fn get_verification_info(&self, address: Address) -> Option<VerificationInfo> {
+        self.replica_connection_pool
+            .connection_tagged("api")
+            .await
+            .unwrap()
+            .contract_verification_dal()
+            .get_contract_verification_info(address)
+            .await
+            .unwrap()
+    }
+
+    async fn etherscan_get_source_code(
+        &self,
+        params: &EtherscanParams,
+    ) -> Result<Response<String>, String> {
+        let address = params.address("address")?;
+        let source_code = match self.get_verification_info(address).await {
+            Some(info) => EtherscanSourceCode::new(info),
+            None => EtherscanSourceCode::not_verified(),
+        };
+        Ok(etherscan_ok([source_code]))
+    }
+
+    async fn etherscan_get_abi(
+        &self,
+        params: &EtherscanParams,
+    ) -> Result<Response<String>, String> {
+        let address = params.address("address")?;
+        let info = self
+            .get_verification_info(address)
+            .await
+            .ok_or(NOT_VERIFIED_MESSAGE)?;
+        Ok(etherscan_ok(info.artifacts.abi.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(pairs: &[(&str, &str)]) -> EtherscanParams {
+        EtherscanParams::new(
+            pairs
+                .iter()
+                .map(|&(key, value)| (key.to_owned(), value.to_owned())),
+        )
+    }
+
+    #[test]
+    fn normalizing_compiler_versions() {
+        assert_eq!(
+            normalize_compiler_version("v0.8.24+commit.e11b9ed9"),
+            "0.8.24"
+        );
+        assert_eq!(normalize_compiler_version("0.8.24"), "0.8.24");
+        assert_eq!(
+            normalize_compiler_version("zkVM-0.8.24-1.0.0"),
+            "zkVM-0.8.24-1.0.0"
+        );
+        assert_eq!(normalize_zk_compiler_version("1.5.0"), "v1.5.0");
+        assert_eq!(
+            latest_version(vec!["v1.3.9".into(), "v1.5.0".into(), "v1.3.21".into()]),
+            Some("v1.5.0".to_owned())
+        );
+    }
+
+    #[test]
+    fn parsing_single_file_request() {
+        let params = params(&[
+            (
+                "contractaddress",
+                "0x000000000000000000000000000000000000800a",
+            ),
+            ("sourceCode", "contract Counter {}"),
+            ("codeformat", "solidity-single-file"),
+            ("contractname", "Counter"),
+            ("compilerversion", "v0.8.24+commit.e11b9ed9"),
+            ("optimizationUsed", "1"),
+            ("constructorArguements", "0x0102"),
+        ]);
+        let request = parse_verification_request(&params, Some("v1.5.0".to_owned())).unwrap();
+
+        assert_eq!(request.contract_address, Address::from_low_u64_be(0x800a));
+        assert!(matches!(
+            request.source_code_data,
+            SourceCodeData::SolSingleFile(ref code) if code == "contract Counter {}"
+        ));
+        assert_eq!(request.contract_name, "Counter");
+        assert_eq!(request.compiler_versions.compiler_version(), "0.8.24");
+        assert_eq!(request.compiler_versions.zk_compiler_version(), "v1.5.0");
+        assert!(request.optimization_used);
+        assert_eq!(request.constructor_arguments.0, [1, 2]);
+    }
+
+    #[test]
+    fn parsing_standard_json_request() {
+        let params = params(&[
+            (
+                "contractaddress",
+                "0x000000000000000000000000000000000000800a",
+            ),
+            ("sourceCode", r#"{"language":"Solidity","sources":{}}"#),
+            ("codeformat", "solidity-standard-json-input"),
+            ("contractname", "contracts/Counter.sol:Counter"),
+            ("compilerversion", "v0.8.24+commit.e11b9ed9"),
+            ("zksolcVersion", "v1.4.1"),
+        ]);
+        let request = parse_verification_request(&params, None).unwrap();
+        assert!(matches!(
+            request.source_code_data,
+            SourceCodeData::StandardJsonInput(_)
+        ));
+        assert_eq!(request.compiler_versions.zk_compiler_version(), "v1.4.1");
+        assert!(!request.optimization_used);
+
+        let mut params = params;
+        params.0.remove("zksolcversion");
+        let err = parse_verification_request(&params, None).unwrap_err();
+        assert!(err.contains("zksolcversion"), "{err}");
+    }
+}
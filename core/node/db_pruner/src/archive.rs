@@ -0,0 +1,114 @@
+//! Archiving of pruned data to the object store.
+
+use std::{ops, sync::Arc};
+
+use anyhow::Context as _;
+use zksync_dal::{Connection, Core, CoreDal};
+use zksync_object_store::ObjectStore;
+use zksync_types::{
+    api,
+    pruning::{ArchivedL1Batch, ArchivedL2Block},
+    L1BatchNumber, L2BlockNumber, L2ChainId,
+};
+
+use crate::metrics::METRICS;
+
+/// Archives L2 blocks, transactions, receipts and events to the object store before they are hard-pruned,
+/// so that the API server can serve them for pruned blocks.
+#[derive(Debug, Clone)]
+pub struct PrunedDataArchiver {
+    blob_store: Arc<dyn ObjectStore>,
+    l2_chain_id: L2ChainId,
+}
+
+impl PrunedDataArchiver {
+    pub fn new(blob_store: Arc<dyn ObjectStore>, l2_chain_id: L2ChainId) -> Self {
+        Self {
+            blob_store,
+            l2_chain_id,
+        }
+    }
+
+    /// Archives all L1 batches in the specified range and records them in Postgres. Should be called before hard pruning
+    /// outside the pruning DB transaction; archiving is idempotent, so it can be safely repeated.
+    pub(crate) async fn archive_l1_batches(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        l1_batches: ops::RangeInclusive<L1BatchNumber>,
+    ) -> anyhow::Result<()> {
+        for l1_batch_number in l1_batches.start().0..=l1_batches.end().0 {
+            let l1_batch_number = L1BatchNumber(l1_batch_number);
+            let latency = METRICS.archiving_latency.start();
+            let (archive, l2_blocks) = self.load_l1_batch(storage, l1_batch_number).await?;
+            let tx_hashes: Vec<_> = archive.transaction_hashes().collect();
+            self.blob_store
+                .put(l1_batch_number, &archive)
+                .await
+                .with_context(|| format!("failed archiving L1 batch #{l1_batch_number}"))?;
+            storage
+                .pruning_dal()
+                .insert_archived_l1_batch(l1_batch_number, l2_blocks, &tx_hashes)
+                .await?;
+
+            let latency = latency.observe();
+            METRICS.archived_l1_batches.inc();
+            tracing::debug!("Archived L1 batch #{l1_batch_number} in {latency:?}");
+        }
+        Ok(())
+    }
+
+    async fn load_l1_batch(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<(ArchivedL1Batch, ops::RangeInclusive<L2BlockNumber>)> {
+        let (first_l2_block, last_l2_block) = storage
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(l1_batch_number)
+            .await?
+            .with_context(|| format!("L1 batch #{l1_batch_number} has no L2 blocks"))?;
+
+        let mut l2_blocks = Vec::with_capacity((last_l2_block.0 - first_l2_block.0 + 1) as usize);
+        for l2_block_number in first_l2_block.0..=last_l2_block.0 {
+            let l2_block_number = L2BlockNumber(l2_block_number);
+            let block = storage
+                .blocks_web3_dal()
+                .get_api_block(l2_block_number)
+                .await?
+                .with_context(|| format!("L2 block #{l2_block_number} is missing in storage"))?;
+
+            let mut transactions = storage
+                .transactions_web3_dal()
+                .get_transactions(&block.transactions, self.l2_chain_id)
+                .await?;
+            anyhow::ensure!(
+                transactions.len() == block.transactions.len(),
+                "storage inconsistency: L2 block #{l2_block_number} has {} tx hashes, but only {} transactions",
+                block.transactions.len(),
+                transactions.len()
+            );
+            // `get_transactions()` returns transactions in an arbitrary order.
+            transactions.sort_unstable_by_key(|tx| tx.transaction_index);
+            let mut receipts = storage
+                .transactions_web3_dal()
+                .get_transaction_receipts(&block.transactions)
+                .await?;
+            receipts.sort_unstable_by_key(|receipt| receipt.transaction_index);
+
+            let transactions = transactions
+                .into_iter()
+                .map(api::TransactionVariant::Full)
+                .collect();
+            l2_blocks.push(ArchivedL2Block {
+                block: block.with_transactions(transactions),
+                receipts,
+            });
+        }
+
+        let archive = ArchivedL1Batch {
+            l1_batch_number,
+            l2_blocks,
+        };
+        Ok((archive, first_l2_block..=last_l2_block))
+    }
+}
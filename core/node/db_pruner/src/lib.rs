@@ -1,6 +1,7 @@
 //! Postgres pruning component.
 
 use std::{
+    collections::HashMap,
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -8,18 +9,24 @@ use std::{
 use anyhow::Context as _;
 use serde::{Deserialize, Serialize};
 use tokio::sync::watch;
-use zksync_dal::{pruning_dal::PruningInfo, Connection, ConnectionPool, Core, CoreDal};
+use zksync_dal::{
+    pruning_dal::{PrunedDataKind, PruningInfo},
+    Connection, ConnectionPool, Core, CoreDal,
+};
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_types::{L1BatchNumber, L2BlockNumber};
 
+pub use self::archive::PrunedDataArchiver;
 use self::{
     metrics::{ConditionOutcome, PruneType, METRICS},
     prune_conditions::{
-        ConsistencyCheckerProcessedBatch, L1BatchExistsCondition, L1BatchOlderThanPruneCondition,
-        NextL1BatchHasMetadataCondition, NextL1BatchWasExecutedCondition, PruneCondition,
+        ConsistencyCheckerProcessedBatch, DatabaseSizeExceedsBudgetCondition,
+        L1BatchExistsCondition, L1BatchOlderThanPruneCondition, NextL1BatchHasMetadataCondition,
+        NextL1BatchWasExecutedCondition, PruneCondition,
     },
 };
 
+mod archive;
 mod metrics;
 mod prune_conditions;
 #[cfg(test)]
@@ -36,6 +43,13 @@ pub struct DbPrunerConfig {
     /// Minimum age of an L1 batch in order for it to be eligible for pruning. Setting this to zero
     /// will effectively disable this pruning criterion.
     pub minimum_l1_batch_age: Duration,
+    /// If set, L1 batches will only be pruned while the estimated size of the database (in bytes) exceeds this budget.
+    /// Other criteria still apply; e.g., [`Self::minimum_l1_batch_age`] effectively becomes the minimum retention period.
+    pub database_size_budget: Option<u64>,
+    /// Minimum age of an L1 batch in order for data of a specific kind to be pruned. Allows retaining some kinds of data
+    /// (e.g., events) for longer than the rest of L1 batch data. Data is never pruned earlier than the rest of L1 batch data.
+    /// Data kinds not mentioned here are pruned together with the rest of L1 batch data.
+    pub data_retention: HashMap<PrunedDataKind, Duration>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -79,6 +93,7 @@ pub struct DbPruner {
     connection_pool: ConnectionPool<Core>,
     health_updater: HealthUpdater,
     prune_conditions: Vec<Arc<dyn PruneCondition>>,
+    archiver: Option<PrunedDataArchiver>,
 }
 
 impl DbPruner {
@@ -104,6 +119,12 @@ impl DbPruner {
                 pool: connection_pool.clone(),
             }));
         }
+        if let Some(budget) = config.database_size_budget {
+            conditions.push(Arc::new(DatabaseSizeExceedsBudgetCondition {
+                budget,
+                pool: connection_pool.clone(),
+            }));
+        }
 
         Self::with_conditions(config, connection_pool, conditions)
     }
@@ -118,9 +139,17 @@ impl DbPruner {
             connection_pool,
             health_updater: ReactiveHealthCheck::new("db_pruner").1,
             prune_conditions,
+            archiver: None,
         }
     }
 
+    /// Enables archiving L1 batch data (L2 blocks, transactions, receipts and events) to the object store
+    /// before it is hard-pruned.
+    pub fn with_archiver(mut self, archiver: PrunedDataArchiver) -> Self {
+        self.archiver = Some(archiver);
+        self
+    }
+
     pub fn health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
     }
@@ -224,12 +253,157 @@ impl DbPruner {
         Ok(true)
     }
 
+    /// Returns the minimum L1 batch age for data of the specified kind to be pruned, or `None` if the data
+    /// should be pruned together with the rest of L1 batch data.
+    fn data_retention(&self, kind: PrunedDataKind) -> Option<Duration> {
+        let retention = self.config.data_retention.get(&kind).copied();
+        if kind == PrunedDataKind::Events {
+            // Transaction receipts include events, so events are retained at least as long as transactions.
+            let transactions_retention = self
+                .config
+                .data_retention
+                .get(&PrunedDataKind::Transactions)
+                .copied();
+            return retention.max(transactions_retention);
+        }
+        retention
+    }
+
+    /// Soft-prunes kinds of data pruned separately from the rest of L1 batch data. Returns `true` if there is data
+    /// to hard-prune.
+    async fn soft_prune_data_kinds(
+        &self,
+        storage: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<bool> {
+        let mut transaction = storage.start_transaction().await?;
+        let main_pruning_info = transaction.pruning_dal().get_pruning_info().await?;
+        let main_soft_pruned_l1_batch = main_pruning_info.last_soft_pruned_l1_batch;
+
+        let mut has_data_to_hard_prune = false;
+        // Transaction receipts include events, so events are never pruned before transactions. Thus, transactions
+        // are processed first.
+        let mut max_events_l1_batch_to_prune = main_soft_pruned_l1_batch;
+        for kind in [PrunedDataKind::Transactions, PrunedDataKind::Events] {
+            let mut dal = transaction.pruning_dal();
+            let pruning_info = match (
+                dal.get_data_kind_pruning_info(kind).await?,
+                self.data_retention(kind),
+            ) {
+                (None, None) => continue,
+                (None, Some(_)) => {
+                    tracing::info!("Starting pruning {kind:?} separately from the rest of data");
+                    dal.start_pruning_data_kind_separately(kind).await?;
+                    main_pruning_info
+                }
+                (Some(info), None) if info == main_pruning_info => {
+                    tracing::info!("Pruning of {kind:?} has caught up with the rest of data");
+                    dal.stop_pruning_data_kind_separately(kind).await?;
+                    continue;
+                }
+                (Some(info), _) => info,
+            };
+
+            let max_l1_batch_to_prune = match kind {
+                PrunedDataKind::Transactions => main_soft_pruned_l1_batch,
+                PrunedDataKind::Events => max_events_l1_batch_to_prune,
+            };
+            let last_soft_pruned_l1_batch = self
+                .soft_prune_data_kind(&mut transaction, kind, &pruning_info, max_l1_batch_to_prune)
+                .await?;
+            if kind == PrunedDataKind::Transactions {
+                max_events_l1_batch_to_prune =
+                    max_events_l1_batch_to_prune.min(last_soft_pruned_l1_batch);
+            }
+            has_data_to_hard_prune |=
+                last_soft_pruned_l1_batch != pruning_info.last_hard_pruned_l1_batch;
+        }
+
+        transaction.commit().await?;
+        Ok(has_data_to_hard_prune)
+    }
+
+    /// Soft-prunes the next chunk of data of the specified kind if possible. Returns the last soft-pruned L1 batch
+    /// for this data kind.
+    async fn soft_prune_data_kind(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        kind: PrunedDataKind,
+        pruning_info: &PruningInfo,
+        max_l1_batch_to_prune: Option<L1BatchNumber>,
+    ) -> anyhow::Result<Option<L1BatchNumber>> {
+        let last_soft_pruned_l1_batch = pruning_info.last_soft_pruned_l1_batch;
+        let Some(max_l1_batch_to_prune) = max_l1_batch_to_prune else {
+            return Ok(last_soft_pruned_l1_batch);
+        };
+        let next_l1_batch_to_prune = L1BatchNumber(
+            last_soft_pruned_l1_batch.unwrap_or(L1BatchNumber(0)).0
+                + self.config.pruned_batch_chunk_size,
+        )
+        .min(max_l1_batch_to_prune);
+        if last_soft_pruned_l1_batch >= Some(next_l1_batch_to_prune)
+            || !self
+                .is_data_prunable(next_l1_batch_to_prune, self.data_retention(kind))
+                .await?
+        {
+            return Ok(last_soft_pruned_l1_batch);
+        }
+
+        let (_, next_l2_block_to_prune) = storage
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(next_l1_batch_to_prune)
+            .await?
+            .with_context(|| format!("L1 batch #{next_l1_batch_to_prune} is ready to be pruned, but has no L2 blocks"))?;
+        storage
+            .pruning_dal()
+            .soft_prune_data_kind(kind, next_l1_batch_to_prune, next_l2_block_to_prune)
+            .await?;
+        tracing::info!(
+            "Soft pruned {kind:?} up to L1 batch {next_l1_batch_to_prune} and L2 block {next_l2_block_to_prune}"
+        );
+        Ok(Some(next_l1_batch_to_prune))
+    }
+
+    async fn is_data_prunable(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        retention: Option<Duration>,
+    ) -> anyhow::Result<bool> {
+        let Some(minimum_age) = retention else {
+            return Ok(true); // Data is catching up with the rest of pruned data
+        };
+        let condition = L1BatchOlderThanPruneCondition {
+            minimum_age,
+            pool: self.connection_pool.clone(),
+        };
+        let is_prunable = condition.is_batch_prunable(l1_batch_number).await?;
+        METRICS.observe_condition(
+            &condition,
+            if is_prunable {
+                ConditionOutcome::Success
+            } else {
+                ConditionOutcome::Fail
+            },
+        );
+        Ok(is_prunable)
+    }
+
     async fn hard_prune(
         &self,
         storage: &mut Connection<'_, Core>,
         stop_receiver: &mut watch::Receiver<bool>,
     ) -> anyhow::Result<PruningIterationOutcome> {
         let latency = METRICS.pruning_chunk_duration[&PruneType::Hard].start();
+        // Data is archived before starting the pruning transaction, so that the transaction isn't held open
+        // during object store uploads. Archiving is idempotent, so it's safe to repeat it if hard pruning fails.
+        let pruning_info = storage.pruning_dal().get_pruning_info().await?;
+        tokio::select! {
+            result = self.archive_l1_batches_to_prune(storage, &pruning_info) => result?,
+            _ = stop_receiver.changed() => {
+                tracing::info!("Archiving pruned data interrupted");
+                return Ok(PruningIterationOutcome::Interrupted);
+            }
+        }
+
         let mut transaction = storage.start_transaction().await?;
 
         let mut current_pruning_info = transaction.pruning_dal().get_pruning_info().await?;
@@ -241,13 +415,20 @@ impl DbPruner {
             current_pruning_info.last_soft_pruned_l2_block.with_context(|| {
                 format!("bogus pruning info {current_pruning_info:?}: trying to hard-prune data, but there is no soft-pruned L2 block")
             })?;
+        // If the main data is already hard-pruned, only separately pruned data kinds need to be hard-pruned.
+        let is_main_data_pruned =
+            current_pruning_info.last_hard_pruned_l1_batch == Some(last_soft_pruned_l1_batch);
 
         let mut dal = transaction.pruning_dal();
         let stats = tokio::select! {
-            result = dal.hard_prune_batches_range(
-                last_soft_pruned_l1_batch,
-                last_soft_pruned_l2_block,
-            ) => result?,
+            result = async {
+                if is_main_data_pruned {
+                    dal.hard_prune_data_kinds().await
+                } else {
+                    dal.hard_prune_batches_range(last_soft_pruned_l1_batch, last_soft_pruned_l2_block)
+                        .await
+                }
+            } => result?,
 
             _ = stop_receiver.changed() => {
                 // `hard_prune_batches_range()` can take a long time. It looks better to roll back it explicitly here if a node is getting shut down
@@ -271,6 +452,29 @@ impl DbPruner {
         Ok(PruningIterationOutcome::Pruned)
     }
 
+    /// Archives soft-pruned L1 batches that are not hard-pruned yet if the archiver is configured.
+    async fn archive_l1_batches_to_prune(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        pruning_info: &PruningInfo,
+    ) -> anyhow::Result<()> {
+        let Some(archiver) = &self.archiver else {
+            return Ok(());
+        };
+        let Some(last_soft_pruned_l1_batch) = pruning_info.last_soft_pruned_l1_batch else {
+            return Ok(());
+        };
+        if pruning_info.last_hard_pruned_l1_batch == Some(last_soft_pruned_l1_batch) {
+            return Ok(());
+        }
+        let first_l1_batch_to_prune = pruning_info
+            .last_hard_pruned_l1_batch
+            .map_or(L1BatchNumber(0), |number| number + 1);
+        archiver
+            .archive_l1_batches(storage, first_l1_batch_to_prune..=last_soft_pruned_l1_batch)
+            .await
+    }
+
     async fn run_single_iteration(
         &self,
         stop_receiver: &mut watch::Receiver<bool>,
@@ -283,8 +487,11 @@ impl DbPruner {
         if current_pruning_info.last_soft_pruned_l1_batch
             == current_pruning_info.last_hard_pruned_l1_batch
         {
+            // Separately pruned data kinds are processed first, so that newly configured kinds are excluded
+            // from pruning the rest of L1 batch data right away.
+            let data_pruning_done = self.soft_prune_data_kinds(&mut storage).await?;
             let pruning_done = self.soft_prune(&mut storage).await?;
-            if !pruning_done {
+            if !pruning_done && !data_pruning_done {
                 return Ok(PruningIterationOutcome::NoOp);
             }
         }
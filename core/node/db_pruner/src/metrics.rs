@@ -53,6 +53,14 @@ pub(super) struct DbPrunerMetrics {
     pub pruning_chunk_duration: Family<PruneType, Histogram<Duration>>,
     /// Number of not-pruned L1 batches.
     pub not_pruned_l1_batches_count: Gauge<u64>,
+    /// Estimated size of the database. Only reported if the database size budget is configured.
+    #[metrics(unit = Unit::Bytes)]
+    pub estimated_database_size: Gauge<u64>,
+    /// Latency of archiving a single L1 batch to the object store.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub archiving_latency: Histogram<Duration>,
+    /// Number of L1 batches archived to the object store.
+    pub archived_l1_batches: Counter,
     /// Number of entities deleted during a single hard pruning iteration, grouped by entity type.
     #[metrics(buckets = ENTITY_COUNT_BUCKETS)]
     deleted_entities: Family<PrunedEntityType, Histogram<u64>>,
@@ -5,6 +5,8 @@ use chrono::Utc;
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_types::L1BatchNumber;
 
+use crate::metrics::METRICS;
+
 #[async_trait]
 pub(crate) trait PruneCondition: fmt::Debug + fmt::Display + Send + Sync + 'static {
     fn metric_label(&self) -> &'static str;
@@ -43,6 +45,37 @@ impl PruneCondition for L1BatchOlderThanPruneCondition {
     }
 }
 
+#[derive(Debug)]
+pub(super) struct DatabaseSizeExceedsBudgetCondition {
+    /// Budget in bytes.
+    pub budget: u64,
+    pub pool: ConnectionPool<Core>,
+}
+
+impl fmt::Display for DatabaseSizeExceedsBudgetCondition {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "estimated database size exceeds {} bytes",
+            self.budget
+        )
+    }
+}
+
+#[async_trait]
+impl PruneCondition for DatabaseSizeExceedsBudgetCondition {
+    fn metric_label(&self) -> &'static str {
+        "database_size_exceeds_budget"
+    }
+
+    async fn is_batch_prunable(&self, _l1_batch_number: L1BatchNumber) -> anyhow::Result<bool> {
+        let mut storage = self.pool.connection_tagged("db_pruner").await?;
+        let estimated_size = storage.system_dal().estimate_database_size().await?;
+        METRICS.estimated_database_size.set(estimated_size);
+        Ok(estimated_size > self.budget)
+    }
+}
+
 #[derive(Debug)]
 pub(super) struct NextL1BatchWasExecutedCondition {
     pub pool: ConnectionPool<Core>,
@@ -11,9 +11,12 @@ use zksync_node_test_utils::{
     create_l1_batch, create_l1_batch_metadata, create_l2_block,
     l1_batch_metadata_to_commitment_artifacts,
 };
+use zksync_object_store::MockObjectStore;
 use zksync_types::{
-    aggregated_operations::AggregatedActionType, block::L2BlockHeader, Address, L2BlockNumber,
-    ProtocolVersion, H256,
+    aggregated_operations::AggregatedActionType,
+    block::L2BlockHeader,
+    pruning::{ArchivedL1Batch, ArchivedL2Block},
+    Address, L2BlockNumber, L2ChainId, ProtocolVersion, H256,
 };
 
 use super::*;
@@ -79,6 +82,8 @@ async fn is_l1_batch_prunable_works() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 1,
             minimum_l1_batch_age: Duration::ZERO,
+            database_size_budget: None,
+            data_retention: HashMap::new(),
         },
         ConnectionPool::test_pool().await,
         vec![failing_check, other_failing_check],
@@ -152,6 +157,8 @@ async fn hard_pruning_ignores_conditions_checks() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 5,
             minimum_l1_batch_age: Duration::ZERO,
+            database_size_budget: None,
+            data_retention: HashMap::new(),
         },
         pool.clone(),
         vec![nothing_prunable_check],
@@ -191,6 +198,8 @@ async fn pruner_catches_up_with_hard_pruning_up_to_soft_pruning_boundary_ignorin
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 5,
             minimum_l1_batch_age: Duration::ZERO,
+            database_size_budget: None,
+            data_retention: HashMap::new(),
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -239,6 +248,8 @@ async fn unconstrained_pruner_with_fresh_database() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            database_size_budget: None,
+            data_retention: HashMap::new(),
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -289,6 +300,8 @@ async fn pruning_blocked_after_first_chunk() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            database_size_budget: None,
+            data_retention: HashMap::new(),
         },
         pool.clone(),
         vec![first_chunk_prunable_check],
@@ -326,6 +339,112 @@ async fn pruning_blocked_after_first_chunk() {
     );
 }
 
+#[test(tokio::test)]
+async fn pruner_with_separately_pruned_events() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    insert_l2_blocks(&mut conn, 10, 2).await;
+
+    let pruner = DbPruner::with_conditions(
+        DbPrunerConfig {
+            removal_delay: Duration::ZERO,
+            pruned_batch_chunk_size: 3,
+            minimum_l1_batch_age: Duration::ZERO,
+            database_size_budget: None,
+            data_retention: HashMap::from([(PrunedDataKind::Events, Duration::from_secs(3_600))]),
+        },
+        pool.clone(),
+        vec![], //No checks, so every batch is prunable
+    );
+    let (_stop_sender, mut stop_receiver) = watch::channel(false);
+    pruner
+        .run_single_iteration(&mut stop_receiver)
+        .await
+        .unwrap();
+
+    let expected_pruning_info = PruningInfo {
+        last_soft_pruned_l1_batch: Some(L1BatchNumber(3)),
+        last_soft_pruned_l2_block: Some(L2BlockNumber(7)),
+        last_hard_pruned_l1_batch: Some(L1BatchNumber(3)),
+        last_hard_pruned_l2_block: Some(L2BlockNumber(7)),
+    };
+    assert_eq!(
+        conn.pruning_dal().get_pruning_info().await.unwrap(),
+        expected_pruning_info
+    );
+    // Events are not pruned since L1 batches are not old enough (in fact, they don't have headers at all).
+    assert_eq!(
+        conn.pruning_dal()
+            .get_data_kind_pruning_info(PrunedDataKind::Events)
+            .await
+            .unwrap(),
+        Some(PruningInfo::default())
+    );
+    assert_eq!(
+        conn.pruning_dal()
+            .get_data_kind_pruning_info(PrunedDataKind::Transactions)
+            .await
+            .unwrap(),
+        None
+    );
+    // L2 blocks are retained since they are required to access events.
+    let l2_block_header = conn
+        .blocks_dal()
+        .get_l2_block_header(L2BlockNumber(0))
+        .await
+        .unwrap();
+    assert!(l2_block_header.is_some());
+
+    // Disable separate pruning for events. Events should catch up with the rest of pruned data.
+    let nothing_prunable_check = Arc::new(ConditionMock::name("nothing prunable"));
+    let pruner = DbPruner::with_conditions(
+        DbPrunerConfig {
+            removal_delay: Duration::ZERO,
+            pruned_batch_chunk_size: 3,
+            minimum_l1_batch_age: Duration::ZERO,
+            database_size_budget: None,
+            data_retention: HashMap::new(),
+        },
+        pool.clone(),
+        vec![nothing_prunable_check],
+    );
+    let outcome = pruner
+        .run_single_iteration(&mut stop_receiver)
+        .await
+        .unwrap();
+    assert_matches!(outcome, PruningIterationOutcome::Pruned);
+    assert_eq!(
+        conn.pruning_dal()
+            .get_data_kind_pruning_info(PrunedDataKind::Events)
+            .await
+            .unwrap(),
+        Some(expected_pruning_info)
+    );
+    let l2_block_header = conn
+        .blocks_dal()
+        .get_l2_block_header(L2BlockNumber(0))
+        .await
+        .unwrap();
+    assert!(l2_block_header.is_none());
+
+    let outcome = pruner
+        .run_single_iteration(&mut stop_receiver)
+        .await
+        .unwrap();
+    assert_matches!(outcome, PruningIterationOutcome::NoOp);
+    assert_eq!(
+        conn.pruning_dal()
+            .get_data_kind_pruning_info(PrunedDataKind::Events)
+            .await
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        conn.pruning_dal().get_pruning_info().await.unwrap(),
+        expected_pruning_info
+    );
+}
+
 #[tokio::test]
 async fn pruner_is_resistant_to_errors() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -339,6 +458,8 @@ async fn pruner_is_resistant_to_errors() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            database_size_budget: None,
+            data_retention: HashMap::new(),
         },
         pool.clone(),
         vec![erroneous_condition],
@@ -496,6 +617,91 @@ async fn real_conditions_work_as_expected() {
     );
 }
 
+#[tokio::test]
+async fn database_size_condition() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+
+    let condition = DatabaseSizeExceedsBudgetCondition {
+        budget: 0,
+        pool: pool.clone(),
+    };
+    assert!(condition.is_batch_prunable(L1BatchNumber(0)).await.unwrap());
+    let condition = DatabaseSizeExceedsBudgetCondition {
+        budget: u64::MAX,
+        pool: pool.clone(),
+    };
+    assert!(!condition.is_batch_prunable(L1BatchNumber(0)).await.unwrap());
+}
+
+#[tokio::test]
+async fn pruned_data_is_archived() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    insert_l2_blocks(&mut conn, 10, 2).await;
+
+    let blob_store = MockObjectStore::arc();
+    let archiver = PrunedDataArchiver::new(blob_store.clone(), L2ChainId::default());
+    let pruner = DbPruner::with_conditions(
+        DbPrunerConfig {
+            removal_delay: Duration::ZERO,
+            pruned_batch_chunk_size: 3,
+            minimum_l1_batch_age: Duration::ZERO,
+            database_size_budget: None,
+            data_retention: HashMap::new(),
+        },
+        pool.clone(),
+        vec![], // No checks, so every batch is prunable
+    )
+    .with_archiver(archiver);
+    let (_stop_sender, mut stop_receiver) = watch::channel(false);
+    let outcome = pruner
+        .run_single_iteration(&mut stop_receiver)
+        .await
+        .unwrap();
+    assert_matches!(outcome, PruningIterationOutcome::Pruned);
+
+    for l1_batch_number in 0..=3 {
+        let archive: ArchivedL1Batch = blob_store
+            .get(L1BatchNumber(l1_batch_number))
+            .await
+            .unwrap();
+        assert_eq!(archive.l1_batch_number, L1BatchNumber(l1_batch_number));
+        let l2_block_numbers: Vec<_> = archive
+            .l2_blocks
+            .iter()
+            .map(ArchivedL2Block::number)
+            .collect();
+        assert_eq!(
+            l2_block_numbers,
+            [
+                L2BlockNumber(l1_batch_number * 2),
+                L2BlockNumber(l1_batch_number * 2 + 1)
+            ]
+        );
+    }
+    blob_store
+        .get::<ArchivedL1Batch>(L1BatchNumber(4))
+        .await
+        .unwrap_err();
+
+    let archived_l1_batch = conn
+        .pruning_dal()
+        .get_archived_l1_batch_for_l2_block(L2BlockNumber(5))
+        .await
+        .unwrap();
+    assert_eq!(archived_l1_batch, Some(L1BatchNumber(2)));
+    let archived_l1_batch = conn
+        .pruning_dal()
+        .get_archived_l1_batch_for_l2_block(L2BlockNumber(8))
+        .await
+        .unwrap();
+    assert_eq!(archived_l1_batch, None);
+}
+
 #[tokio::test]
 async fn pruner_with_real_conditions() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -508,6 +714,8 @@ async fn pruner_with_real_conditions() {
         removal_delay: Duration::from_millis(10), // non-zero to not have a tight loop in `DbPruner::run()`
         pruned_batch_chunk_size: 1,
         minimum_l1_batch_age: Duration::ZERO,
+        database_size_budget: None,
+        data_retention: HashMap::new(),
     };
     let pruner = DbPruner::new(config, pool.clone());
     let mut health_check = pruner.health_check();
@@ -564,6 +772,8 @@ async fn pruning_iteration_timely_shuts_down() {
             removal_delay: Duration::MAX, // intentionally chosen so that pruning iterations stuck
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            database_size_budget: None,
+            data_retention: HashMap::new(),
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -593,6 +803,8 @@ async fn pruner_timely_shuts_down() {
             removal_delay: Duration::MAX, // intentionally chosen so that pruning iterations stuck
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            database_size_budget: None,
+            data_retention: HashMap::new(),
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
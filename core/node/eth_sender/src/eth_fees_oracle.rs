@@ -8,19 +8,35 @@ use zksync_eth_client::{ClientError, EnrichedClientError};
 use zksync_node_fee_model::l1_gas_price::L1TxParamsProvider;
 use zksync_types::eth_sender::TxHistory;
 
-use crate::EthSenderError;
+use crate::{
+    fee_escalation::{EscalationContext, FeeEscalation},
+    EthSenderError,
+};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub(crate) struct EthFees {
     pub(crate) base_fee_per_gas: u64,
     pub(crate) priority_fee_per_gas: u64,
     pub(crate) blob_base_fee_per_gas: Option<u64>,
 }
 
+impl EthFees {
+    /// Multiplies all fees by the specified factor.
+    pub(crate) fn scale(&self, multiplier: f64) -> Self {
+        let scale = |fee: u64| (fee as f64 * multiplier) as u64;
+        Self {
+            base_fee_per_gas: scale(self.base_fee_per_gas),
+            priority_fee_per_gas: scale(self.priority_fee_per_gas),
+            blob_base_fee_per_gas: self.blob_base_fee_per_gas.map(scale),
+        }
+    }
+}
+
 pub(crate) trait EthFeesOracle: 'static + Sync + Send + fmt::Debug {
     fn calculate_fees(
         &self,
         previous_sent_tx: &Option<TxHistory>,
+        escalation_context: Option<&EscalationContext>,
         has_blob_sidecar: bool,
         time_in_mempool: u32,
     ) -> Result<EthFees, EthSenderError>;
@@ -30,30 +46,48 @@ pub(crate) trait EthFeesOracle: 'static + Sync + Send + fmt::Debug {
 pub(crate) struct GasAdjusterFeesOracle {
     pub gas_adjuster: Arc<dyn L1TxParamsProvider>,
     pub max_acceptable_priority_fee_in_gwei: u64,
+    pub fee_escalation: Box<dyn FeeEscalation>,
 }
 
 impl GasAdjusterFeesOracle {
+    fn escalated_fees(&self, escalation_context: Option<&EscalationContext>) -> Option<EthFees> {
+        let escalated_fees = self.fee_escalation.escalated_fees(escalation_context?)?;
+        // Escalation must not trigger the sanity check on the priority fee below.
+        Some(EthFees {
+            priority_fee_per_gas: min(
+                escalated_fees.priority_fee_per_gas,
+                self.max_acceptable_priority_fee_in_gwei,
+            ),
+            ..escalated_fees
+        })
+    }
+
     fn calculate_fees_with_blob_sidecar(
         &self,
         previous_sent_tx: &Option<TxHistory>,
+        escalated_fees: Option<EthFees>,
     ) -> Result<EthFees, EthSenderError> {
-        let base_fee_per_gas = self.gas_adjuster.get_blob_tx_base_fee();
-        let priority_fee_per_gas = self.gas_adjuster.get_blob_tx_priority_fee();
-        let blob_base_fee_per_gas = Some(self.gas_adjuster.get_blob_tx_blob_base_fee());
+        let mut base_fee_per_gas = self.gas_adjuster.get_blob_tx_base_fee();
+        let mut priority_fee_per_gas = self.gas_adjuster.get_blob_tx_priority_fee();
+        let mut blob_base_fee_per_gas = Some(self.gas_adjuster.get_blob_tx_blob_base_fee());
 
         if let Some(previous_sent_tx) = previous_sent_tx {
             // for blob transactions on re-sending need to double all gas prices
-            return Ok(EthFees {
-                base_fee_per_gas: max(previous_sent_tx.base_fee_per_gas * 2, base_fee_per_gas),
-                priority_fee_per_gas: max(
-                    previous_sent_tx.priority_fee_per_gas * 2,
-                    priority_fee_per_gas,
-                ),
-                blob_base_fee_per_gas: max(
-                    previous_sent_tx.blob_base_fee_per_gas.map(|v| v * 2),
-                    blob_base_fee_per_gas,
-                ),
-            });
+            base_fee_per_gas = max(previous_sent_tx.base_fee_per_gas * 2, base_fee_per_gas);
+            priority_fee_per_gas = max(
+                previous_sent_tx.priority_fee_per_gas * 2,
+                priority_fee_per_gas,
+            );
+            blob_base_fee_per_gas = max(
+                previous_sent_tx.blob_base_fee_per_gas.map(|v| v * 2),
+                blob_base_fee_per_gas,
+            );
+        }
+        if let Some(escalated_fees) = escalated_fees {
+            base_fee_per_gas = max(base_fee_per_gas, escalated_fees.base_fee_per_gas);
+            priority_fee_per_gas = max(priority_fee_per_gas, escalated_fees.priority_fee_per_gas);
+            blob_base_fee_per_gas =
+                max(blob_base_fee_per_gas, escalated_fees.blob_base_fee_per_gas);
         }
         Ok(EthFees {
             base_fee_per_gas,
@@ -65,9 +99,13 @@ impl GasAdjusterFeesOracle {
     fn calculate_fees_no_blob_sidecar(
         &self,
         previous_sent_tx: &Option<TxHistory>,
+        escalated_fees: Option<EthFees>,
         time_in_mempool: u32,
     ) -> Result<EthFees, EthSenderError> {
-        let base_fee_per_gas = self.gas_adjuster.get_base_fee(time_in_mempool);
+        let mut base_fee_per_gas = self.gas_adjuster.get_base_fee(time_in_mempool);
+        if let Some(escalated_fees) = escalated_fees {
+            base_fee_per_gas = max(base_fee_per_gas, escalated_fees.base_fee_per_gas);
+        }
         if let Some(previous_sent_tx) = previous_sent_tx {
             self.verify_base_fee_not_too_low_on_resend(
                 previous_sent_tx.id,
@@ -85,6 +123,9 @@ impl GasAdjusterFeesOracle {
                 (previous_sent_tx.priority_fee_per_gas * 6) / 5 + 1,
             );
         }
+        if let Some(escalated_fees) = escalated_fees {
+            priority_fee_per_gas = max(priority_fee_per_gas, escalated_fees.priority_fee_per_gas);
+        }
 
         // Extra check to prevent sending transaction will extremely high priority fee.
         if priority_fee_per_gas > self.max_acceptable_priority_fee_in_gwei {
@@ -137,13 +178,15 @@ impl EthFeesOracle for GasAdjusterFeesOracle {
     fn calculate_fees(
         &self,
         previous_sent_tx: &Option<TxHistory>,
+        escalation_context: Option<&EscalationContext>,
         has_blob_sidecar: bool,
         time_in_mempool: u32,
     ) -> Result<EthFees, EthSenderError> {
+        let escalated_fees = self.escalated_fees(escalation_context);
         if has_blob_sidecar {
-            self.calculate_fees_with_blob_sidecar(previous_sent_tx)
+            self.calculate_fees_with_blob_sidecar(previous_sent_tx, escalated_fees)
         } else {
-            self.calculate_fees_no_blob_sidecar(previous_sent_tx, time_in_mempool)
+            self.calculate_fees_no_blob_sidecar(previous_sent_tx, escalated_fees, time_in_mempool)
         }
     }
 }
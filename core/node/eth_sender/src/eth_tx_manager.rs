@@ -5,18 +5,26 @@ use tokio::sync::watch;
 use zksync_config::configs::eth_sender::SenderConfig;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_eth_client::{
-    encode_blob_tx_with_sidecar, BoundEthInterface, ExecutedTxStatus, RawTransactionBytes,
+    encode_blob_tx_with_sidecar, BoundEthInterface, ClientError, EnrichedClientError,
+    ExecutedTxStatus, RawTransactionBytes,
 };
 use zksync_node_fee_model::l1_gas_price::L1TxParamsProvider;
 use zksync_shared_metrics::BlockL1Stage;
-use zksync_types::{eth_sender::EthTx, Address, L1BlockNumber, H256, U256};
+use zksync_types::{
+    eth_sender::{EthTx, EthTxReceiptPrices, TxHistory},
+    Address, L1BlockNumber, H256, U256,
+};
 use zksync_utils::time::seconds_since_epoch;
 
 use super::{metrics::METRICS, EthSenderError};
 use crate::{
     abstract_l1_interface::{AbstractL1Interface, L1BlockNumbers, OperatorNonce, RealL1Interface},
     eth_fees_oracle::{EthFees, EthFeesOracle, GasAdjusterFeesOracle},
-    metrics::TransactionType,
+    fee_escalation::{
+        blob_cost_in_wei, blob_count, fee_escalation_from_config, max_tx_cost_in_wei,
+        EscalationContext,
+    },
+    metrics::{L1SpendKind, TransactionType},
 };
 
 /// The component is responsible for managing sending eth_txs attempts:
@@ -46,6 +54,7 @@ impl EthTxManager {
         let fees_oracle = GasAdjusterFeesOracle {
             gas_adjuster,
             max_acceptable_priority_fee_in_gwei: config.max_acceptable_priority_fee_in_gwei,
+            fee_escalation: fee_escalation_from_config(&config),
         };
         Self {
             l1_interface: Box::new(RealL1Interface {
@@ -107,17 +116,32 @@ impl EthTxManager {
             .get_last_sent_eth_tx(tx.id)
             .await
             .unwrap();
+        let tx_history = storage
+            .eth_sender_dal()
+            .get_tx_history_to_check(tx.id)
+            .await
+            .unwrap();
+        let deadline_progress = EscalationContext::deadline_progress(
+            &self.config,
+            tx.tx_type,
+            tx.created_at_timestamp,
+            seconds_since_epoch(),
+        );
+        let escalation_context = EscalationContext::new(&tx_history, deadline_progress);
         let has_blob_sidecar = tx.blob_sidecar.is_some();
 
-        let EthFees {
-            base_fee_per_gas,
-            priority_fee_per_gas,
-            blob_base_fee_per_gas,
-        } = self.fees_oracle.calculate_fees(
+        let fees = self.fees_oracle.calculate_fees(
             &previous_sent_tx,
+            escalation_context.as_ref(),
             has_blob_sidecar,
             time_in_mempool,
         )?;
+        self.check_spend_budget(storage, tx, &fees).await?;
+        let EthFees {
+            base_fee_per_gas,
+            priority_fee_per_gas,
+            blob_base_fee_per_gas,
+        } = fees;
 
         if let Some(previous_sent_tx) = previous_sent_tx {
             METRICS.transaction_resent.inc();
@@ -215,6 +239,46 @@ impl EthTxManager {
         Ok(signed_tx.hash)
     }
 
+    /// Checks that the cost of sending `tx` with the specified fees fits into the L1 spend budget
+    /// for the L1 batches covered by the transaction. The cost is estimated using the gas predicted
+    /// for the transaction itself (i.e., based on its operation type and L1 batches), rather than
+    /// the global gas limit.
+    async fn check_spend_budget(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        tx: &EthTx,
+        fees: &EthFees,
+    ) -> Result<(), EthSenderError> {
+        let Some(max_spend_per_batch_gwei) = self.config.max_l1_spend_per_batch_gwei else {
+            return Ok(());
+        };
+        let batch_count = storage
+            .blocks_dal()
+            .get_l1_batches_for_eth_tx_id(tx.id)
+            .await
+            .unwrap()
+            .len()
+            .max(1);
+        let budget_in_wei =
+            u128::from(max_spend_per_batch_gwei) * 1_000_000_000 * batch_count as u128;
+        let max_cost_in_wei = max_tx_cost_in_wei(fees, tx.predicted_gas_cost, blob_count(tx));
+        if max_cost_in_wei <= budget_in_wei {
+            return Ok(());
+        }
+
+        METRICS.l1_spend_budget_exceeded[&tx.tx_type.into()].inc();
+        tracing::warn!(
+            "Sending tx {} with fees {fees:?} can cost up to {max_cost_in_wei} wei, \
+             which exceeds the budget of {budget_in_wei} wei for {batch_count} L1 batches",
+            tx.id
+        );
+        let err = ClientError::Custom("L1 spend budget per batch is exceeded".into());
+        let err = EnrichedClientError::new(err, "check_spend_budget")
+            .with_arg("max_cost_in_wei", &max_cost_in_wei)
+            .with_arg("budget_in_wei", &budget_in_wei);
+        Err(err.into())
+    }
+
     async fn send_raw_transaction(
         &self,
         storage: &mut Connection<'_, Core>,
@@ -501,7 +565,21 @@ impl EthTxManager {
             .await
             .unwrap();
 
-        METRICS
+        let receipt_prices = EthTxReceiptPrices {
+            effective_gas_price: tx_status.receipt.effective_gas_price,
+            blob_gas_used: tx_status.receipt.blob_gas_used,
+            blob_gas_price: tx_status.receipt.blob_gas_price,
+        };
+        if let Err(err) = storage
+            .eth_sender_dal()
+            .save_receipt_prices(tx.id, &receipt_prices)
+            .await
+        {
+            // Prices are only used for reporting, so failing to persist them shouldn't block confirmation.
+            tracing::warn!("Failed saving receipt prices for eth_tx {}: {err:#}", tx.id);
+        }
+
+        let l1_batch_count = METRICS
             .track_eth_tx_metrics(storage, BlockL1Stage::Mined, tx)
             .await;
 
@@ -532,6 +610,60 @@ impl EthTxManager {
             .unwrap_or(0);
         let waited_blocks = tx_status.receipt.block_number.unwrap().as_u32() - sent_at_block;
         METRICS.l1_blocks_waited_in_mempool[&tx_type_label].observe(waited_blocks.into());
+
+        let tx_history = storage
+            .eth_sender_dal()
+            .get_tx_history_to_check(tx.id)
+            .await
+            .unwrap();
+        METRICS.l1_tx_attempts[&tx_type_label].observe(tx_history.len());
+        let tx_cost = self.track_l1_spend(tx, &tx_status, &tx_history);
+        if l1_batch_count > 0 {
+            METRICS.l1_batch_submission_cost_gwei[&tx_type_label]
+                .observe((tx_cost / l1_batch_count as u128) as u64);
+        }
+    }
+
+    /// Tracks the amount spent on a confirmed transaction. Returns the total spent amount in gwei.
+    pub(crate) fn track_l1_spend(
+        &self,
+        tx: &EthTx,
+        tx_status: &ExecutedTxStatus,
+        tx_history: &[TxHistory],
+    ) -> u128 {
+        const WEI_IN_GWEI: u128 = 1_000_000_000;
+
+        let tx_type_label = tx.tx_type.into();
+        let receipt = &tx_status.receipt;
+        let mut execution_cost = 0;
+        if let (Some(gas_used), Some(gas_price)) = (receipt.gas_used, receipt.effective_gas_price) {
+            METRICS.l1_effective_gas_price[&tx_type_label].observe(gas_price.low_u64());
+            execution_cost = gas_used.saturating_mul(gas_price).low_u128() / WEI_IN_GWEI;
+            METRICS.l1_spend_gwei[&(tx.tx_type, L1SpendKind::Execution).into()]
+                .inc_by(execution_cost as u64);
+        }
+
+        // Prefer the exact blob cost from the receipt; fall back to the estimate based on the confirmed attempt.
+        let blob_cost = if let (Some(blob_gas_used), Some(blob_gas_price)) =
+            (receipt.blob_gas_used, receipt.blob_gas_price)
+        {
+            METRICS.l1_blob_gas_price[&tx_type_label].observe(blob_gas_price.low_u64());
+            Some(blob_gas_used.saturating_mul(blob_gas_price).low_u128())
+        } else {
+            tx_history
+                .iter()
+                .find(|attempt| attempt.tx_hash == tx_status.tx_hash)
+                .and_then(|attempt| attempt.blob_base_fee_per_gas)
+                .map(|blob_base_fee_per_gas| {
+                    blob_cost_in_wei(blob_base_fee_per_gas, blob_count(tx))
+                })
+        };
+        let blob_cost = blob_cost.map_or(0, |cost| cost / WEI_IN_GWEI);
+        if blob_cost > 0 {
+            METRICS.l1_spend_gwei[&(tx.tx_type, L1SpendKind::Blobs).into()]
+                .inc_by(blob_cost as u64);
+        }
+        execution_cost + blob_cost
     }
 
     pub async fn run(mut self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
@@ -0,0 +1,269 @@
+//! Strategies for escalating fees of L1 transactions that are stuck in the mempool.
+
+use std::fmt;
+
+use zksync_config::configs::eth_sender::{FeeEscalationStrategy, SenderConfig};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType,
+    eth_sender::{EthTx, EthTxBlobSidecar, TxHistory},
+};
+
+use crate::eth_fees_oracle::EthFees;
+
+/// Amount of blob gas consumed by a single blob.
+const GAS_PER_BLOB: u64 = 131_072;
+
+/// Information about the previous sending attempts of an L1 transaction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct EscalationContext {
+    /// Fees used by the first sending attempt.
+    pub initial_fees: EthFees,
+    /// Number of previous sending attempts.
+    pub attempts: u32,
+    /// Fraction of the operation deadline elapsed since the transaction was created, in `[0, 1]`.
+    pub deadline_progress: f64,
+}
+
+impl EscalationContext {
+    /// Creates a context from the sending attempts history, ordered from the most recent attempt.
+    /// Returns `None` if the transaction was never sent.
+    pub fn new(history: &[TxHistory], deadline_progress: f64) -> Option<Self> {
+        let first_attempt = history.last()?;
+        Some(Self {
+            initial_fees: EthFees {
+                base_fee_per_gas: first_attempt.base_fee_per_gas,
+                priority_fee_per_gas: first_attempt.priority_fee_per_gas,
+                blob_base_fee_per_gas: first_attempt.blob_base_fee_per_gas,
+            },
+            attempts: history.len() as u32,
+            deadline_progress: deadline_progress.clamp(0.0, 1.0),
+        })
+    }
+
+    /// Computes the fraction of the operation deadline elapsed since the transaction was created.
+    pub fn deadline_progress(
+        config: &SenderConfig,
+        tx_type: AggregatedActionType,
+        created_at_timestamp: u64,
+        now: u64,
+    ) -> f64 {
+        let deadline = match tx_type {
+            AggregatedActionType::Commit => config.aggregated_block_commit_deadline,
+            AggregatedActionType::PublishProofOnchain => config.aggregated_block_prove_deadline,
+            AggregatedActionType::Execute => config.aggregated_block_execute_deadline,
+        };
+        if deadline == 0 {
+            return 1.0;
+        }
+        let elapsed = now.saturating_sub(created_at_timestamp);
+        (elapsed as f64 / deadline as f64).min(1.0)
+    }
+}
+
+/// Strategy defining the minimal fees for resending a stuck L1 transaction.
+pub(crate) trait FeeEscalation: 'static + Sync + Send + fmt::Debug {
+    /// Returns the multiplier applied to the first attempt fees, or `None` if the strategy
+    /// relies solely on the network fees and replacement rules.
+    fn fee_multiplier(&self, context: &EscalationContext) -> Option<f64>;
+
+    /// Returns the minimal fees for the next sending attempt.
+    fn escalated_fees(&self, context: &EscalationContext) -> Option<EthFees> {
+        let multiplier = self.fee_multiplier(context)?;
+        Some(context.initial_fees.scale(multiplier))
+    }
+}
+
+/// Bumps fees by the minimum amount required to replace the transaction.
+#[derive(Debug)]
+pub(crate) struct MinimalEscalation;
+
+impl FeeEscalation for MinimalEscalation {
+    fn fee_multiplier(&self, _context: &EscalationContext) -> Option<f64> {
+        None
+    }
+}
+
+/// Increases fees by a fixed share of the first attempt fees on each resend.
+#[derive(Debug)]
+pub(crate) struct LinearEscalation {
+    pub bump_percent: u64,
+}
+
+impl FeeEscalation for LinearEscalation {
+    fn fee_multiplier(&self, context: &EscalationContext) -> Option<f64> {
+        let bump = self.bump_percent as f64 / 100.0;
+        Some(1.0 + bump * f64::from(context.attempts))
+    }
+}
+
+/// Multiplies fees by a fixed factor on each resend, up to a cap.
+#[derive(Debug)]
+pub(crate) struct ExponentialEscalation {
+    pub bump_percent: u64,
+    pub max_multiplier: f64,
+}
+
+impl FeeEscalation for ExponentialEscalation {
+    fn fee_multiplier(&self, context: &EscalationContext) -> Option<f64> {
+        let factor = 1.0 + self.bump_percent as f64 / 100.0;
+        let exponent = i32::try_from(context.attempts).unwrap_or(i32::MAX);
+        Some(factor.powi(exponent).min(self.max_multiplier))
+    }
+}
+
+/// Escalates fees harder as the operation deadline approaches.
+#[derive(Debug)]
+pub(crate) struct DeadlineAwareEscalation {
+    pub max_multiplier: f64,
+}
+
+impl FeeEscalation for DeadlineAwareEscalation {
+    fn fee_multiplier(&self, context: &EscalationContext) -> Option<f64> {
+        let progress = context.deadline_progress;
+        Some(1.0 + (self.max_multiplier - 1.0).max(0.0) * progress * progress)
+    }
+}
+
+/// Creates the fee escalation strategy specified in the config.
+pub(crate) fn fee_escalation_from_config(config: &SenderConfig) -> Box<dyn FeeEscalation> {
+    match config.fee_escalation_strategy {
+        FeeEscalationStrategy::Minimal => Box::new(MinimalEscalation),
+        FeeEscalationStrategy::Linear => Box::new(LinearEscalation {
+            bump_percent: config.fee_escalation_bump_percent,
+        }),
+        FeeEscalationStrategy::Exponential => Box::new(ExponentialEscalation {
+            bump_percent: config.fee_escalation_bump_percent,
+            max_multiplier: config.fee_escalation_max_multiplier(),
+        }),
+        FeeEscalationStrategy::DeadlineAware => Box::new(DeadlineAwareEscalation {
+            max_multiplier: config.fee_escalation_max_multiplier(),
+        }),
+    }
+}
+
+/// Returns the number of blobs carried by an L1 transaction.
+pub(crate) fn blob_count(tx: &EthTx) -> usize {
+    match &tx.blob_sidecar {
+        Some(EthTxBlobSidecar::EthTxBlobSidecarV1(sidecar)) => sidecar.blobs.len(),
+        None => 0,
+    }
+}
+
+/// Returns the maximum amount (in wei) that an L1 transaction with the specified fees can spend.
+pub(crate) fn max_tx_cost_in_wei(fees: &EthFees, gas_limit: u64, blob_count: usize) -> u128 {
+    let execution_cost = u128::from(gas_limit)
+        * (u128::from(fees.base_fee_per_gas) + u128::from(fees.priority_fee_per_gas));
+    let blob_cost = u128::from(fees.blob_base_fee_per_gas.unwrap_or(0))
+        * u128::from(GAS_PER_BLOB)
+        * blob_count as u128;
+    execution_cost + blob_cost
+}
+
+/// Returns the amount (in wei) spent on blobs by a mined L1 transaction.
+pub(crate) fn blob_cost_in_wei(blob_base_fee_per_gas: u64, blob_count: usize) -> u128 {
+    u128::from(blob_base_fee_per_gas) * u128::from(GAS_PER_BLOB) * blob_count as u128
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_config::EthConfig;
+
+    use super::*;
+
+    fn context(attempts: u32, deadline_progress: f64) -> EscalationContext {
+        EscalationContext {
+            initial_fees: EthFees {
+                base_fee_per_gas: 1_000,
+                priority_fee_per_gas: 100,
+                blob_base_fee_per_gas: Some(10),
+            },
+            attempts,
+            deadline_progress,
+        }
+    }
+
+    #[test]
+    fn minimal_escalation_relies_on_network_fees() {
+        assert_eq!(MinimalEscalation.escalated_fees(&context(3, 0.5)), None);
+    }
+
+    #[test]
+    fn linear_escalation() {
+        let strategy = LinearEscalation { bump_percent: 20 };
+        let fees = strategy.escalated_fees(&context(1, 0.0)).unwrap();
+        assert_eq!(
+            fees,
+            EthFees {
+                base_fee_per_gas: 1_200,
+                priority_fee_per_gas: 120,
+                blob_base_fee_per_gas: Some(12),
+            }
+        );
+        let fees = strategy.escalated_fees(&context(5, 0.0)).unwrap();
+        assert_eq!(fees.base_fee_per_gas, 2_000);
+    }
+
+    #[test]
+    fn exponential_escalation_is_capped() {
+        let strategy = ExponentialEscalation {
+            bump_percent: 100,
+            max_multiplier: 5.0,
+        };
+        let fees = strategy.escalated_fees(&context(2, 0.0)).unwrap();
+        assert_eq!(fees.base_fee_per_gas, 4_000);
+        assert_eq!(fees.priority_fee_per_gas, 400);
+        let fees = strategy.escalated_fees(&context(10, 0.0)).unwrap();
+        assert_eq!(fees.base_fee_per_gas, 5_000);
+        assert_eq!(fees.blob_base_fee_per_gas, Some(50));
+    }
+
+    #[test]
+    fn deadline_aware_escalation() {
+        let strategy = DeadlineAwareEscalation {
+            max_multiplier: 3.0,
+        };
+        let fees = strategy.escalated_fees(&context(1, 0.0)).unwrap();
+        assert_eq!(fees.base_fee_per_gas, 1_000);
+        let fees = strategy.escalated_fees(&context(1, 0.5)).unwrap();
+        assert_eq!(fees.base_fee_per_gas, 1_500);
+        let fees = strategy.escalated_fees(&context(1, 1.0)).unwrap();
+        assert_eq!(fees.base_fee_per_gas, 3_000);
+    }
+
+    #[test]
+    fn computing_deadline_progress() {
+        let config = SenderConfig {
+            aggregated_block_commit_deadline: 100,
+            aggregated_block_execute_deadline: 0,
+            ..EthConfig::for_tests().sender.unwrap()
+        };
+        let progress = EscalationContext::deadline_progress(
+            &config,
+            AggregatedActionType::Commit,
+            1_000,
+            1_025,
+        );
+        assert_eq!(progress, 0.25);
+        let progress = EscalationContext::deadline_progress(
+            &config,
+            AggregatedActionType::Commit,
+            1_000,
+            2_000,
+        );
+        assert_eq!(progress, 1.0);
+        let progress = EscalationContext::deadline_progress(
+            &config,
+            AggregatedActionType::Execute,
+            1_000,
+            1_000,
+        );
+        assert_eq!(progress, 1.0);
+    }
+
+    #[test]
+    fn computing_max_tx_cost() {
+        let fees = context(0, 0.0).initial_fees;
+        assert_eq!(max_tx_cost_in_wei(&fees, 10, 0), 11_000);
+        assert_eq!(max_tx_cost_in_wei(&fees, 10, 2), 11_000 + 10 * 131_072 * 2);
+    }
+}
@@ -3,6 +3,7 @@ mod aggregator;
 mod error;
 mod eth_tx_aggregator;
 mod eth_tx_manager;
+mod fee_escalation;
 mod metrics;
 mod publish_criterion;
 mod utils;
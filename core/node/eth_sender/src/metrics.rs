@@ -67,6 +67,30 @@ impl From<(AggregatedActionType, &'static str)> for AggregationReasonLabels {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub(super) enum L1SpendKind {
+    /// Fees paid for the gas used by transaction execution.
+    Execution,
+    /// Fees paid for blobs (taken from the receipt or estimated using the blob fee of the confirmed attempt).
+    Blobs,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet)]
+pub(super) struct L1SpendLabels {
+    r#type: ActionTypeLabel,
+    kind: L1SpendKind,
+}
+
+impl From<(AggregatedActionType, L1SpendKind)> for L1SpendLabels {
+    fn from((op, kind): (AggregatedActionType, L1SpendKind)) -> Self {
+        Self {
+            r#type: op.into(),
+            kind,
+        }
+    }
+}
+
 /// Roughly exponential buckets for fees (100M – 500B).
 const FEE_BUCKETS: Buckets = Buckets::values(&[
     1e7, 2e7, 5e7, 1e8, 2e8, 5e8, 1e9, 2e9, 5e9, 1e10, 2e10, 5e10, 1e11, 2e11, 5e11,
@@ -107,6 +131,22 @@ pub(super) struct EthSenderMetrics {
     pub l1_blocks_waited_in_mempool: Family<ActionTypeLabel, Histogram<u64>>,
     /// Number of L1 batches aggregated for publishing with a specific reason.
     pub block_aggregation_reason: Family<AggregationReasonLabels, Counter>,
+    /// Total amount spent on confirmed L1 transactions, in gwei.
+    pub l1_spend_gwei: Family<L1SpendLabels, Counter>,
+    /// Number of sending attempts made before an L1 transaction got confirmed.
+    #[metrics(buckets = Buckets::linear(1.0..=10.0, 1.0))]
+    pub l1_tx_attempts: Family<ActionTypeLabel, Histogram<usize>>,
+    /// Number of sending attempts skipped because they exceeded the L1 spend budget per batch.
+    pub l1_spend_budget_exceeded: Family<ActionTypeLabel, Counter>,
+    /// Effective gas price of confirmed L1 transactions as reported in their receipts.
+    #[metrics(buckets = FEE_BUCKETS)]
+    pub l1_effective_gas_price: Family<ActionTypeLabel, Histogram<u64>>,
+    /// Blob gas price of confirmed L1 transactions as reported in their receipts.
+    #[metrics(buckets = FEE_BUCKETS)]
+    pub l1_blob_gas_price: Family<ActionTypeLabel, Histogram<u64>>,
+    /// Amount spent on an L1 transaction divided by the number of L1 batches it covers, in gwei.
+    #[metrics(buckets = Buckets::exponential(1e3..=1e9, 4.0))]
+    pub l1_batch_submission_cost_gwei: Family<ActionTypeLabel, Histogram<u64>>,
 }
 
 impl EthSenderMetrics {
@@ -118,12 +158,13 @@ impl EthSenderMetrics {
         self.last_known_l1_block[&BlockNumberVariant::Safe].set(l1_block_numbers.safe.0 as usize);
     }
 
+    /// Tracks latencies for L1 batches covered by the transaction. Returns the number of these batches.
     pub async fn track_eth_tx_metrics(
         &self,
         connection: &mut Connection<'_, Core>,
         l1_stage: BlockL1Stage,
         tx: &EthTx,
-    ) {
+    ) -> usize {
         let metrics_latency = self.metrics_latency.start();
         let stage = BlockStage::L1 {
             l1_stage,
@@ -139,9 +180,10 @@ impl EthSenderMetrics {
         // This should be only the case when some blocks were reverted.
         if l1_batch_headers.is_empty() {
             tracing::warn!("No L1 batches were found for eth_tx with id = {}", tx.id);
-            return;
+            return 0;
         }
 
+        let l1_batch_count = l1_batch_headers.len();
         for header in l1_batch_headers {
             APP_METRICS.block_latency[&stage].observe(Duration::from_secs(
                 seconds_since_epoch() - header.timestamp,
@@ -150,6 +192,7 @@ impl EthSenderMetrics {
             APP_METRICS.processed_l1_txs[&stage.into()].inc_by(header.tx_count() as u64);
         }
         metrics_latency.observe();
+        l1_batch_count
     }
 }
 
@@ -9,7 +9,7 @@ use zksync_config::{
 };
 use zksync_contracts::BaseSystemContractsHashes;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
-use zksync_eth_client::clients::MockEthereum;
+use zksync_eth_client::{clients::MockEthereum, ExecutedTxStatus};
 use zksync_l1_contract_interface::i_executor::methods::{ExecuteBatches, ProveBatches};
 use zksync_node_fee_model::l1_gas_price::GasAdjuster;
 use zksync_node_test_utils::{create_l1_batch, l1_batch_metadata_to_commitment_artifacts};
@@ -23,7 +23,7 @@ use zksync_types::{
     helpers::unix_timestamp_ms,
     l2_to_l1_log::{L2ToL1Log, UserL2ToL1Log},
     pubdata_da::PubdataDA,
-    web3::contract::Error,
+    web3::{contract::Error, TransactionReceipt},
     Address, L1BatchNumber, ProtocolVersion, ProtocolVersionId, H256,
 };
 
@@ -203,6 +203,22 @@ impl EthSenderTester {
         }
     }
 
+    /// Recreates the transaction manager with the specified L1 spend budget per L1 batch.
+    fn with_max_l1_spend_per_batch(mut self, max_l1_spend_per_batch_gwei: u64) -> Self {
+        let config = SenderConfig {
+            max_l1_spend_per_batch_gwei: Some(max_l1_spend_per_batch_gwei),
+            ..EthConfig::for_tests().sender.unwrap()
+        };
+        self.manager = EthTxManager::new(
+            self.conn.clone(),
+            config,
+            self.gas_adjuster.clone(),
+            self.gateway.clone(),
+            None,
+        );
+        self
+    }
+
     async fn storage(&self) -> Connection<'_, Core> {
         self.conn.connection().await.unwrap()
     }
@@ -596,6 +612,141 @@ async fn dont_resend_already_mined(commitment_mode: L1BatchCommitmentMode) -> an
     Ok(())
 }
 
+#[test_casing(2, COMMITMENT_MODES)]
+#[tokio::test]
+async fn transaction_is_held_back_if_spend_budget_is_exceeded(
+    commitment_mode: L1BatchCommitmentMode,
+) -> anyhow::Result<()> {
+    const GWEI: u64 = 1_000_000_000;
+
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let mut tester = EthSenderTester::new(
+        connection_pool.clone(),
+        vec![GWEI, GWEI, GWEI, 1, 1, 1],
+        false,
+        false,
+        commitment_mode,
+    )
+    .await
+    // The execute transaction is predicted to use 241k gas, so the worst-case cost with the default 1 gwei
+    // priority fee is ~241k gwei if the base fee is negligible, and ~964k gwei if the median base fee is 1 gwei
+    // (the base fee is multiplied by 3). The budget lies in between.
+    .with_max_l1_spend_per_batch(500_000);
+
+    // After this, median should be 1 gwei
+    tester.gateway.advance_block_number(3);
+    tester.gas_adjuster.keep_updated().await?;
+
+    insert_genesis_protocol_version(&tester).await;
+    tester
+        .storage()
+        .await
+        .blocks_dal()
+        .insert_mock_l1_batch(&mock_l1_batch_header(1))
+        .await
+        .unwrap();
+    let tx = tester
+        .aggregator
+        .save_eth_tx(
+            &mut tester.conn.connection().await.unwrap(),
+            &get_dummy_operation(1),
+            false,
+        )
+        .await?;
+
+    let block = tester.get_block_numbers().await.latest;
+    let err = tester
+        .manager
+        .send_eth_tx(&mut tester.conn.connection().await.unwrap(), &tx, 0, block)
+        .await
+        .unwrap_err();
+    assert_matches!(err, EthSenderError::EthereumGateway(_));
+    assert_eq!(tester.gateway.sent_tx_count(), 0);
+    let inflight_txs = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .get_inflight_txs()
+        .await
+        .unwrap();
+    assert!(inflight_txs.is_empty(), "{inflight_txs:?}");
+
+    // Now, median is 1 wei, so the transaction fits into the budget
+    tester.gateway.advance_block_number(3);
+    tester.gas_adjuster.keep_updated().await?;
+    let block = tester.get_block_numbers().await.latest;
+    let hash = tester
+        .manager
+        .send_eth_tx(&mut tester.conn.connection().await.unwrap(), &tx, 0, block)
+        .await?;
+
+    assert_eq!(tester.gateway.sent_tx_count(), 1);
+    let inflight_txs = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .get_inflight_txs()
+        .await
+        .unwrap();
+    assert_eq!(inflight_txs.len(), 1);
+    let sent_tx = tester
+        .manager
+        .l1_interface()
+        .get_tx(hash)
+        .await
+        .unwrap()
+        .expect("no transaction");
+    assert_eq!(
+        sent_tx.max_fee_per_gas.unwrap() - sent_tx.max_priority_fee_per_gas.unwrap(),
+        3.into() // `1 * 3 * 2^0`
+    );
+
+    Ok(())
+}
+
+#[test_casing(2, COMMITMENT_MODES)]
+#[tokio::test]
+async fn tracking_l1_spend(commitment_mode: L1BatchCommitmentMode) {
+    const GWEI: u64 = 1_000_000_000;
+
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let mut tester = EthSenderTester::new(
+        connection_pool.clone(),
+        vec![100; 100],
+        false,
+        false,
+        commitment_mode,
+    )
+    .await;
+    insert_genesis_protocol_version(&tester).await;
+    let tx = tester
+        .aggregator
+        .save_eth_tx(
+            &mut tester.conn.connection().await.unwrap(),
+            &DUMMY_OPERATION,
+            false,
+        )
+        .await
+        .unwrap();
+
+    let mut tx_status = ExecutedTxStatus {
+        tx_hash: H256::repeat_byte(1),
+        success: true,
+        receipt: TransactionReceipt {
+            gas_used: Some(100_000.into()),
+            effective_gas_price: Some((2 * GWEI).into()),
+            ..TransactionReceipt::default()
+        },
+    };
+    let spent_gwei = tester.manager.track_l1_spend(&tx, &tx_status, &[]);
+    assert_eq!(spent_gwei, 200_000);
+
+    tx_status.receipt.blob_gas_used = Some(131_072.into());
+    tx_status.receipt.blob_gas_price = Some((10 * GWEI).into());
+    let spent_gwei = tester.manager.track_l1_spend(&tx, &tx_status, &[]);
+    assert_eq!(spent_gwei, 200_000 + 1_310_720);
+}
+
 #[test_casing(2, COMMITMENT_MODES)]
 #[tokio::test]
 async fn three_scenarios(commitment_mode: L1BatchCommitmentMode) -> anyhow::Result<()> {
@@ -835,6 +986,70 @@ async fn correct_order_for_confirmations(
     Ok(())
 }
 
+#[test_casing(2, COMMITMENT_MODES)]
+#[tokio::test]
+async fn l1_batch_submission_details(commitment_mode: L1BatchCommitmentMode) {
+    let mut tester = EthSenderTester::new(
+        ConnectionPool::<Core>::test_pool().await,
+        vec![100; 100],
+        true,
+        false,
+        commitment_mode,
+    )
+    .await;
+
+    insert_genesis_protocol_version(&tester).await;
+    let genesis_l1_batch = insert_l1_batch(&tester, L1BatchNumber(0)).await;
+    let first_l1_batch = insert_l1_batch(&tester, L1BatchNumber(1)).await;
+
+    let commit_tx_hash = commit_l1_batch(
+        &mut tester,
+        genesis_l1_batch.clone(),
+        first_l1_batch.clone(),
+        true,
+    )
+    .await;
+    let prove_tx_hash = prove_l1_batch(
+        &mut tester,
+        genesis_l1_batch.clone(),
+        first_l1_batch.clone(),
+        false,
+    )
+    .await;
+
+    let details = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .get_l1_batch_submission_details(L1BatchNumber(1))
+        .await
+        .unwrap()
+        .expect("no details for L1 batch");
+    assert_eq!(details.number, L1BatchNumber(1));
+    let commit = details.commit.expect("no commit details");
+    assert_eq!(commit.l1_batch_count, 1);
+    assert_eq!(commit.confirmed_tx_hash, Some(commit_tx_hash));
+    assert_eq!(commit.gas_used, Some(21_000));
+    assert_eq!(commit.attempts.len(), 1);
+    assert_eq!(commit.attempts[0].tx_hash, commit_tx_hash);
+
+    let prove = details.prove.expect("no prove details");
+    assert_eq!(prove.confirmed_tx_hash, None);
+    assert_eq!(prove.gas_used, None);
+    assert_eq!(prove.attempts.len(), 1);
+    assert_eq!(prove.attempts[0].tx_hash, prove_tx_hash);
+    assert!(details.execute.is_none());
+
+    let missing_details = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .get_l1_batch_submission_details(L1BatchNumber(2))
+        .await
+        .unwrap();
+    assert!(missing_details.is_none());
+}
+
 #[test_casing(2, COMMITMENT_MODES)]
 #[tokio::test]
 async fn skipped_l1_batch_at_the_start(
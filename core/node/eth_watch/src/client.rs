@@ -24,11 +24,15 @@ pub trait EthClient: 'static + fmt::Debug + Send + Sync {
     ) -> EnrichedClientResult<Vec<Log>>;
     /// Returns finalized L1 block number.
     async fn finalized_block_number(&self) -> EnrichedClientResult<u64>;
+    /// Returns hash of the L1 block with the specified number, or `None` if there is no such block.
+    async fn block_hash(&self, number: u64) -> EnrichedClientResult<Option<H256>>;
     /// Returns scheduler verification key hash by verifier address.
     async fn scheduler_vk_hash(&self, verifier_address: Address)
         -> Result<H256, ContractCallError>;
     /// Sets list of topics to return events for.
     fn set_topics(&mut self, topics: Vec<H256>);
+    /// Sets list of contracts to return events for in addition to the default ones.
+    fn set_extra_contract_addresses(&mut self, addresses: Vec<Address>);
 }
 
 pub const RETRY_LIMIT: usize = 5;
@@ -40,6 +44,7 @@ const TOO_MANY_RESULTS_ALCHEMY: &str = "response size exceeded";
 pub struct EthHttpQueryClient {
     client: Box<DynClient<L1>>,
     topics: Vec<H256>,
+    extra_contract_addresses: Vec<Address>,
     diamond_proxy_addr: Address,
     governance_address: Address,
     // Only present for post-shared bridge chains.
@@ -64,6 +69,7 @@ impl EthHttpQueryClient {
         Self {
             client: client.for_component("watch"),
             topics: Vec::new(),
+            extra_contract_addresses: Vec::new(),
             diamond_proxy_addr,
             state_transition_manager_address,
             governance_address,
@@ -87,6 +93,7 @@ impl EthHttpQueryClient {
                 ]
                 .into_iter()
                 .flatten()
+                .chain(self.extra_contract_addresses.iter().copied())
                 .collect(),
             )
             .from_block(from)
@@ -206,7 +213,19 @@ impl EthClient for EthHttpQueryClient {
         }
     }
 
+    async fn block_hash(&self, number: u64) -> EnrichedClientResult<Option<H256>> {
+        let block = self
+            .client
+            .block(BlockId::Number(BlockNumber::Number(number.into())))
+            .await?;
+        Ok(block.and_then(|block| block.hash))
+    }
+
     fn set_topics(&mut self, topics: Vec<H256>) {
         self.topics = topics;
     }
+
+    fn set_extra_contract_addresses(&mut self, addresses: Vec<Address>) {
+        self.extra_contract_addresses = addresses;
+    }
 }
@@ -16,6 +16,7 @@ use crate::{
 pub struct GovernanceUpgradesEventProcessor {
     // ZKsync diamond proxy
     target_contract_address: Address,
+    governance_address: Address,
     /// Last protocol version seen. Used to skip events for already known upgrade proposals.
     last_seen_protocol_version: ProtocolSemanticVersion,
     upgrade_proposal_signature: H256,
@@ -24,11 +25,13 @@ pub struct GovernanceUpgradesEventProcessor {
 impl GovernanceUpgradesEventProcessor {
     pub fn new(
         target_contract_address: Address,
+        governance_address: Address,
         last_seen_protocol_version: ProtocolSemanticVersion,
         governance_contract: &Contract,
     ) -> Self {
         Self {
             target_contract_address,
+            governance_address,
             last_seen_protocol_version,
             upgrade_proposal_signature: governance_contract
                 .event("TransparentOperationScheduled")
@@ -139,4 +142,12 @@ impl EventProcessor for GovernanceUpgradesEventProcessor {
     fn relevant_topic(&self) -> H256 {
         self.upgrade_proposal_signature
     }
+
+    fn name(&self) -> &'static str {
+        "governance_upgrades"
+    }
+
+    fn contract_addresses(&self) -> Vec<Address> {
+        vec![self.governance_address]
+    }
 }
@@ -2,7 +2,7 @@ use std::fmt;
 
 use zksync_dal::{Connection, Core};
 use zksync_eth_client::{ContractCallError, EnrichedClientError};
-use zksync_types::{web3::Log, H256};
+use zksync_types::{web3::Log, Address, H256};
 
 pub(crate) use self::{
     governance_upgrades::GovernanceUpgradesEventProcessor, priority_ops::PriorityOpsEventProcessor,
@@ -14,7 +14,7 @@ mod priority_ops;
 
 /// Errors issued by an [`EventProcessor`].
 #[derive(Debug, thiserror::Error)]
-pub(super) enum EventProcessorError {
+pub enum EventProcessorError {
     #[error("failed parsing a log into {log_kind}: {source:?}")]
     LogParse {
         log_kind: &'static str,
@@ -25,6 +25,9 @@ pub(super) enum EventProcessorError {
     Client(#[from] EnrichedClientError),
     #[error("Contract call error: {0}")]
     ContractCall(#[from] ContractCallError),
+    /// L1 reorg that cannot be handled by re-syncing the affected block range. Such reorgs are considered fatal.
+    #[error("unrecoverable L1 reorg detected at block #{block_number}: {details}")]
+    L1Reorg { block_number: u64, details: String },
     /// Internal errors are considered fatal (i.e., they bubble up and lead to the watcher termination).
     #[error("internal processing error: {0:?}")]
     Internal(#[from] anyhow::Error),
@@ -41,9 +44,17 @@ impl EventProcessorError {
 
 /// Processor for a single type of events emitted by the L1 contract. [`EthWatch`](crate::EthWatch)
 /// feeds events to all processors one-by-one.
+///
+/// Besides built-in processors, custom ones can be registered using [`EthWatch::add_event_processor()`](crate::EthWatch::add_event_processor()).
+/// Each processor has its own cursor (the last processed L1 block) persisted in Postgres, so processors
+/// can be added to an existing node without re-processing events for other processors.
 #[async_trait::async_trait]
-pub(super) trait EventProcessor: 'static + fmt::Debug + Send + Sync {
-    /// Processes given events. All events are guaranteed to match [`Self::relevant_topic()`].
+pub trait EventProcessor: 'static + fmt::Debug + Send + Sync {
+    /// Processes given events. All events are guaranteed to match [`Self::relevant_topic()`]
+    /// and to be emitted by one of [`Self::contract_addresses()`].
+    ///
+    /// The same events may be supplied to the processor several times (e.g., after a restart or an L1 reorg),
+    /// so the processor must be able to skip already processed events.
     async fn process_events(
         &mut self,
         storage: &mut Connection<'_, Core>,
@@ -53,4 +64,11 @@ pub(super) trait EventProcessor: 'static + fmt::Debug + Send + Sync {
 
     /// Relevant topic which defines what events to be processed
     fn relevant_topic(&self) -> H256;
+
+    /// Unique name of the processor. Used as a key for the processor cursor persisted in Postgres.
+    fn name(&self) -> &'static str;
+
+    /// Addresses of contracts emitting relevant events. Must be non-empty; events with the relevant topic
+    /// emitted by other contracts are never supplied to the processor.
+    fn contract_addresses(&self) -> Vec<Address>;
 }
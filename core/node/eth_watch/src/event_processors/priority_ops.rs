@@ -4,7 +4,7 @@ use anyhow::Context;
 use zksync_contracts::hyperchain_contract;
 use zksync_dal::{Connection, Core, CoreDal, DalError};
 use zksync_shared_metrics::{TxStage, APP_METRICS};
-use zksync_types::{l1::L1Tx, web3::Log, PriorityOpId, H256};
+use zksync_types::{l1::L1Tx, web3::Log, Address, PriorityOpId, H256};
 
 use crate::{
     client::EthClient,
@@ -15,13 +15,18 @@ use crate::{
 /// Responsible for saving new priority L1 transactions to the database.
 #[derive(Debug)]
 pub struct PriorityOpsEventProcessor {
+    diamond_proxy_address: Address,
     next_expected_priority_id: PriorityOpId,
     new_priority_request_signature: H256,
 }
 
 impl PriorityOpsEventProcessor {
-    pub fn new(next_expected_priority_id: PriorityOpId) -> anyhow::Result<Self> {
+    pub fn new(
+        diamond_proxy_address: Address,
+        next_expected_priority_id: PriorityOpId,
+    ) -> anyhow::Result<Self> {
         Ok(Self {
+            diamond_proxy_address,
             next_expected_priority_id,
             new_priority_request_signature: hyperchain_contract()
                 .event("NewPriorityRequest")
@@ -99,4 +104,12 @@ impl EventProcessor for PriorityOpsEventProcessor {
     fn relevant_topic(&self) -> H256 {
         self.new_priority_request_signature
     }
+
+    fn name(&self) -> &'static str {
+        "priority_ops"
+    }
+
+    fn contract_addresses(&self) -> Vec<Address> {
+        vec![self.diamond_proxy_address]
+    }
 }
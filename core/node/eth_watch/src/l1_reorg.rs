@@ -0,0 +1,233 @@
+//! Tracking of processed L1 blocks used to detect L1 reorgs.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use zksync_dal::{eth_watcher_dal::ProcessedL1Block, Connection, Core, CoreDal, DalError};
+use zksync_types::{
+    web3::{keccak256, BlockNumber as Web3BlockNumber, Log},
+    H256,
+};
+
+use crate::{
+    client::{EthClient, RETRY_LIMIT},
+    event_processors::EventProcessorError,
+    metrics::METRICS,
+};
+
+/// Maximum number of processed L1 blocks tracked by the watcher. Reorgs deeper than the oldest
+/// tracked block cannot be handled and halt the watcher.
+const MAX_TRACKED_BLOCKS: usize = 256;
+
+/// Hashes of L1 blocks processed by [`EthWatch`](crate::EthWatch), together with the events ingested from them.
+/// Blocks are persisted in Postgres, so that reorgs spanning node restarts are detected as well.
+#[derive(Debug, Default)]
+pub(crate) struct ProcessedL1Blocks {
+    /// `None` if blocks are not loaded from Postgres yet.
+    blocks: Option<BTreeMap<u64, ProcessedL1Block>>,
+}
+
+impl ProcessedL1Blocks {
+    fn event_digest(event: &Log) -> H256 {
+        let mut bytes = Vec::with_capacity(event.topics.len() * 32 + event.data.0.len());
+        for topic in &event.topics {
+            bytes.extend_from_slice(topic.as_bytes());
+        }
+        bytes.extend_from_slice(&event.data.0);
+        H256(keccak256(&bytes))
+    }
+
+    async fn load(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+    ) -> Result<&mut BTreeMap<u64, ProcessedL1Block>, EventProcessorError> {
+        if self.blocks.is_none() {
+            let blocks = storage
+                .eth_watcher_dal()
+                .get_processed_l1_blocks()
+                .await
+                .map_err(DalError::generalize)?;
+            tracing::info!("Loaded {} processed L1 blocks from Postgres", blocks.len());
+            let blocks = blocks.into_iter().map(|block| (block.number, block));
+            self.blocks = Some(blocks.collect());
+        }
+        Ok(self.blocks.as_mut().unwrap())
+    }
+
+    /// Records the last processed L1 block together with the events ingested from the processed block range.
+    /// This should be called *before* the events are ingested, so that events are always covered by the reorg check.
+    pub async fn record(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        block_number: u64,
+        block_hash: Option<H256>,
+        events: &[Log],
+    ) -> Result<(), EventProcessorError> {
+        let blocks = self.load(storage).await?;
+        let mut updated_blocks = BTreeSet::new();
+        if let Some(hash) = block_hash {
+            let block = blocks
+                .entry(block_number)
+                .or_insert_with(|| ProcessedL1Block {
+                    number: block_number,
+                    hash,
+                    event_digests: vec![],
+                });
+            if block.hash != hash {
+                *block = ProcessedL1Block {
+                    number: block_number,
+                    hash,
+                    event_digests: vec![],
+                };
+            }
+            updated_blocks.insert(block_number);
+        }
+
+        for event in events {
+            let (Some(number), Some(hash)) = (event.block_number, event.block_hash) else {
+                continue;
+            };
+            let number = number.as_u64();
+            let block = blocks.entry(number).or_insert_with(|| ProcessedL1Block {
+                number,
+                hash,
+                event_digests: vec![],
+            });
+            if block.hash != hash {
+                // The event block hash is authoritative since it refers to the data that is actually ingested.
+                *block = ProcessedL1Block {
+                    number,
+                    hash,
+                    event_digests: vec![],
+                };
+            }
+            let digest = Self::event_digest(event);
+            if !block.event_digests.contains(&digest) {
+                block.event_digests.push(digest);
+            }
+            updated_blocks.insert(number);
+        }
+
+        while blocks.len() > MAX_TRACKED_BLOCKS {
+            blocks.pop_first();
+        }
+        let first_retained_block = blocks.first_key_value().map(|(&number, _)| number);
+
+        let mut dal = storage.eth_watcher_dal();
+        for number in updated_blocks {
+            if let Some(block) = blocks.get(&number) {
+                dal.save_processed_l1_block(block)
+                    .await
+                    .map_err(DalError::generalize)?;
+            }
+        }
+        if let Some(first_retained_block) = first_retained_block {
+            dal.delete_processed_l1_blocks_before(first_retained_block)
+                .await
+                .map_err(DalError::generalize)?;
+        }
+        Ok(())
+    }
+
+    /// Checks whether processed L1 blocks were reorged. On a hash mismatch for the last processed block, walks back
+    /// to the fork point, i.e. the latest processed block that is still canonical. Returns the fork point if
+    /// the affected block range can be safely re-synced, i.e., all events ingested from orphaned blocks are present
+    /// in the canonical chain as well. Orphaned blocks are not removed until [`Self::truncate()`] is called,
+    /// so the reorg is detected again if the node restarts before re-syncing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventProcessorError::L1Reorg`] if the reorg cannot be handled by re-syncing.
+    pub async fn check_for_reorg(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        client: &dyn EthClient,
+    ) -> Result<Option<u64>, EventProcessorError> {
+        let blocks = self.load(storage).await?;
+        let Some((&last_number, last_block)) = blocks.last_key_value() else {
+            return Ok(None);
+        };
+        let actual_hash = client.block_hash(last_number).await?;
+        if actual_hash == Some(last_block.hash) {
+            return Ok(None);
+        }
+
+        METRICS.l1_reorgs.inc();
+        tracing::warn!(
+            "L1 reorg detected: hash of processed L1 block #{last_number} changed from {:?} to {actual_hash:?}",
+            last_block.hash
+        );
+
+        let mut fork_point = None;
+        for (&number, block) in blocks.iter().rev().skip(1) {
+            if client.block_hash(number).await? == Some(block.hash) {
+                fork_point = Some(number);
+                break;
+            }
+        }
+        let Some(fork_point) = fork_point else {
+            return Err(EventProcessorError::L1Reorg {
+                block_number: last_number,
+                details: format!(
+                    "reorg is deeper than the oldest tracked L1 block #{}",
+                    blocks.first_key_value().map_or(last_number, |(&n, _)| n)
+                ),
+            });
+        };
+
+        let orphaned_blocks = blocks.range(fork_point + 1..);
+        let ingested_digests: Vec<_> = orphaned_blocks
+            .clone()
+            .flat_map(|(_, block)| block.event_digests.iter().copied())
+            .collect();
+        if !ingested_digests.is_empty() {
+            let canonical_events = client
+                .get_events(
+                    Web3BlockNumber::Number((fork_point + 1).into()),
+                    Web3BlockNumber::Number(last_number.into()),
+                    RETRY_LIMIT,
+                )
+                .await?;
+            let canonical_digests: HashSet<_> =
+                canonical_events.iter().map(Self::event_digest).collect();
+            if let Some(missing) = ingested_digests
+                .iter()
+                .find(|digest| !canonical_digests.contains(digest))
+            {
+                let (&orphaned_number, _) = orphaned_blocks
+                    .clone()
+                    .find(|(_, block)| block.event_digests.contains(missing))
+                    .unwrap(); // `missing` is taken from one of `orphaned_blocks`
+                return Err(EventProcessorError::L1Reorg {
+                    block_number: orphaned_number,
+                    details: format!(
+                        "event with digest {missing:?} was ingested from an orphaned L1 block \
+                         and is not present in the canonical chain"
+                    ),
+                });
+            }
+        }
+
+        tracing::warn!(
+            "All events ingested from orphaned L1 blocks are present in the canonical chain; \
+             re-syncing L1 blocks after #{fork_point}"
+        );
+        Ok(Some(fork_point))
+    }
+
+    /// Removes processed blocks after the specified fork point. Should be called after processor cursors
+    /// are rewound to the fork point.
+    pub async fn truncate(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        fork_point: u64,
+    ) -> Result<(), EventProcessorError> {
+        let blocks = self.load(storage).await?;
+        blocks.split_off(&(fork_point + 1));
+        storage
+            .eth_watcher_dal()
+            .delete_processed_l1_blocks_after(fork_point)
+            .await
+            .map_err(DalError::generalize)?;
+        Ok(())
+    }
+}
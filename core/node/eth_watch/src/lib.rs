@@ -2,29 +2,33 @@
 //! protocol upgrades etc.
 //! New events are accepted to the ZKsync network once they have the sufficient amount of L1 confirmations.
 
-use std::time::Duration;
+use std::{collections::HashSet, time::Duration};
 
 use anyhow::Context as _;
 use tokio::sync::watch;
-use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalError};
 use zksync_system_constants::PRIORITY_EXPIRATION;
 use zksync_types::{
-    ethabi::Contract, protocol_version::ProtocolSemanticVersion,
-    web3::BlockNumber as Web3BlockNumber, Address, PriorityOpId,
+    ethabi::Contract,
+    protocol_version::ProtocolSemanticVersion,
+    web3::{BlockNumber as Web3BlockNumber, Log},
+    Address, PriorityOpId,
 };
 
-pub use self::client::EthHttpQueryClient;
 use self::{
-    client::{EthClient, RETRY_LIMIT},
-    event_processors::{
-        EventProcessor, EventProcessorError, GovernanceUpgradesEventProcessor,
-        PriorityOpsEventProcessor,
-    },
+    client::RETRY_LIMIT,
+    event_processors::{GovernanceUpgradesEventProcessor, PriorityOpsEventProcessor},
+    l1_reorg::ProcessedL1Blocks,
     metrics::{PollStage, METRICS},
 };
+pub use self::{
+    client::{EthClient, EthHttpQueryClient},
+    event_processors::{EventProcessor, EventProcessorError},
+};
 
 mod client;
 mod event_processors;
+mod l1_reorg;
 mod metrics;
 #[cfg(test)]
 mod tests;
@@ -36,21 +40,48 @@ struct EthWatchState {
     last_processed_ethereum_block: u64,
 }
 
+/// Event processor registered in [`EthWatch`] together with its cursor.
+#[derive(Debug)]
+struct RegisteredProcessor {
+    processor: Box<dyn EventProcessor>,
+    /// Addresses of contracts emitting events relevant to the processor.
+    contract_addresses: HashSet<Address>,
+    /// Last L1 block processed by the processor. `None` if the cursor is not loaded from Postgres yet.
+    last_processed_block: Option<u64>,
+}
+
+impl RegisteredProcessor {
+    fn is_relevant(&self, event: &Log, from_block: u64) -> bool {
+        if event.topics.first() != Some(&self.processor.relevant_topic()) {
+            return false;
+        }
+        if !self.contract_addresses.contains(&event.address) {
+            return false;
+        }
+        event
+            .block_number
+            .map_or(true, |number| number.as_u64() >= from_block)
+    }
+}
+
 /// Ethereum watcher component.
 #[derive(Debug)]
 pub struct EthWatch {
     client: Box<dyn EthClient>,
     poll_interval: Duration,
-    event_processors: Vec<Box<dyn EventProcessor>>,
-    last_processed_ethereum_block: u64,
+    event_processors: Vec<RegisteredProcessor>,
+    /// L1 block to start processing from for processors without a persisted cursor.
+    initial_l1_block: u64,
+    processed_l1_blocks: ProcessedL1Blocks,
     pool: ConnectionPool<Core>,
 }
 
 impl EthWatch {
     pub async fn new(
         diamond_proxy_addr: Address,
+        governance_address: Address,
         governance_contract: &Contract,
-        mut client: Box<dyn EthClient>,
+        client: Box<dyn EthClient>,
         pool: ConnectionPool<Core>,
         poll_interval: Duration,
     ) -> anyhow::Result<Self> {
@@ -60,30 +91,72 @@ impl EthWatch {
         drop(storage);
 
         let priority_ops_processor =
-            PriorityOpsEventProcessor::new(state.next_expected_priority_id)?;
+            PriorityOpsEventProcessor::new(diamond_proxy_addr, state.next_expected_priority_id)?;
         let governance_upgrades_processor = GovernanceUpgradesEventProcessor::new(
             diamond_proxy_addr,
+            governance_address,
             state.last_seen_protocol_version,
             governance_contract,
         );
-        let event_processors: Vec<Box<dyn EventProcessor>> = vec![
-            Box::new(priority_ops_processor),
-            Box::new(governance_upgrades_processor),
-        ];
-
-        let topics = event_processors
-            .iter()
-            .map(|processor| processor.relevant_topic())
-            .collect();
-        client.set_topics(topics);
-
-        Ok(Self {
+
+        let mut this = Self {
             client,
             poll_interval,
-            event_processors,
-            last_processed_ethereum_block: state.last_processed_ethereum_block,
+            event_processors: vec![],
+            initial_l1_block: state.last_processed_ethereum_block,
+            processed_l1_blocks: ProcessedL1Blocks::default(),
             pool,
-        })
+        };
+        this.add_event_processor(Box::new(priority_ops_processor))?;
+        this.add_event_processor(Box::new(governance_upgrades_processor))?;
+        Ok(this)
+    }
+
+    /// Registers an additional event processor. Events for the processor are fetched starting from
+    /// its cursor persisted in Postgres, or from the block the watcher was initialized with if there is no cursor yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a processor with the same [name](EventProcessor::name()) is already registered,
+    /// or if the processor doesn't specify [contract addresses](EventProcessor::contract_addresses()).
+    pub fn add_event_processor(
+        &mut self,
+        processor: Box<dyn EventProcessor>,
+    ) -> anyhow::Result<()> {
+        let name = processor.name();
+        anyhow::ensure!(
+            self.event_processors
+                .iter()
+                .all(|registered| registered.processor.name() != name),
+            "event processor `{name}` is already registered"
+        );
+        let contract_addresses: HashSet<_> = processor.contract_addresses().into_iter().collect();
+        anyhow::ensure!(
+            !contract_addresses.is_empty(),
+            "event processor `{name}` doesn't specify contract addresses"
+        );
+        self.event_processors.push(RegisteredProcessor {
+            processor,
+            contract_addresses,
+            last_processed_block: None,
+        });
+
+        let mut topics = vec![];
+        let mut contract_addresses = vec![];
+        for registered in &self.event_processors {
+            let topic = registered.processor.relevant_topic();
+            if !topics.contains(&topic) {
+                topics.push(topic);
+            }
+            for &address in &registered.contract_addresses {
+                if !contract_addresses.contains(&address) {
+                    contract_addresses.push(address);
+                }
+            }
+        }
+        self.client.set_topics(topics);
+        self.client.set_extra_contract_addresses(contract_addresses);
+        Ok(())
     }
 
     async fn initialize_state(
@@ -143,14 +216,22 @@ impl EthWatch {
                     tracing::error!("Internal error processing new blocks: {err:?}");
                     return Err(err);
                 }
+                Err(err @ EventProcessorError::L1Reorg { .. }) => {
+                    // Events from orphaned L1 blocks may have already been ingested, so we cannot proceed.
+                    tracing::error!("Failed to process new blocks: {err}");
+                    return Err(err.into());
+                }
                 Err(err) => {
                     // This is an error because otherwise we could potentially miss a priority operation
                     // thus entering priority mode, which is not desired.
                     tracing::error!("Failed to process new blocks: {err}");
-                    self.last_processed_ethereum_block =
-                        Self::initialize_state(&*self.client, &mut storage)
-                            .await?
-                            .last_processed_ethereum_block;
+                    self.initial_l1_block = Self::initialize_state(&*self.client, &mut storage)
+                        .await?
+                        .last_processed_ethereum_block;
+                    // Cursors will be reloaded from Postgres on the next iteration.
+                    for registered in &mut self.event_processors {
+                        registered.last_processed_block = None;
+                    }
                 }
             }
         }
@@ -159,39 +240,115 @@ impl EthWatch {
         Ok(())
     }
 
+    /// Loads cursors of processors from Postgres and returns the minimum cursor value.
+    async fn load_cursors(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+    ) -> Result<u64, EventProcessorError> {
+        let mut min_cursor = u64::MAX;
+        for registered in &mut self.event_processors {
+            let cursor = match registered.last_processed_block {
+                Some(cursor) => cursor,
+                None => {
+                    let cursor = storage
+                        .eth_watcher_dal()
+                        .get_last_processed_l1_block(registered.processor.name())
+                        .await
+                        .map_err(DalError::generalize)?
+                        .unwrap_or(self.initial_l1_block);
+                    registered.last_processed_block = Some(cursor);
+                    cursor
+                }
+            };
+            min_cursor = min_cursor.min(cursor);
+        }
+        Ok(min_cursor)
+    }
+
+    /// Rewinds cursors of all processors so that L1 blocks after `fork_point` are re-synced.
+    async fn rewind_cursors(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        fork_point: u64,
+    ) -> Result<(), EventProcessorError> {
+        self.load_cursors(storage).await?;
+        for registered in &mut self.event_processors {
+            let cursor = registered.last_processed_block.unwrap(); // loaded above
+            if cursor > fork_point {
+                storage
+                    .eth_watcher_dal()
+                    .set_last_processed_l1_block(registered.processor.name(), fork_point)
+                    .await
+                    .map_err(DalError::generalize)?;
+                registered.last_processed_block = Some(fork_point);
+            }
+        }
+        Ok(())
+    }
+
     #[tracing::instrument(skip_all)]
     async fn loop_iteration(
         &mut self,
         storage: &mut Connection<'_, Core>,
     ) -> Result<(), EventProcessorError> {
+        if let Some(fork_point) = self
+            .processed_l1_blocks
+            .check_for_reorg(storage, self.client.as_ref())
+            .await?
+        {
+            // Cursors are rewound before orphaned blocks are removed, so that the reorg is re-detected
+            // if the watcher is interrupted in between.
+            self.rewind_cursors(storage, fork_point).await?;
+            self.processed_l1_blocks
+                .truncate(storage, fork_point)
+                .await?;
+        }
+        let from_block = self.load_cursors(storage).await?;
+
         let stage_latency = METRICS.poll_eth_node[&PollStage::Request].start();
         let to_block = self.client.finalized_block_number().await?;
-        if to_block <= self.last_processed_ethereum_block {
+        if to_block <= from_block {
             return Ok(());
         }
+        // Obtained before the events so that a reorg happening in between is detected on the next iteration.
+        let to_block_hash = self.client.block_hash(to_block).await?;
 
         let events = self
             .client
             .get_events(
-                Web3BlockNumber::Number(self.last_processed_ethereum_block.into()),
+                Web3BlockNumber::Number(from_block.into()),
                 Web3BlockNumber::Number(to_block.into()),
                 RETRY_LIMIT,
             )
             .await?;
         stage_latency.observe();
+        // Blocks are recorded before the events are ingested, so that the ingested events are always
+        // covered by the reorg check, even if the watcher is interrupted.
+        self.processed_l1_blocks
+            .record(storage, to_block, to_block_hash, &events)
+            .await?;
 
-        for processor in &mut self.event_processors {
-            let relevant_topic = processor.relevant_topic();
+        for registered in &mut self.event_processors {
+            let processor_from_block = registered.last_processed_block.unwrap(); // loaded above
+            if to_block <= processor_from_block {
+                continue;
+            }
             let processor_events = events
                 .iter()
-                .filter(|event| event.topics.first() == Some(&relevant_topic))
+                .filter(|event| registered.is_relevant(event, processor_from_block))
                 .cloned()
                 .collect();
-            processor
+            registered
+                .processor
                 .process_events(storage, &*self.client, processor_events)
                 .await?;
+            storage
+                .eth_watcher_dal()
+                .set_last_processed_l1_block(registered.processor.name(), to_block)
+                .await
+                .map_err(DalError::generalize)?;
+            registered.last_processed_block = Some(to_block);
         }
-        self.last_processed_ethereum_block = to_block;
         Ok(())
     }
 }
@@ -20,6 +20,8 @@ pub(super) struct EthWatcherMetrics {
     /// Latency of polling and processing events split by stage.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub poll_eth_node: Family<PollStage, Histogram<Duration>>,
+    /// Number of detected L1 reorgs affecting processed L1 blocks.
+    pub l1_reorgs: Counter,
 }
 
 #[vise::register]
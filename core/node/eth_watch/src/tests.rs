@@ -1,4 +1,8 @@
-use std::{collections::HashMap, convert::TryInto, sync::Arc};
+use std::{
+    collections::HashMap,
+    convert::TryInto,
+    sync::{Arc, Mutex},
+};
 
 use tokio::sync::RwLock;
 use zksync_contracts::{governance_contract, hyperchain_contract};
@@ -15,14 +19,20 @@ use zksync_types::{
     ProtocolVersionId, Transaction, H256, U256,
 };
 
-use crate::{client::EthClient, EthWatch};
+use crate::{client::EthClient, EthWatch, EventProcessor, EventProcessorError};
+
+const DIAMOND_PROXY_ADDR: Address = Address::repeat_byte(0x01);
+const GOVERNANCE_ADDR: Address = Address::repeat_byte(0x02);
 
 #[derive(Debug)]
 struct FakeEthClientData {
     transactions: HashMap<u64, Vec<Log>>,
     diamond_upgrades: HashMap<u64, Vec<Log>>,
     governance_upgrades: HashMap<u64, Vec<Log>>,
+    other_events: HashMap<u64, Vec<Log>>,
     last_finalized_block_number: u64,
+    /// First blocks of the forks introduced by L1 reorgs.
+    reorgs: Vec<u64>,
 }
 
 impl FakeEthClientData {
@@ -31,10 +41,19 @@ impl FakeEthClientData {
             transactions: Default::default(),
             diamond_upgrades: Default::default(),
             governance_upgrades: Default::default(),
+            other_events: Default::default(),
             last_finalized_block_number: 0,
+            reorgs: vec![],
         }
     }
 
+    fn block_hash(&self, number: u64) -> H256 {
+        let fork_index = self.reorgs.iter().filter(|&&from| number >= from).count();
+        let mut hash = H256::from_low_u64_be(number);
+        hash.0[0] = fork_index as u8;
+        hash
+    }
+
     fn add_transactions(&mut self, transactions: &[L1Tx]) {
         for transaction in transactions {
             let eth_block = transaction.eth_block();
@@ -57,6 +76,17 @@ impl FakeEthClientData {
     fn set_last_finalized_block_number(&mut self, number: u64) {
         self.last_finalized_block_number = number;
     }
+
+    /// Replaces all blocks starting from `from_block` with empty blocks from a new fork.
+    fn reorg(&mut self, from_block: u64) {
+        self.reorgs.push(from_block);
+        self.transactions.retain(|&number, _| number < from_block);
+        self.diamond_upgrades
+            .retain(|&number, _| number < from_block);
+        self.governance_upgrades
+            .retain(|&number, _| number < from_block);
+        self.other_events.retain(|&number, _| number < from_block);
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -86,6 +116,20 @@ impl MockEthClient {
             .set_last_finalized_block_number(number);
     }
 
+    async fn add_event(&mut self, eth_block: u64, event: Log) {
+        self.inner
+            .write()
+            .await
+            .other_events
+            .entry(eth_block)
+            .or_default()
+            .push(event);
+    }
+
+    async fn reorg(&mut self, from_block: u64) {
+        self.inner.write().await.reorg(from_block);
+    }
+
     async fn block_to_number(&self, block: BlockNumber) -> u64 {
         match block {
             BlockNumber::Earliest => 0,
@@ -110,6 +154,7 @@ impl EthClient for MockEthClient {
         let to = self.block_to_number(to).await;
         let mut logs = vec![];
         for number in from..=to {
+            let start = logs.len();
             if let Some(ops) = self.inner.read().await.transactions.get(&number) {
                 logs.extend_from_slice(ops);
             }
@@ -119,12 +164,21 @@ impl EthClient for MockEthClient {
             if let Some(ops) = self.inner.read().await.governance_upgrades.get(&number) {
                 logs.extend_from_slice(ops);
             }
+            if let Some(events) = self.inner.read().await.other_events.get(&number) {
+                logs.extend_from_slice(events);
+            }
+            let block_hash = self.inner.read().await.block_hash(number);
+            for log in &mut logs[start..] {
+                log.block_hash = Some(block_hash);
+            }
         }
         Ok(logs)
     }
 
     fn set_topics(&mut self, _topics: Vec<Hash>) {}
 
+    fn set_extra_contract_addresses(&mut self, _addresses: Vec<Address>) {}
+
     async fn scheduler_vk_hash(
         &self,
         _verifier_address: Address,
@@ -135,6 +189,10 @@ impl EthClient for MockEthClient {
     async fn finalized_block_number(&self) -> EnrichedClientResult<u64> {
         Ok(self.inner.read().await.last_finalized_block_number)
     }
+
+    async fn block_hash(&self, number: u64) -> EnrichedClientResult<Option<H256>> {
+        Ok(Some(self.inner.read().await.block_hash(number)))
+    }
 }
 
 fn build_l1_tx(serial_id: u64, eth_block: u64) -> L1Tx {
@@ -197,9 +255,16 @@ fn build_upgrade_tx(id: ProtocolVersionId, eth_block: u64) -> ProtocolUpgradeTx
 }
 
 async fn create_test_watcher(connection_pool: ConnectionPool<Core>) -> (EthWatch, MockEthClient) {
-    let client = MockEthClient::new();
+    create_test_watcher_with_client(connection_pool, MockEthClient::new()).await
+}
+
+async fn create_test_watcher_with_client(
+    connection_pool: ConnectionPool<Core>,
+    client: MockEthClient,
+) -> (EthWatch, MockEthClient) {
     let watcher = EthWatch::new(
-        Address::default(),
+        DIAMOND_PROXY_ADDR,
+        GOVERNANCE_ADDR,
         &governance_contract(),
         Box::new(client.clone()),
         connection_pool,
@@ -291,7 +356,8 @@ async fn test_normal_operation_governance_upgrades() {
 
     let mut client = MockEthClient::new();
     let mut watcher = EthWatch::new(
-        Address::default(),
+        DIAMOND_PROXY_ADDR,
+        GOVERNANCE_ADDR,
         &governance_contract(),
         Box::new(client.clone()),
         connection_pool.clone(),
@@ -458,6 +524,340 @@ async fn test_overlapping_batches() {
     assert_eq!(tx.common_data.serial_id.0, 4);
 }
 
+#[tokio::test]
+async fn l1_reorg_without_ingested_events_is_resynced() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (mut watcher, mut client) = create_test_watcher(connection_pool.clone()).await;
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    client.add_transactions(&[build_l1_tx(0, 10)]).await;
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(get_all_db_txs(&mut storage).await.len(), 1);
+
+    // The new fork contains a priority op in the already processed block range.
+    client.reorg(12).await;
+    client.add_transactions(&[build_l1_tx(1, 13)]).await;
+    client.set_last_finalized_block_number(20).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+
+    let mut db_txs: Vec<L1Tx> = get_all_db_txs(&mut storage)
+        .await
+        .into_iter()
+        .map(|tx| tx.try_into().unwrap())
+        .collect();
+    db_txs.sort_by_key(|tx| tx.common_data.serial_id);
+    assert_eq!(db_txs.len(), 2);
+    assert_eq!(db_txs[1].common_data.serial_id.0, 1);
+    assert_eq!(db_txs[1].common_data.eth_block, 13);
+}
+
+#[tokio::test]
+async fn l1_reorg_with_reincluded_events_is_resynced() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (mut watcher, mut client) = create_test_watcher(connection_pool.clone()).await;
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    client
+        .add_transactions(&[build_l1_tx(0, 10), build_l1_tx(1, 14)])
+        .await;
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+
+    // The priority op is included into the new fork at the same height.
+    client.reorg(12).await;
+    client
+        .add_transactions(&[build_l1_tx(1, 14), build_l1_tx(2, 16)])
+        .await;
+    client.set_last_finalized_block_number(20).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(get_all_db_txs(&mut storage).await.len(), 3);
+}
+
+#[tokio::test]
+async fn l1_reorg_orphaning_ingested_events_is_fatal() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (mut watcher, mut client) = create_test_watcher(connection_pool.clone()).await;
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    client
+        .add_transactions(&[build_l1_tx(0, 10), build_l1_tx(1, 14)])
+        .await;
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+
+    // The ingested priority op from block 14 is orphaned.
+    client.reorg(12).await;
+    client.set_last_finalized_block_number(20).await;
+    let err = watcher.loop_iteration(&mut storage).await.unwrap_err();
+    assert!(
+        matches!(
+            err,
+            EventProcessorError::L1Reorg {
+                block_number: 14,
+                ..
+            }
+        ),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn l1_reorg_is_detected_after_restart() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (mut watcher, mut client) = create_test_watcher(connection_pool.clone()).await;
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    client.add_transactions(&[build_l1_tx(0, 10)]).await;
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    drop(watcher);
+
+    let processed_blocks = storage
+        .eth_watcher_dal()
+        .get_processed_l1_blocks()
+        .await
+        .unwrap();
+    let processed_block_numbers: Vec<_> =
+        processed_blocks.iter().map(|block| block.number).collect();
+    assert_eq!(processed_block_numbers, [10, 15]);
+
+    // The reorg happens while the watcher is stopped.
+    client.reorg(12).await;
+    client.add_transactions(&[build_l1_tx(1, 13)]).await;
+    client.set_last_finalized_block_number(20).await;
+    let (mut watcher, _) = create_test_watcher_with_client(connection_pool.clone(), client).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+
+    let mut db_txs: Vec<L1Tx> = get_all_db_txs(&mut storage)
+        .await
+        .into_iter()
+        .map(|tx| tx.try_into().unwrap())
+        .collect();
+    db_txs.sort_by_key(|tx| tx.common_data.serial_id);
+    assert_eq!(db_txs.len(), 2);
+    assert_eq!(db_txs[1].common_data.eth_block, 13);
+
+    // Orphaned blocks are replaced with the canonical ones.
+    let processed_blocks = storage
+        .eth_watcher_dal()
+        .get_processed_l1_blocks()
+        .await
+        .unwrap();
+    let processed_block_numbers: Vec<_> =
+        processed_blocks.iter().map(|block| block.number).collect();
+    assert_eq!(processed_block_numbers, [10, 13, 20]);
+}
+
+#[tokio::test]
+async fn l1_reorg_orphaning_ingested_events_is_fatal_after_restart() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (mut watcher, mut client) = create_test_watcher(connection_pool.clone()).await;
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    client
+        .add_transactions(&[build_l1_tx(0, 10), build_l1_tx(1, 14)])
+        .await;
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    drop(watcher);
+
+    // The reorg spans multiple blocks and orphans the priority op from block 14.
+    client.reorg(11).await;
+    client.set_last_finalized_block_number(20).await;
+    let (mut watcher, _) = create_test_watcher_with_client(connection_pool.clone(), client).await;
+    let err = watcher.loop_iteration(&mut storage).await.unwrap_err();
+    assert!(
+        matches!(
+            err,
+            EventProcessorError::L1Reorg {
+                block_number: 14,
+                ..
+            }
+        ),
+        "{err:?}"
+    );
+}
+
+/// Event processor collecting all events it receives.
+#[derive(Debug, Clone)]
+struct CollectingEventProcessor {
+    topic: H256,
+    contract_address: Address,
+    events: Arc<Mutex<Vec<Log>>>,
+}
+
+impl CollectingEventProcessor {
+    fn new() -> Self {
+        Self {
+            topic: H256::repeat_byte(0xee),
+            contract_address: Address::repeat_byte(0xaa),
+            events: Arc::default(),
+        }
+    }
+
+    fn event(&self, contract_address: Address, eth_block: u64) -> Log {
+        Log {
+            address: contract_address,
+            topics: vec![self.topic],
+            data: vec![1, 2, 3].into(),
+            block_number: Some(eth_block.into()),
+            ..Log::default()
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventProcessor for CollectingEventProcessor {
+    async fn process_events(
+        &mut self,
+        _storage: &mut Connection<'_, Core>,
+        _client: &dyn EthClient,
+        events: Vec<Log>,
+    ) -> Result<(), EventProcessorError> {
+        self.events.lock().unwrap().extend(events);
+        Ok(())
+    }
+
+    fn relevant_topic(&self) -> H256 {
+        self.topic
+    }
+
+    fn name(&self) -> &'static str {
+        "collecting"
+    }
+
+    fn contract_addresses(&self) -> Vec<Address> {
+        vec![self.contract_address]
+    }
+}
+
+#[tokio::test]
+async fn custom_event_processor() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (mut watcher, mut client) = create_test_watcher(connection_pool.clone()).await;
+    let processor = CollectingEventProcessor::new();
+    watcher
+        .add_event_processor(Box::new(processor.clone()))
+        .unwrap();
+    // Processor names must be unique.
+    watcher
+        .add_event_processor(Box::new(processor.clone()))
+        .unwrap_err();
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    client.add_transactions(&[build_l1_tx(0, 10)]).await;
+    let expected_event = processor.event(processor.contract_address, 12);
+    client.add_event(12, expected_event.clone()).await;
+    // Priority op emitted by the contract of the custom processor; must not be processed as a priority op.
+    let mut foreign_priority_op = tx_into_log(build_l1_tx(1, 12));
+    foreign_priority_op.address = processor.contract_address;
+    client.add_event(12, foreign_priority_op).await;
+    // Emitted by an unrelated contract; must be filtered out.
+    client
+        .add_event(13, processor.event(Address::repeat_byte(0xbb), 13))
+        .await;
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+
+    let events = processor.events.lock().unwrap().clone();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].data, expected_event.data);
+    assert_eq!(events[0].block_number, expected_event.block_number);
+    assert_eq!(get_all_db_txs(&mut storage).await.len(), 1);
+
+    for name in ["priority_ops", "governance_upgrades", "collecting"] {
+        let cursor = storage
+            .eth_watcher_dal()
+            .get_last_processed_l1_block(name)
+            .await
+            .unwrap();
+        assert_eq!(cursor, Some(15), "{name}");
+    }
+}
+
+#[tokio::test]
+async fn event_processor_without_contract_addresses_is_rejected() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (mut watcher, _) = create_test_watcher(connection_pool).await;
+
+    #[derive(Debug)]
+    struct NoAddressesProcessor(CollectingEventProcessor);
+
+    #[async_trait::async_trait]
+    impl EventProcessor for NoAddressesProcessor {
+        async fn process_events(
+            &mut self,
+            storage: &mut Connection<'_, Core>,
+            client: &dyn EthClient,
+            events: Vec<Log>,
+        ) -> Result<(), EventProcessorError> {
+            self.0.process_events(storage, client, events).await
+        }
+
+        fn relevant_topic(&self) -> H256 {
+            self.0.relevant_topic()
+        }
+
+        fn name(&self) -> &'static str {
+            "no_addresses"
+        }
+
+        fn contract_addresses(&self) -> Vec<Address> {
+            vec![]
+        }
+    }
+
+    let processor = NoAddressesProcessor(CollectingEventProcessor::new());
+    let err = watcher
+        .add_event_processor(Box::new(processor))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("doesn't specify contract addresses"), "{err}");
+}
+
+#[tokio::test]
+async fn event_processor_cursors_are_persisted() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let mut storage = connection_pool.connection().await.unwrap();
+    storage
+        .eth_watcher_dal()
+        .set_last_processed_l1_block("collecting", 20)
+        .await
+        .unwrap();
+
+    let (mut watcher, mut client) = create_test_watcher(connection_pool.clone()).await;
+    let processor = CollectingEventProcessor::new();
+    watcher
+        .add_event_processor(Box::new(processor.clone()))
+        .unwrap();
+
+    // This event precedes the persisted cursor, so it must not be processed.
+    client
+        .add_event(12, processor.event(processor.contract_address, 12))
+        .await;
+    client
+        .add_event(22, processor.event(processor.contract_address, 22))
+        .await;
+    client.add_transactions(&[build_l1_tx(0, 12)]).await;
+    client.set_last_finalized_block_number(25).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+
+    let events = processor.events.lock().unwrap().clone();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].block_number, Some(22.into()));
+    // Other processors start from the initial block.
+    assert_eq!(get_all_db_txs(&mut storage).await.len(), 1);
+}
+
 async fn get_all_db_txs(storage: &mut Connection<'_, Core>) -> Vec<Transaction> {
     storage.transactions_dal().reset_mempool().await.unwrap();
     storage
@@ -491,7 +891,7 @@ fn tx_into_log(tx: L1Tx) -> Log {
     );
 
     Log {
-        address: Address::repeat_byte(0x1),
+        address: DIAMOND_PROXY_ADDR,
         topics: vec![hyperchain_contract()
             .event("NewPriorityRequest")
             .expect("NewPriorityRequest event is missing in abi")
@@ -520,7 +920,7 @@ fn upgrade_into_governor_log(upgrade: ProtocolUpgrade, eth_block: u64) -> Log {
         .chain(ethabi::encode(&[diamond_cut]))
         .collect();
     let governance_call = Token::Tuple(vec![
-        Token::Address(Default::default()),
+        Token::Address(DIAMOND_PROXY_ADDR),
         Token::Uint(U256::default()),
         Token::Bytes(diamond_upgrade_calldata),
     ]);
@@ -532,7 +932,7 @@ fn upgrade_into_governor_log(upgrade: ProtocolUpgrade, eth_block: u64) -> Log {
     let final_data = ethabi::encode(&[Token::FixedBytes(vec![0u8; 32]), governance_operation]);
 
     Log {
-        address: Address::repeat_byte(0x1),
+        address: GOVERNANCE_ADDR,
         topics: vec![
             governance_contract()
                 .event("TransparentOperationScheduled")
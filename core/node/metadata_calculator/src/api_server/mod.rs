@@ -28,11 +28,23 @@ mod tests;
 struct TreeProofsRequest {
     l1_batch_number: L1BatchNumber,
     hashed_keys: Vec<U256>,
+    /// If set, the response will contain a single compact multi-proof for all entries
+    /// instead of separate Merkle paths for each entry.
+    #[serde(default, skip_serializing_if = "is_false")]
+    compact: bool,
+}
+
+fn is_false(&value: &bool) -> bool {
+    !value
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct TreeProofsResponse {
+    /// Empty if a compact proof was requested.
+    #[serde(default)]
     entries: Vec<TreeEntryWithProof>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    multi_proof: Option<TreeEntriesWithMultiProof>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -77,6 +89,61 @@ impl TreeEntryWithProof {
     }
 }
 
+/// Tree entry included into [`TreeEntriesWithMultiProof`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TreeMultiProofEntry {
+    pub key: U256,
+    #[serde(default, skip_serializing_if = "H256::is_zero")]
+    pub value: H256,
+    #[serde(default, skip_serializing_if = "TreeEntryWithProof::is_zero")]
+    pub index: u64,
+}
+
+/// Compact proof for multiple tree entries. Hashes shared by Merkle paths of several entries
+/// are included only once.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TreeEntriesWithMultiProof {
+    /// Proven entries sorted by key.
+    pub entries: Vec<TreeMultiProofEntry>,
+    /// Lengths of Merkle paths for each of `entries`.
+    pub path_lengths: Vec<u16>,
+    /// Hashes necessary to restore the tree root hash in the depth-first traversal order.
+    pub hashes: Vec<H256>,
+}
+
+impl TreeEntriesWithMultiProof {
+    fn new(src: zksync_merkle_tree::TreeEntriesWithMultiProof) -> Self {
+        let entries = src.entries.into_iter().map(|entry| TreeMultiProofEntry {
+            key: entry.key,
+            value: entry.value,
+            index: entry.leaf_index,
+        });
+        Self {
+            entries: entries.collect(),
+            path_lengths: src.path_lengths,
+            hashes: src.hashes,
+        }
+    }
+
+    /// Verifies the proof.
+    pub fn verify(&self, trusted_root_hash: H256) -> anyhow::Result<()> {
+        let entries = self
+            .entries
+            .iter()
+            .map(|entry| zksync_merkle_tree::TreeEntry {
+                key: entry.key,
+                value: entry.value,
+                leaf_index: entry.index,
+            });
+        zksync_merkle_tree::TreeEntriesWithMultiProof {
+            entries: entries.collect(),
+            path_lengths: self.path_lengths.clone(),
+            hashes: self.hashes.clone(),
+        }
+        .verify(&Blake2Hasher, trusted_root_hash)
+    }
+}
+
 /// Server-side tree API error.
 #[derive(Debug)]
 enum TreeApiServerError {
@@ -175,6 +242,34 @@ pub trait TreeApiClient: 'static + Send + Sync + fmt::Debug {
         l1_batch_number: L1BatchNumber,
         hashed_keys: Vec<U256>,
     ) -> Result<Vec<TreeEntryWithProof>, TreeApiError>;
+
+    /// Obtains a compact multi-proof for the specified `hashed_keys` at the specified tree version
+    /// (= L1 batch number). Entries in the returned proof are sorted by key and deduplicated.
+    ///
+    /// The default implementation builds the multi-proof from proofs returned by [`Self::get_proofs()`].
+    async fn get_multi_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<TreeEntriesWithMultiProof, TreeApiError> {
+        let proofs = self
+            .get_proofs(l1_batch_number, hashed_keys.clone())
+            .await?;
+        let proofs = hashed_keys.into_iter().zip(proofs).map(|(key, proof)| {
+            let mut merkle_path = proof.merkle_path;
+            merkle_path.reverse();
+            zksync_merkle_tree::TreeEntryWithProof {
+                base: zksync_merkle_tree::TreeEntry {
+                    key,
+                    value: proof.value,
+                    leaf_index: proof.index,
+                },
+                merkle_path,
+            }
+        });
+        let multi_proof = zksync_merkle_tree::TreeEntriesWithMultiProof::new(proofs.collect());
+        Ok(TreeEntriesWithMultiProof::new(multi_proof))
+    }
 }
 
 /// In-memory client implementation.
@@ -202,6 +297,21 @@ impl TreeApiClient for LazyAsyncTreeReader {
             Err(TreeApiError::NotReady(None))
         }
     }
+
+    async fn get_multi_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<TreeEntriesWithMultiProof, TreeApiError> {
+        if let Some(reader) = self.read() {
+            reader
+                .get_multi_proof_inner(l1_batch_number, hashed_keys)
+                .await
+                .map_err(TreeApiError::NoVersion)
+        } else {
+            Err(TreeApiError::NotReady(None))
+        }
+    }
 }
 
 /// [`TreeApiClient`] implementation requesting data from a Merkle tree API server.
@@ -270,13 +380,42 @@ impl TreeApiClient for TreeApiHttpClient {
         l1_batch_number: L1BatchNumber,
         hashed_keys: Vec<U256>,
     ) -> Result<Vec<TreeEntryWithProof>, TreeApiError> {
+        let request = TreeProofsRequest {
+            l1_batch_number,
+            hashed_keys,
+            compact: false,
+        };
+        Ok(self.request_proofs(request).await?.entries)
+    }
+
+    async fn get_multi_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<TreeEntriesWithMultiProof, TreeApiError> {
+        let request = TreeProofsRequest {
+            l1_batch_number,
+            hashed_keys,
+            compact: true,
+        };
+        let response = self.request_proofs(request).await?;
+        let multi_proof = response.multi_proof.with_context(|| {
+            format!("no multi-proof in response for L1 batch #{l1_batch_number}")
+        })?;
+        Ok(multi_proof)
+    }
+}
+
+impl TreeApiHttpClient {
+    async fn request_proofs(
+        &self,
+        request: TreeProofsRequest,
+    ) -> Result<TreeProofsResponse, TreeApiError> {
+        let l1_batch_number = request.l1_batch_number;
         let response = self
             .inner
             .post(&self.proofs_url)
-            .json(&TreeProofsRequest {
-                l1_batch_number,
-                hashed_keys,
-            })
+            .json(&request)
             .send()
             .await
             .map_err(|err| {
@@ -305,7 +444,7 @@ impl TreeApiClient for TreeApiHttpClient {
         let response: TreeProofsResponse = response.json().await.with_context(|| {
             format!("failed deserializing proofs for L1 batch #{l1_batch_number}")
         })?;
-        Ok(response.entries)
+        Ok(response)
     }
 }
 
@@ -329,16 +468,42 @@ impl AsyncTreeReader {
         Ok(proofs.into_iter().map(TreeEntryWithProof::new).collect())
     }
 
+    async fn get_multi_proof_inner(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<TreeEntriesWithMultiProof, NoVersionError> {
+        let multi_proof = self
+            .clone()
+            .entries_with_multi_proof(l1_batch_number, hashed_keys)
+            .await?;
+        Ok(TreeEntriesWithMultiProof::new(multi_proof))
+    }
+
     async fn get_proofs_handler(
         State(this): State<Self>,
         Json(request): Json<TreeProofsRequest>,
     ) -> Result<Json<TreeProofsResponse>, TreeApiServerError> {
         let latency = API_METRICS.latency[&MerkleTreeApiMethod::GetProofs].start();
-        let entries = this
-            .get_proofs_inner(request.l1_batch_number, request.hashed_keys)
-            .await
-            .map_err(TreeApiServerError::NoTreeVersion)?;
-        let response = TreeProofsResponse { entries };
+        let response = if request.compact {
+            let multi_proof = this
+                .get_multi_proof_inner(request.l1_batch_number, request.hashed_keys)
+                .await
+                .map_err(TreeApiServerError::NoTreeVersion)?;
+            TreeProofsResponse {
+                entries: vec![],
+                multi_proof: Some(multi_proof),
+            }
+        } else {
+            let entries = this
+                .get_proofs_inner(request.l1_batch_number, request.hashed_keys)
+                .await
+                .map_err(TreeApiServerError::NoTreeVersion)?;
+            TreeProofsResponse {
+                entries,
+                multi_proof: None,
+            }
+        };
         latency.observe();
         Ok(Json(response))
     }
@@ -8,7 +8,7 @@ use tokio::{
     io::AsyncWriteExt,
     net::{TcpListener, TcpSocket},
 };
-use zksync_dal::{ConnectionPool, Core};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
 
 use super::*;
 use crate::tests::{gen_storage_logs, reset_db_state, run_calculator, setup_calculator};
@@ -25,10 +25,9 @@ async fn merkle_tree_api() {
     let calculator_task = tokio::spawn(run_calculator(calculator));
 
     let (stop_sender, stop_receiver) = watch::channel(false);
+    let tree_reader = tree_reader.wait().await.unwrap();
     let api_server = tree_reader
-        .wait()
-        .await
-        .unwrap()
+        .clone()
         .create_api_server(&api_addr, stop_receiver.clone())
         .unwrap();
     let local_addr = *api_server.local_addr();
@@ -51,7 +50,7 @@ async fn merkle_tree_api() {
     hashed_keys.extend((0_u8..10).map(|byte| U256::from_big_endian(&[byte; 32])));
 
     let proofs = api_client
-        .get_proofs(L1BatchNumber(5), hashed_keys)
+        .get_proofs(L1BatchNumber(5), hashed_keys.clone())
         .await
         .unwrap();
     assert_eq!(proofs.len(), 20);
@@ -61,6 +60,35 @@ async fn merkle_tree_api() {
         assert!(!proof.merkle_path.is_empty());
     }
 
+    let root_hash = pool
+        .connection()
+        .await
+        .unwrap()
+        .blocks_dal()
+        .get_l1_batch_state_root(L1BatchNumber(5))
+        .await
+        .unwrap()
+        .expect("no root hash");
+    let multi_proof = api_client
+        .get_multi_proof(L1BatchNumber(5), hashed_keys.clone())
+        .await
+        .unwrap();
+    assert_eq!(multi_proof.entries.len(), 20);
+    assert_eq!(
+        multi_proof
+            .entries
+            .iter()
+            .filter(|entry| entry.index != 0)
+            .count(),
+        10
+    );
+    multi_proof.verify(root_hash).unwrap();
+    let local_multi_proof = tree_reader
+        .get_multi_proof_inner(L1BatchNumber(5), hashed_keys)
+        .await
+        .unwrap();
+    assert_eq!(local_multi_proof, multi_proof);
+
     let err = api_client
         .get_proofs(L1BatchNumber(10), vec![])
         .await
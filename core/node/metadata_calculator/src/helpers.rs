@@ -22,8 +22,8 @@ use zksync_health_check::{CheckHealth, Health, HealthStatus, ReactiveHealthCheck
 use zksync_merkle_tree::{
     domain::{TreeMetadata, ZkSyncTree, ZkSyncTreeReader},
     recovery::{MerkleTreeRecovery, PersistenceThreadHandle},
-    Database, Key, MerkleTreeColumnFamily, NoVersionError, RocksDBWrapper, TreeEntry,
-    TreeEntryWithProof, TreeInstruction,
+    Database, Key, MerkleTreeColumnFamily, NoVersionError, RocksDBWrapper,
+    TreeEntriesWithMultiProof, TreeEntry, TreeEntryWithProof, TreeInstruction,
 };
 use zksync_storage::{RocksDB, RocksDBOptions, StalledWritesRetries, WeakRocksDB};
 use zksync_types::{
@@ -44,6 +44,8 @@ pub struct MerkleTreeInfo {
     pub mode: MerkleTreeMode,
     pub root_hash: H256,
     pub next_l1_batch_number: L1BatchNumber,
+    /// Oldest L1 batch retained in the tree, i.e., the first L1 batch for which Merkle proofs can be obtained.
+    /// Depending on the pruning settings, this may be greater than the first L1 batch retained in Postgres.
     pub min_l1_batch_number: Option<L1BatchNumber>,
     pub leaf_count: u64,
 }
@@ -307,9 +309,19 @@ pub struct AsyncTreeReader {
 }
 
 impl AsyncTreeReader {
+    /// Returns the underlying RocksDB instance, e.g. to create checkpoints of it.
+    pub fn rocksdb(&self) -> RocksDB<MerkleTreeColumnFamily> {
+        self.inner.db().clone().into_inner()
+    }
+
+    /// Returns the underlying synchronous tree reader, e.g. to read from the tree in blocking contexts.
+    pub fn sync_reader(&self) -> ZkSyncTreeReader {
+        self.inner.clone()
+    }
+
     fn downgrade(&self) -> WeakAsyncTreeReader {
         WeakAsyncTreeReader {
-            db: self.inner.db().clone().into_inner().downgrade(),
+            db: self.rocksdb().downgrade(),
             mode: self.mode,
         }
     }
@@ -366,6 +378,18 @@ impl AsyncTreeReader {
             .await
             .unwrap()
     }
+
+    pub async fn entries_with_multi_proof(
+        self,
+        l1_batch_number: L1BatchNumber,
+        keys: Vec<Key>,
+    ) -> Result<TreeEntriesWithMultiProof, NoVersionError> {
+        tokio::task::spawn_blocking(move || {
+            self.inner.entries_with_multi_proof(l1_batch_number, &keys)
+        })
+        .await
+        .unwrap()
+    }
 }
 
 /// Version of async tree reader that holds a weak reference to RocksDB. Used in [`MerkleTreeHealthCheck`].
@@ -385,7 +409,7 @@ impl WeakAsyncTreeReader {
 }
 
 /// Lazily initialized [`AsyncTreeReader`].
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LazyAsyncTreeReader(pub(super) watch::Receiver<Option<AsyncTreeReader>>);
 
 impl LazyAsyncTreeReader {
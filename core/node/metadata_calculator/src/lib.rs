@@ -3,6 +3,7 @@
 
 use std::{
     num::{NonZeroU32, NonZeroUsize},
+    path::Path,
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -15,7 +16,9 @@ use zksync_config::configs::{
 };
 use zksync_dal::{ConnectionPool, Core};
 use zksync_health_check::{CheckHealth, HealthUpdater, ReactiveHealthCheck};
+use zksync_merkle_tree::MerkleTreeColumnFamily;
 use zksync_object_store::ObjectStore;
+use zksync_rocksdb_checkpoints::restore_latest_checkpoint;
 
 use self::{
     helpers::{create_db, Delayer, GenericAsyncTree, MerkleTreeHealth, MerkleTreeHealthCheck},
@@ -128,6 +131,7 @@ pub struct MetadataCalculator {
     object_store: Option<Arc<dyn ObjectStore>>,
     pool: ConnectionPool<Core>,
     recovery_pool: ConnectionPool<Core>,
+    checkpoint_store: Option<Arc<dyn ObjectStore>>,
     delayer: Delayer,
     health_updater: HealthUpdater,
     max_l1_batches_per_iter: usize,
@@ -168,6 +172,7 @@ impl MetadataCalculator {
             pruning_handles_sender: oneshot::channel().0,
             object_store,
             recovery_pool: pool.clone(),
+            checkpoint_store: None,
             pool,
             delayer: Delayer::new(config.delay_interval),
             health_updater,
@@ -183,6 +188,14 @@ impl MetadataCalculator {
         self
     }
 
+    /// Sets an object store with RocksDB checkpoints of the tree. If the tree RocksDB doesn't exist on the calculator start,
+    /// it will be bootstrapped from the latest checkpoint in the store (if any) and then caught up with Postgres.
+    /// Checkpoints can be uploaded using [`RocksdbCheckpointTask`](zksync_rocksdb_checkpoints::RocksdbCheckpointTask).
+    pub fn with_checkpoint_store(mut self, checkpoint_store: Arc<dyn ObjectStore>) -> Self {
+        self.checkpoint_store = Some(checkpoint_store);
+        self
+    }
+
     /// Returns a health check for this calculator.
     pub fn tree_health_check(&self) -> impl CheckHealth {
         MerkleTreeHealthCheck::new(self.health_updater.subscribe(), self.tree_reader())
@@ -193,13 +206,44 @@ impl MetadataCalculator {
         LazyAsyncTreeReader(self.tree_reader.subscribe())
     }
 
-    /// Returns a task that can be used to prune the Merkle tree according to the pruning logs in Postgres.
+    /// Returns a task that can be used to prune the Merkle tree according to the pruning logs in Postgres
+    /// and tree retention settings (the latter can be configured on the returned task).
     /// This method should be called once; only the latest returned task will do any job, all previous ones
     /// will terminate immediately.
     pub fn pruning_task(&mut self, poll_interval: Duration) -> MerkleTreePruningTask {
         let (pruning_handles_sender, pruning_handles) = oneshot::channel();
         self.pruning_handles_sender = pruning_handles_sender;
-        MerkleTreePruningTask::new(pruning_handles, self.pool.clone(), poll_interval)
+        MerkleTreePruningTask::new(
+            pruning_handles,
+            self.tree_reader(),
+            self.pool.clone(),
+            poll_interval,
+        )
+    }
+
+    /// Restores the tree RocksDB from the latest checkpoint if the checkpoint store is set and the DB doesn't exist.
+    /// Returns `true` if the DB was restored.
+    async fn restore_from_checkpoint(&self) -> anyhow::Result<bool> {
+        let Some(checkpoint_store) = &self.checkpoint_store else {
+            return Ok(false);
+        };
+        let db_path = Path::new(&self.config.db_path);
+        if db_path.exists() {
+            return Ok(false);
+        }
+
+        let manifest =
+            restore_latest_checkpoint::<MerkleTreeColumnFamily>(&**checkpoint_store, db_path)
+                .await
+                .context("failed restoring Merkle tree RocksDB from checkpoint")?;
+        let Some(manifest) = manifest else {
+            return Ok(false);
+        };
+        tracing::info!(
+            "Restored Merkle tree RocksDB from checkpoint #{}; the tree will be checked against and caught up with Postgres",
+            manifest.id
+        );
+        Ok(true)
     }
 
     async fn create_tree(&self) -> anyhow::Result<GenericAsyncTree> {
@@ -223,6 +267,7 @@ impl MetadataCalculator {
     }
 
     pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let restored_from_checkpoint = self.restore_from_checkpoint().await?;
         let tree = self.create_tree().await?;
         let tree = tree
             .ensure_ready(
@@ -240,6 +285,9 @@ impl MetadataCalculator {
         let tree_reader = tree.reader();
         self.tree_reader.send_replace(Some(tree_reader));
 
+        if restored_from_checkpoint {
+            tree.ensure_restored_tree_matches(&self.pool).await?;
+        }
         tree.ensure_consistency(&self.delayer, &self.pool, &mut stop_receiver)
             .await?;
         if !self.pruning_handles_sender.is_closed() {
@@ -1,6 +1,6 @@
 //! Merkle tree pruning logic.
 
-use std::time::Duration;
+use std::{num::NonZeroU32, time::Duration};
 
 use anyhow::Context as _;
 use serde::Serialize;
@@ -10,6 +10,8 @@ use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthChe
 use zksync_merkle_tree::{MerkleTreePruner, MerkleTreePrunerHandle, RocksDBWrapper};
 use zksync_types::L1BatchNumber;
 
+use crate::LazyAsyncTreeReader;
+
 pub(super) type PruningHandles = (MerkleTreePruner<RocksDBWrapper>, MerkleTreePrunerHandle);
 
 #[derive(Debug, Serialize)]
@@ -36,12 +38,115 @@ impl From<MerkleTreePruningTaskHealth> for Health {
     }
 }
 
-/// Task performing Merkle tree pruning according to the pruning entries in Postgres.
+/// Policy determining the target retained Merkle tree version.
+#[derive(Debug)]
+struct TreeRetentionPolicy {
+    tree_reader: LazyAsyncTreeReader,
+    pool: ConnectionPool<Core>,
+    retained_l1_batches: Option<NonZeroU32>,
+    retain_for_snapshots: bool,
+    /// Maximum number of L1 batches an incomplete snapshot can hold back tree pruning by. Incomplete snapshots
+    /// exceeding this limit are considered abandoned and are ignored.
+    max_snapshot_hold: u32,
+    /// Last incomplete snapshot that was reported to hold back pruning; used to not spam logs.
+    reported_snapshot_l1_batch: Option<L1BatchNumber>,
+}
+
+impl TreeRetentionPolicy {
+    /// Default value for [`Self::max_snapshot_hold`].
+    const DEFAULT_MAX_SNAPSHOT_HOLD: u32 = 10_000;
+
+    async fn target_retained_l1_batch(&mut self) -> anyhow::Result<Option<L1BatchNumber>> {
+        let mut storage = self.pool.connection_tagged("metadata_calculator").await?;
+        let pruning_info = storage.pruning_dal().get_pruning_info().await?;
+        let oldest_snapshot_l1_batch = if self.retain_for_snapshots {
+            storage
+                .snapshots_dal()
+                .get_oldest_incomplete_snapshot_l1_batch_number()
+                .await?
+        } else {
+            None
+        };
+        let last_executed_l1_batch = if self.retained_l1_batches.is_some() {
+            storage
+                .blocks_dal()
+                .get_number_of_last_l1_batch_executed_on_eth()
+                .await?
+        } else {
+            None
+        };
+        drop(storage);
+
+        let postgres_target = pruning_info
+            .last_hard_pruned_l1_batch
+            .map(|l1_batch_number| l1_batch_number + 1);
+        // Similarly to Postgres pruning, the last executed L1 batch and all following L1 batches are always retained.
+        let retention_target = match (
+            self.retained_l1_batches,
+            self.tree_reader.read(),
+            last_executed_l1_batch,
+        ) {
+            (Some(count), Some(reader), Some(last_executed_l1_batch)) => {
+                let next_l1_batch_number = reader.info().await.next_l1_batch_number;
+                next_l1_batch_number
+                    .0
+                    .checked_sub(count.get())
+                    .map(|target| L1BatchNumber(target).min(last_executed_l1_batch))
+            }
+            _ => None,
+        };
+        // `None` is less than any `Some(_)`, so this selects the most aggressive policy.
+        let Some(target) = postgres_target.max(retention_target) else {
+            return Ok(None);
+        };
+        let Some(snapshot_l1_batch) = oldest_snapshot_l1_batch else {
+            return Ok(Some(target));
+        };
+        if snapshot_l1_batch >= target {
+            return Ok(Some(target));
+        }
+
+        let hold = target.0 - snapshot_l1_batch.0;
+        let is_abandoned = hold > self.max_snapshot_hold;
+        if self.reported_snapshot_l1_batch != Some(snapshot_l1_batch) {
+            self.reported_snapshot_l1_batch = Some(snapshot_l1_batch);
+            if is_abandoned {
+                tracing::warn!(
+                    "Incomplete snapshot for L1 batch #{snapshot_l1_batch} holds back tree pruning by {hold} L1 batches, \
+                     which exceeds the limit of {} L1 batches; ignoring the snapshot. The snapshot is probably abandoned \
+                     and should be removed",
+                    self.max_snapshot_hold
+                );
+            } else {
+                tracing::warn!(
+                    "Incomplete snapshot for L1 batch #{snapshot_l1_batch} holds back tree pruning by {hold} L1 batches \
+                     (target: L1 batch #{target}); if the snapshot is abandoned, it should be removed"
+                );
+            }
+        }
+        Ok(Some(if is_abandoned {
+            target
+        } else {
+            snapshot_l1_batch
+        }))
+    }
+}
+
+/// Task performing Merkle tree pruning according to the pruning entries in Postgres and the tree retention settings.
+///
+/// The tree is pruned according to the most aggressive of the following policies:
+///
+/// - Following Postgres pruning (i.e., tree versions for L1 batches hard-pruned in Postgres are pruned as well).
+/// - Retaining the specified number of the latest tree versions (if configured). Tree versions for L1 batches
+///   starting from the last executed one are never pruned by this policy.
+///
+/// Additionally, tree versions for L1 batches of snapshots that are being created are retained (unless disabled).
+/// Snapshots holding back pruning for too long are considered abandoned and are ignored.
 #[derive(Debug)]
 #[must_use = "Task should `run()` in a managed Tokio task"]
 pub struct MerkleTreePruningTask {
     handles: oneshot::Receiver<PruningHandles>,
-    pool: ConnectionPool<Core>,
+    retention_policy: TreeRetentionPolicy,
     health_updater: HealthUpdater,
     poll_interval: Duration,
 }
@@ -49,22 +154,44 @@ pub struct MerkleTreePruningTask {
 impl MerkleTreePruningTask {
     pub(super) fn new(
         handles: oneshot::Receiver<PruningHandles>,
+        tree_reader: LazyAsyncTreeReader,
         pool: ConnectionPool<Core>,
         poll_interval: Duration,
     ) -> Self {
         Self {
             handles,
-            pool,
+            retention_policy: TreeRetentionPolicy {
+                tree_reader,
+                pool,
+                retained_l1_batches: None,
+                retain_for_snapshots: true,
+                max_snapshot_hold: TreeRetentionPolicy::DEFAULT_MAX_SNAPSHOT_HOLD,
+                reported_snapshot_l1_batch: None,
+            },
             health_updater: ReactiveHealthCheck::new("tree_pruner").1,
             poll_interval,
         }
     }
 
+    /// Sets the number of the latest tree versions (= L1 batches) to retain. If set, the tree will be pruned
+    /// even if Postgres isn't, but only up to the last executed L1 batch.
+    pub fn with_retained_l1_batches(mut self, count: Option<NonZeroU32>) -> Self {
+        self.retention_policy.retained_l1_batches = count;
+        self
+    }
+
+    /// Sets whether tree versions for L1 batches of snapshots that are being created should be retained.
+    /// Enabled by default.
+    pub fn with_snapshot_retention(mut self, retain_for_snapshots: bool) -> Self {
+        self.retention_policy.retain_for_snapshots = retain_for_snapshots;
+        self
+    }
+
     pub fn health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
     }
 
-    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+    pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         // The pruning task is "affected" (not functioning) until the Merkle tree is initialized.
         self.health_updater
             .update(MerkleTreePruningTaskHealth::Initialization.into());
@@ -97,12 +224,9 @@ impl MerkleTreePruningTask {
         let pruner_task_handle = tokio::task::spawn_blocking(|| pruner.run());
 
         while !*stop_receiver.borrow_and_update() {
-            let mut storage = self.pool.connection_tagged("metadata_calculator").await?;
-            let pruning_info = storage.pruning_dal().get_pruning_info().await?;
-            drop(storage);
-
-            if let Some(l1_batch_number) = pruning_info.last_hard_pruned_l1_batch {
-                let target_retained_l1_batch_number = l1_batch_number + 1;
+            if let Some(target_retained_l1_batch_number) =
+                self.retention_policy.target_retained_l1_batch().await?
+            {
                 let target_retained_version = u64::from(target_retained_l1_batch_number.0);
                 let Ok(prev_target_version) =
                     pruner_handle.set_target_retained_version(target_retained_version)
@@ -115,7 +239,8 @@ impl MerkleTreePruningTask {
                         .context("Merkle tree pruning thread panicked")?;
                 };
 
-                if prev_target_version != target_retained_version {
+                // The target version can only grow, so it's only updated if it's greater than the previous one.
+                if prev_target_version < target_retained_version {
                     let health = MerkleTreePruningTaskHealth::Pruning {
                         target_retained_l1_batch_number: Some(target_retained_l1_batch_number),
                     };
@@ -146,12 +271,17 @@ impl MerkleTreePruningTask {
 mod tests {
     use tempfile::TempDir;
     use test_casing::test_casing;
+    use zksync_dal::Connection;
     use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
     use zksync_node_test_utils::prepare_recovery_snapshot;
-    use zksync_types::{L1BatchNumber, L2BlockNumber};
+    use zksync_types::{
+        aggregated_operations::AggregatedActionType, snapshots::SnapshotVersion, L1BatchNumber,
+        L2BlockNumber, H256,
+    };
 
     use super::*;
     use crate::{
+        helpers::{AsyncTreeReader, MerkleTreeInfo},
         tests::{extend_db_state_from_l1_batch, gen_storage_logs, mock_config, reset_db_state},
         MetadataCalculator,
     };
@@ -209,6 +339,115 @@ mod tests {
             .await;
     }
 
+    async fn mark_l1_batch_as_executed(storage: &mut Connection<'_, Core>, number: u32) {
+        storage
+            .eth_sender_dal()
+            .insert_bogus_confirmed_eth_tx(
+                L1BatchNumber(number),
+                AggregatedActionType::Execute,
+                H256::from_low_u64_be(number.into()),
+                chrono::Utc::now(),
+            )
+            .await
+            .unwrap();
+    }
+
+    async fn wait_for_min_l1_batch(
+        reader: &AsyncTreeReader,
+        expected_min_l1_batch: L1BatchNumber,
+    ) -> MerkleTreeInfo {
+        let tree_info = loop {
+            let tree_info = reader.clone().info().await;
+            if tree_info.min_l1_batch_number.unwrap() >= expected_min_l1_batch {
+                break tree_info;
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        };
+        assert_eq!(tree_info.min_l1_batch_number, Some(expected_min_l1_batch));
+        tree_info
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    enum SnapshotScenario {
+        NoSnapshot,
+        PendingSnapshot,
+        AbandonedSnapshot,
+    }
+
+    impl SnapshotScenario {
+        const ALL: [Self; 3] = [
+            Self::NoSnapshot,
+            Self::PendingSnapshot,
+            Self::AbandonedSnapshot,
+        ];
+    }
+
+    #[test_casing(3, SnapshotScenario::ALL)]
+    #[tokio::test]
+    async fn tree_pruning_with_retained_l1_batches(scenario: SnapshotScenario) {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+        let config = mock_config(temp_dir.path());
+        let mut storage = pool.connection().await.unwrap();
+        insert_genesis_batch(&mut storage, &GenesisParams::mock())
+            .await
+            .unwrap();
+        reset_db_state(&pool, 5).await;
+        mark_l1_batch_as_executed(&mut storage, 3).await;
+        if !matches!(scenario, SnapshotScenario::NoSnapshot) {
+            // The snapshot has no persisted storage log chunks, i.e., it's not complete.
+            storage
+                .snapshots_dal()
+                .add_snapshot(
+                    SnapshotVersion::Version0,
+                    L1BatchNumber(2),
+                    1,
+                    "file:///factory_deps.bin",
+                )
+                .await
+                .unwrap();
+        }
+
+        let mut calculator = MetadataCalculator::new(config, None, pool.clone())
+            .await
+            .unwrap();
+        let reader = calculator.tree_reader();
+        // Postgres is not pruned, so tree pruning is driven solely by the retention settings.
+        let mut pruning_task = calculator
+            .pruning_task(POLL_INTERVAL)
+            .with_retained_l1_batches(NonZeroU32::new(2));
+        if matches!(scenario, SnapshotScenario::AbandonedSnapshot) {
+            // The snapshot holds back pruning by 1 L1 batch, which exceeds the limit.
+            pruning_task.retention_policy.max_snapshot_hold = 0;
+        }
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let calculator_handle = tokio::spawn(calculator.run(stop_receiver.clone()));
+        let pruning_task_handle = tokio::spawn(pruning_task.run(stop_receiver));
+
+        let reader = reader.wait().await.unwrap();
+        while reader.clone().info().await.next_l1_batch_number < L1BatchNumber(6) {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+
+        // The retention target (L1 batch #4) is capped by the last executed L1 batch.
+        let expected_min_l1_batch = match scenario {
+            SnapshotScenario::PendingSnapshot => L1BatchNumber(2),
+            SnapshotScenario::NoSnapshot | SnapshotScenario::AbandonedSnapshot => L1BatchNumber(3),
+        };
+        wait_for_min_l1_batch(&reader, expected_min_l1_batch).await;
+        reader.verify_consistency(L1BatchNumber(5)).await.unwrap();
+
+        if !matches!(scenario, SnapshotScenario::PendingSnapshot) {
+            mark_l1_batch_as_executed(&mut storage, 5).await;
+            wait_for_min_l1_batch(&reader, L1BatchNumber(4)).await;
+            reader.verify_consistency(L1BatchNumber(5)).await.unwrap();
+        }
+
+        stop_sender.send_replace(true);
+        calculator_handle.await.unwrap().unwrap();
+        pruning_task_handle.await.unwrap().unwrap();
+    }
+
     #[derive(Debug)]
     enum PrematureExitScenario {
         CalculatorDrop,
@@ -13,11 +13,12 @@ use zksync_config::configs::{
 };
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_health_check::{CheckHealth, HealthStatus};
-use zksync_merkle_tree::domain::ZkSyncTree;
+use zksync_merkle_tree::{domain::ZkSyncTree, MerkleTreeColumnFamily};
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
 use zksync_node_test_utils::{create_l1_batch, create_l2_block};
 use zksync_object_store::{MockObjectStore, ObjectStore};
 use zksync_prover_interface::inputs::PrepareBasicCircuitsJob;
+use zksync_rocksdb_checkpoints::RocksdbCheckpointer;
 use zksync_storage::RocksDB;
 use zksync_types::{
     block::{L1BatchHeader, L1BatchTreeData},
@@ -514,6 +515,94 @@ async fn shutting_down_calculator() {
         .unwrap();
 }
 
+#[tokio::test]
+async fn restoring_tree_from_checkpoint() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let calculator = setup_lightweight_calculator(temp_dir.path(), pool.clone(), true).await;
+    reset_db_state(&pool, 5).await;
+    run_calculator(calculator).await;
+
+    let checkpoint_store = MockObjectStore::arc();
+    let db = RocksDB::<MerkleTreeColumnFamily>::new(&temp_dir.path().join("new")).unwrap();
+    RocksdbCheckpointer::new(
+        db,
+        checkpoint_store.clone(),
+        temp_dir.path().join("checkpoints"),
+    )
+    .upload_checkpoint()
+    .await
+    .unwrap();
+
+    let new_logs = gen_storage_logs(100..200, 10);
+    extend_db_state(&mut pool.connection().await.unwrap(), new_logs).await;
+
+    let restored_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let calculator = setup_lightweight_calculator(restored_dir.path(), pool.clone(), true)
+        .await
+        .with_checkpoint_store(checkpoint_store);
+    assert!(calculator.restore_from_checkpoint().await.unwrap());
+    let tree = calculator.create_tree().await.unwrap();
+    let GenericAsyncTree::Ready(tree) = tree else {
+        panic!("Unexpected tree state: {tree:?}");
+    };
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(6));
+    drop(tree);
+
+    let restored_root_hash = run_calculator(calculator).await;
+    let calculator = setup_lightweight_calculator(temp_dir.path(), pool, true).await;
+    let root_hash = run_calculator(calculator).await;
+    assert_eq!(restored_root_hash, root_hash);
+}
+
+#[tokio::test]
+async fn restoring_tree_from_diverging_checkpoint() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let calculator = setup_lightweight_calculator(temp_dir.path(), pool.clone(), true).await;
+    reset_db_state(&pool, 5).await;
+    run_calculator(calculator).await;
+
+    let checkpoint_store = MockObjectStore::arc();
+    let db = RocksDB::<MerkleTreeColumnFamily>::new(&temp_dir.path().join("new")).unwrap();
+    RocksdbCheckpointer::new(
+        db,
+        checkpoint_store.clone(),
+        temp_dir.path().join("checkpoints"),
+    )
+    .upload_checkpoint()
+    .await
+    .unwrap();
+
+    // Replace the last L1 batches in Postgres with ones having different tree data.
+    let mut storage = pool.connection().await.unwrap();
+    remove_l1_batches(&mut storage, L1BatchNumber(3)).await;
+    extend_db_state(&mut storage, gen_storage_logs(100..200, 5)).await;
+    for number in 4..9 {
+        let new_tree_data = L1BatchTreeData {
+            hash: H256::from_low_u64_be(number.into()),
+            rollup_last_leaf_index: 200, // doesn't matter
+        };
+        storage
+            .blocks_dal()
+            .save_l1_batch_tree_data(L1BatchNumber(number), &new_tree_data)
+            .await
+            .unwrap();
+    }
+    drop(storage);
+
+    let restored_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let calculator = setup_lightweight_calculator(restored_dir.path(), pool, true)
+        .await
+        .with_checkpoint_store(checkpoint_store);
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let err = run_with_timeout(RUN_TIMEOUT, calculator.run(stop_receiver))
+        .await
+        .unwrap_err();
+    let err = format!("{err:#}");
+    assert!(err.contains("diverges from Postgres"), "{err}");
+}
+
 async fn test_postgres_backup_recovery(
     sleep_between_batches: bool,
     insert_batch_without_metadata: bool,
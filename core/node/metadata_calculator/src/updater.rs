@@ -394,6 +394,42 @@ impl AsyncTree {
         Ok(data_matches)
     }
 
+    /// Checks that a tree restored from a RocksDB checkpoint matches Postgres. Unlike with the local tree,
+    /// a divergence is not fixed by truncating the tree; it means that the checkpoint is corrupted or belongs
+    /// to another chain, so the node must not start.
+    pub(crate) async fn ensure_restored_tree_matches(
+        &self,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let Some(last_tree_l1_batch) = self.next_l1_batch_number().checked_sub(1) else {
+            return Ok(()); // The restored tree is empty; nothing to check
+        };
+        let mut storage = pool.connection_tagged("metadata_calculator").await?;
+        let Some(last_l1_batch_with_tree_data) = storage
+            .blocks_dal()
+            .get_last_l1_batch_number_with_tree_data()
+            .await?
+        else {
+            tracing::warn!(
+                "Postgres contains no L1 batches with tree data; cannot check the Merkle tree restored from checkpoint"
+            );
+            return Ok(());
+        };
+
+        // The restored tree may be ahead of Postgres; in this case, it will be truncated afterwards.
+        let l1_batch = L1BatchNumber(last_tree_l1_batch).min(last_l1_batch_with_tree_data);
+        anyhow::ensure!(
+            self.l1_batch_matches(&mut storage, l1_batch).await?,
+            "Merkle tree restored from checkpoint diverges from Postgres at L1 batch #{l1_batch}; \
+             the checkpoint is corrupted or belongs to another chain. Remove the tree RocksDB \
+             and fix the checkpoint store configuration"
+        );
+        tracing::info!(
+            "Merkle tree restored from checkpoint matches Postgres at L1 batch #{l1_batch}"
+        );
+        Ok(())
+    }
+
     /// Ensures that the tree is consistent with Postgres, truncating the tree if necessary.
     /// This will wait for at least one L1 batch to appear in Postgres if necessary.
     pub(crate) async fn ensure_consistency(
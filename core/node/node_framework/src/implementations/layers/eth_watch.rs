@@ -3,7 +3,7 @@ use std::time::Duration;
 use zksync_config::{ContractsConfig, EthWatchConfig};
 use zksync_contracts::governance_contract;
 use zksync_dal::{ConnectionPool, Core};
-use zksync_eth_watch::{EthHttpQueryClient, EthWatch};
+use zksync_eth_watch::{EthHttpQueryClient, EthWatch, EventProcessor};
 use zksync_types::{ethabi::Contract, Address};
 
 use crate::{
@@ -19,7 +19,8 @@ use crate::{
 /// Wiring layer for ethereum watcher
 ///
 /// Responsible for initializing and running of [`EthWatch`] component, that polls the Ethereum node for the relevant events,
-/// such as priority operations (aka L1 transactions), protocol upgrades etc.
+/// such as priority operations (aka L1 transactions), protocol upgrades etc. Additional events can be watched
+/// by registering custom [`EventProcessor`]s via [`Self::with_event_processor()`].
 ///
 /// ## Requests resources
 ///
@@ -33,6 +34,7 @@ use crate::{
 pub struct EthWatchLayer {
     eth_watch_config: EthWatchConfig,
     contracts_config: ContractsConfig,
+    event_processors: Vec<Box<dyn EventProcessor>>,
 }
 
 impl EthWatchLayer {
@@ -40,8 +42,15 @@ impl EthWatchLayer {
         Self {
             eth_watch_config,
             contracts_config,
+            event_processors: Vec::new(),
         }
     }
+
+    /// Registers an additional event processor in the watcher.
+    pub fn with_event_processor(mut self, processor: Box<dyn EventProcessor>) -> Self {
+        self.event_processors.push(processor);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -70,7 +79,9 @@ impl WiringLayer for EthWatchLayer {
             client: eth_client,
             governance_contract: governance_contract(),
             diamond_proxy_address: self.contracts_config.diamond_proxy_addr,
+            governance_address: self.contracts_config.governance_addr,
             poll_interval: self.eth_watch_config.poll_interval(),
+            event_processors: self.event_processors,
         }));
 
         Ok(())
@@ -83,7 +94,9 @@ struct EthWatchTask {
     client: EthHttpQueryClient,
     governance_contract: Contract,
     diamond_proxy_address: Address,
+    governance_address: Address,
     poll_interval: Duration,
+    event_processors: Vec<Box<dyn EventProcessor>>,
 }
 
 #[async_trait::async_trait]
@@ -93,14 +106,18 @@ impl Task for EthWatchTask {
     }
 
     async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
-        let eth_watch = EthWatch::new(
+        let mut eth_watch = EthWatch::new(
             self.diamond_proxy_address,
+            self.governance_address,
             &self.governance_contract,
             Box::new(self.client),
             self.main_pool,
             self.poll_interval,
         )
         .await?;
+        for processor in self.event_processors {
+            eth_watch.add_event_processor(processor)?;
+        }
 
         eth_watch.run(stop_receiver.0).await
     }
@@ -1,14 +1,22 @@
 use std::{
     net::{Ipv4Addr, SocketAddr},
+    num::NonZeroU32,
     sync::Arc,
     time::Duration,
 };
 
 use anyhow::Context as _;
-use zksync_config::configs::{api::MerkleTreeApiConfig, database::MerkleTreeMode};
+use zksync_config::{
+    configs::{api::MerkleTreeApiConfig, database::MerkleTreeMode},
+    ObjectStoreConfig,
+};
 use zksync_metadata_calculator::{
     LazyAsyncTreeReader, MerkleTreePruningTask, MetadataCalculator, MetadataCalculatorConfig,
 };
+use zksync_object_store::{ObjectStore, ObjectStoreFactory};
+use zksync_rocksdb_checkpoints::RocksdbCheckpointTask;
+// Public re-export to not require the user to directly depend on `zksync_rocksdb_checkpoints`.
+pub use zksync_rocksdb_checkpoints::RocksdbCheckpointConfig;
 use zksync_storage::RocksDB;
 
 use crate::{
@@ -16,7 +24,7 @@ use crate::{
         healthcheck::AppHealthCheckResource,
         object_store::ObjectStoreResource,
         pools::{MasterPool, PoolResource, ReplicaPool},
-        web3_api::TreeApiClientResource,
+        web3_api::{TreeApiClientResource, TreeReaderResource},
     },
     service::{ServiceContext, StopReceiver},
     task::{Task, TaskId},
@@ -35,16 +43,22 @@ use crate::{
 /// ## Adds resources
 ///
 /// - `TreeApiClientResource`
+/// - `TreeReaderResource`
 ///
 /// ## Adds tasks
 ///
 /// - `MetadataCalculatorTask`
 /// - `TreeApiTask` (if requested)
+/// - `MerkleTreePruningTask` (if requested)
+/// - `TreeCheckpointTask` (if requested)
 #[derive(Debug)]
 pub struct MetadataCalculatorLayer {
     config: MetadataCalculatorConfig,
     tree_api_config: Option<MerkleTreeApiConfig>,
     pruning_config: Option<Duration>,
+    tree_retained_l1_batches: Option<NonZeroU32>,
+    tree_retain_for_snapshots: bool,
+    checkpoint_config: Option<(RocksdbCheckpointConfig, ObjectStoreConfig)>,
 }
 
 impl MetadataCalculatorLayer {
@@ -53,6 +67,9 @@ impl MetadataCalculatorLayer {
             config,
             tree_api_config: None,
             pruning_config: None,
+            tree_retained_l1_batches: None,
+            tree_retain_for_snapshots: true,
+            checkpoint_config: None,
         }
     }
 
@@ -65,6 +82,29 @@ impl MetadataCalculatorLayer {
         self.pruning_config = Some(pruning_config);
         self
     }
+
+    /// Configures Merkle tree retention independently of Postgres pruning. Only has an effect if pruning
+    /// is enabled via [`Self::with_pruning_config()`].
+    pub fn with_tree_retention(
+        mut self,
+        retained_l1_batches: Option<NonZeroU32>,
+        retain_for_snapshots: bool,
+    ) -> Self {
+        self.tree_retained_l1_batches = retained_l1_batches;
+        self.tree_retain_for_snapshots = retain_for_snapshots;
+        self
+    }
+
+    /// Enables periodic uploading of tree RocksDB checkpoints to the object store with the specified config.
+    /// If the tree RocksDB doesn't exist on node start, it will be restored from the latest checkpoint.
+    pub fn with_checkpoint_config(
+        mut self,
+        checkpoint_config: RocksdbCheckpointConfig,
+        object_store_config: ObjectStoreConfig,
+    ) -> Self {
+        self.checkpoint_config = Some((checkpoint_config, object_store_config));
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -91,6 +131,14 @@ impl WiringLayer for MetadataCalculatorLayer {
                 Some(store)
             }
         };
+        let checkpoint_store = match &self.checkpoint_config {
+            Some((_, object_store_config)) => Some(
+                ObjectStoreFactory::new(object_store_config.clone())
+                    .create_store()
+                    .await?,
+            ),
+            None => None,
+        };
 
         let mut metadata_calculator = MetadataCalculator::new(
             self.config,
@@ -99,6 +147,10 @@ impl WiringLayer for MetadataCalculatorLayer {
         )
         .await?
         .with_recovery_pool(recovery_pool);
+        if let Some(checkpoint_store) = &checkpoint_store {
+            metadata_calculator =
+                metadata_calculator.with_checkpoint_store(checkpoint_store.clone());
+        }
 
         let AppHealthCheckResource(app_health) = context.get_resource_or_default().await;
         app_health
@@ -115,16 +167,30 @@ impl WiringLayer for MetadataCalculatorLayer {
         }
 
         if let Some(pruning_removal_delay) = self.pruning_config {
-            let pruning_task = Box::new(metadata_calculator.pruning_task(pruning_removal_delay));
+            let pruning_task = metadata_calculator
+                .pruning_task(pruning_removal_delay)
+                .with_retained_l1_batches(self.tree_retained_l1_batches)
+                .with_snapshot_retention(self.tree_retain_for_snapshots);
+            let pruning_task = Box::new(pruning_task);
             app_health
                 .insert_component(pruning_task.health_check())
                 .map_err(|err| WiringError::Internal(err.into()))?;
             context.add_task(pruning_task);
         }
 
+        if let (Some((config, _)), Some(object_store)) = (self.checkpoint_config, checkpoint_store)
+        {
+            context.add_task(Box::new(TreeCheckpointTask {
+                tree_reader: metadata_calculator.tree_reader(),
+                object_store,
+                config,
+            }));
+        }
+
         context.insert_resource(TreeApiClientResource(Arc::new(
             metadata_calculator.tree_reader(),
         )))?;
+        context.insert_resource(TreeReaderResource(metadata_calculator.tree_reader()))?;
 
         context.add_task(Box::new(metadata_calculator));
 
@@ -175,6 +241,32 @@ impl Task for TreeApiTask {
     }
 }
 
+#[derive(Debug)]
+pub struct TreeCheckpointTask {
+    tree_reader: LazyAsyncTreeReader,
+    object_store: Arc<dyn ObjectStore>,
+    config: RocksdbCheckpointConfig,
+}
+
+#[async_trait::async_trait]
+impl Task for TreeCheckpointTask {
+    fn id(&self) -> TaskId {
+        "merkle_tree_checkpoint_task".into()
+    }
+
+    async fn run(self: Box<Self>, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        let Some(reader) = self.tree_reader.wait().await else {
+            // Tree is dropped before initialized, e.g. because the node is getting shut down.
+            tracing::warn!("Tree is dropped before initialized, not uploading tree checkpoints");
+            stop_receiver.0.changed().await?;
+            return Ok(());
+        };
+        RocksdbCheckpointTask::new(reader.rocksdb(), self.object_store, self.config)
+            .run(stop_receiver.0)
+            .await
+    }
+}
+
 #[async_trait::async_trait]
 impl Task for MerkleTreePruningTask {
     fn id(&self) -> TaskId {
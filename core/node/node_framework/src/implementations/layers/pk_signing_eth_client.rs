@@ -3,7 +3,7 @@ use zksync_config::{
     configs::{wallets, ContractsConfig},
     EthConfig,
 };
-use zksync_eth_client::clients::PKSigningClient;
+use zksync_eth_client::clients::signing_client_for_wallet;
 use zksync_types::L1ChainId;
 
 use crate::{
@@ -14,7 +14,8 @@ use crate::{
     wiring_layer::{WiringError, WiringLayer},
 };
 
-/// Wiring layer for [`PKSigningClient`].
+/// Wiring layer for signing Ethereum clients. Depending on the wallets config, transactions are signed
+/// either with a private key, or by a remote signer.
 ///
 /// ## Requests resources
 ///
@@ -55,7 +56,6 @@ impl WiringLayer for PKSigningEthClientLayer {
     }
 
     async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
-        let private_key = self.wallets.operator.private_key();
         let gas_adjuster_config = self
             .eth_sender_config
             .gas_adjuster
@@ -63,27 +63,26 @@ impl WiringLayer for PKSigningEthClientLayer {
             .context("gas_adjuster config is missing")?;
         let EthInterfaceResource(query_client) = context.get_resource().await?;
 
-        let signing_client = PKSigningClient::new_raw(
-            private_key.clone(),
+        let signing_client = signing_client_for_wallet(
+            &self.wallets.operator,
             self.contracts_config.diamond_proxy_addr,
             gas_adjuster_config.default_priority_fee_per_gas,
             self.l1_chain_id,
             query_client.clone(),
-        );
-        context.insert_resource(BoundEthInterfaceResource(Box::new(signing_client)))?;
+        )
+        .context("failed creating signing client for operator")?;
+        context.insert_resource(BoundEthInterfaceResource(signing_client))?;
 
         if let Some(blob_operator) = &self.wallets.blob_operator {
-            let private_key = blob_operator.private_key();
-            let signing_client_for_blobs = PKSigningClient::new_raw(
-                private_key.clone(),
+            let signing_client_for_blobs = signing_client_for_wallet(
+                blob_operator,
                 self.contracts_config.diamond_proxy_addr,
                 gas_adjuster_config.default_priority_fee_per_gas,
                 self.l1_chain_id,
                 query_client,
-            );
-            context.insert_resource(BoundEthInterfaceForBlobsResource(Box::new(
-                signing_client_for_blobs,
-            )))?;
+            )
+            .context("failed creating signing client for blob operator")?;
+            context.insert_resource(BoundEthInterfaceForBlobsResource(signing_client_for_blobs))?;
         }
 
         Ok(())
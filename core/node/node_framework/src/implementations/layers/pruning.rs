@@ -1,6 +1,10 @@
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
-use zksync_node_db_pruner::{DbPruner, DbPrunerConfig};
+use zksync_config::ObjectStoreConfig;
+use zksync_dal::pruning_dal::PrunedDataKind;
+use zksync_node_db_pruner::{DbPruner, DbPrunerConfig, PrunedDataArchiver};
+use zksync_object_store::ObjectStoreFactory;
+use zksync_types::L2ChainId;
 
 use crate::{
     implementations::resources::{
@@ -27,6 +31,9 @@ pub struct PruningLayer {
     pruning_removal_delay: Duration,
     pruning_chunk_size: u32,
     minimum_l1_batch_age: Duration,
+    database_size_budget: Option<u64>,
+    data_retention: HashMap<PrunedDataKind, Duration>,
+    archive: Option<(ObjectStoreConfig, L2ChainId)>,
 }
 
 impl PruningLayer {
@@ -39,8 +46,34 @@ impl PruningLayer {
             pruning_removal_delay,
             pruning_chunk_size,
             minimum_l1_batch_age,
+            database_size_budget: None,
+            data_retention: HashMap::new(),
+            archive: None,
         }
     }
+
+    /// Sets the database size budget (in bytes). If set, L1 batches will only be pruned while the estimated
+    /// database size exceeds the budget.
+    pub fn with_database_size_budget(mut self, budget: Option<u64>) -> Self {
+        self.database_size_budget = budget;
+        self
+    }
+
+    /// Sets retention periods for data kinds that should be pruned separately from the rest of L1 batch data.
+    pub fn with_data_retention(mut self, retention: HashMap<PrunedDataKind, Duration>) -> Self {
+        self.data_retention = retention;
+        self
+    }
+
+    /// Enables archiving of pruned L1 batches to the object store with the specified config.
+    pub fn with_archive(
+        mut self,
+        config: Option<ObjectStoreConfig>,
+        l2_chain_id: L2ChainId,
+    ) -> Self {
+        self.archive = config.map(|config| (config, l2_chain_id));
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -53,14 +86,22 @@ impl WiringLayer for PruningLayer {
         let pool_resource = context.get_resource::<PoolResource<MasterPool>>().await?;
         let main_pool = pool_resource.get().await?;
 
-        let db_pruner = DbPruner::new(
+        let mut db_pruner = DbPruner::new(
             DbPrunerConfig {
                 removal_delay: self.pruning_removal_delay,
                 pruned_batch_chunk_size: self.pruning_chunk_size,
                 minimum_l1_batch_age: self.minimum_l1_batch_age,
+                database_size_budget: self.database_size_budget,
+                data_retention: self.data_retention,
             },
             main_pool,
         );
+        if let Some((archive_config, l2_chain_id)) = self.archive {
+            let blob_store = ObjectStoreFactory::new(archive_config)
+                .create_store()
+                .await?;
+            db_pruner = db_pruner.with_archiver(PrunedDataArchiver::new(blob_store, l2_chain_id));
+        }
 
         let AppHealthCheckResource(app_health) = context.get_resource_or_default().await;
         app_health
@@ -0,0 +1,86 @@
+use zksync_config::SnapshotsCreatorConfig;
+use zksync_object_store::ObjectStoreFactory;
+use zksync_snapshots_creator::{PeriodicSnapshotsCreator, SnapshotCreator, MIN_CHUNK_COUNT};
+
+use crate::{
+    implementations::resources::{
+        healthcheck::AppHealthCheckResource,
+        object_store::ObjectStoreResource,
+        pools::{MasterPool, PoolResource, ReplicaPool},
+    },
+    service::{ServiceContext, StopReceiver},
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Wiring layer for the periodic snapshots creator.
+///
+/// ## Requests resources
+///
+/// - `PoolResource<MasterPool>`
+/// - `PoolResource<ReplicaPool>`
+/// - `ObjectStoreResource` (only if the object store is not specified in the snapshots creator config)
+/// - `AppHealthCheckResource` (adds a health check)
+///
+/// ## Adds tasks
+///
+/// - `PeriodicSnapshotsCreator`
+#[derive(Debug)]
+pub struct SnapshotsCreatorLayer {
+    config: SnapshotsCreatorConfig,
+}
+
+impl SnapshotsCreatorLayer {
+    pub fn new(config: SnapshotsCreatorConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for SnapshotsCreatorLayer {
+    fn layer_name(&self) -> &'static str {
+        "snapshots_creator_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let master_pool = context
+            .get_resource::<PoolResource<MasterPool>>()
+            .await?
+            .get_singleton()
+            .await?;
+        let replica_pool = context
+            .get_resource::<PoolResource<ReplicaPool>>()
+            .await?
+            .get_custom(self.config.concurrent_queries_count)
+            .await?;
+        let blob_store = if let Some(object_store_config) = self.config.object_store.clone() {
+            ObjectStoreFactory::new(object_store_config)
+                .create_store()
+                .await?
+        } else {
+            context.get_resource::<ObjectStoreResource>().await?.0
+        };
+
+        let creator = SnapshotCreator::new(blob_store, master_pool, replica_pool);
+        let periodic_creator = PeriodicSnapshotsCreator::new(creator, self.config, MIN_CHUNK_COUNT);
+
+        let AppHealthCheckResource(app_health) = context.get_resource_or_default().await;
+        app_health
+            .insert_component(periodic_creator.health_check())
+            .map_err(WiringError::internal)?;
+
+        context.add_task(Box::new(periodic_creator));
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for PeriodicSnapshotsCreator {
+    fn id(&self) -> TaskId {
+        "snapshots_creator".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
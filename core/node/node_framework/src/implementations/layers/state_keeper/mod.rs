@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
 use anyhow::Context;
-use zksync_state::{AsyncCatchupTask, ReadStorageFactory};
+use zksync_config::ObjectStoreConfig;
+use zksync_object_store::{ObjectStore, ObjectStoreFactory};
+use zksync_rocksdb_checkpoints::RocksdbCheckpointTask;
+use zksync_state::{AsyncCatchupTask, ReadStorageFactory, RocksdbCell};
 use zksync_state_keeper::{
     seal_criteria::ConditionalSealer, AsyncRocksdbCache, BatchExecutor, OutputHandler,
     StateKeeperIO, ZkSyncStateKeeper,
@@ -13,7 +16,8 @@ pub mod main_batch_executor;
 pub mod mempool_io;
 pub mod output_handler;
 
-// Public re-export to not require the user to directly depend on `zksync_state`.
+// Public re-exports to not require the user to directly depend on `zksync_state` / `zksync_rocksdb_checkpoints`.
+pub use zksync_rocksdb_checkpoints::RocksdbCheckpointConfig;
 pub use zksync_state::RocksdbStorageOptions;
 
 use crate::{
@@ -43,10 +47,12 @@ use crate::{
 ///
 /// - `RocksdbCatchupTask`
 /// - `StateKeeperTask`
+/// - `RocksdbCheckpointsTask` (if requested)
 #[derive(Debug)]
 pub struct StateKeeperLayer {
     state_keeper_db_path: String,
     rocksdb_options: RocksdbStorageOptions,
+    checkpoint_config: Option<(RocksdbCheckpointConfig, ObjectStoreConfig)>,
 }
 
 impl StateKeeperLayer {
@@ -54,8 +60,20 @@ impl StateKeeperLayer {
         Self {
             state_keeper_db_path,
             rocksdb_options,
+            checkpoint_config: None,
         }
     }
+
+    /// Enables periodic uploading of state keeper cache checkpoints to the object store with the specified config.
+    /// If the cache doesn't exist on node start, it will be restored from the latest checkpoint.
+    pub fn with_checkpoint_config(
+        mut self,
+        checkpoint_config: RocksdbCheckpointConfig,
+        object_store_config: ObjectStoreConfig,
+    ) -> Self {
+        self.checkpoint_config = Some((checkpoint_config, object_store_config));
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -86,11 +104,22 @@ impl WiringLayer for StateKeeperLayer {
         let sealer = context.get_resource::<ConditionalSealerResource>().await?.0;
         let master_pool = context.get_resource::<PoolResource<MasterPool>>().await?;
 
-        let (storage_factory, task) = AsyncRocksdbCache::new(
+        let (storage_factory, mut task) = AsyncRocksdbCache::new(
             master_pool.get_custom(2).await?,
             self.state_keeper_db_path,
             self.rocksdb_options,
         );
+        if let Some((config, object_store_config)) = self.checkpoint_config {
+            let object_store = ObjectStoreFactory::new(object_store_config)
+                .create_store()
+                .await?;
+            task = task.with_checkpoint_store(object_store.clone());
+            context.add_task(Box::new(RocksdbCheckpointsTask {
+                rocksdb_cell: storage_factory.rocksdb_cell(),
+                object_store,
+                config,
+            }));
+        }
         context.add_task(Box::new(RocksdbCatchupTask(task)));
 
         context.add_task(Box::new(StateKeeperTask {
@@ -154,3 +183,35 @@ impl Task for RocksdbCatchupTask {
         Ok(())
     }
 }
+
+#[derive(Debug)]
+struct RocksdbCheckpointsTask {
+    rocksdb_cell: RocksdbCell,
+    object_store: Arc<dyn ObjectStore>,
+    config: RocksdbCheckpointConfig,
+}
+
+#[async_trait::async_trait]
+impl Task for RocksdbCheckpointsTask {
+    fn id(&self) -> TaskId {
+        "state_keeper/rocksdb_checkpoints_task".into()
+    }
+
+    async fn run(self: Box<Self>, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        let rocksdb = tokio::select! {
+            res = self.rocksdb_cell.wait() => res,
+            _ = stop_receiver.0.changed() => return Ok(()),
+        };
+        let Ok(rocksdb) = rocksdb else {
+            // The catch-up task has failed or was interrupted; its error (if any) will be reported by the task itself.
+            tracing::warn!(
+                "RocksDB cache is dropped before initialized, not uploading its checkpoints"
+            );
+            stop_receiver.0.changed().await?;
+            return Ok(());
+        };
+        RocksdbCheckpointTask::new(rocksdb, self.object_store, self.config)
+            .run(stop_receiver.0)
+            .await
+    }
+}
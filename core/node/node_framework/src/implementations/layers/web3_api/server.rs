@@ -2,8 +2,9 @@ use std::{num::NonZeroU32, time::Duration};
 
 use tokio::{sync::oneshot, task::JoinHandle};
 use zksync_circuit_breaker::replication_lag::ReplicationLagChecker;
-use zksync_config::configs::api::MaxResponseSize;
+use zksync_config::{configs::api::MaxResponseSize, ObjectStoreConfig};
 use zksync_node_api_server::web3::{state::InternalApiConfig, ApiBuilder, ApiServer, Namespace};
+use zksync_object_store::ObjectStoreFactory;
 
 use crate::{
     implementations::resources::{
@@ -32,6 +33,8 @@ pub struct Web3ServerOptionalConfig {
     pub replication_lag_limit: Option<Duration>,
     // Used by the external node.
     pub pruning_info_refresh_interval: Option<Duration>,
+    // Used by the external node.
+    pub pruned_data_archive: Option<ObjectStoreConfig>,
 }
 
 impl Web3ServerOptionalConfig {
@@ -174,6 +177,12 @@ impl WiringLayer for Web3ServerLayer {
             api_builder =
                 api_builder.with_pruning_info_refresh_interval(pruning_info_refresh_interval);
         }
+        if let Some(archive_config) = self.optional_config.pruned_data_archive.clone() {
+            let blob_store = ObjectStoreFactory::new(archive_config)
+                .create_store()
+                .await?;
+            api_builder = api_builder.with_pruned_data_archive(blob_store);
+        }
         let replication_lag_limit = self.optional_config.replication_lag_limit;
         api_builder = self.optional_config.apply(api_builder);
         let server = api_builder.build()?;
@@ -19,7 +19,7 @@ use crate::{
         main_node_client::MainNodeClientResource,
         pools::{PoolResource, ReplicaPool},
         state_keeper::ConditionalSealerResource,
-        web3_api::{TxSenderResource, TxSinkResource},
+        web3_api::{TreeReaderResource, TxSenderResource, TxSinkResource},
     },
     service::{ServiceContext, StopReceiver},
     task::{Task, TaskId},
@@ -42,6 +42,7 @@ pub struct PostgresStorageCachesConfig {
 /// - `PoolResource<ReplicaPool>`
 /// - `ConditionalSealerResource` (optional)
 /// - `FeeInputResource`
+/// - `TreeReaderResource` (if reading VM storage from the Merkle tree is enabled)
 ///
 /// ## Adds resources
 ///
@@ -59,6 +60,7 @@ pub struct TxSenderLayer {
     max_vm_concurrency: usize,
     api_contracts: ApiContracts,
     whitelisted_tokens_for_aa_cache: bool,
+    vm_storage_from_merkle_tree: bool,
 }
 
 impl TxSenderLayer {
@@ -74,6 +76,7 @@ impl TxSenderLayer {
             max_vm_concurrency,
             api_contracts,
             whitelisted_tokens_for_aa_cache: false,
+            vm_storage_from_merkle_tree: false,
         }
     }
 
@@ -85,6 +88,15 @@ impl TxSenderLayer {
         self.whitelisted_tokens_for_aa_cache = value;
         self
     }
+
+    /// Enables reading VM storage from the Merkle tree when the tree retains the necessary state.
+    /// Disabled by default.
+    ///
+    /// Has no effect if `TreeReaderResource` is not present (i.e., the tree doesn't run in the same process).
+    pub fn with_vm_storage_from_merkle_tree(mut self, value: bool) -> Self {
+        self.vm_storage_from_merkle_tree = value;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -134,6 +146,20 @@ impl WiringLayer for TxSenderLayer {
         if let Some(sealer) = sealer {
             tx_sender = tx_sender.with_sealer(sealer);
         }
+        if self.vm_storage_from_merkle_tree {
+            match context.get_resource::<TreeReaderResource>().await {
+                Ok(TreeReaderResource(tree_reader)) => {
+                    tx_sender = tx_sender.with_tree_reader(tree_reader);
+                }
+                Err(WiringError::ResourceLacking { .. }) => {
+                    tracing::warn!(
+                        "Reading VM storage from the Merkle tree is enabled, but the tree is not available; \
+                         VM storage will be read from Postgres"
+                    );
+                }
+                Err(other) => return Err(other),
+            }
+        }
 
         // Add the task for updating the whitelisted tokens for the AA cache.
         if self.whitelisted_tokens_for_aa_cache {
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use zksync_metadata_calculator::api_server::TreeApiClient;
+use zksync_metadata_calculator::{api_server::TreeApiClient, LazyAsyncTreeReader};
 use zksync_node_api_server::{
     tx_sender::{tx_sink::TxSink, TxSender},
     web3::mempool_cache::MempoolCache,
@@ -38,6 +38,16 @@ impl Resource for TreeApiClientResource {
     }
 }
 
+/// A resource that provides a local Merkle tree reader to the service, e.g. to read VM storage from the tree.
+#[derive(Debug, Clone)]
+pub struct TreeReaderResource(pub LazyAsyncTreeReader);
+
+impl Resource for TreeReaderResource {
+    fn name() -> String {
+        "api/tree_reader".into()
+    }
+}
+
 /// A resource that provides [`MempoolCache`] to the service.
 #[derive(Debug, Clone)]
 pub struct MempoolCacheResource(pub MempoolCache);
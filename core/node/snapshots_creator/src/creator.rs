@@ -1,6 +1,6 @@
 //! [`SnapshotCreator`] and tightly related types.
 
-use std::sync::Arc;
+use std::{ops, sync::Arc};
 
 use anyhow::Context as _;
 use tokio::sync::Semaphore;
@@ -9,8 +9,9 @@ use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalResult};
 use zksync_object_store::ObjectStore;
 use zksync_types::{
     snapshots::{
-        uniform_hashed_keys_chunk, SnapshotFactoryDependencies, SnapshotFactoryDependency,
-        SnapshotMetadata, SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey, SnapshotVersion,
+        uniform_hashed_keys_chunk, SnapshotDiffManifest, SnapshotDiffPart,
+        SnapshotFactoryDependencies, SnapshotFactoryDependency, SnapshotMetadata,
+        SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey, SnapshotVersion,
     },
     L1BatchNumber, L2BlockNumber,
 };
@@ -58,15 +59,29 @@ impl SnapshotProgress {
 
 /// Creator of a single storage snapshot.
 #[derive(Debug)]
-pub(crate) struct SnapshotCreator {
-    pub blob_store: Arc<dyn ObjectStore>,
-    pub master_pool: ConnectionPool<Core>,
-    pub replica_pool: ConnectionPool<Core>,
+pub struct SnapshotCreator {
+    pub(crate) blob_store: Arc<dyn ObjectStore>,
+    pub(crate) master_pool: ConnectionPool<Core>,
+    pub(crate) replica_pool: ConnectionPool<Core>,
     #[cfg(test)]
-    pub event_listener: Box<dyn HandleEvent>,
+    pub(crate) event_listener: Box<dyn HandleEvent>,
 }
 
 impl SnapshotCreator {
+    pub fn new(
+        blob_store: Arc<dyn ObjectStore>,
+        master_pool: ConnectionPool<Core>,
+        replica_pool: ConnectionPool<Core>,
+    ) -> Self {
+        Self {
+            blob_store,
+            master_pool,
+            replica_pool,
+            #[cfg(test)]
+            event_listener: Box::new(()),
+        }
+    }
+
     async fn connect_to_replica(&self) -> DalResult<Connection<'_, Core>> {
         self.replica_pool
             .connection_tagged("snapshots_creator")
@@ -187,13 +202,10 @@ impl SnapshotCreator {
         Ok(output_filepath)
     }
 
-    /// Returns `Ok(None)` if the created snapshot would coincide with `latest_snapshot`.
-    async fn initialize_snapshot_progress(
-        config: &SnapshotsCreatorConfig,
-        min_chunk_count: u64,
-        latest_snapshot: Option<&SnapshotMetadata>,
+    /// Selects the L1 batch to create a snapshot for.
+    async fn select_snapshot_l1_batch(
         conn: &mut Connection<'_, Core>,
-    ) -> anyhow::Result<Option<SnapshotProgress>> {
+    ) -> anyhow::Result<L1BatchNumber> {
         // We subtract 1 so that after restore, EN node has at least one L1 batch to fetch.
         let sealed_l1_batch_number = conn.blocks_dal().get_sealed_l1_batch_number().await?;
         let sealed_l1_batch_number = sealed_l1_batch_number.context("No L1 batches in Postgres")?;
@@ -214,7 +226,17 @@ impl SnapshotCreator {
                      could be impossible. This should never happen"
                 )
             })?;
+        Ok(l1_batch_number)
+    }
 
+    /// Returns `Ok(None)` if the created snapshot would coincide with `latest_snapshot`.
+    async fn initialize_snapshot_progress(
+        config: &SnapshotsCreatorConfig,
+        min_chunk_count: u64,
+        latest_snapshot: Option<&SnapshotMetadata>,
+        conn: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<Option<SnapshotProgress>> {
+        let l1_batch_number = Self::select_snapshot_l1_batch(conn).await?;
         let latest_snapshot_l1_batch_number =
             latest_snapshot.map(|snapshot| snapshot.l1_batch_number);
         if latest_snapshot_l1_batch_number == Some(l1_batch_number) {
@@ -273,8 +295,222 @@ impl SnapshotCreator {
         }
     }
 
+    async fn process_diff_storage_logs_single_chunk(
+        &self,
+        semaphore: &Semaphore,
+        l2_block_numbers: ops::RangeInclusive<L2BlockNumber>,
+        l1_batch_number: L1BatchNumber,
+        chunk_id: u64,
+        chunk_count: u64,
+    ) -> anyhow::Result<()> {
+        let _permit = semaphore.acquire().await?;
+        let hashed_keys_range = uniform_hashed_keys_chunk(chunk_id, chunk_count);
+        let mut conn = self.connect_to_replica().await?;
+
+        let latency =
+            METRICS.storage_logs_processing_duration[&StorageChunkStage::LoadFromPostgres].start();
+        let logs = conn
+            .snapshots_creator_dal()
+            .get_storage_logs_diff_chunk(l2_block_numbers, l1_batch_number, hashed_keys_range)
+            .await
+            .context("Error fetching storage logs diff")?;
+        drop(conn);
+        let latency = latency.observe();
+        tracing::info!(
+            "Loaded diff chunk {chunk_id} ({} logs) from Postgres in {latency:?}",
+            logs.len()
+        );
+
+        let latency =
+            METRICS.storage_logs_processing_duration[&StorageChunkStage::SaveToGcs].start();
+        let storage_logs_chunk = SnapshotDiffPart(SnapshotStorageLogsChunk { storage_logs: logs });
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number,
+            chunk_id,
+        };
+        let filename = self
+            .blob_store
+            .put(key, &storage_logs_chunk)
+            .await
+            .context("Error storing storage logs diff chunk in blob store")?;
+        let latency = latency.observe();
+
+        let tasks_left = METRICS.storage_logs_chunks_left_to_process.dec_by(1) - 1;
+        tracing::info!(
+            "Saved diff chunk {chunk_id} (overall progress {}/{chunk_count}) in {latency:?} to location: {filename}",
+            chunk_count - tasks_left as u64
+        );
+        Ok(())
+    }
+
+    async fn process_diff_factory_deps(
+        &self,
+        l2_block_numbers: ops::RangeInclusive<L2BlockNumber>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        let mut conn = self.connect_to_replica().await?;
+        let latency =
+            METRICS.factory_deps_processing_duration[&FactoryDepsStage::LoadFromPostgres].start();
+        let factory_deps = conn
+            .snapshots_creator_dal()
+            .get_factory_deps_in_l2_blocks(l2_block_numbers)
+            .await?;
+        drop(conn);
+        let latency = latency.observe();
+        tracing::info!(
+            "Loaded {} factory deps added after the base snapshot in {latency:?}",
+            factory_deps.len()
+        );
+
+        let latency =
+            METRICS.factory_deps_processing_duration[&FactoryDepsStage::SaveToGcs].start();
+        let factory_deps = factory_deps
+            .into_iter()
+            .map(|(_, bytecode)| SnapshotFactoryDependency {
+                bytecode: bytecode.into(),
+            })
+            .collect();
+        let factory_deps = SnapshotDiffPart(SnapshotFactoryDependencies { factory_deps });
+        let filename = self
+            .blob_store
+            .put(l1_batch_number, &factory_deps)
+            .await
+            .context("Error storing factory deps diff in blob store")?;
+        let latency = latency.observe();
+        tracing::info!(
+            "Saved {} factory deps in {latency:?} to location: {filename}",
+            factory_deps.0.factory_deps.len()
+        );
+        Ok(())
+    }
+
+    /// Creates a differential snapshot on top of the newest existing snapshot. Unlike full snapshots, differential
+    /// snapshots are not resumed after a restart; they are expected to be small enough to be recreated from scratch.
+    ///
+    /// Returns `Ok(false)` if there is no suitable base snapshot, or if the newest full snapshot is at least
+    /// `full_snapshot_l1_batch_interval` L1 batches old; in these cases, a full snapshot should be created instead.
+    async fn create_diff_snapshot(
+        &self,
+        config: &SnapshotsCreatorConfig,
+        min_chunk_count: u64,
+    ) -> anyhow::Result<bool> {
+        let mut master_conn = self
+            .master_pool
+            .connection_tagged("snapshots_creator")
+            .await?;
+        let latest_snapshot = master_conn
+            .snapshots_dal()
+            .get_newest_snapshot_metadata()
+            .await?;
+        let Some(latest_snapshot) = latest_snapshot.filter(SnapshotMetadata::is_complete) else {
+            tracing::info!(
+                "There is no complete full snapshot to use as a base for a differential snapshot; \
+                 creating a full snapshot"
+            );
+            return Ok(false);
+        };
+        let latest_diff_l1_batch_number = master_conn
+            .snapshots_dal()
+            .get_newest_snapshot_diff_l1_batch_number()
+            .await?;
+        drop(master_conn);
+        let base_l1_batch_number = latest_diff_l1_batch_number
+            .map_or(latest_snapshot.l1_batch_number, |number| {
+                number.max(latest_snapshot.l1_batch_number)
+            });
+
+        let mut conn = self.connect_to_replica().await?;
+        let l1_batch_number = Self::select_snapshot_l1_batch(&mut conn).await?;
+        if l1_batch_number <= base_l1_batch_number {
+            tracing::info!(
+                "Snapshot at L1 batch #{base_l1_batch_number} is already created; exiting"
+            );
+            return Ok(true);
+        }
+        let full_snapshot_age = l1_batch_number.0 - latest_snapshot.l1_batch_number.0;
+        if full_snapshot_age >= config.full_snapshot_l1_batch_interval {
+            tracing::info!(
+                "Newest full snapshot for L1 batch #{} is {full_snapshot_age} L1 batches old; creating a full snapshot",
+                latest_snapshot.l1_batch_number
+            );
+            return Ok(false);
+        }
+
+        let (_, base_l2_block_number) = conn
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(base_l1_batch_number)
+            .await?
+            .with_context(|| {
+                format!("No L2 blocks for base snapshot L1 batch #{base_l1_batch_number}; was it pruned?")
+            })?;
+        let (_, l2_block_number) = conn
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(l1_batch_number)
+            .await?
+            .context("No L2 blocks for L1 batch")?;
+        let l2_block_numbers = (base_l2_block_number + 1)..=l2_block_number;
+        let modified_keys_count = conn
+            .snapshots_creator_dal()
+            .get_modified_keys_count(l2_block_numbers.clone())
+            .await?;
+        drop(conn);
+
+        let chunk_count = modified_keys_count
+            .div_ceil(config.storage_logs_chunk_size)
+            .max(min_chunk_count);
+        METRICS.storage_logs_chunks_count.set(chunk_count);
+        tracing::info!(
+            "Creating differential snapshot for L1 batch #{l1_batch_number} on top of the snapshot \
+             for L1 batch #{base_l1_batch_number}; {modified_keys_count} modified keys are divided into {chunk_count} chunks"
+        );
+
+        self.process_diff_factory_deps(l2_block_numbers.clone(), l1_batch_number)
+            .await?;
+
+        METRICS
+            .storage_logs_chunks_left_to_process
+            .set(chunk_count as usize);
+        let semaphore = Semaphore::new(config.concurrent_queries_count as usize);
+        let tasks = (0..chunk_count).map(|chunk_id| {
+            self.process_diff_storage_logs_single_chunk(
+                &semaphore,
+                l2_block_numbers.clone(),
+                l1_batch_number,
+                chunk_id,
+                chunk_count,
+            )
+        });
+        futures::future::try_join_all(tasks).await?;
+
+        // The manifest is saved last, so that its presence in the object store signals that the snapshot is complete.
+        let manifest = SnapshotDiffManifest {
+            version: SnapshotVersion::Version0.into(),
+            base_l1_batch_number,
+            l1_batch_number,
+            l2_block_number,
+            storage_logs_chunk_count: chunk_count,
+        };
+        self.blob_store
+            .put(l1_batch_number, &manifest)
+            .await
+            .context("Error storing differential snapshot manifest in blob store")?;
+        let mut master_conn = self
+            .master_pool
+            .connection_tagged("snapshots_creator")
+            .await?;
+        master_conn
+            .snapshots_dal()
+            .add_snapshot_diff(&manifest)
+            .await?;
+
+        METRICS.snapshot_l1_batch.set(l1_batch_number.0.into());
+        Ok(true)
+    }
+
+    /// Creates a new snapshot or resumes creating a pending one. Does nothing if a snapshot for the L1 batch
+    /// that would be selected is already created.
     pub async fn run(
-        self,
+        &self,
         config: SnapshotsCreatorConfig,
         min_chunk_count: u64,
     ) -> anyhow::Result<()> {
@@ -284,6 +520,12 @@ impl SnapshotCreator {
         );
         let latency = METRICS.snapshot_generation_duration.start();
 
+        if config.diff_snapshots && self.create_diff_snapshot(&config, min_chunk_count).await? {
+            let elapsed = latency.observe();
+            tracing::info!("snapshot_generation_duration: {elapsed:?}");
+            return Ok(());
+        }
+
         let Some(progress) = self
             .load_or_initialize_snapshot_progress(&config, min_chunk_count)
             .await?
@@ -0,0 +1,34 @@
+//! Snapshot creator component. Snapshots can be created either by a one-off run of [`SnapshotCreator`]
+//! (e.g., in a standalone binary triggered on a schedule), or periodically by [`PeriodicSnapshotsCreator`]
+//! running as a node component.
+//!
+//! # Assumptions
+//!
+//! The snapshot creator is fault-tolerant; if it stops in the middle of creating a snapshot,
+//! this snapshot will be continued from roughly the same point after the restart. If this is
+//! undesired, remove the `snapshots` table record corresponding to the pending snapshot.
+//!
+//! It is assumed that the snapshot creator is run as a singleton process (no more than 1 instance
+//! at a time).
+//!
+//! # Differential snapshots
+//!
+//! If `diff_snapshots` is enabled in the config, the creator produces a differential snapshot on top
+//! of the newest existing (full or differential) snapshot. Such a snapshot only contains storage logs
+//! and factory deps changed since the base snapshot, together with a manifest linking to the base snapshot.
+//! Unlike full snapshots, differential snapshots are not resumed after a restart.
+//!
+//! A full snapshot is still created once the newest full snapshot is `full_snapshot_l1_batch_interval` L1 batches old,
+//! so that differential snapshot chains remain short. The periodic creator removes differential snapshots older than
+//! the newest full snapshot.
+
+pub use self::{creator::SnapshotCreator, periodic::PeriodicSnapshotsCreator};
+
+mod creator;
+mod metrics;
+mod periodic;
+#[cfg(test)]
+mod tests;
+
+/// Minimum number of storage log chunks to produce.
+pub const MIN_CHUNK_COUNT: u64 = 10;
@@ -2,7 +2,9 @@
 
 use std::time::Duration;
 
-use vise::{Buckets, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit};
+use vise::{
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "stage", rename_all = "snake_case")]
@@ -37,6 +39,12 @@ pub(crate) struct SnapshotsCreatorMetrics {
     /// Latency of factory deps processing split by stage.
     #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
     pub factory_deps_processing_duration: Family<FactoryDepsStage, Histogram<Duration>>,
+    /// Number of complete full snapshots retained by the periodic snapshots creator.
+    pub retained_snapshots: Gauge<usize>,
+    /// Number of snapshots removed by the periodic snapshots creator.
+    pub removed_snapshots: Counter,
+    /// Number of differential snapshots removed by the periodic snapshots creator.
+    pub removed_snapshot_diffs: Counter,
 }
 
 #[vise::register]
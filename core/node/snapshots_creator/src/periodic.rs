@@ -0,0 +1,336 @@
+//! [`PeriodicSnapshotsCreator`] and tightly related types.
+
+use anyhow::Context as _;
+use serde::Serialize;
+use tokio::sync::{watch, Semaphore};
+use zksync_config::SnapshotsCreatorConfig;
+use zksync_dal::CoreDal;
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_object_store::ObjectStoreError;
+use zksync_types::{
+    snapshots::{
+        SnapshotDiffManifest, SnapshotDiffMetadata, SnapshotDiffPart, SnapshotFactoryDependencies,
+        SnapshotMetadata, SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey,
+    },
+    L1BatchNumber,
+};
+
+use crate::{metrics::METRICS, SnapshotCreator};
+
+const CONCURRENT_REMOVE_REQUESTS: usize = 20;
+
+fn ignore_not_found_errors(err: ObjectStoreError) -> Result<(), ObjectStoreError> {
+    match err {
+        ObjectStoreError::KeyNotFound(err) => {
+            tracing::debug!("Ignoring 'not found' object store error: {err}");
+            Ok(())
+        }
+        _ => Err(err),
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct SnapshotsCreatorHealth {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_snapshot_l1_batch: Option<L1BatchNumber>,
+    retained_snapshots: Vec<L1BatchNumber>,
+}
+
+/// Snapshots creator running as a node component. Creates a full (or, if configured, differential) snapshot
+/// every `l1_batch_interval` L1 batches and removes full snapshots beyond the `retained_snapshots_count`
+/// newest ones, including their object store data.
+///
+/// A pending snapshot is resumed after a restart using the progress tracked in Postgres. Differential snapshots
+/// older than the newest complete full snapshot are removed as well.
+#[derive(Debug)]
+pub struct PeriodicSnapshotsCreator {
+    creator: SnapshotCreator,
+    config: SnapshotsCreatorConfig,
+    min_chunk_count: u64,
+    health_updater: HealthUpdater,
+}
+
+impl PeriodicSnapshotsCreator {
+    pub fn new(
+        creator: SnapshotCreator,
+        config: SnapshotsCreatorConfig,
+        min_chunk_count: u64,
+    ) -> Self {
+        Self {
+            creator,
+            config,
+            min_chunk_count,
+            health_updater: ReactiveHealthCheck::new("snapshots_creator").1,
+        }
+    }
+
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    /// Checks whether a snapshot should be created or resumed.
+    async fn should_create_snapshot(&self) -> anyhow::Result<bool> {
+        let mut conn = self
+            .creator
+            .master_pool
+            .connection_tagged("snapshots_creator")
+            .await?;
+        let newest_snapshot = conn.snapshots_dal().get_newest_snapshot_metadata().await?;
+        if let Some(snapshot) = newest_snapshot.as_ref().filter(|s| !s.is_complete()) {
+            tracing::info!(
+                "Snapshot for L1 batch #{} is pending; resuming its creation",
+                snapshot.l1_batch_number
+            );
+            return Ok(true);
+        }
+
+        let mut last_snapshot_l1_batch = newest_snapshot.map(|snapshot| snapshot.l1_batch_number);
+        if self.config.diff_snapshots {
+            let newest_diff_l1_batch = conn
+                .snapshots_dal()
+                .get_newest_snapshot_diff_l1_batch_number()
+                .await?;
+            last_snapshot_l1_batch = last_snapshot_l1_batch.max(newest_diff_l1_batch);
+        }
+        let Some(sealed_l1_batch_number) = conn.blocks_dal().get_sealed_l1_batch_number().await?
+        else {
+            return Ok(false);
+        };
+        // The snapshot creator selects the L1 batch preceding the sealed one.
+        let Some(snapshot_l1_batch) = sealed_l1_batch_number.0.checked_sub(1) else {
+            return Ok(false);
+        };
+
+        Ok(match last_snapshot_l1_batch {
+            None => true,
+            Some(last) => {
+                let next_snapshot_l1_batch = last.0.saturating_add(self.config.l1_batch_interval);
+                snapshot_l1_batch >= next_snapshot_l1_batch
+            }
+        })
+    }
+
+    /// Removes complete full snapshots beyond the configured retention count. Returns L1 batch numbers
+    /// of the retained snapshots in descending order.
+    async fn prune_snapshots(&self) -> anyhow::Result<Vec<L1BatchNumber>> {
+        let retained_count = self.config.retained_snapshots_count as usize;
+        let mut conn = self
+            .creator
+            .master_pool
+            .connection_tagged("snapshots_creator")
+            .await?;
+        let mut snapshots = conn
+            .snapshots_dal()
+            .get_all_complete_snapshots()
+            .await?
+            .snapshots_l1_batch_numbers;
+        if snapshots.len() <= retained_count {
+            return Ok(snapshots);
+        }
+
+        let first_retained_l1_batch = snapshots[retained_count - 1];
+        let removed_snapshots = conn
+            .snapshots_dal()
+            .get_snapshots_before(first_retained_l1_batch)
+            .await?;
+        drop(conn);
+
+        // Files are removed before Postgres rows, so that a failure while removing files doesn't leave orphaned files.
+        // Removal is retried on the next iteration in this case; missing files are ignored.
+        for snapshot in &removed_snapshots {
+            self.remove_snapshot_files(snapshot).await?;
+        }
+        self.creator
+            .master_pool
+            .connection_tagged("snapshots_creator")
+            .await?
+            .snapshots_dal()
+            .delete_snapshots_before(first_retained_l1_batch)
+            .await?;
+        tracing::info!(
+            "Removed {} snapshot(s) older than L1 batch #{first_retained_l1_batch}",
+            removed_snapshots.len()
+        );
+
+        METRICS
+            .removed_snapshots
+            .inc_by(removed_snapshots.len() as u64);
+        snapshots.truncate(retained_count);
+        Ok(snapshots)
+    }
+
+    async fn remove_snapshot_files(&self, snapshot: &SnapshotMetadata) -> anyhow::Result<()> {
+        let l1_batch_number = snapshot.l1_batch_number;
+        let blob_store = &self.creator.blob_store;
+        tracing::info!("Removing files for snapshot for L1 batch #{l1_batch_number}");
+        blob_store
+            .remove::<SnapshotFactoryDependencies>(l1_batch_number)
+            .await
+            .or_else(ignore_not_found_errors)
+            .with_context(|| {
+                format!("failed removing factory deps for snapshot for L1 batch #{l1_batch_number}")
+            })?;
+
+        let remove_semaphore = &Semaphore::new(CONCURRENT_REMOVE_REQUESTS);
+        let chunk_count = snapshot.storage_logs_filepaths.len() as u64;
+        let remove_futures = (0..chunk_count).map(|chunk_id| async move {
+            let _permit = remove_semaphore
+                .acquire()
+                .await
+                .context("semaphore is never closed")?;
+            let key = SnapshotStorageLogsStorageKey {
+                l1_batch_number,
+                chunk_id,
+            };
+            blob_store
+                .remove::<SnapshotStorageLogsChunk>(key)
+                .await
+                .or_else(ignore_not_found_errors)
+                .with_context(|| format!("failed removing storage logs chunk {key:?}"))
+        });
+        futures::future::try_join_all(remove_futures).await?;
+        Ok(())
+    }
+
+    /// Removes differential snapshots older than the newest complete full snapshot. Such snapshots are superseded
+    /// by the full snapshot, and new differential snapshots are never based on them.
+    async fn prune_snapshot_diffs(
+        &self,
+        newest_snapshot_l1_batch: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        let mut conn = self
+            .creator
+            .master_pool
+            .connection_tagged("snapshots_creator")
+            .await?;
+        let removed_diffs = conn
+            .snapshots_dal()
+            .get_snapshot_diffs_before(newest_snapshot_l1_batch)
+            .await?;
+        drop(conn);
+        if removed_diffs.is_empty() {
+            return Ok(());
+        }
+
+        // Similarly to full snapshots, files are removed before Postgres rows.
+        for diff in &removed_diffs {
+            self.remove_snapshot_diff_files(diff).await?;
+        }
+        self.creator
+            .master_pool
+            .connection_tagged("snapshots_creator")
+            .await?
+            .snapshots_dal()
+            .delete_snapshot_diffs_before(newest_snapshot_l1_batch)
+            .await?;
+        tracing::info!(
+            "Removed {} differential snapshot(s) older than L1 batch #{newest_snapshot_l1_batch}",
+            removed_diffs.len()
+        );
+        METRICS
+            .removed_snapshot_diffs
+            .inc_by(removed_diffs.len() as u64);
+        Ok(())
+    }
+
+    async fn remove_snapshot_diff_files(&self, diff: &SnapshotDiffMetadata) -> anyhow::Result<()> {
+        let l1_batch_number = diff.l1_batch_number;
+        let blob_store = &self.creator.blob_store;
+        tracing::info!("Removing files for differential snapshot for L1 batch #{l1_batch_number}");
+        // The manifest is removed first since its presence signals that the differential snapshot is complete.
+        blob_store
+            .remove::<SnapshotDiffManifest>(l1_batch_number)
+            .await
+            .or_else(ignore_not_found_errors)
+            .with_context(|| {
+                format!("failed removing manifest for differential snapshot for L1 batch #{l1_batch_number}")
+            })?;
+        blob_store
+            .remove::<SnapshotDiffPart<SnapshotFactoryDependencies>>(l1_batch_number)
+            .await
+            .or_else(ignore_not_found_errors)
+            .with_context(|| {
+                format!("failed removing factory deps for differential snapshot for L1 batch #{l1_batch_number}")
+            })?;
+
+        let remove_semaphore = &Semaphore::new(CONCURRENT_REMOVE_REQUESTS);
+        let remove_futures = (0..diff.storage_logs_chunk_count).map(|chunk_id| async move {
+            let _permit = remove_semaphore
+                .acquire()
+                .await
+                .context("semaphore is never closed")?;
+            let key = SnapshotStorageLogsStorageKey {
+                l1_batch_number,
+                chunk_id,
+            };
+            blob_store
+                .remove::<SnapshotDiffPart<SnapshotStorageLogsChunk>>(key)
+                .await
+                .or_else(ignore_not_found_errors)
+                .with_context(|| format!("failed removing storage logs diff chunk {key:?}"))
+        });
+        futures::future::try_join_all(remove_futures).await?;
+        Ok(())
+    }
+
+    pub(crate) async fn run_single_iteration(&self) -> anyhow::Result<()> {
+        if self.should_create_snapshot().await? {
+            self.creator
+                .run(self.config.clone(), self.min_chunk_count)
+                .await?;
+        }
+        let retained_snapshots = self.prune_snapshots().await?;
+        if let Some(&newest_snapshot_l1_batch) = retained_snapshots.first() {
+            self.prune_snapshot_diffs(newest_snapshot_l1_batch).await?;
+        }
+
+        METRICS.retained_snapshots.set(retained_snapshots.len());
+        let health = SnapshotsCreatorHealth {
+            last_snapshot_l1_batch: retained_snapshots.first().copied(),
+            retained_snapshots,
+        };
+        self.health_updater
+            .update(Health::from(HealthStatus::Ready).with_details(health));
+        Ok(())
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.config.retained_snapshots_count > 0,
+            "at least one snapshot must be retained"
+        );
+        let poll_interval = self.config.poll_interval();
+        tracing::info!(
+            "Starting periodic snapshots creator with object store {:?} and config {:?}",
+            self.creator.blob_store,
+            self.config
+        );
+
+        while !*stop_receiver.borrow_and_update() {
+            // Snapshot creation can take a long time, so it's interrupted on a stop signal. This is safe since
+            // the pending snapshot will be resumed after a restart.
+            let result = tokio::select! {
+                result = self.run_single_iteration() => result,
+                _ = stop_receiver.changed() => break,
+            };
+            if let Err(err) = result {
+                tracing::warn!(
+                    "Error creating or pruning snapshots, retrying in {poll_interval:?}, error was: {err:?}"
+                );
+                let health = Health::from(HealthStatus::Affected).with_details(serde_json::json!({
+                    "error": err.to_string(),
+                }));
+                self.health_updater.update(health);
+            }
+
+            if tokio::time::timeout(poll_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, shutting down periodic snapshots creator");
+        Ok(())
+    }
+}
@@ -0,0 +1,890 @@
+//! Lower-level tests for the snapshot creator component.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use async_trait::async_trait;
+use rand::{thread_rng, Rng};
+use zksync_config::SnapshotsCreatorConfig;
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_health_check::{CheckHealth, HealthStatus};
+use zksync_object_store::{Bucket, MockObjectStore, ObjectStore, ObjectStoreError};
+use zksync_types::{
+    block::{L1BatchHeader, L1BatchTreeData, L2BlockHeader},
+    snapshots::{
+        SnapshotDiffManifest, SnapshotDiffPart, SnapshotFactoryDependencies,
+        SnapshotFactoryDependency, SnapshotStorageLog, SnapshotStorageLogsChunk,
+        SnapshotStorageLogsStorageKey,
+    },
+    AccountTreeId, Address, L1BatchNumber, L2BlockNumber, ProtocolVersion, StorageKey, StorageLog,
+    H256,
+};
+
+use crate::{PeriodicSnapshotsCreator, SnapshotCreator, MIN_CHUNK_COUNT};
+
+const TEST_CONFIG: SnapshotsCreatorConfig = SnapshotsCreatorConfig {
+    storage_logs_chunk_size: 1_000_000,
+    concurrent_queries_count: 10,
+    object_store: None,
+    diff_snapshots: false,
+    full_snapshot_l1_batch_interval: 10_000,
+    l1_batch_interval: 1_000,
+    retained_snapshots_count: 3,
+    poll_interval_ms: 60_000,
+};
+const SEQUENTIAL_TEST_CONFIG: SnapshotsCreatorConfig = SnapshotsCreatorConfig {
+    storage_logs_chunk_size: 1_000_000,
+    concurrent_queries_count: 1,
+    object_store: None,
+    diff_snapshots: false,
+    full_snapshot_l1_batch_interval: 10_000,
+    l1_batch_interval: 1_000,
+    retained_snapshots_count: 3,
+    poll_interval_ms: 60_000,
+};
+
+#[derive(Debug)]
+struct TestEventListener {
+    stop_after_chunk_count: usize,
+    processed_chunk_count: AtomicUsize,
+}
+
+impl TestEventListener {
+    fn new(stop_after_chunk_count: usize) -> Self {
+        Self {
+            stop_after_chunk_count,
+            processed_chunk_count: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl HandleEvent for TestEventListener {
+    fn on_chunk_started(&self) -> TestBehavior {
+        let should_stop =
+            self.processed_chunk_count.load(Ordering::SeqCst) >= self.stop_after_chunk_count;
+        TestBehavior::new(should_stop)
+    }
+
+    fn on_chunk_saved(&self) {
+        self.processed_chunk_count.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+impl SnapshotCreator {
+    fn for_tests(blob_store: Arc<dyn ObjectStore>, pool: ConnectionPool<Core>) -> Self {
+        Self {
+            blob_store,
+            master_pool: pool.clone(),
+            replica_pool: pool,
+            event_listener: Box::new(()),
+        }
+    }
+
+    fn stop_after_chunk_count(self, stop_after_chunk_count: usize) -> Self {
+        Self {
+            event_listener: Box::new(TestEventListener::new(stop_after_chunk_count)),
+            ..self
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct TestBehavior {
+    should_exit: bool,
+}
+
+impl TestBehavior {
+    fn new(should_exit: bool) -> Self {
+        Self { should_exit }
+    }
+
+    pub fn should_exit(&self) -> bool {
+        self.should_exit
+    }
+}
+
+pub(crate) trait HandleEvent: fmt::Debug {
+    fn on_chunk_started(&self) -> TestBehavior {
+        TestBehavior::new(false)
+    }
+
+    fn on_chunk_saved(&self) {
+        // Do nothing
+    }
+}
+
+impl HandleEvent for () {}
+
+fn gen_storage_logs(rng: &mut impl Rng, count: usize) -> Vec<StorageLog> {
+    (0..count)
+        .map(|_| {
+            let key = StorageKey::new(AccountTreeId::from_fixed_bytes(rng.gen()), H256(rng.gen()));
+            StorageLog::new_write_log(key, H256(rng.gen()))
+        })
+        .collect()
+}
+
+fn gen_factory_deps(rng: &mut impl Rng, count: usize) -> HashMap<H256, Vec<u8>> {
+    (0..count)
+        .map(|_| {
+            let factory_len = 32 * rng.gen_range(32..256);
+            let mut factory = vec![0_u8; factory_len];
+            rng.fill_bytes(&mut factory);
+            (H256(rng.gen()), factory)
+        })
+        .collect()
+}
+
+#[derive(Debug, Default)]
+struct ExpectedOutputs {
+    deps: HashSet<SnapshotFactoryDependency>,
+    storage_logs: HashSet<SnapshotStorageLog>,
+}
+
+async fn create_l2_block(
+    conn: &mut Connection<'_, Core>,
+    l2_block_number: L2BlockNumber,
+    block_logs: Vec<StorageLog>,
+) {
+    let l2_block_header = L2BlockHeader {
+        number: l2_block_number,
+        timestamp: 0,
+        hash: H256::from_low_u64_be(u64::from(l2_block_number.0)),
+        l1_tx_count: 0,
+        l2_tx_count: 0,
+        fee_account_address: Address::repeat_byte(1),
+        base_fee_per_gas: 0,
+        gas_per_pubdata_limit: 0,
+        batch_fee_input: Default::default(),
+        base_system_contracts_hashes: Default::default(),
+        protocol_version: Some(Default::default()),
+        virtual_blocks: 0,
+        gas_limit: 0,
+    };
+
+    conn.blocks_dal()
+        .insert_l2_block(&l2_block_header)
+        .await
+        .unwrap();
+    conn.storage_logs_dal()
+        .insert_storage_logs(l2_block_number, &block_logs)
+        .await
+        .unwrap();
+}
+
+async fn create_l1_batch(
+    conn: &mut Connection<'_, Core>,
+    l1_batch_number: L1BatchNumber,
+    logs_for_initial_writes: &[StorageLog],
+) {
+    let header = L1BatchHeader::new(l1_batch_number, 0, Default::default(), Default::default());
+    conn.blocks_dal()
+        .insert_mock_l1_batch(&header)
+        .await
+        .unwrap();
+    conn.blocks_dal()
+        .mark_l2_blocks_as_executed_in_l1_batch(l1_batch_number)
+        .await
+        .unwrap();
+
+    let mut written_keys: Vec<_> = logs_for_initial_writes.iter().map(|log| log.key).collect();
+    written_keys.sort_unstable();
+    conn.storage_logs_dedup_dal()
+        .insert_initial_writes(l1_batch_number, &written_keys)
+        .await
+        .unwrap();
+    conn.blocks_dal()
+        .save_l1_batch_tree_data(
+            l1_batch_number,
+            &L1BatchTreeData {
+                hash: H256::zero(),
+                rollup_last_leaf_index: 1,
+            },
+        )
+        .await
+        .unwrap();
+}
+
+async fn prepare_postgres(
+    rng: &mut impl Rng,
+    conn: &mut Connection<'_, Core>,
+    block_count: u32,
+) -> ExpectedOutputs {
+    conn.protocol_versions_dal()
+        .save_protocol_version_with_tx(&ProtocolVersion::default())
+        .await
+        .unwrap();
+
+    let mut outputs = ExpectedOutputs::default();
+    for block_number in 0..block_count {
+        let logs = gen_storage_logs(rng, 100);
+        create_l2_block(conn, L2BlockNumber(block_number), logs.clone()).await;
+
+        let factory_deps = gen_factory_deps(rng, 10);
+        conn.factory_deps_dal()
+            .insert_factory_deps(L2BlockNumber(block_number), &factory_deps)
+            .await
+            .unwrap();
+
+        // Since we generate `logs` randomly, all of them are written the first time.
+        create_l1_batch(conn, L1BatchNumber(block_number), &logs).await;
+
+        if block_number + 1 < block_count {
+            let factory_deps =
+                factory_deps
+                    .into_values()
+                    .map(|bytecode| SnapshotFactoryDependency {
+                        bytecode: bytecode.into(),
+                    });
+            outputs.deps.extend(factory_deps);
+
+            let hashed_keys: Vec<_> = logs.iter().map(|log| log.key.hashed_key()).collect();
+            let expected_l1_batches_and_indices = conn
+                .storage_logs_dal()
+                .get_l1_batches_and_indices_for_initial_writes(&hashed_keys)
+                .await
+                .unwrap();
+
+            let logs = logs.into_iter().map(|log| {
+                let (l1_batch_number_of_initial_write, enumeration_index) =
+                    expected_l1_batches_and_indices[&log.key.hashed_key()];
+                SnapshotStorageLog {
+                    key: log.key,
+                    value: log.value,
+                    l1_batch_number_of_initial_write,
+                    enumeration_index,
+                }
+            });
+            outputs.storage_logs.extend(logs);
+        }
+    }
+    outputs
+}
+
+#[tokio::test]
+async fn persisting_snapshot_metadata() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+
+    // Insert some data to Postgres.
+    let mut conn = pool.connection().await.unwrap();
+    prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    SnapshotCreator::for_tests(object_store, pool.clone())
+        .run(TEST_CONFIG, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+
+    // Check snapshot metadata in Postgres.
+    let snapshots = conn
+        .snapshots_dal()
+        .get_all_complete_snapshots()
+        .await
+        .unwrap();
+    assert_eq!(snapshots.snapshots_l1_batch_numbers.len(), 1);
+    let snapshot_l1_batch_number = snapshots.snapshots_l1_batch_numbers[0];
+    assert_eq!(snapshot_l1_batch_number, L1BatchNumber(8));
+
+    let snapshot_metadata = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(snapshot_l1_batch_number)
+        .await
+        .unwrap()
+        .expect("No snapshot metadata");
+    assert_eq!(snapshot_metadata.l1_batch_number, snapshot_l1_batch_number);
+    let factory_deps_path = &snapshot_metadata.factory_deps_filepath;
+    assert!(factory_deps_path.ends_with(".proto.gzip"));
+    assert_eq!(
+        snapshot_metadata.storage_logs_filepaths.len(),
+        MIN_CHUNK_COUNT as usize
+    );
+    for path in &snapshot_metadata.storage_logs_filepaths {
+        let path = path
+            .as_ref()
+            .unwrap()
+            .strip_prefix("storage_logs_snapshots/")
+            .unwrap();
+        assert!(path.ends_with(".proto.gzip"));
+    }
+}
+
+#[tokio::test]
+async fn persisting_snapshot_factory_deps() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    let expected_outputs = prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(TEST_CONFIG, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+    let snapshot_l1_batch_number = L1BatchNumber(8);
+
+    let SnapshotFactoryDependencies { factory_deps } =
+        object_store.get(snapshot_l1_batch_number).await.unwrap();
+    let actual_deps: HashSet<_> = factory_deps.into_iter().collect();
+    assert_eq!(actual_deps, expected_outputs.deps);
+}
+
+#[tokio::test]
+async fn persisting_snapshot_logs() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    let expected_outputs = prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(TEST_CONFIG, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+    let snapshot_l1_batch_number = L1BatchNumber(8);
+
+    assert_storage_logs(&*object_store, snapshot_l1_batch_number, &expected_outputs).await;
+}
+
+async fn assert_storage_logs(
+    object_store: &dyn ObjectStore,
+    snapshot_l1_batch_number: L1BatchNumber,
+    expected_outputs: &ExpectedOutputs,
+) {
+    let mut actual_logs = HashSet::new();
+    for chunk_id in 0..MIN_CHUNK_COUNT {
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: snapshot_l1_batch_number,
+            chunk_id,
+        };
+        let chunk: SnapshotStorageLogsChunk = object_store.get(key).await.unwrap();
+        actual_logs.extend(chunk.storage_logs.into_iter());
+    }
+    assert_eq!(actual_logs, expected_outputs.storage_logs);
+}
+
+#[tokio::test]
+async fn recovery_workflow() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    let expected_outputs = prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .stop_after_chunk_count(0)
+        .run(SEQUENTIAL_TEST_CONFIG, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+
+    let snapshot_l1_batch_number = L1BatchNumber(8);
+    let snapshot_metadata = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(snapshot_l1_batch_number)
+        .await
+        .unwrap()
+        .expect("No snapshot metadata");
+    assert!(snapshot_metadata
+        .storage_logs_filepaths
+        .iter()
+        .all(Option::is_none));
+
+    let SnapshotFactoryDependencies { factory_deps } =
+        object_store.get(snapshot_l1_batch_number).await.unwrap();
+    let actual_deps: HashSet<_> = factory_deps.into_iter().collect();
+    assert_eq!(actual_deps, expected_outputs.deps);
+
+    // Process 2 storage log chunks, then stop.
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .stop_after_chunk_count(2)
+        .run(SEQUENTIAL_TEST_CONFIG, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+
+    let snapshot_metadata = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(snapshot_l1_batch_number)
+        .await
+        .unwrap()
+        .expect("No snapshot metadata");
+    assert_eq!(
+        snapshot_metadata
+            .storage_logs_filepaths
+            .iter()
+            .flatten()
+            .count(),
+        2
+    );
+
+    // Process the remaining chunks.
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(SEQUENTIAL_TEST_CONFIG, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+
+    assert_storage_logs(&*object_store, snapshot_l1_batch_number, &expected_outputs).await;
+}
+
+#[tokio::test]
+async fn recovery_workflow_with_varying_chunk_size() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    let expected_outputs = prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .stop_after_chunk_count(2)
+        .run(SEQUENTIAL_TEST_CONFIG, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+
+    let snapshot_l1_batch_number = L1BatchNumber(8);
+    let snapshot_metadata = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(snapshot_l1_batch_number)
+        .await
+        .unwrap()
+        .expect("No snapshot metadata");
+    assert_eq!(
+        snapshot_metadata
+            .storage_logs_filepaths
+            .iter()
+            .flatten()
+            .count(),
+        2
+    );
+
+    let config_with_other_size = SnapshotsCreatorConfig {
+        storage_logs_chunk_size: 1, // << should be ignored
+        ..SEQUENTIAL_TEST_CONFIG
+    };
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(config_with_other_size, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+
+    assert_storage_logs(&*object_store, snapshot_l1_batch_number, &expected_outputs).await;
+}
+
+async fn load_storage_state(
+    conn: &mut Connection<'_, Core>,
+    l2_block_number: L2BlockNumber,
+    l1_batch_number: L1BatchNumber,
+) -> HashMap<H256, SnapshotStorageLog> {
+    let all_hashed_keys = H256::zero()..=H256::repeat_byte(0xff);
+    let logs = conn
+        .snapshots_creator_dal()
+        .get_storage_logs_chunk(l2_block_number, l1_batch_number, all_hashed_keys)
+        .await
+        .unwrap();
+    logs.into_iter()
+        .map(|log| (log.key.hashed_key(), log))
+        .collect()
+}
+
+#[tokio::test]
+async fn creating_diff_snapshot() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    let expected_outputs = prepare_postgres(&mut rng, &mut conn, 10).await;
+    let config = SnapshotsCreatorConfig {
+        diff_snapshots: true,
+        ..TEST_CONFIG
+    };
+
+    // Since there are no snapshots yet, a full snapshot should be created.
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(config.clone(), MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+    let snapshots = conn
+        .snapshots_dal()
+        .get_all_complete_snapshots()
+        .await
+        .unwrap();
+    assert_eq!(snapshots.snapshots_l1_batch_numbers, [L1BatchNumber(8)]);
+    assert_storage_logs(&*object_store, L1BatchNumber(8), &expected_outputs).await;
+
+    // Add L1 batches with new and updated storage logs.
+    let new_logs = gen_storage_logs(&mut rng, 50);
+    let updated_logs = expected_outputs
+        .storage_logs
+        .iter()
+        .take(20)
+        .map(|log| StorageLog::new_write_log(log.key, H256(rng.gen())));
+    let block_logs = new_logs.iter().copied().chain(updated_logs).collect();
+    create_l2_block(&mut conn, L2BlockNumber(10), block_logs).await;
+    conn.factory_deps_dal()
+        .insert_factory_deps(L2BlockNumber(10), &gen_factory_deps(&mut rng, 5))
+        .await
+        .unwrap();
+    create_l1_batch(&mut conn, L1BatchNumber(10), &new_logs).await;
+    let new_logs = gen_storage_logs(&mut rng, 10);
+    create_l2_block(&mut conn, L2BlockNumber(11), new_logs.clone()).await;
+    create_l1_batch(&mut conn, L1BatchNumber(11), &new_logs).await;
+
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(config.clone(), MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+
+    let newest_diff = conn
+        .snapshots_dal()
+        .get_newest_snapshot_diff_l1_batch_number()
+        .await
+        .unwrap();
+    assert_eq!(newest_diff, Some(L1BatchNumber(10)));
+    let manifest: SnapshotDiffManifest = object_store.get(L1BatchNumber(10)).await.unwrap();
+    assert_eq!(manifest.base_l1_batch_number, L1BatchNumber(8));
+    assert_eq!(manifest.l2_block_number, L2BlockNumber(10));
+    assert_eq!(manifest.storage_logs_chunk_count, MIN_CHUNK_COUNT);
+
+    let mut actual_logs = HashSet::new();
+    for chunk_id in 0..manifest.storage_logs_chunk_count {
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: L1BatchNumber(10),
+            chunk_id,
+        };
+        let chunk: SnapshotDiffPart<SnapshotStorageLogsChunk> =
+            object_store.get(key).await.unwrap();
+        actual_logs.extend(chunk.0.storage_logs);
+    }
+    // Applying the diff to the base snapshot must produce the storage state at the end of L1 batch #10.
+    let base_state = load_storage_state(&mut conn, L2BlockNumber(8), L1BatchNumber(8)).await;
+    let target_state = load_storage_state(&mut conn, L2BlockNumber(10), L1BatchNumber(10)).await;
+    let expected_logs: HashSet<_> = target_state
+        .into_values()
+        .filter(|log| base_state.get(&log.key.hashed_key()) != Some(log))
+        .collect();
+    assert_eq!(expected_logs.len(), 100 + 50 + 20); // logs from L1 batches #9 and #10
+    assert_eq!(actual_logs, expected_logs);
+
+    let SnapshotDiffPart(SnapshotFactoryDependencies { factory_deps }) =
+        object_store.get(L1BatchNumber(10)).await.unwrap();
+    let actual_deps: HashSet<_> = factory_deps.into_iter().collect();
+    let all_deps = conn
+        .snapshots_creator_dal()
+        .get_all_factory_deps(L2BlockNumber(10))
+        .await
+        .unwrap();
+    let expected_deps: HashSet<_> = all_deps
+        .into_iter()
+        .map(|(_, bytecode)| SnapshotFactoryDependency {
+            bytecode: bytecode.into(),
+        })
+        .filter(|dep| !expected_outputs.deps.contains(dep))
+        .collect();
+    assert_eq!(expected_deps.len(), 15);
+    assert_eq!(actual_deps, expected_deps);
+
+    // A repeated run should not create any new snapshots.
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(config, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+    let newest_diff = conn
+        .snapshots_dal()
+        .get_newest_snapshot_diff_l1_batch_number()
+        .await
+        .unwrap();
+    assert_eq!(newest_diff, Some(L1BatchNumber(10)));
+}
+
+async fn add_l1_batch(rng: &mut impl Rng, conn: &mut Connection<'_, Core>, number: u32) {
+    let logs = gen_storage_logs(rng, 10);
+    create_l2_block(conn, L2BlockNumber(number), logs.clone()).await;
+    create_l1_batch(conn, L1BatchNumber(number), &logs).await;
+}
+
+#[tokio::test]
+async fn periodic_snapshots_creator_basics() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    prepare_postgres(&mut rng, &mut conn, 10).await;
+    let config = SnapshotsCreatorConfig {
+        l1_batch_interval: 2,
+        retained_snapshots_count: 2,
+        ..TEST_CONFIG
+    };
+    let creator = PeriodicSnapshotsCreator::new(
+        SnapshotCreator::for_tests(object_store.clone(), pool.clone()),
+        config,
+        MIN_CHUNK_COUNT,
+    );
+
+    // The first snapshot should be created immediately.
+    creator.run_single_iteration().await.unwrap();
+    let snapshots = conn
+        .snapshots_dal()
+        .get_all_complete_snapshots()
+        .await
+        .unwrap();
+    assert_eq!(snapshots.snapshots_l1_batch_numbers, [L1BatchNumber(8)]);
+    let health = creator.health_check().check_health().await;
+    assert_eq!(health.status(), HealthStatus::Ready);
+
+    // The next snapshot should only be created after `l1_batch_interval` L1 batches.
+    add_l1_batch(&mut rng, &mut conn, 10).await;
+    creator.run_single_iteration().await.unwrap();
+    let snapshots = conn
+        .snapshots_dal()
+        .get_all_complete_snapshots()
+        .await
+        .unwrap();
+    assert_eq!(snapshots.snapshots_l1_batch_numbers, [L1BatchNumber(8)]);
+
+    add_l1_batch(&mut rng, &mut conn, 11).await;
+    creator.run_single_iteration().await.unwrap();
+    let snapshots = conn
+        .snapshots_dal()
+        .get_all_complete_snapshots()
+        .await
+        .unwrap();
+    assert_eq!(
+        snapshots.snapshots_l1_batch_numbers,
+        [L1BatchNumber(10), L1BatchNumber(8)]
+    );
+
+    // Creating the third snapshot should lead to the oldest one being removed.
+    add_l1_batch(&mut rng, &mut conn, 12).await;
+    add_l1_batch(&mut rng, &mut conn, 13).await;
+    creator.run_single_iteration().await.unwrap();
+    let snapshots = conn
+        .snapshots_dal()
+        .get_all_complete_snapshots()
+        .await
+        .unwrap();
+    assert_eq!(
+        snapshots.snapshots_l1_batch_numbers,
+        [L1BatchNumber(12), L1BatchNumber(10)]
+    );
+    let removed_snapshot = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(L1BatchNumber(8))
+        .await
+        .unwrap();
+    assert!(removed_snapshot.is_none(), "{removed_snapshot:?}");
+
+    let err = object_store
+        .get::<SnapshotFactoryDependencies>(L1BatchNumber(8))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ObjectStoreError::KeyNotFound(_)), "{err:?}");
+    for chunk_id in 0..MIN_CHUNK_COUNT {
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: L1BatchNumber(8),
+            chunk_id,
+        };
+        let err = object_store
+            .get::<SnapshotStorageLogsChunk>(key)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ObjectStoreError::KeyNotFound(_)), "{err:?}");
+    }
+    // Files for retained snapshots must be intact.
+    object_store
+        .get::<SnapshotFactoryDependencies>(L1BatchNumber(10))
+        .await
+        .unwrap();
+
+    let health = creator.health_check().check_health().await;
+    let details = health.details().unwrap();
+    assert_eq!(details["last_snapshot_l1_batch"], 12);
+    assert_eq!(details["retained_snapshots"], serde_json::json!([12, 10]));
+}
+
+#[tokio::test]
+async fn periodic_snapshots_creator_with_diff_snapshots() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    prepare_postgres(&mut rng, &mut conn, 10).await;
+    let config = SnapshotsCreatorConfig {
+        diff_snapshots: true,
+        full_snapshot_l1_batch_interval: 3,
+        l1_batch_interval: 1,
+        retained_snapshots_count: 2,
+        ..TEST_CONFIG
+    };
+    let creator = PeriodicSnapshotsCreator::new(
+        SnapshotCreator::for_tests(object_store.clone(), pool.clone()),
+        config,
+        MIN_CHUNK_COUNT,
+    );
+
+    creator.run_single_iteration().await.unwrap();
+    let snapshots = conn
+        .snapshots_dal()
+        .get_all_complete_snapshots()
+        .await
+        .unwrap();
+    assert_eq!(snapshots.snapshots_l1_batch_numbers, [L1BatchNumber(8)]);
+
+    for number in [10, 11] {
+        add_l1_batch(&mut rng, &mut conn, number).await;
+        creator.run_single_iteration().await.unwrap();
+        let newest_diff = conn
+            .snapshots_dal()
+            .get_newest_snapshot_diff_l1_batch_number()
+            .await
+            .unwrap();
+        assert_eq!(newest_diff, Some(L1BatchNumber(number - 1)));
+    }
+    let manifest: SnapshotDiffManifest = object_store.get(L1BatchNumber(10)).await.unwrap();
+    assert_eq!(manifest.base_l1_batch_number, L1BatchNumber(9));
+
+    // The newest full snapshot becomes `full_snapshot_l1_batch_interval` L1 batches old, so a full snapshot
+    // should be created, and all differential snapshots should be removed.
+    add_l1_batch(&mut rng, &mut conn, 12).await;
+    creator.run_single_iteration().await.unwrap();
+    let snapshots = conn
+        .snapshots_dal()
+        .get_all_complete_snapshots()
+        .await
+        .unwrap();
+    assert_eq!(
+        snapshots.snapshots_l1_batch_numbers,
+        [L1BatchNumber(11), L1BatchNumber(8)]
+    );
+    let newest_diff = conn
+        .snapshots_dal()
+        .get_newest_snapshot_diff_l1_batch_number()
+        .await
+        .unwrap();
+    assert_eq!(newest_diff, None);
+
+    for l1_batch_number in [L1BatchNumber(9), L1BatchNumber(10)] {
+        let err = object_store
+            .get::<SnapshotDiffManifest>(l1_batch_number)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ObjectStoreError::KeyNotFound(_)), "{err:?}");
+        let err = object_store
+            .get::<SnapshotDiffPart<SnapshotFactoryDependencies>>(l1_batch_number)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ObjectStoreError::KeyNotFound(_)), "{err:?}");
+        for chunk_id in 0..MIN_CHUNK_COUNT {
+            let key = SnapshotStorageLogsStorageKey {
+                l1_batch_number,
+                chunk_id,
+            };
+            let err = object_store
+                .get::<SnapshotDiffPart<SnapshotStorageLogsChunk>>(key)
+                .await
+                .unwrap_err();
+            assert!(matches!(err, ObjectStoreError::KeyNotFound(_)), "{err:?}");
+        }
+    }
+
+    // The next differential snapshot should be based on the new full snapshot.
+    add_l1_batch(&mut rng, &mut conn, 13).await;
+    creator.run_single_iteration().await.unwrap();
+    let manifest: SnapshotDiffManifest = object_store.get(L1BatchNumber(12)).await.unwrap();
+    assert_eq!(manifest.base_l1_batch_number, L1BatchNumber(11));
+}
+
+/// Object store failing all removals while `fail_removals` is set.
+#[derive(Debug)]
+struct StoreWithFailingRemovals {
+    inner: Arc<dyn ObjectStore>,
+    fail_removals: AtomicBool,
+}
+
+#[async_trait]
+impl ObjectStore for StoreWithFailingRemovals {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        self.inner.get_raw(bucket, key).await
+    }
+
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        self.inner.put_raw(bucket, key, value).await
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        if self.fail_removals.load(Ordering::Relaxed) {
+            return Err(ObjectStoreError::Other {
+                is_transient: true,
+                source: "removal failed".into(),
+            });
+        }
+        self.inner.remove_raw(bucket, key).await
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        self.inner.storage_prefix_raw(bucket)
+    }
+}
+
+#[tokio::test]
+async fn periodic_snapshots_creator_retries_removing_files() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let inner_store = MockObjectStore::arc();
+    let object_store = Arc::new(StoreWithFailingRemovals {
+        inner: inner_store.clone(),
+        fail_removals: AtomicBool::new(true),
+    });
+    let mut conn = pool.connection().await.unwrap();
+    prepare_postgres(&mut rng, &mut conn, 10).await;
+    let config = SnapshotsCreatorConfig {
+        l1_batch_interval: 2,
+        retained_snapshots_count: 1,
+        ..TEST_CONFIG
+    };
+    let creator = PeriodicSnapshotsCreator::new(
+        SnapshotCreator::for_tests(object_store.clone(), pool.clone()),
+        config,
+        MIN_CHUNK_COUNT,
+    );
+    creator.run_single_iteration().await.unwrap();
+
+    add_l1_batch(&mut rng, &mut conn, 10).await;
+    add_l1_batch(&mut rng, &mut conn, 11).await;
+    creator.run_single_iteration().await.unwrap_err();
+    // The snapshot must not be removed from Postgres if its files are not removed.
+    let snapshots = conn
+        .snapshots_dal()
+        .get_all_complete_snapshots()
+        .await
+        .unwrap();
+    assert_eq!(
+        snapshots.snapshots_l1_batch_numbers,
+        [L1BatchNumber(10), L1BatchNumber(8)]
+    );
+    inner_store
+        .get::<SnapshotFactoryDependencies>(L1BatchNumber(8))
+        .await
+        .unwrap();
+
+    object_store.fail_removals.store(false, Ordering::Relaxed);
+    creator.run_single_iteration().await.unwrap();
+    let snapshots = conn
+        .snapshots_dal()
+        .get_all_complete_snapshots()
+        .await
+        .unwrap();
+    assert_eq!(snapshots.snapshots_l1_batch_numbers, [L1BatchNumber(10)]);
+    let err = inner_store
+        .get::<SnapshotFactoryDependencies>(L1BatchNumber(8))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ObjectStoreError::KeyNotFound(_)), "{err:?}");
+}
@@ -32,6 +32,11 @@ impl AsyncRocksdbCache {
             task.with_db_options(state_keeper_db_options),
         )
     }
+
+    /// Returns a handle to the RocksDB cache, e.g. to create checkpoints of it once it's caught up.
+    pub fn rocksdb_cell(&self) -> RocksdbCell {
+        self.rocksdb_cell.clone()
+    }
 }
 
 #[async_trait]
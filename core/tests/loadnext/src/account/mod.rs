@@ -4,8 +4,11 @@ use std::{
     time::{Duration, Instant},
 };
 
-use futures::{channel::mpsc, SinkExt};
-use tokio::sync::RwLock;
+use futures::{channel::mpsc, FutureExt, SinkExt};
+use tokio::{
+    sync::RwLock,
+    time::{Interval, MissedTickBehavior},
+};
 use zksync_contracts::test_contracts::LoadnextContractExecutionParams;
 use zksync_types::{api::TransactionReceipt, Address, Nonce, H256, U256, U64};
 use zksync_web3_decl::{
@@ -14,12 +17,14 @@ use zksync_web3_decl::{
 };
 
 use crate::{
-    account::tx_command_executor::SubmitResult,
+    account::tx_command_executor::{ReceiptStatus, SubmitResult},
     account_pool::{AddressPool, TestWallet},
     command::{ExpectedOutcome, IncorrectnessModifier, TxCommand, TxType},
     config::{LoadtestConfig, RequestLimiters},
     constants::{MAX_L1_TRANSACTIONS, POLLING_INTERVAL},
-    report::{Report, ReportBuilder, ReportLabel},
+    open_loop::ArrivalSchedule,
+    report::{Report, ReportBuilder, ReportLabel, TxLatencies},
+    scenario::CommandSource,
     sdk::{error::ClientError, operations::SyncTransactionHandle},
     utils::format_gwei,
 };
@@ -44,6 +49,10 @@ struct InflightTx {
     tx_hash: H256,
     attempt: usize,
     start: Instant,
+    /// Time the transaction was intended to be sent at; latencies are measured from it.
+    intended_start: Instant,
+    submit_latency: Duration,
+    inclusion_latency: Option<Duration>,
     command: TxCommand,
 }
 
@@ -75,6 +84,11 @@ pub struct AccountLifespan {
     inflight_txs: VecDeque<InflightTx>,
     /// Current account nonce, it is None at the beginning and will be set after the first transaction
     current_nonce: Option<Nonce>,
+    /// Source of commands defined by a scenario or a replayed trace. If not set, commands are generated randomly.
+    command_source: Option<Arc<CommandSource>>,
+    /// Schedule of transaction arrivals in the open-loop mode. If not set, transactions are sent as soon as
+    /// the account has capacity for them.
+    arrival_schedule: Option<Arc<ArrivalSchedule>>,
 }
 
 impl AccountLifespan {
@@ -86,6 +100,8 @@ impl AccountLifespan {
         report_sink: mpsc::Sender<Report>,
         main_l2_token: Address,
         paymaster_address: Address,
+        command_source: Option<Arc<CommandSource>>,
+        arrival_schedule: Option<Arc<ArrivalSchedule>>,
     ) -> Self {
         Self {
             wallet: test_account,
@@ -99,6 +115,8 @@ impl AccountLifespan {
             report_sink,
             inflight_txs: Default::default(),
             current_nonce: None,
+            command_source,
+            arrival_schedule,
         }
     }
 
@@ -128,27 +146,43 @@ impl AccountLifespan {
             modifier: IncorrectnessModifier::None,
             to: Address::zero(),
             amount: U256::zero(),
+            calldata: vec![],
         };
-        self.execute_command(deploy_command.clone()).await?;
+        self.execute_command(deploy_command.clone(), None).await?;
         self.wait_for_all_inflight_tx().await?;
 
         let mut timer = tokio::time::interval(POLLING_INTERVAL);
+        // Polls skipped while sending transactions shouldn't be caught up with in a burst.
+        timer.set_missed_tick_behavior(MissedTickBehavior::Delay);
         let mut l1_tx_count = 0;
         loop {
-            let command = self.generate_command();
+            let Some(command) = self.next_command().await else {
+                // The scenario is finished; wait for the remaining transactions to be reported.
+                return self.wait_for_all_inflight_tx().await;
+            };
             let is_l1_transaction =
                 matches!(command.command_type, TxType::L1Execute | TxType::Deposit);
             if is_l1_transaction && l1_tx_count >= MAX_L1_TRANSACTIONS {
                 continue; // Skip command to not run out of Ethereum on L1
             }
 
+            // In the open-loop mode, the time spent waiting for the mempool capacity is included into latencies.
+            let intended_start = match self.arrival_schedule.clone() {
+                Some(schedule) => Some(self.wait_for_arrival(&schedule, &mut timer).await?),
+                None => None,
+            };
             // The new transaction should be sent only if mempool is not full
             loop {
                 if self.inflight_txs.len() >= self.config.max_inflight_txs {
                     timer.tick().await;
                     self.check_inflight_txs().await?;
                 } else {
-                    self.execute_command(command).await?;
+                    // In-flight transactions are polled on each polling interval even if the mempool isn't full,
+                    // so that their inclusion is noticed (and latencies are measured) in time.
+                    if timer.tick().now_or_never().is_some() {
+                        self.check_inflight_txs().await?;
+                    }
+                    self.execute_command(command, intended_start).await?;
                     l1_tx_count += u64::from(is_l1_transaction);
                     break;
                 }
@@ -156,6 +190,22 @@ impl AccountLifespan {
         }
     }
 
+    /// Waits for the next scheduled arrival, polling in-flight transactions in the meantime.
+    async fn wait_for_arrival(
+        &mut self,
+        schedule: &ArrivalSchedule,
+        timer: &mut Interval,
+    ) -> Result<Instant, Aborted> {
+        let arrival = schedule.next_arrival();
+        tokio::pin!(arrival);
+        loop {
+            tokio::select! {
+                intended_start = &mut arrival => return Ok(intended_start),
+                _ = timer.tick() => self.check_inflight_txs().await?,
+            }
+        }
+    }
+
     async fn wait_for_all_inflight_tx(&mut self) -> Result<(), Aborted> {
         let mut timer = tokio::time::interval(POLLING_INTERVAL);
         while !self.inflight_txs.is_empty() {
@@ -166,10 +216,9 @@ impl AccountLifespan {
     }
 
     async fn check_inflight_txs(&mut self) -> Result<(), Aborted> {
-        // No need to wait for confirmation for all tx, one check for each tx is enough.
-        // If some txs haven't been processed yet, we'll check them in the next iteration.
-        // Due to natural sleep for sending tx, usually more than 1 tx can be already
-        // processed and have a receipt
+        // All in-flight txs are checked rather than stopping at the first pending one; otherwise, inclusion
+        // of the txs sent after it would be noticed late, inflating their latencies. Txs that haven't been
+        // processed yet are checked again in the next iteration.
         let start = Instant::now();
         tracing::trace!(
             "Account {:?}: check_inflight_txs len {:?}",
@@ -177,10 +226,15 @@ impl AccountLifespan {
             self.inflight_txs.len()
         );
 
-        while let Some(tx) = self.inflight_txs.pop_front() {
-            let receipt = self.get_tx_receipt_for_committed_block(tx.tx_hash).await;
+        let mut pending_txs = VecDeque::with_capacity(self.inflight_txs.len());
+        while let Some(mut tx) = self.inflight_txs.pop_front() {
+            let receipt = self.get_tx_receipt_status(tx.tx_hash).await;
+            if let Ok(ReceiptStatus::Included(_) | ReceiptStatus::Committed(_)) = &receipt {
+                tx.inclusion_latency
+                    .get_or_insert_with(|| tx.intended_start.elapsed());
+            }
             match receipt {
-                Ok(Some(transaction_receipt)) => {
+                Ok(ReceiptStatus::Committed(transaction_receipt)) => {
                     let label = self.verify_receipt(
                         &transaction_receipt,
                         &tx.command.modifier.expected_outcome(),
@@ -197,19 +251,28 @@ impl AccountLifespan {
                         tx.start.elapsed(),
                         tx.attempt,
                     );
-                    self.report(label, tx.start.elapsed(), tx.attempt, tx.command)
-                        .await?;
+                    let finality_latency = tx.intended_start.elapsed();
+                    let latencies = TxLatencies {
+                        submit: tx.submit_latency,
+                        inclusion: tx.inclusion_latency.unwrap_or(finality_latency),
+                        finality: finality_latency,
+                    };
+                    let report = self
+                        .build_report(label, finality_latency, tx.attempt, tx.command)
+                        .latencies(latencies)
+                        .finish();
+                    self.send_report(report).await?;
                 }
                 other => {
                     tracing::trace!(
                         "Account {:?}: check_inflight_txs tx not yet included: {other:?}",
                         self.wallet.wallet.address()
                     );
-                    self.inflight_txs.push_front(tx);
-                    break;
+                    pending_txs.push_back(tx);
                 }
             }
         }
+        self.inflight_txs = pending_txs;
         tracing::trace!(
             "Account {:?}: check_inflight_txs complete {:?}",
             self.wallet.wallet.address(),
@@ -250,11 +313,17 @@ impl AccountLifespan {
         }
     }
 
-    /// Executes a command with support of retries:
+    /// Executes a command with support of retries. Latencies are measured from `intended_start` if it is provided,
+    /// and from the start of the latest attempt otherwise.
+    ///
     /// If command fails due to the network/API error, it will be retried multiple times
     /// before considering it completely failed. Such an approach makes us a bit more resilient to
     /// volatile errors such as random connection drop or insufficient fee error.
-    async fn execute_command(&mut self, command: TxCommand) -> Result<(), Aborted> {
+    async fn execute_command(
+        &mut self,
+        command: TxCommand,
+        intended_start: Option<Instant>,
+    ) -> Result<(), Aborted> {
         // We consider API errors to be somewhat likely, thus we will retry the operation if it fails
         // due to connection issues.
         const MAX_RETRIES: usize = 3;
@@ -262,6 +331,7 @@ impl AccountLifespan {
         let mut attempt = 0;
         loop {
             let start = Instant::now();
+            let intended_start = intended_start.unwrap_or(start);
             let result = self.execute_tx_command(&command).await;
 
             let submit_result = match result {
@@ -289,6 +359,9 @@ impl AccountLifespan {
                     self.inflight_txs.push_back(InflightTx {
                         tx_hash,
                         start,
+                        intended_start,
+                        submit_latency: intended_start.elapsed(),
+                        inclusion_latency: None,
                         attempt,
                         command: command.clone(),
                     });
@@ -329,6 +402,17 @@ impl AccountLifespan {
         retries: usize,
         command: TxCommand,
     ) -> Result<(), Aborted> {
+        let report = self.build_report(label, time, retries, command).finish();
+        self.send_report(report).await
+    }
+
+    fn build_report(
+        &self,
+        label: ReportLabel,
+        time: Duration,
+        retries: usize,
+        command: TxCommand,
+    ) -> ReportBuilder {
         if let ReportLabel::ActionFailed { error } = &label {
             tracing::error!(
                 "Command failed: from {:?}, {command:#?} ({error})",
@@ -336,14 +420,12 @@ impl AccountLifespan {
             )
         }
 
-        let report = ReportBuilder::default()
+        ReportBuilder::default()
             .label(label)
             .reporter(self.wallet.wallet.address())
             .time(time)
             .retries(retries)
             .action(command)
-            .finish();
-        self.send_report(report).await
     }
 
     async fn send_report(&mut self, report: Report) -> Result<(), Aborted> {
@@ -398,12 +480,19 @@ impl AccountLifespan {
         Ok(submit_result)
     }
 
-    /// Prepares a list of random operations to be executed by an account.
-    fn generate_command(&mut self) -> TxCommand {
-        TxCommand::random(
-            &mut self.wallet.rng,
-            self.wallet.wallet.address(),
-            &self.addresses,
-        )
+    /// Returns the next operation to be executed by an account, or `None` if there are no more operations.
+    async fn next_command(&mut self) -> Option<TxCommand> {
+        let own_address = self.wallet.wallet.address();
+        if let Some(source) = &self.command_source {
+            source
+                .next_command(&mut self.wallet.rng, own_address, &self.addresses)
+                .await
+        } else {
+            Some(TxCommand::random(
+                &mut self.wallet.rng,
+                own_address,
+                &self.addresses,
+            ))
+        }
     }
 }
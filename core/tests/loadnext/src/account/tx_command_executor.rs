@@ -34,6 +34,17 @@ pub enum SubmitResult {
     ReportLabel(ReportLabel),
 }
 
+/// Status of a submitted transaction.
+#[derive(Debug)]
+pub enum ReceiptStatus {
+    /// Transaction is not included into an L2 block yet.
+    Pending,
+    /// Transaction is included into an L2 block, but the block is not committed to L1 yet.
+    Included(TransactionReceipt),
+    /// Transaction is included into an L2 block committed to L1.
+    Committed(TransactionReceipt),
+}
+
 impl AccountLifespan {
     pub(super) async fn execute_tx_command(
         &mut self,
@@ -53,6 +64,8 @@ impl AccountLifespan {
                 self.execute_loadnext_contract(command, ExecutionType::L1)
                     .await
             }
+            TxType::Transfer => self.execute_transfer(command).await,
+            TxType::RawCall => self.execute_raw_call(command).await,
         }
     }
 
@@ -254,6 +267,84 @@ impl AccountLifespan {
         Ok(self.apply_modifier(tx, command.modifier).await)
     }
 
+    async fn execute_transfer(&mut self, command: &TxCommand) -> Result<SubmitResult, ClientError> {
+        let tx = self.build_transfer(command).await?;
+        self.execute_submit(tx, command.modifier).await
+    }
+
+    async fn build_transfer(&self, command: &TxCommand) -> Result<L2Tx, ClientError> {
+        let wallet = self.wallet.wallet.clone();
+
+        let mut builder = wallet
+            .start_transfer()
+            .to(command.to)
+            .amount(command.amount)
+            .token(self.main_l2_token);
+
+        let fee = builder
+            .estimate_fee(Some(get_approval_based_paymaster_input_for_estimation(
+                self.paymaster_address,
+                self.main_l2_token,
+                MIN_ALLOWANCE_FOR_PAYMASTER_ESTIMATE.into(),
+            )))
+            .await?;
+        let paymaster_params = get_approval_based_paymaster_input(
+            self.paymaster_address,
+            self.main_l2_token,
+            fee.max_total_fee(),
+            Vec::new(),
+        );
+        builder = builder.fee(fee);
+        builder = builder.paymaster_params(paymaster_params);
+
+        if let Some(nonce) = self.current_nonce {
+            builder = builder.nonce(nonce);
+        }
+
+        let tx = builder.tx().await.map_err(Self::tx_creation_error)?;
+
+        Ok(self.apply_modifier(tx, command.modifier).await)
+    }
+
+    async fn execute_raw_call(&mut self, command: &TxCommand) -> Result<SubmitResult, ClientError> {
+        let tx = self.build_raw_call(command).await?;
+        self.execute_submit(tx, command.modifier).await
+    }
+
+    async fn build_raw_call(&self, command: &TxCommand) -> Result<L2Tx, ClientError> {
+        let wallet = &self.wallet.wallet;
+
+        let mut builder = wallet
+            .start_execute_contract()
+            .calldata(command.calldata.clone())
+            .contract_address(command.to)
+            .factory_deps(vec![]);
+
+        let fee = builder
+            .estimate_fee(Some(get_approval_based_paymaster_input_for_estimation(
+                self.paymaster_address,
+                self.main_l2_token,
+                MIN_ALLOWANCE_FOR_PAYMASTER_ESTIMATE.into(),
+            )))
+            .await?;
+        let paymaster_params = get_approval_based_paymaster_input(
+            self.paymaster_address,
+            self.main_l2_token,
+            fee.max_total_fee(),
+            Vec::new(),
+        );
+        builder = builder.fee(fee);
+        builder = builder.paymaster_params(paymaster_params);
+
+        if let Some(nonce) = self.current_nonce {
+            builder = builder.nonce(nonce);
+        }
+
+        let tx = builder.tx().await.map_err(Self::tx_creation_error)?;
+
+        Ok(self.apply_modifier(tx, command.modifier).await)
+    }
+
     async fn execute_deploy_contract(
         &mut self,
         command: &TxCommand,
@@ -440,10 +531,11 @@ impl AccountLifespan {
         Ok(self.apply_modifier(tx, command.modifier).await)
     }
 
-    pub(crate) async fn get_tx_receipt_for_committed_block(
+    /// Returns the receipt of the transaction together with its inclusion status.
+    pub(crate) async fn get_tx_receipt_status(
         &mut self,
         tx_hash: H256,
-    ) -> Result<Option<TransactionReceipt>, ClientError> {
+    ) -> Result<ReceiptStatus, ClientError> {
         let response = self
             .wallet
             .wallet
@@ -452,7 +544,7 @@ impl AccountLifespan {
             .await?;
 
         let Some(receipt) = response else {
-            return Ok(None);
+            return Ok(ReceiptStatus::Pending);
         };
 
         let block_number = receipt.block_number;
@@ -465,9 +557,9 @@ impl AccountLifespan {
             .await?;
         if let Some(received_number) = response.map(|block| block.number) {
             if block_number <= received_number {
-                return Ok(Some(receipt));
+                return Ok(ReceiptStatus::Committed(receipt));
             }
         }
-        Ok(None)
+        Ok(ReceiptStatus::Included(receipt))
     }
 }
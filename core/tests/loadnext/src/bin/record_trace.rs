@@ -0,0 +1,42 @@
+//! Records a traffic trace from a range of L2 blocks to be replayed by the loadtest.
+//!
+//! See the "Traffic replay" section in `README.md` for details.
+
+use anyhow::Context as _;
+use loadnext::{config::RecordTraceConfig, scenario::record_trace};
+use zksync_types::{L2BlockNumber, L2ChainId};
+use zksync_web3_decl::client::{Client, L2};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let _guard = zksync_vlog::ObservabilityBuilder::new().build();
+
+    let config = RecordTraceConfig::from_env().context("invalid trace recording config")?;
+    anyhow::ensure!(
+        config.from_block <= config.to_block,
+        "invalid block range {}..={}",
+        config.from_block,
+        config.to_block
+    );
+    let l2_chain_id = L2ChainId::try_from(config.l2_chain_id)
+        .map_err(|err| anyhow::anyhow!("invalid L2 chain ID: {err}"))?;
+    let client: Client<L2> = Client::http(
+        config
+            .l2_rpc_address
+            .parse()
+            .context("invalid L2 RPC URL")?,
+    )?
+    .for_network(l2_chain_id.into())
+    .build();
+
+    let blocks = L2BlockNumber(config.from_block)..=L2BlockNumber(config.to_block);
+    let trace = record_trace(&client, blocks).await?;
+    trace.write(&config.output_path)?;
+    tracing::info!(
+        "Recorded {} transactions spanning {:?} to {}",
+        trace.entries.len(),
+        trace.duration(),
+        config.output_path.display()
+    );
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use once_cell::sync::OnceCell;
-use rand::Rng;
+use rand::{seq::SliceRandom, Rng};
 use static_assertions::const_assert;
 use zksync_types::{Address, U256};
 
@@ -8,6 +8,7 @@ use crate::{
     all::{All, AllWeighted},
     config::TransactionWeights,
     rng::{LoadtestRng, WeightedRandom},
+    scenario::TransactionMix,
 };
 
 static WEIGHTS: OnceCell<[(TxType, f32); 5]> = OnceCell::new();
@@ -22,6 +23,10 @@ pub enum TxType {
     DeployContract,
     L1Execute,
     L2Execute,
+    /// Transfer of the main ERC-20 token on L2.
+    Transfer,
+    /// Call to an arbitrary contract with the provided calldata; used to replay recorded traffic.
+    RawCall,
 }
 
 impl TxType {
@@ -61,9 +66,28 @@ impl TxType {
             Self::WithdrawToOther,
             Self::L1Execute,
             Self::L2Execute,
+            Self::Transfer,
+            Self::RawCall,
         ]
     }
 
+    /// Selects a random transaction type according to the weights in the provided mix.
+    fn random_from_mix(rng: &mut LoadtestRng, mix: &TransactionMix) -> Self {
+        let weighted = [
+            (Self::Deposit, mix.deposit),
+            (Self::WithdrawToSelf, mix.withdrawal / 2.0),
+            (Self::WithdrawToOther, mix.withdrawal / 2.0),
+            (Self::DeployContract, mix.deploy),
+            (Self::L1Execute, mix.l1_execute),
+            (Self::L2Execute, mix.l2_execute),
+            (Self::Transfer, mix.transfer),
+        ];
+        weighted
+            .choose_weighted(rng, |item| item.1)
+            .expect("transaction mix is validated when loading the scenario")
+            .0
+    }
+
     fn is_target_self(self) -> bool {
         matches!(self, Self::WithdrawToSelf)
     }
@@ -151,6 +175,8 @@ pub struct TxCommand {
     pub to: Address,
     /// Transaction amount (0 if not applicable).
     pub amount: U256,
+    /// Calldata for `RawCall` transactions (empty if not applicable).
+    pub calldata: Vec<u8>,
 }
 
 impl TxCommand {
@@ -161,7 +187,18 @@ impl TxCommand {
         Self::new_with_type(rng, own_address, addresses, command_type)
     }
 
-    fn new_with_type(
+    /// Generates a random transaction command with the type chosen according to the provided mix.
+    pub fn random_with_mix(
+        rng: &mut LoadtestRng,
+        own_address: Address,
+        addresses: &AddressPool,
+        mix: &TransactionMix,
+    ) -> Self {
+        let command_type = TxType::random_from_mix(rng, mix);
+        Self::new_with_type(rng, own_address, addresses, command_type)
+    }
+
+    pub(crate) fn new_with_type(
         rng: &mut LoadtestRng,
         own_address: Address,
         addresses: &AddressPool,
@@ -172,6 +209,7 @@ impl TxCommand {
             modifier: IncorrectnessModifier::random(rng),
             to: addresses.random_address(rng),
             amount: Self::random_amount(rng),
+            calldata: vec![],
         };
 
         // Check whether we should use a self as a target.
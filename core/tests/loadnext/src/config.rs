@@ -116,6 +116,12 @@ pub struct LoadtestConfig {
     #[serde(default = "default_max_inflight_txs")]
     pub max_inflight_txs: usize,
 
+    /// Rate (transactions per second) at which transactions are sent in the open-loop mode. If not set,
+    /// accounts send transactions as fast as they can (closed-loop mode). In the open-loop mode, `max_inflight_txs`
+    /// still applies, so `accounts_amount` should be large enough to sustain the rate.
+    #[serde(default)]
+    pub arrival_rate_tps: Option<f64>,
+
     /// All of test accounts get split into groups that share the
     /// deployed contract address. This helps to emulate the behavior of
     /// sending `Execute` to the same contract and reading its events by
@@ -137,6 +143,26 @@ pub struct LoadtestConfig {
     /// in an eventual test failure anyway (e.g., a failure processing transactions).
     #[serde(default)]
     pub fail_fast: bool,
+
+    /// Path to a YAML or JSON scenario describing load phases and latency assertions.
+    /// If set, the test duration and transaction weights are taken from the scenario.
+    #[serde(default)]
+    pub scenario_path: Option<PathBuf>,
+
+    /// Path to a traffic trace (as recorded by the `record_trace` binary) to replay.
+    /// Mutually exclusive with `scenario_path`.
+    #[serde(default)]
+    pub replay_trace_path: Option<PathBuf>,
+
+    /// Speedup factor for replaying a traffic trace, e.g. 2.0 replays the trace twice as fast as it was recorded.
+    #[serde(default = "default_replay_speed")]
+    pub replay_speed: f64,
+}
+
+fn default_replay_speed() -> f64 {
+    let result = 1.0;
+    tracing::info!("Using default REPLAY_SPEED: {result}");
+    result
 }
 
 fn default_max_inflight_txs() -> usize {
@@ -266,6 +292,29 @@ impl LoadtestConfig {
     }
 }
 
+/// Configuration for recording a traffic trace to be replayed by the loadtest.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RecordTraceConfig {
+    /// Address of the zkSync web3 API to record transactions from.
+    #[serde(default = "default_l2_rpc_address")]
+    pub l2_rpc_address: String,
+    /// L2 chain ID of the zkSync network.
+    #[serde(default = "default_l2_chain_id")]
+    pub l2_chain_id: u64,
+    /// First L2 block to record.
+    pub from_block: u32,
+    /// Last L2 block to record (inclusive).
+    pub to_block: u32,
+    /// Path to write the recorded trace to.
+    pub output_path: PathBuf,
+}
+
+impl RecordTraceConfig {
+    pub fn from_env() -> envy::Result<Self> {
+        envy::from_env()
+    }
+}
+
 /// Configuration for the weights of loadtest operations
 /// We use a random selection based on weight of operations. To perform some operations frequently, the developer must set the weight higher.
 ///
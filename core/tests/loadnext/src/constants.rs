@@ -41,3 +41,7 @@ pub const TARGET_PAYMASTER_BALANCE: u128 = 10u128.pow(18) * 60;
 /// It should be roughly equal (or maybe a bit higher) than the actual used tokens in the transaction for the most precise
 /// estimations. Note, however that is must not be higher than the ERC20 balance of the account.
 pub const MIN_ALLOWANCE_FOR_PAYMASTER_ESTIMATE: u128 = 10u128.pow(18);
+
+/// Extra time given to accounts to receive receipts for inflight transactions after a scenario or a replayed trace
+/// is finished.
+pub const SCENARIO_COMPLETION_MARGIN: Duration = Duration::from_secs(60);
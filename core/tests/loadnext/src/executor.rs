@@ -16,8 +16,10 @@ use crate::{
     config::{ExecutionConfig, LoadtestConfig, RequestLimiters},
     constants::*,
     metrics::LOADTEST_METRICS,
+    open_loop::ArrivalSchedule,
     report::ReportBuilder,
     report_collector::{LoadtestResult, ReportCollector},
+    scenario::CommandSource,
     sdk::{
         ethereum::{PriorityOpHolder, DEFAULT_PRIORITY_FEE},
         utils::{
@@ -45,14 +47,36 @@ pub struct Executor {
     execution_config: ExecutionConfig,
     l2_main_token: Address,
     pool: AccountPool,
+    command_source: Option<Arc<CommandSource>>,
+    arrival_schedule: Option<Arc<ArrivalSchedule>>,
 }
 
 impl Executor {
     /// Creates a new Executor entity.
     pub async fn new(
-        config: LoadtestConfig,
+        mut config: LoadtestConfig,
         execution_config: ExecutionConfig,
     ) -> anyhow::Result<Self> {
+        let command_source = CommandSource::from_config(&config)?;
+        if let Some(source) = &command_source {
+            let duration = source.duration() + SCENARIO_COMPLETION_MARGIN;
+            tracing::info!("Overriding test duration with {duration:?} according to the scenario");
+            config.duration_sec = duration.as_secs();
+        }
+        let arrival_schedule = if let Some(rate_tps) = config.arrival_rate_tps {
+            anyhow::ensure!(
+                rate_tps.is_finite() && rate_tps > 0.0,
+                "arrival rate must be positive"
+            );
+            anyhow::ensure!(
+                command_source.is_none(),
+                "open-loop mode cannot be used together with a scenario or a replayed trace"
+            );
+            tracing::info!("Running in open-loop mode with {rate_tps} TPS arrival rate");
+            Some(Arc::new(ArrivalSchedule::new(rate_tps)))
+        } else {
+            None
+        };
         let pool = AccountPool::new(&config).await?;
 
         // derive L2 main token address
@@ -70,6 +94,8 @@ impl Executor {
             execution_config,
             pool,
             l2_main_token,
+            command_source: command_source.map(Arc::new),
+            arrival_schedule,
         })
     }
 
@@ -512,6 +538,10 @@ impl Executor {
             self.config.duration(),
             self.config.prometheus_label.clone(),
             self.config.fail_fast,
+            self.command_source
+                .as_ref()
+                .map(|source| source.assertions().to_vec())
+                .unwrap_or_default(),
         );
         let report_collector_future = tokio::spawn(report_collector.run());
 
@@ -573,6 +603,8 @@ impl Executor {
                             report_sender.clone(),
                             main_token,
                             paymaster_address,
+                            self.command_source.clone(),
+                            self.arrival_schedule.clone(),
                         );
                         let limiters = Arc::clone(&limiters);
                         tokio::spawn(async move { account.run(&limiters).await })
@@ -596,6 +628,13 @@ impl Executor {
         tracing::info!("Waiting for the account futures to be completed...");
         future::try_join_all(account_tasks).await?;
         tracing::info!("All the spawned tasks are completed");
+        if let Some(schedule) = &self.arrival_schedule {
+            tracing::info!(
+                "Open-loop mode: {} arrivals scheduled, {} of them lagged behind the schedule",
+                schedule.total_arrivals(),
+                schedule.lagging_arrivals()
+            );
+        }
 
         Ok(report_collector_future.await?)
     }
@@ -10,8 +10,10 @@ pub mod corrupted_tx;
 pub mod executor;
 pub mod fs_utils;
 pub(crate) mod metrics;
+pub mod open_loop;
 pub mod report;
 pub mod report_collector;
 pub mod rng;
+pub mod scenario;
 pub(crate) mod sdk;
 pub mod utils;
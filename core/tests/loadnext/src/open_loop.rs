@@ -0,0 +1,99 @@
+//! Open-loop load generation.
+//!
+//! By default, each account sends a new transaction as soon as it has capacity for it (closed loop). If the server
+//! slows down, the test sends fewer transactions, and the latency degradation is hidden (the so-called coordinated
+//! omission). In the open-loop mode, transactions are scheduled to arrive at a constant rate regardless of
+//! the server behavior, and latencies are measured from the scheduled arrival time. Thus, the time an arrival
+//! spends waiting for a free account is included into the reported latencies.
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use once_cell::sync::OnceCell;
+
+/// Arrivals lagging behind the schedule by more than this duration are counted as lagging.
+const LAG_THRESHOLD: Duration = Duration::from_secs(1);
+
+/// Schedule of transaction arrivals at a constant rate shared among all test accounts.
+#[derive(Debug)]
+pub struct ArrivalSchedule {
+    interval: Duration,
+    started_at: OnceCell<Instant>,
+    next_arrival: AtomicU64,
+    lagging_arrivals: AtomicU64,
+}
+
+impl ArrivalSchedule {
+    pub fn new(rate_tps: f64) -> Self {
+        assert!(
+            rate_tps.is_finite() && rate_tps > 0.0,
+            "arrival rate must be positive"
+        );
+        Self {
+            interval: Duration::from_secs_f64(1.0 / rate_tps),
+            started_at: OnceCell::new(),
+            next_arrival: AtomicU64::new(0),
+            lagging_arrivals: AtomicU64::new(0),
+        }
+    }
+
+    fn arrival_time(&self, started_at: Instant, index: u64) -> Instant {
+        started_at + self.interval.mul_f64(index as f64)
+    }
+
+    /// Reserves the next arrival and waits until it is due. Returns the scheduled arrival time.
+    /// The schedule starts on the first call.
+    pub async fn next_arrival(&self) -> Instant {
+        let started_at = *self.started_at.get_or_init(Instant::now);
+        let index = self.next_arrival.fetch_add(1, Ordering::Relaxed);
+        let arrival = self.arrival_time(started_at, index);
+
+        let lag = Instant::now().saturating_duration_since(arrival);
+        if lag > LAG_THRESHOLD {
+            tracing::debug!("Arrival #{index} is lagging behind the schedule by {lag:?}");
+            self.lagging_arrivals.fetch_add(1, Ordering::Relaxed);
+        }
+        tokio::time::sleep_until(arrival.into()).await;
+        arrival
+    }
+
+    /// Returns the number of arrivals scheduled so far.
+    pub fn total_arrivals(&self) -> u64 {
+        self.next_arrival.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of arrivals that couldn't be picked up by an account in time. A significant number
+    /// of lagging arrivals means that there are not enough accounts to sustain the configured arrival rate.
+    pub fn lagging_arrivals(&self) -> u64 {
+        self.lagging_arrivals.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arrivals_are_evenly_spaced() {
+        let schedule = ArrivalSchedule::new(4.0);
+        let started_at = Instant::now();
+        for index in 0..10 {
+            assert_eq!(
+                schedule.arrival_time(started_at, index) - started_at,
+                Duration::from_millis(250 * index)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn arrivals_are_reserved_in_order() {
+        let schedule = ArrivalSchedule::new(1_000.0);
+        let first = schedule.next_arrival().await;
+        let second = schedule.next_arrival().await;
+        assert_eq!(second - first, Duration::from_millis(1));
+        assert_eq!(schedule.total_arrivals(), 2);
+        assert_eq!(schedule.lagging_arrivals(), 0);
+    }
+}
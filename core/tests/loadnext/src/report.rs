@@ -27,6 +27,50 @@ pub struct Report {
     pub retries: usize,
     /// Duration of the latest execution attempt.
     pub time: Duration,
+    /// Latencies of the different transaction lifecycle stages. Only set for successfully submitted transactions.
+    pub latencies: Option<TxLatencies>,
+}
+
+/// Stage of the transaction lifecycle a latency is measured for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum LatencyKind {
+    /// Until the transaction is accepted by the API server.
+    Submit,
+    /// Until the transaction is included into an L2 block.
+    Inclusion,
+    /// Until the L2 block with the transaction is committed to L1.
+    Finality,
+}
+
+impl All for LatencyKind {
+    fn all() -> &'static [Self] {
+        const ALL: &[LatencyKind] = &[
+            LatencyKind::Submit,
+            LatencyKind::Inclusion,
+            LatencyKind::Finality,
+        ];
+
+        ALL
+    }
+}
+
+/// Transaction latencies. All latencies are measured from the intended transaction start, which in the open-loop mode
+/// is the scheduled arrival time rather than the time the transaction was actually sent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TxLatencies {
+    pub submit: Duration,
+    pub inclusion: Duration,
+    pub finality: Duration,
+}
+
+impl TxLatencies {
+    pub fn get(&self, kind: LatencyKind) -> Duration {
+        match kind {
+            LatencyKind::Submit => self.submit,
+            LatencyKind::Inclusion => self.inclusion,
+            LatencyKind::Finality => self.finality,
+        }
+    }
 }
 
 /// Builder structure for `Report`.
@@ -44,6 +88,7 @@ impl Default for ReportBuilder {
                 action: ActionType::Tx(TxActionType::Execute(ExecutionType::L2)),
                 retries: 0,
                 time: Duration::ZERO,
+                latencies: None,
             },
         }
     }
@@ -75,6 +120,11 @@ impl ReportBuilder {
         self
     }
 
+    pub fn latencies(mut self, latencies: TxLatencies) -> Self {
+        self.report.latencies = Some(latencies);
+        self
+    }
+
     pub fn finish(self) -> Report {
         self.report
     }
@@ -86,6 +136,7 @@ impl ReportBuilder {
             action: ActionType::InitComplete,
             retries: 0,
             time: Duration::ZERO,
+            latencies: None,
         }
     }
 }
@@ -123,6 +174,7 @@ pub enum TxActionType {
     Deposit,
     DeployContract,
     Execute(ExecutionType),
+    Transfer,
 }
 
 impl All for TxActionType {
@@ -133,6 +185,7 @@ impl All for TxActionType {
             TxActionType::DeployContract,
             TxActionType::Execute(ExecutionType::L2),
             TxActionType::Execute(ExecutionType::L1),
+            TxActionType::Transfer,
         ];
 
         ALL
@@ -144,9 +197,10 @@ impl From<TxType> for TxActionType {
         match command {
             TxType::Deposit => Self::Deposit,
             TxType::WithdrawToSelf | TxType::WithdrawToOther => Self::Withdraw,
-            TxType::L2Execute => Self::Execute(ExecutionType::L2),
+            TxType::L2Execute | TxType::RawCall => Self::Execute(ExecutionType::L2),
             TxType::L1Execute => Self::Execute(ExecutionType::L1),
             TxType::DeployContract => Self::DeployContract,
+            TxType::Transfer => Self::Transfer,
         }
     }
 }
@@ -0,0 +1,130 @@
+use std::{collections::HashMap, time::Duration};
+
+use hdrhistogram::Histogram;
+
+use crate::{
+    all::All,
+    report::{LatencyKind, TxActionType, TxLatencies},
+};
+
+/// Max latency that can be recorded precisely; larger latencies are saturated to this value.
+const MAX_TRACKED_LATENCY: Duration = Duration::from_secs(3_600);
+/// Number of significant decimal digits kept by histograms.
+const SIGNIFICANT_DIGITS: u8 = 3;
+/// Percentiles included into the report.
+const REPORTED_PERCENTILES: [f64; 5] = [50.0, 90.0, 99.0, 99.9, 100.0];
+
+/// Collector for the transaction latencies split by the transaction lifecycle stages.
+///
+/// Unlike `MetricsCollector`, it uses HDR histograms with a fixed relative precision, so that high
+/// percentiles are reported precisely rather than as a range window.
+#[derive(Debug, Clone)]
+pub struct LatencyCollector {
+    histograms: HashMap<(TxActionType, LatencyKind), Histogram<u64>>,
+}
+
+impl Default for LatencyCollector {
+    fn default() -> Self {
+        let histograms = TxActionType::all()
+            .iter()
+            .flat_map(|&action| LatencyKind::all().iter().map(move |&kind| (action, kind)))
+            .map(|key| {
+                let histogram = Histogram::new_with_bounds(
+                    1,
+                    MAX_TRACKED_LATENCY.as_millis() as u64,
+                    SIGNIFICANT_DIGITS,
+                )
+                .expect("invalid histogram bounds");
+                (key, histogram)
+            })
+            .collect();
+        Self { histograms }
+    }
+}
+
+impl LatencyCollector {
+    pub fn add_latencies(&mut self, action: TxActionType, latencies: &TxLatencies) {
+        for &kind in LatencyKind::all() {
+            if let Some(histogram) = self.histograms.get_mut(&(action, kind)) {
+                let latency_ms = latencies.get(kind).as_millis() as u64;
+                histogram.saturating_record(latency_ms.max(1));
+            }
+        }
+    }
+
+    /// Returns the latency for the specified percentile in the `0.0..=100.0` range, or `None`
+    /// if no latencies were recorded.
+    pub fn percentile(
+        &self,
+        action: TxActionType,
+        kind: LatencyKind,
+        percentile: f64,
+    ) -> Option<Duration> {
+        let histogram = self.histograms.get(&(action, kind))?;
+        if histogram.is_empty() {
+            return None;
+        }
+        let latency_ms = histogram.value_at_percentile(percentile);
+        Some(Duration::from_millis(latency_ms))
+    }
+
+    pub fn report(&self) {
+        tracing::info!("Transaction latencies: [p50 p90 p99 p99.9 max]");
+        for &action in TxActionType::all() {
+            for &kind in LatencyKind::all() {
+                let histogram = &self.histograms[&(action, kind)];
+                if histogram.is_empty() {
+                    continue;
+                }
+                let percentiles: Vec<_> = REPORTED_PERCENTILES
+                    .iter()
+                    .filter_map(|&percentile| self.percentile(action, kind, percentile))
+                    .map(|latency| format!("{}ms", latency.as_millis()))
+                    .collect();
+                tracing::info!(
+                    "{action:?} {kind:?}: [{}] ({} samples)",
+                    percentiles.join(" "),
+                    histogram.len()
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::ExecutionType;
+
+    #[test]
+    fn collecting_latencies() {
+        let mut collector = LatencyCollector::default();
+        let action = TxActionType::Execute(ExecutionType::L2);
+        for i in 1..=100 {
+            let latencies = TxLatencies {
+                submit: Duration::from_millis(i),
+                inclusion: Duration::from_millis(i * 10),
+                finality: Duration::from_secs(i),
+            };
+            collector.add_latencies(action, &latencies);
+        }
+
+        let p90 = collector.percentile(action, LatencyKind::Submit, 90.0);
+        assert_eq!(p90, Some(Duration::from_millis(90)));
+        let p99 = collector.percentile(action, LatencyKind::Inclusion, 99.0);
+        assert_eq!(p99, Some(Duration::from_millis(990)));
+        // Histograms preserve 3 significant digits.
+        let max = collector
+            .percentile(action, LatencyKind::Finality, 100.0)
+            .unwrap();
+        assert!(
+            (Duration::from_millis(99_900)..=Duration::from_millis(100_100)).contains(&max),
+            "{max:?}"
+        );
+
+        assert_eq!(
+            collector.percentile(TxActionType::Deposit, LatencyKind::Submit, 50.0),
+            None
+        );
+    }
+}
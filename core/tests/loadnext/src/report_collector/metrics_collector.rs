@@ -51,6 +51,14 @@ impl TimeHistogram {
         self.total == 0
     }
 
+    /// Adds all entries from another histogram to this one.
+    pub fn merge(&mut self, other: &Self) {
+        for (range_start, count) in &other.histogram {
+            *self.histogram.entry(*range_start).or_default() += count;
+        }
+        self.total += other.total;
+    }
+
     /// Returns the time range for the requested distribution percentile.
     pub fn percentile(&self, percentile: u64) -> (Duration, Duration) {
         let lower_gap_float = self.total as f64 * percentile as f64 / 100.0;
@@ -129,6 +137,22 @@ impl MetricsCollector {
             .and_modify(|hist| hist.add_metric(time));
     }
 
+    /// Returns the time range for the requested percentile over all actions matching the predicate,
+    /// or `None` if no such actions were recorded.
+    pub fn percentile_for(
+        &self,
+        mut predicate: impl FnMut(ActionType) -> bool,
+        percentile: u64,
+    ) -> Option<(Duration, Duration)> {
+        let mut merged = TimeHistogram::default();
+        for (action, histogram) in &self.action_stats {
+            if predicate(*action) {
+                merged.merge(histogram);
+            }
+        }
+        (!merged.is_empty()).then(|| merged.percentile(percentile))
+    }
+
     pub fn report(&self) {
         tracing::info!("Action: [10 percentile, 50 percentile, 90 percentile]");
         for (action, histogram) in &self.action_stats {
@@ -232,4 +256,21 @@ mod tests {
         assert_eq!(histogram.percentile(50), second_range);
         assert_eq!(histogram.percentile(100), third_range);
     }
+
+    #[test]
+    fn histogram_merge() {
+        let mut histogram = TimeHistogram::default();
+        histogram.add_metric(Duration::from_millis(50));
+        let mut other = TimeHistogram::default();
+        other.add_metric(Duration::from_millis(150));
+        other.add_metric(Duration::from_millis(1_500));
+
+        histogram.merge(&other);
+        assert_eq!(histogram.total, 3);
+        assert_eq!(histogram.histogram[&100], 1);
+        assert_eq!(
+            histogram.percentile(100),
+            (Duration::from_millis(1_000), Duration::from_millis(1_999))
+        );
+    }
 }
@@ -6,9 +6,11 @@ use operation_results_collector::OperationResultsCollector;
 use crate::{
     metrics::LOADTEST_METRICS,
     report::{ActionType, Report, ReportLabel},
-    report_collector::metrics_collector::MetricsCollector,
+    report_collector::{latency_collector::LatencyCollector, metrics_collector::MetricsCollector},
+    scenario::LatencyAssertion,
 };
 
+mod latency_collector;
 mod metrics_collector;
 mod operation_results_collector;
 
@@ -24,6 +26,7 @@ struct Collectors {
     start: Instant,
     is_aborted: bool,
     metrics: MetricsCollector,
+    latencies: LatencyCollector,
     operation_results: OperationResultsCollector,
 }
 
@@ -33,6 +36,7 @@ impl Collectors {
             start: Instant::now(),
             is_aborted: false,
             metrics: MetricsCollector::default(),
+            latencies: LatencyCollector::default(),
             operation_results: OperationResultsCollector::new(loadtest_duration),
         }
     }
@@ -40,13 +44,47 @@ impl Collectors {
     fn report(&self, prometheus_label: String) {
         let actual_duration = self.start.elapsed();
         self.metrics.report();
+        self.latencies.report();
         if !self.is_aborted {
             LOADTEST_METRICS.tps[&prometheus_label].set(self.operation_results.nominal_tps());
         }
         self.operation_results.report(actual_duration);
     }
 
-    fn final_resolution(&self, expected_tx_count: Option<usize>) -> LoadtestResult {
+    /// Checks latency assertions. Since latencies are tracked by a histogram, an assertion is considered failed
+    /// only if the lower bound of the percentile range exceeds the allowed latency.
+    fn check_latency_assertions(&self, assertions: &[LatencyAssertion]) -> bool {
+        let mut all_passed = true;
+        for assertion in assertions {
+            let range = self.metrics.percentile_for(
+                |action| assertion.target.matches(action),
+                assertion.percentile,
+            );
+            let Some((range_start, range_end)) = range else {
+                tracing::warn!("No data to check latency assertion {assertion:?}");
+                all_passed = false;
+                continue;
+            };
+
+            let passed = range_start <= assertion.max_latency();
+            let outcome = if passed { "passed" } else { "FAILED" };
+            tracing::info!(
+                "Latency assertion {outcome}: {:?} p{} is in [{range_start:?}, {range_end:?}], \
+                 max allowed {:?}",
+                assertion.target,
+                assertion.percentile,
+                assertion.max_latency()
+            );
+            all_passed &= passed;
+        }
+        all_passed
+    }
+
+    fn final_resolution(
+        &self,
+        expected_tx_count: Option<usize>,
+        latency_assertions: &[LatencyAssertion],
+    ) -> LoadtestResult {
         let is_tx_count_acceptable = expected_tx_count.map_or(true, |expected_count| {
             const MIN_ACCEPTABLE_DELTA: f64 = -10.0;
             const MAX_ACCEPTABLE_DELTA: f64 = 100.0;
@@ -60,7 +98,11 @@ impl Collectors {
             (MIN_ACCEPTABLE_DELTA..=MAX_ACCEPTABLE_DELTA).contains(&delta)
         });
 
-        if !is_tx_count_acceptable || self.operation_results.tx_results.failures() > 0 {
+        let are_assertions_passed = self.check_latency_assertions(latency_assertions);
+        if !is_tx_count_acceptable
+            || !are_assertions_passed
+            || self.operation_results.tx_results.failures() > 0
+        {
             LoadtestResult::TestFailed
         } else {
             LoadtestResult::TestPassed
@@ -77,6 +119,7 @@ impl Collectors {
 /// Currently, only the following collectors are used:
 ///
 /// - MetricsCollector, which builds time distribution histograms for each kind of performed action.
+/// - LatencyCollector, which builds HDR histograms of submit, inclusion and finality latencies for transactions.
 /// - OperationResultsCollector, a primitive collector that counts the amount of failures and decides whether
 ///   test is passed.
 ///
@@ -100,6 +143,7 @@ pub struct ReportCollector {
     loadtest_duration: Duration,
     prometheus_label: String,
     fail_fast: bool,
+    latency_assertions: Vec<LatencyAssertion>,
 }
 
 impl ReportCollector {
@@ -109,6 +153,7 @@ impl ReportCollector {
         loadtest_duration: Duration,
         prometheus_label: String,
         fail_fast: bool,
+        latency_assertions: Vec<LatencyAssertion>,
     ) -> Self {
         Self {
             reports_stream,
@@ -116,6 +161,7 @@ impl ReportCollector {
             loadtest_duration,
             prometheus_label,
             fail_fast,
+            latency_assertions,
         }
     }
 
@@ -138,6 +184,11 @@ impl ReportCollector {
                 if matches!(&report.label, ReportLabel::ActionDone) {
                     // We only count successfully created statistics.
                     collectors.metrics.add_metric(report.action, report.time);
+                    if let (ActionType::Tx(action), Some(latencies)) =
+                        (report.action, &report.latencies)
+                    {
+                        collectors.latencies.add_latencies(action, latencies);
+                    }
                 }
                 collectors
                     .operation_results
@@ -175,7 +226,7 @@ impl ReportCollector {
         // Now we can output the statistics.
         if let Some(collectors) = collectors {
             collectors.report(self.prometheus_label);
-            collectors.final_resolution(self.expected_tx_count)
+            collectors.final_resolution(self.expected_tx_count, &self.latency_assertions)
         } else {
             tracing::error!("Test failed before initialization was completed");
             LoadtestResult::TestFailed
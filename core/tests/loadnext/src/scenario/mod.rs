@@ -0,0 +1,416 @@
+//! Declarative load test scenarios.
+//!
+//! A scenario is a YAML or JSON file describing a sequence of phases. Each phase runs for the specified
+//! duration with its own transaction mix and (optionally) a target TPS shared by all test accounts.
+//! Latency assertions are checked once the test is finished. An example scenario:
+//!
+//! ```yaml
+//! name: ramp-up
+//! phases:
+//!   - name: warm-up
+//!     duration_sec: 60
+//!     target_tps: 10
+//!     mix: { transfer: 1.0, l2_execute: 1.0 }
+//!   - name: peak
+//!     duration_sec: 300
+//!     mix: { transfer: 1.0, l2_execute: 3.0, deploy: 0.1, withdrawal: 0.2, deposit: 0.05 }
+//! assertions:
+//!   - { target: all_transactions, percentile: 90, max_latency_ms: 5000 }
+//! ```
+
+use std::{
+    path::Path,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context as _;
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use zksync_types::Address;
+
+pub use self::replay::{record_trace, ReplayFeed, TraceEntry, TraceTxKind, TrafficTrace};
+use crate::{
+    account::ExecutionType,
+    account_pool::AddressPool,
+    command::TxCommand,
+    config::LoadtestConfig,
+    report::{ActionType, TxActionType},
+    rng::LoadtestRng,
+};
+
+mod replay;
+
+/// Relative weights of transaction types in a phase. Omitted types are not sent.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TransactionMix {
+    /// L2 transfers of the main ERC-20 token paid via the testnet paymaster.
+    pub transfer: f32,
+    /// Deployments of the loadnext contract.
+    pub deploy: f32,
+    /// Calls to the loadnext contract on L2.
+    pub l2_execute: f32,
+    /// Calls to the loadnext contract requested via L1.
+    pub l1_execute: f32,
+    /// Withdrawals of the main token to L1.
+    pub withdrawal: f32,
+    /// L1→L2 deposits of the main token.
+    pub deposit: f32,
+}
+
+impl TransactionMix {
+    fn validate(&self) -> anyhow::Result<()> {
+        let weights = [
+            self.transfer,
+            self.deploy,
+            self.l2_execute,
+            self.l1_execute,
+            self.withdrawal,
+            self.deposit,
+        ];
+        anyhow::ensure!(
+            weights
+                .iter()
+                .all(|&weight| weight >= 0.0 && weight.is_finite()),
+            "transaction weights must be non-negative"
+        );
+        anyhow::ensure!(
+            weights.iter().any(|&weight| weight > 0.0),
+            "at least one transaction weight must be positive"
+        );
+        Ok(())
+    }
+}
+
+/// Phase of a scenario.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Phase {
+    pub name: String,
+    pub duration_sec: u64,
+    /// Target rate of transactions sent by all accounts together. If not set, accounts send transactions
+    /// as fast as `max_inflight_txs` allows.
+    #[serde(default)]
+    pub target_tps: Option<f64>,
+    pub mix: TransactionMix,
+}
+
+/// Kind of actions a latency assertion applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LatencyTarget {
+    AllTransactions,
+    Transfer,
+    DeployContract,
+    L2Execute,
+    L1Execute,
+    Withdrawal,
+    Deposit,
+    ApiRequests,
+}
+
+impl LatencyTarget {
+    pub fn matches(self, action: ActionType) -> bool {
+        match (self, action) {
+            (Self::AllTransactions, ActionType::Tx(_)) => true,
+            (Self::Transfer, ActionType::Tx(TxActionType::Transfer)) => true,
+            (Self::DeployContract, ActionType::Tx(TxActionType::DeployContract)) => true,
+            (Self::L2Execute, ActionType::Tx(TxActionType::Execute(ExecutionType::L2))) => true,
+            (Self::L1Execute, ActionType::Tx(TxActionType::Execute(ExecutionType::L1))) => true,
+            (Self::Withdrawal, ActionType::Tx(TxActionType::Withdraw)) => true,
+            (Self::Deposit, ActionType::Tx(TxActionType::Deposit)) => true,
+            (Self::ApiRequests, ActionType::Api(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Assertion on a latency percentile checked after the test is finished.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LatencyAssertion {
+    pub target: LatencyTarget,
+    /// Percentile in the `0..=100` range.
+    pub percentile: u64,
+    pub max_latency_ms: u64,
+}
+
+impl LatencyAssertion {
+    pub fn max_latency(&self) -> Duration {
+        Duration::from_millis(self.max_latency_ms)
+    }
+}
+
+/// Declarative description of a load test.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    pub name: String,
+    pub phases: Vec<Phase>,
+    #[serde(default)]
+    pub assertions: Vec<LatencyAssertion>,
+}
+
+impl Scenario {
+    /// Loads a scenario from a YAML or JSON file; the format is determined by the file extension.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed reading scenario from {}", path.display()))?;
+        let scenario: Self = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => serde_json::from_str(&contents).context("invalid JSON scenario")?,
+            _ => serde_yaml::from_str(&contents).context("invalid YAML scenario")?,
+        };
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(!self.phases.is_empty(), "scenario has no phases");
+        for phase in &self.phases {
+            phase
+                .mix
+                .validate()
+                .with_context(|| format!("invalid mix in phase `{}`", phase.name))?;
+            anyhow::ensure!(
+                phase.duration_sec > 0,
+                "phase `{}` has zero duration",
+                phase.name
+            );
+            if let Some(tps) = phase.target_tps {
+                anyhow::ensure!(
+                    tps > 0.0 && tps.is_finite(),
+                    "phase `{}` has invalid target TPS",
+                    phase.name
+                );
+            }
+        }
+        for assertion in &self.assertions {
+            anyhow::ensure!(
+                assertion.percentile <= 100,
+                "invalid percentile in assertion {assertion:?}"
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the total duration of all phases.
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(self.phases.iter().map(|phase| phase.duration_sec).sum())
+    }
+
+    /// Returns the phase active after `elapsed` time since the scenario start.
+    fn phase_at(&self, elapsed: Duration) -> Option<&Phase> {
+        let mut phase_end = Duration::ZERO;
+        self.phases.iter().find(|phase| {
+            phase_end += Duration::from_secs(phase.duration_sec);
+            elapsed < phase_end
+        })
+    }
+}
+
+/// Runtime state of a scenario shared among all test accounts.
+#[derive(Debug)]
+pub struct ScenarioSchedule {
+    scenario: Scenario,
+    /// Set when the first account requests a command, so that the initial funds distribution isn't counted.
+    started_at: OnceCell<Instant>,
+    /// Earliest time the next transaction can be sent at, used to maintain the target TPS.
+    next_slot: Mutex<Option<Instant>>,
+}
+
+impl ScenarioSchedule {
+    pub fn new(scenario: Scenario) -> Self {
+        Self {
+            scenario,
+            started_at: OnceCell::new(),
+            next_slot: Mutex::new(None),
+        }
+    }
+
+    pub fn scenario(&self) -> &Scenario {
+        &self.scenario
+    }
+
+    /// Returns the next command to be executed by an account, or `None` if the scenario is finished.
+    async fn next_command(
+        &self,
+        rng: &mut LoadtestRng,
+        own_address: Address,
+        addresses: &AddressPool,
+    ) -> Option<TxCommand> {
+        let started_at = *self.started_at.get_or_init(Instant::now);
+        let phase = self.scenario.phase_at(started_at.elapsed())?;
+        if let Some(tps) = phase.target_tps {
+            self.wait_for_slot(tps).await;
+        }
+        Some(TxCommand::random_with_mix(
+            rng,
+            own_address,
+            addresses,
+            &phase.mix,
+        ))
+    }
+
+    async fn wait_for_slot(&self, tps: f64) {
+        let interval = Duration::from_secs_f64(1.0 / tps);
+        let slot = {
+            let mut next_slot = self.next_slot.lock().unwrap();
+            let now = Instant::now();
+            let slot = next_slot.map_or(now, |next_slot| next_slot.max(now));
+            *next_slot = Some(slot + interval);
+            slot
+        };
+        tokio::time::sleep_until(slot.into()).await;
+    }
+}
+
+/// Source of transaction commands for test accounts replacing the default random generation.
+#[derive(Debug)]
+pub enum CommandSource {
+    Scenario(ScenarioSchedule),
+    Replay(ReplayFeed),
+}
+
+impl CommandSource {
+    /// Creates a command source from the scenario or the traffic trace specified in the config, if any.
+    pub fn from_config(config: &LoadtestConfig) -> anyhow::Result<Option<Self>> {
+        match (&config.scenario_path, &config.replay_trace_path) {
+            (Some(_), Some(_)) => {
+                anyhow::bail!("scenario and replayed trace cannot be specified simultaneously")
+            }
+            (Some(path), None) => {
+                let scenario = Scenario::load(path)?;
+                tracing::info!(
+                    "Running scenario `{}` with {} phases",
+                    scenario.name,
+                    scenario.phases.len()
+                );
+                Ok(Some(Self::Scenario(ScenarioSchedule::new(scenario))))
+            }
+            (None, Some(path)) => {
+                anyhow::ensure!(config.replay_speed > 0.0, "replay speed must be positive");
+                let trace = TrafficTrace::read(path)?;
+                tracing::info!(
+                    "Replaying {} transactions from {} at {}x speed",
+                    trace.entries.len(),
+                    path.display(),
+                    config.replay_speed
+                );
+                Ok(Some(Self::Replay(ReplayFeed::new(
+                    trace,
+                    config.replay_speed,
+                ))))
+            }
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// Returns the next command to be executed by an account, or `None` if there are no more commands.
+    pub async fn next_command(
+        &self,
+        rng: &mut LoadtestRng,
+        own_address: Address,
+        addresses: &AddressPool,
+    ) -> Option<TxCommand> {
+        match self {
+            Self::Scenario(schedule) => schedule.next_command(rng, own_address, addresses).await,
+            Self::Replay(feed) => feed.next_command(rng, own_address, addresses).await,
+        }
+    }
+
+    /// Returns the expected duration of the test.
+    pub fn duration(&self) -> Duration {
+        match self {
+            Self::Scenario(schedule) => schedule.scenario.duration(),
+            Self::Replay(feed) => feed.duration(),
+        }
+    }
+
+    /// Returns latency assertions to check after the test is finished.
+    pub fn assertions(&self) -> &[LatencyAssertion] {
+        match self {
+            Self::Scenario(schedule) => &schedule.scenario.assertions,
+            Self::Replay(_) => &[],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::ApiActionType;
+
+    const SCENARIO: &str = r#"
+name: test
+phases:
+  - name: warm-up
+    duration_sec: 10
+    target_tps: 5
+    mix: { transfer: 1.0 }
+  - name: peak
+    duration_sec: 20
+    mix: { transfer: 1.0, l2_execute: 2.0 }
+assertions:
+  - { target: all_transactions, percentile: 90, max_latency_ms: 5000 }
+"#;
+
+    #[test]
+    fn parsing_scenario() {
+        let scenario: Scenario = serde_yaml::from_str(SCENARIO).unwrap();
+        scenario.validate().unwrap();
+        assert_eq!(scenario.phases.len(), 2);
+        assert_eq!(scenario.phases[0].target_tps, Some(5.0));
+        assert_eq!(scenario.phases[1].mix.l2_execute, 2.0);
+        assert_eq!(scenario.phases[1].mix.deposit, 0.0);
+        assert_eq!(scenario.duration(), Duration::from_secs(30));
+        assert_eq!(
+            scenario.assertions[0].target,
+            LatencyTarget::AllTransactions
+        );
+
+        let json = serde_json::to_string(&scenario).unwrap();
+        let restored: Scenario = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, scenario);
+    }
+
+    #[test]
+    fn invalid_scenarios_are_rejected() {
+        let mut scenario: Scenario = serde_yaml::from_str(SCENARIO).unwrap();
+        scenario.phases[0].mix = TransactionMix::default();
+        assert!(scenario.validate().is_err());
+
+        let mut scenario: Scenario = serde_yaml::from_str(SCENARIO).unwrap();
+        scenario.phases[1].target_tps = Some(0.0);
+        assert!(scenario.validate().is_err());
+
+        let unknown_field = SCENARIO.replace("transfer: 1.0 }", "transfers: 1.0 }");
+        assert!(serde_yaml::from_str::<Scenario>(&unknown_field).is_err());
+    }
+
+    #[test]
+    fn selecting_phase() {
+        let scenario: Scenario = serde_yaml::from_str(SCENARIO).unwrap();
+        let phase_name = |secs| {
+            scenario
+                .phase_at(Duration::from_secs(secs))
+                .map(|phase| phase.name.as_str())
+        };
+        assert_eq!(phase_name(0), Some("warm-up"));
+        assert_eq!(phase_name(9), Some("warm-up"));
+        assert_eq!(phase_name(10), Some("peak"));
+        assert_eq!(phase_name(29), Some("peak"));
+        assert_eq!(phase_name(30), None);
+    }
+
+    #[test]
+    fn latency_targets() {
+        let transfer = ActionType::Tx(TxActionType::Transfer);
+        let api = ActionType::Api(ApiActionType::Balance);
+        assert!(LatencyTarget::AllTransactions.matches(transfer));
+        assert!(LatencyTarget::Transfer.matches(transfer));
+        assert!(!LatencyTarget::Deposit.matches(transfer));
+        assert!(!LatencyTarget::AllTransactions.matches(api));
+        assert!(LatencyTarget::ApiRequests.matches(api));
+    }
+}
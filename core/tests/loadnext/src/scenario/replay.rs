@@ -0,0 +1,362 @@
+//! Recording and replay of real traffic.
+//!
+//! A traffic trace is recorded from the transactions of a range of L2 blocks (as returned by
+//! `zks_getRawBlockTransactions`) and stored as a JSON Lines file. During replay, test accounts send
+//! transactions of the same kinds at the same relative times as in the trace. Since test accounts don't own
+//! the funds of the original senders, transfers are replayed with random amounts between test accounts,
+//! deployments deploy the loadnext contract, and L1 transactions are replayed as deposits; contract calls
+//! are replayed with the original recipient and calldata.
+
+use std::{
+    collections::VecDeque,
+    fs,
+    io::{BufRead, BufReader, BufWriter, Write},
+    ops::RangeInclusive,
+    path::Path,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context as _;
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use zksync_system_constants::CONTRACT_DEPLOYER_ADDRESS;
+use zksync_types::{
+    web3::Bytes, Address, ExecuteTransactionCommon, L2BlockNumber, Transaction, U256,
+};
+use zksync_web3_decl::{
+    client::{Client, L2},
+    namespaces::ZksNamespaceClient,
+};
+
+use crate::{
+    account_pool::AddressPool,
+    command::{IncorrectnessModifier, TxCommand, TxType},
+    rng::LoadtestRng,
+};
+
+/// Kind of a recorded transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceTxKind {
+    /// L2 transaction with empty calldata.
+    Transfer,
+    /// L2 transaction calling the contract deployer.
+    Deploy,
+    /// L2 transaction calling a contract.
+    Call,
+    /// Priority (L1→L2) transaction.
+    L1Transaction,
+}
+
+/// Single transaction in a traffic trace.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceEntry {
+    /// Offset from the first transaction in the trace.
+    pub offset_ms: u64,
+    pub kind: TraceTxKind,
+    pub to: Address,
+    #[serde(default)]
+    pub value: U256,
+    #[serde(default)]
+    pub calldata: Bytes,
+}
+
+impl TraceEntry {
+    fn classify(tx: &Transaction) -> Option<TraceTxKind> {
+        Some(match &tx.common_data {
+            ExecuteTransactionCommon::L1(_) => TraceTxKind::L1Transaction,
+            ExecuteTransactionCommon::L2(_)
+                if tx.execute.contract_address == CONTRACT_DEPLOYER_ADDRESS =>
+            {
+                TraceTxKind::Deploy
+            }
+            ExecuteTransactionCommon::L2(_) if tx.execute.calldata.is_empty() => {
+                TraceTxKind::Transfer
+            }
+            ExecuteTransactionCommon::L2(_) => TraceTxKind::Call,
+            ExecuteTransactionCommon::ProtocolUpgrade(_) => return None,
+        })
+    }
+
+    fn to_command(
+        &self,
+        rng: &mut LoadtestRng,
+        own_address: Address,
+        addresses: &AddressPool,
+    ) -> TxCommand {
+        let command_type = match self.kind {
+            TraceTxKind::Transfer => TxType::Transfer,
+            TraceTxKind::Deploy => TxType::DeployContract,
+            TraceTxKind::Call => TxType::RawCall,
+            TraceTxKind::L1Transaction => TxType::Deposit,
+        };
+        let mut command = TxCommand::new_with_type(rng, own_address, addresses, command_type);
+        // Replayed traffic is expected to be valid.
+        command.modifier = IncorrectnessModifier::None;
+        if self.kind == TraceTxKind::Call {
+            command.to = self.to;
+            command.amount = U256::zero();
+            command.calldata = self.calldata.0.clone();
+        }
+        command
+    }
+}
+
+/// Recorded traffic, ordered by the transaction offset.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TrafficTrace {
+    pub entries: Vec<TraceEntry>,
+}
+
+impl TrafficTrace {
+    /// Builds a trace from transactions. Transaction times are taken from their received timestamps
+    /// if available, and from the provided block timestamps (in seconds) otherwise.
+    pub fn from_transactions(transactions: impl IntoIterator<Item = (u64, Transaction)>) -> Self {
+        let mut timed_entries: Vec<_> = transactions
+            .into_iter()
+            .filter_map(|(block_timestamp, tx)| {
+                let kind = TraceEntry::classify(&tx)?;
+                let timestamp_ms = if tx.received_timestamp_ms > 0 {
+                    tx.received_timestamp_ms
+                } else {
+                    block_timestamp * 1_000
+                };
+                let entry = TraceEntry {
+                    offset_ms: 0,
+                    kind,
+                    to: tx.execute.contract_address,
+                    value: tx.execute.value,
+                    calldata: tx.execute.calldata.into(),
+                };
+                Some((timestamp_ms, entry))
+            })
+            .collect();
+        timed_entries.sort_by_key(|(timestamp_ms, _)| *timestamp_ms);
+
+        let start_ms = timed_entries
+            .first()
+            .map_or(0, |(timestamp_ms, _)| *timestamp_ms);
+        let entries = timed_entries
+            .into_iter()
+            .map(|(timestamp_ms, mut entry)| {
+                entry.offset_ms = timestamp_ms - start_ms;
+                entry
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Reads a trace from a JSON Lines file.
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let file = fs::File::open(path)
+            .with_context(|| format!("failed opening trace {}", path.display()))?;
+        let mut entries = vec![];
+        for (i, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry: TraceEntry = serde_json::from_str(&line)
+                .with_context(|| format!("invalid trace entry at line {}", i + 1))?;
+            entries.push(entry);
+        }
+        anyhow::ensure!(
+            entries
+                .windows(2)
+                .all(|pair| pair[0].offset_ms <= pair[1].offset_ms),
+            "trace entries are not ordered by offset"
+        );
+        Ok(Self { entries })
+    }
+
+    /// Writes the trace to a JSON Lines file.
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        let file = fs::File::create(path)
+            .with_context(|| format!("failed creating trace {}", path.display()))?;
+        let mut writer = BufWriter::new(file);
+        for entry in &self.entries {
+            serde_json::to_writer(&mut writer, entry)?;
+            writeln!(writer)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Returns the time between the first and the last transaction in the trace.
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.entries.last().map_or(0, |entry| entry.offset_ms))
+    }
+}
+
+/// Records a traffic trace from transactions in the specified range of L2 blocks.
+pub async fn record_trace(
+    client: &Client<L2>,
+    blocks: RangeInclusive<L2BlockNumber>,
+) -> anyhow::Result<TrafficTrace> {
+    let mut transactions = vec![];
+    for number in blocks.start().0..=blocks.end().0 {
+        let number = L2BlockNumber(number);
+        let details = client
+            .get_block_details(number)
+            .await
+            .with_context(|| format!("failed getting details for L2 block #{number}"))?
+            .with_context(|| format!("L2 block #{number} is missing"))?;
+        let block_transactions = client
+            .get_raw_block_transactions(number)
+            .await
+            .with_context(|| format!("failed getting transactions for L2 block #{number}"))?;
+        tracing::debug!(
+            "Recorded {} transactions from L2 block #{number}",
+            block_transactions.len()
+        );
+        transactions.extend(
+            block_transactions
+                .into_iter()
+                .map(|tx| (details.base.timestamp, tx)),
+        );
+    }
+    Ok(TrafficTrace::from_transactions(transactions))
+}
+
+/// Queue of recorded transactions shared among test accounts during replay.
+#[derive(Debug)]
+pub struct ReplayFeed {
+    entries: Mutex<VecDeque<TraceEntry>>,
+    duration: Duration,
+    speed: f64,
+    started_at: OnceCell<Instant>,
+}
+
+impl ReplayFeed {
+    /// Creates a feed replaying the trace `speed` times faster than it was recorded.
+    pub fn new(trace: TrafficTrace, speed: f64) -> Self {
+        assert!(speed > 0.0, "replay speed must be positive");
+        Self {
+            duration: trace.duration().div_f64(speed),
+            entries: Mutex::new(trace.entries.into()),
+            speed,
+            started_at: OnceCell::new(),
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Waits until the next recorded transaction is due and returns a command replaying it,
+    /// or `None` if the trace is exhausted.
+    pub(super) async fn next_command(
+        &self,
+        rng: &mut LoadtestRng,
+        own_address: Address,
+        addresses: &AddressPool,
+    ) -> Option<TxCommand> {
+        let entry = self.entries.lock().unwrap().pop_front()?;
+        let started_at = *self.started_at.get_or_init(Instant::now);
+        let offset = Duration::from_millis(entry.offset_ms).div_f64(self.speed);
+        tokio::time::sleep_until((started_at + offset).into()).await;
+        Some(entry.to_command(rng, own_address, addresses))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{l2::L2Tx, Nonce};
+
+    use super::*;
+
+    fn l2_tx(
+        contract_address: Address,
+        calldata: Vec<u8>,
+        received_timestamp_ms: u64,
+    ) -> Transaction {
+        let mut tx: Transaction = L2Tx::new(
+            contract_address,
+            calldata,
+            Nonce(0),
+            Default::default(),
+            Address::repeat_byte(1),
+            U256::zero(),
+            vec![],
+            Default::default(),
+        )
+        .into();
+        tx.received_timestamp_ms = received_timestamp_ms;
+        tx
+    }
+
+    #[test]
+    fn building_trace_from_transactions() {
+        let contract = Address::repeat_byte(2);
+        let transactions = [
+            (100, l2_tx(contract, vec![1, 2, 3], 100_500)),
+            (100, l2_tx(Address::repeat_byte(3), vec![], 100_000)),
+            (101, l2_tx(CONTRACT_DEPLOYER_ADDRESS, vec![0; 4], 0)),
+        ];
+        let trace = TrafficTrace::from_transactions(transactions);
+
+        let kinds: Vec<_> = trace.entries.iter().map(|entry| entry.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                TraceTxKind::Transfer,
+                TraceTxKind::Call,
+                TraceTxKind::Deploy
+            ]
+        );
+        let offsets: Vec<_> = trace.entries.iter().map(|entry| entry.offset_ms).collect();
+        assert_eq!(offsets, [0, 500, 1_000]);
+        assert_eq!(trace.entries[1].to, contract);
+        assert_eq!(trace.entries[1].calldata.0, [1, 2, 3]);
+        assert_eq!(trace.duration(), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn trace_roundtrip() {
+        let trace = TrafficTrace {
+            entries: vec![
+                TraceEntry {
+                    offset_ms: 0,
+                    kind: TraceTxKind::L1Transaction,
+                    to: Address::repeat_byte(1),
+                    value: 10.into(),
+                    calldata: Bytes::default(),
+                },
+                TraceEntry {
+                    offset_ms: 250,
+                    kind: TraceTxKind::Call,
+                    to: Address::repeat_byte(2),
+                    value: U256::zero(),
+                    calldata: vec![0xde, 0xad].into(),
+                },
+            ],
+        };
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("trace.jsonl");
+        trace.write(&path).unwrap();
+        assert_eq!(TrafficTrace::read(&path).unwrap(), trace);
+
+        let feed = ReplayFeed::new(trace, 2.0);
+        assert_eq!(feed.duration(), Duration::from_millis(125));
+    }
+
+    #[test]
+    fn unordered_trace_is_rejected() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("trace.jsonl");
+        let entry = |offset_ms| TraceEntry {
+            offset_ms,
+            kind: TraceTxKind::Transfer,
+            to: Address::zero(),
+            value: U256::zero(),
+            calldata: Bytes::default(),
+        };
+        TrafficTrace {
+            entries: vec![entry(10), entry(5)],
+        }
+        .write(&path)
+        .unwrap();
+        assert!(TrafficTrace::read(&path).is_err());
+    }
+}
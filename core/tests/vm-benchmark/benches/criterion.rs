@@ -1,5 +1,8 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use zksync_vm_benchmark_harness::{cut_to_allowed_bytecode_size, get_deploy_tx, BenchmarkingVm};
+use zksync_vm_benchmark_harness::{
+    cut_to_allowed_bytecode_size, get_deploy_tx, get_deploy_tx_for_version, BenchmarkingVm,
+    BENCHMARKED_VM_VERSIONS,
+};
 
 fn benches_in_folder(c: &mut Criterion) {
     for path in std::fs::read_dir("deployment_benchmarks").unwrap() {
@@ -16,5 +19,30 @@ fn benches_in_folder(c: &mut Criterion) {
     }
 }
 
-criterion_group!(benches, benches_in_folder);
+/// Benchmarks the same transactions on all supported VM versions. Run with `cargo bench --bench criterion multivm`
+/// to only run these benchmarks.
+fn multivm_benches_in_folder(c: &mut Criterion) {
+    for path in std::fs::read_dir("deployment_benchmarks").unwrap() {
+        let path = path.unwrap().path();
+
+        let test_contract = std::fs::read(&path).expect("failed to read file");
+
+        let code = cut_to_allowed_bytecode_size(&test_contract).unwrap();
+        let name = path.file_name().unwrap().to_str().unwrap();
+        let mut group = c.benchmark_group(format!("multivm/{name}"));
+        for version in BENCHMARKED_VM_VERSIONS {
+            let tx = get_deploy_tx_for_version(code, version);
+            group.bench_function(format!("{version:?}"), |b| {
+                b.iter(|| {
+                    BenchmarkingVm::with_version(version)
+                        .unwrap()
+                        .run_transaction(black_box(&tx))
+                })
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, benches_in_folder, multivm_benches_in_folder);
 criterion_main!(benches);
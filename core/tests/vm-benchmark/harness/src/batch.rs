@@ -0,0 +1,306 @@
+//! Real L1 batches recorded from Postgres, so that they can be re-executed without a database.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::Path,
+    rc::Rc,
+};
+
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+use zksync_multivm::{
+    interface::{
+        ExecutionResult, FinishedL1Batch, L1BatchEnv, L2BlockEnv, SystemEnv, VmInterface,
+        VmInterfaceHistoryEnabled,
+    },
+    vm_latest::HistoryEnabled,
+    VmInstance,
+};
+use zksync_state::{InMemoryStorage, ReadStorage, StorageView, WriteStorage};
+use zksync_types::{
+    block::L2BlockExecutionData, web3::Bytes, StorageKey, StorageValue, Transaction, H256,
+};
+
+/// Storage slot accessed during batch execution that was present in the storage at the batch start.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageSlot {
+    pub key: StorageKey,
+    pub value: StorageValue,
+    pub enumeration_index: u64,
+}
+
+/// Factory dependency loaded during batch execution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FactoryDep {
+    pub hash: H256,
+    pub bytecode: Bytes,
+}
+
+/// L1 batch together with all the state necessary to execute it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedBatch {
+    pub l1_batch_env: L1BatchEnv,
+    pub system_env: SystemEnv,
+    pub l2_blocks: Vec<L2BlockExecutionData>,
+    pub storage_slots: Vec<StorageSlot>,
+    pub factory_deps: Vec<FactoryDep>,
+}
+
+impl RecordedBatch {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let file =
+            File::open(path).with_context(|| format!("failed opening {}", path.display()))?;
+        serde_json::from_reader(BufReader::new(file))
+            .with_context(|| format!("failed parsing recorded batch from {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let file =
+            File::create(path).with_context(|| format!("failed creating {}", path.display()))?;
+        serde_json::to_writer(BufWriter::new(file), self)
+            .with_context(|| format!("failed writing recorded batch to {}", path.display()))
+    }
+
+    pub fn transaction_count(&self) -> usize {
+        self.l2_blocks.iter().map(|block| block.txs.len()).sum()
+    }
+
+    /// Creates in-memory storage containing the state accessed by the batch.
+    pub fn storage(&self) -> InMemoryStorage {
+        in_memory_storage(&self.storage_slots, &self.factory_deps)
+    }
+
+    /// Executes the batch on top of the provided storage, which should be created with [`Self::storage()`].
+    pub fn execute(&self, storage: &InMemoryStorage) -> anyhow::Result<FinishedL1Batch> {
+        execute_batch(
+            self.l1_batch_env.clone(),
+            self.system_env.clone(),
+            &self.l2_blocks,
+            storage,
+        )
+    }
+}
+
+fn in_memory_storage(
+    storage_slots: &[StorageSlot],
+    factory_deps: &[FactoryDep],
+) -> InMemoryStorage {
+    let mut storage = InMemoryStorage::default();
+    for slot in storage_slots {
+        storage.set_value_hashed_enum(slot.key.hashed_key(), slot.enumeration_index, slot.value);
+    }
+    for dep in factory_deps {
+        storage.store_factory_dep(dep.hash, dep.bytecode.0.clone());
+    }
+    storage
+}
+
+/// Storage accesses recorded by [`RecordingStorage`].
+#[derive(Debug, Default)]
+pub struct StorageAccesses {
+    /// Accessed slots with their values and enumeration indices; `None` for slots missing from the storage.
+    slots: HashMap<StorageKey, Option<(StorageValue, u64)>>,
+    factory_deps: HashMap<H256, Vec<u8>>,
+}
+
+impl StorageAccesses {
+    pub fn storage_slots(&self) -> Vec<StorageSlot> {
+        let mut slots: Vec<_> = self
+            .slots
+            .iter()
+            .filter_map(|(key, entry)| {
+                let (value, enumeration_index) = (*entry)?;
+                Some(StorageSlot {
+                    key: *key,
+                    value,
+                    enumeration_index,
+                })
+            })
+            .collect();
+        slots.sort_unstable_by_key(|slot| slot.enumeration_index);
+        slots
+    }
+
+    pub fn factory_deps(&self) -> Vec<FactoryDep> {
+        let mut deps: Vec<_> = self
+            .factory_deps
+            .iter()
+            .map(|(hash, bytecode)| FactoryDep {
+                hash: *hash,
+                bytecode: Bytes(bytecode.clone()),
+            })
+            .collect();
+        deps.sort_unstable_by_key(|dep| dep.hash);
+        deps
+    }
+}
+
+/// Storage wrapper recording all slots and factory dependencies accessed via it.
+#[derive(Debug)]
+pub struct RecordingStorage<S> {
+    inner: S,
+    accesses: Rc<RefCell<StorageAccesses>>,
+}
+
+impl<S: ReadStorage> RecordingStorage<S> {
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            accesses: Rc::default(),
+        }
+    }
+
+    /// Returns a handle to the accesses recorded by this storage.
+    pub fn accesses(&self) -> Rc<RefCell<StorageAccesses>> {
+        self.accesses.clone()
+    }
+
+    fn record_slot(&mut self, key: &StorageKey) -> Option<(StorageValue, u64)> {
+        if let Some(entry) = self.accesses.borrow().slots.get(key) {
+            return *entry;
+        }
+        let entry = self
+            .inner
+            .get_enumeration_index(key)
+            .map(|enumeration_index| (self.inner.read_value(key), enumeration_index));
+        self.accesses.borrow_mut().slots.insert(*key, entry);
+        entry
+    }
+}
+
+impl<S: ReadStorage> ReadStorage for RecordingStorage<S> {
+    fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+        self.record_slot(key)
+            .map_or_else(StorageValue::zero, |(value, _)| value)
+    }
+
+    fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        self.record_slot(key).is_none()
+    }
+
+    fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+        let bytecode = self.inner.load_factory_dep(hash)?;
+        self.accesses
+            .borrow_mut()
+            .factory_deps
+            .insert(hash, bytecode.clone());
+        Some(bytecode)
+    }
+
+    fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+        self.record_slot(key)
+            .map(|(_, enumeration_index)| enumeration_index)
+    }
+}
+
+/// Executes all transactions in the L1 batch and seals it, similarly to the state keeper.
+///
+/// # Errors
+///
+/// Returns an error if any transaction is rejected by the bootloader, which shouldn't happen for sealed batches.
+pub fn execute_batch<S: ReadStorage>(
+    l1_batch_env: L1BatchEnv,
+    system_env: SystemEnv,
+    l2_blocks: &[L2BlockExecutionData],
+    storage: S,
+) -> anyhow::Result<FinishedL1Batch> {
+    let storage = StorageView::new(storage).to_rc_ptr();
+    let mut vm = VmInstance::<_, HistoryEnabled>::new(l1_batch_env, system_env, storage);
+    for (i, l2_block) in l2_blocks.iter().enumerate() {
+        if i > 0 {
+            // First L2 block in every batch is already preloaded
+            vm.start_new_l2_block(L2BlockEnv::from_l2_block_data(l2_block));
+        }
+        for tx in &l2_block.txs {
+            execute_tx(&mut vm, tx).with_context(|| {
+                format!(
+                    "failed executing transaction {:?} in L2 block #{}",
+                    tx.hash(),
+                    l2_block.number
+                )
+            })?;
+        }
+    }
+    Ok(vm.finish_batch())
+}
+
+fn execute_tx<S: WriteStorage>(
+    vm: &mut VmInstance<S, HistoryEnabled>,
+    tx: &Transaction,
+) -> anyhow::Result<()> {
+    // Attempt to run VM with bytecode compression on.
+    vm.make_snapshot();
+    let (compression_result, mut result) =
+        vm.execute_transaction_with_bytecode_compression(tx.clone(), true);
+    if compression_result.is_ok() {
+        vm.pop_snapshot_no_rollback();
+    } else {
+        // If failed with bytecode compression, attempt to run without bytecode compression.
+        vm.rollback_to_the_latest_snapshot();
+        let (compression_result, retried_result) =
+            vm.execute_transaction_with_bytecode_compression(tx.clone(), false);
+        compression_result.context("compression can't fail if we don't apply it")?;
+        result = retried_result;
+    }
+
+    if let ExecutionResult::Halt { reason } = result.result {
+        anyhow::bail!("transaction was rejected by the bootloader: {reason}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{AccountTreeId, Address};
+
+    use super::*;
+
+    #[test]
+    fn recorded_storage_matches_original() {
+        let key = |i: u64| {
+            StorageKey::new(
+                AccountTreeId::new(Address::repeat_byte(1)),
+                H256::from_low_u64_be(i),
+            )
+        };
+        let mut original = InMemoryStorage::default();
+        original.set_value(key(1), H256::repeat_byte(0x11));
+        original.set_value(key(2), H256::zero());
+        original.set_value(key(3), H256::repeat_byte(0x33));
+        original.store_factory_dep(H256::repeat_byte(0xff), vec![1, 2, 3]);
+
+        let mut storage = RecordingStorage::new(&original);
+        assert_eq!(storage.read_value(&key(1)), H256::repeat_byte(0x11));
+        assert!(!storage.is_write_initial(&key(2)));
+        assert!(storage.is_write_initial(&key(4)));
+        assert_eq!(
+            storage.load_factory_dep(H256::repeat_byte(0xff)),
+            Some(vec![1, 2, 3])
+        );
+        assert_eq!(storage.load_factory_dep(H256::zero()), None);
+
+        let accesses = storage.accesses();
+        let accesses = accesses.borrow();
+        let storage_slots = accesses.storage_slots();
+        assert_eq!(storage_slots.len(), 2);
+        let mut restored = in_memory_storage(&storage_slots, &accesses.factory_deps());
+        let mut original = &original;
+
+        for i in 1..=2 {
+            assert_eq!(restored.read_value(&key(i)), original.read_value(&key(i)));
+            assert_eq!(
+                restored.get_enumeration_index(&key(i)),
+                original.get_enumeration_index(&key(i))
+            );
+        }
+        assert!(restored.is_write_initial(&key(3)));
+        assert!(restored.is_write_initial(&key(4)));
+        assert_eq!(
+            restored.load_factory_dep(H256::repeat_byte(0xff)),
+            Some(vec![1, 2, 3])
+        );
+    }
+}
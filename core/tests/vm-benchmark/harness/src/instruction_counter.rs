@@ -1,28 +1,41 @@
 use std::{cell::RefCell, rc::Rc};
 
 use zksync_multivm::{
-    interface::{dyn_tracers::vm_1_5_0::DynTracer, tracer::TracerExecutionStatus},
-    vm_latest::{BootloaderState, HistoryMode, SimpleMemory, VmTracer, ZkSyncVmState},
+    interface::{
+        dyn_tracers::{vm_1_3_3, vm_1_4_0, vm_1_4_1, vm_1_5_0},
+        tracer::{TracerExecutionStatus, VmExecutionStopReason},
+    },
+    vm_1_4_1 as v1_4_1, vm_1_4_2 as v1_4_2, vm_boojum_integration as boojum, vm_latest as latest,
+    vm_refunds_enhancement as refunds, vm_virtual_blocks as virtual_blocks, IntoOldVmTracer,
 };
 use zksync_state::WriteStorage;
 
+/// A tracer that counts the number of instructions executed by the VM.
+///
+/// Supports all VM versions starting from `VmVirtualBlocks`; older VMs don't support custom tracers.
+#[derive(Debug, Clone)]
 pub struct InstructionCounter {
     count: usize,
     output: Rc<RefCell<usize>>,
 }
 
-/// A tracer that counts the number of instructions executed by the VM.
 impl InstructionCounter {
     pub fn new(output: Rc<RefCell<usize>>) -> Self {
         Self { count: 0, output }
     }
+
+    fn finish(&mut self) {
+        *self.output.borrow_mut() = self.count;
+    }
 }
 
-impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for InstructionCounter {
+impl IntoOldVmTracer for InstructionCounter {}
+
+impl<S: WriteStorage, H: latest::HistoryMode> latest::VmTracer<S, H> for InstructionCounter {
     fn finish_cycle(
         &mut self,
-        _state: &mut ZkSyncVmState<S, H>,
-        _bootloader_state: &mut BootloaderState,
+        _state: &mut latest::ZkSyncVmState<S, H>,
+        _bootloader_state: &mut latest::BootloaderState,
     ) -> TracerExecutionStatus {
         self.count += 1;
         TracerExecutionStatus::Continue
@@ -30,12 +43,148 @@ impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for InstructionCounter {
 
     fn after_vm_execution(
         &mut self,
-        _state: &mut ZkSyncVmState<S, H>,
-        _bootloader_state: &BootloaderState,
-        _stop_reason: zksync_multivm::interface::tracer::VmExecutionStopReason,
+        _state: &mut latest::ZkSyncVmState<S, H>,
+        _bootloader_state: &latest::BootloaderState,
+        _stop_reason: VmExecutionStopReason,
     ) {
-        *self.output.borrow_mut() = self.count;
+        self.finish();
+    }
+}
+
+impl<S: WriteStorage, H: latest::HistoryMode> vm_1_5_0::DynTracer<S, latest::SimpleMemory<H>>
+    for InstructionCounter
+{
+}
+
+impl<S: WriteStorage, H: v1_4_2::HistoryMode> v1_4_2::VmTracer<S, H> for InstructionCounter {
+    fn finish_cycle(
+        &mut self,
+        _state: &mut v1_4_2::ZkSyncVmState<S, H>,
+        _bootloader_state: &mut v1_4_2::BootloaderState,
+    ) -> TracerExecutionStatus {
+        self.count += 1;
+        TracerExecutionStatus::Continue
+    }
+
+    fn after_vm_execution(
+        &mut self,
+        _state: &mut v1_4_2::ZkSyncVmState<S, H>,
+        _bootloader_state: &v1_4_2::BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        self.finish();
+    }
+}
+
+impl<S: WriteStorage, H: v1_4_2::HistoryMode> vm_1_4_1::DynTracer<S, v1_4_2::SimpleMemory<H>>
+    for InstructionCounter
+{
+}
+
+impl<S: WriteStorage, H: v1_4_1::HistoryMode> v1_4_1::VmTracer<S, H> for InstructionCounter {
+    fn finish_cycle(
+        &mut self,
+        _state: &mut v1_4_1::ZkSyncVmState<S, H>,
+        _bootloader_state: &mut v1_4_1::BootloaderState,
+    ) -> TracerExecutionStatus {
+        self.count += 1;
+        TracerExecutionStatus::Continue
+    }
+
+    fn after_vm_execution(
+        &mut self,
+        _state: &mut v1_4_1::ZkSyncVmState<S, H>,
+        _bootloader_state: &v1_4_1::BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        self.finish();
+    }
+}
+
+impl<S: WriteStorage, H: v1_4_1::HistoryMode> vm_1_4_1::DynTracer<S, v1_4_1::SimpleMemory<H>>
+    for InstructionCounter
+{
+}
+
+impl<S: WriteStorage, H: boojum::HistoryMode> boojum::VmTracer<S, H> for InstructionCounter {
+    fn finish_cycle(
+        &mut self,
+        _state: &mut boojum::ZkSyncVmState<S, H>,
+        _bootloader_state: &mut boojum::BootloaderState,
+    ) -> TracerExecutionStatus {
+        self.count += 1;
+        TracerExecutionStatus::Continue
+    }
+
+    fn after_vm_execution(
+        &mut self,
+        _state: &mut boojum::ZkSyncVmState<S, H>,
+        _bootloader_state: &boojum::BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        self.finish();
+    }
+}
+
+impl<S: WriteStorage, H: boojum::HistoryMode> vm_1_4_0::DynTracer<S, boojum::SimpleMemory<H>>
+    for InstructionCounter
+{
+}
+
+impl<S: WriteStorage, H: refunds::HistoryMode> refunds::VmTracer<S, H> for InstructionCounter {
+    fn finish_cycle(
+        &mut self,
+        _state: &mut refunds::ZkSyncVmState<S, H>,
+        _bootloader_state: &mut refunds::BootloaderState,
+    ) -> TracerExecutionStatus {
+        self.count += 1;
+        TracerExecutionStatus::Continue
+    }
+
+    fn after_vm_execution(
+        &mut self,
+        _state: &mut refunds::ZkSyncVmState<S, H>,
+        _bootloader_state: &refunds::BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        self.finish();
     }
 }
 
-impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for InstructionCounter {}
+impl<S: WriteStorage, H: refunds::HistoryMode> vm_1_3_3::DynTracer<S, refunds::SimpleMemory<H>>
+    for InstructionCounter
+{
+}
+
+impl<H: virtual_blocks::HistoryMode> virtual_blocks::ExecutionEndTracer<H> for InstructionCounter {}
+
+impl<S: WriteStorage, H: virtual_blocks::HistoryMode> virtual_blocks::ExecutionProcessing<S, H>
+    for InstructionCounter
+{
+    fn after_cycle(
+        &mut self,
+        _state: &mut virtual_blocks::ZkSyncVmState<S, H>,
+        _bootloader_state: &mut virtual_blocks::BootloaderState,
+    ) {
+        self.count += 1;
+    }
+
+    fn after_vm_execution(
+        &mut self,
+        _state: &mut virtual_blocks::ZkSyncVmState<S, H>,
+        _bootloader_state: &virtual_blocks::BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        self.finish();
+    }
+}
+
+impl<S: WriteStorage, H: virtual_blocks::HistoryMode> virtual_blocks::VmTracer<S, H>
+    for InstructionCounter
+{
+}
+
+impl<S: WriteStorage, H: virtual_blocks::HistoryMode>
+    vm_1_3_3::DynTracer<S, virtual_blocks::SimpleMemory<H>> for InstructionCounter
+{
+}
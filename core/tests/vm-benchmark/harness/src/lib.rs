@@ -1,13 +1,15 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
 use once_cell::sync::Lazy;
-use zksync_contracts::{deployer_contract, BaseSystemContracts};
+use zksync_contracts::{deployer_contract, read_zbin_bytecode, BaseSystemContracts};
 use zksync_multivm::{
     interface::{
         L2BlockEnv, TxExecutionMode, VmExecutionMode, VmExecutionResultAndLogs, VmInterface,
     },
+    tracers::TracerDispatcher,
     utils::get_max_gas_per_pubdata_byte,
-    vm_latest::{constants::BATCH_COMPUTATIONAL_GAS_LIMIT, HistoryEnabled, TracerDispatcher, Vm},
+    vm_latest::{constants::BATCH_COMPUTATIONAL_GAS_LIMIT, HistoryEnabled},
+    MultiVMTracer, VmInstance, VmVersion,
 };
 use zksync_state::{InMemoryStorage, StorageView};
 use zksync_types::{
@@ -23,6 +25,9 @@ use zksync_types::{
 };
 use zksync_utils::bytecode::hash_bytecode;
 
+use crate::instruction_counter::InstructionCounter;
+
+pub mod batch;
 mod instruction_counter;
 
 /// Bytecodes have consist of an odd number of 32 byte words
@@ -61,14 +66,102 @@ static CREATE_FUNCTION_SIGNATURE: Lazy<[u8; 4]> = Lazy::new(|| {
 static PRIVATE_KEY: Lazy<K256PrivateKey> =
     Lazy::new(|| K256PrivateKey::from_bytes(H256([42; 32])).expect("invalid key bytes"));
 
-pub struct BenchmarkingVm(Vm<StorageView<&'static InMemoryStorage>, HistoryEnabled>);
+/// VM versions supported by the benchmarking harness, from the oldest to the latest one.
+///
+/// M5 and M6 VMs are not supported since their bootloaders are not shipped with the repository.
+pub const BENCHMARKED_VM_VERSIONS: [VmVersion; 8] = [
+    VmVersion::Vm1_3_2,
+    VmVersion::VmVirtualBlocks,
+    VmVersion::VmVirtualBlocksRefundsEnhancement,
+    VmVersion::VmBoojumIntegration,
+    VmVersion::Vm1_4_1,
+    VmVersion::Vm1_4_2,
+    VmVersion::Vm1_5_0SmallBootloaderMemory,
+    VmVersion::Vm1_5_0IncreasedBootloaderMemory,
+];
+
+/// Returns the latest protocol version executed by the specified VM version, or `None` if the VM version
+/// is not supported by the harness.
+fn protocol_version_for(version: VmVersion) -> Option<ProtocolVersionId> {
+    Some(match version {
+        VmVersion::M5WithoutRefunds
+        | VmVersion::M5WithRefunds
+        | VmVersion::M6Initial
+        | VmVersion::M6BugWithCompressionFixed => return None,
+        VmVersion::Vm1_3_2 => ProtocolVersionId::Version12,
+        VmVersion::VmVirtualBlocks => ProtocolVersionId::Version15,
+        VmVersion::VmVirtualBlocksRefundsEnhancement => ProtocolVersionId::Version17,
+        VmVersion::VmBoojumIntegration => ProtocolVersionId::Version19,
+        VmVersion::Vm1_4_1 => ProtocolVersionId::Version20,
+        VmVersion::Vm1_4_2 => ProtocolVersionId::Version22,
+        VmVersion::Vm1_5_0SmallBootloaderMemory => ProtocolVersionId::Version23,
+        VmVersion::Vm1_5_0IncreasedBootloaderMemory => ProtocolVersionId::latest(),
+    })
+}
+
+/// Base system contracts for each of [`BENCHMARKED_VM_VERSIONS`], so that they are not loaded from disk
+/// each time a VM is created.
+static VERSIONED_SYSTEM_CONTRACTS: Lazy<HashMap<VmVersion, BaseSystemContracts>> =
+    Lazy::new(|| {
+        BENCHMARKED_VM_VERSIONS
+            .into_iter()
+            .filter_map(|version| Some((version, load_system_contracts(version)?)))
+            .collect()
+    });
+
+/// Loads base system contracts with the proved bootloader used by the specified VM version.
+fn load_system_contracts(version: VmVersion) -> Option<BaseSystemContracts> {
+    let bootloader_path = match version {
+        VmVersion::M5WithoutRefunds
+        | VmVersion::M5WithRefunds
+        | VmVersion::M6Initial
+        | VmVersion::M6BugWithCompressionFixed => return None,
+        VmVersion::Vm1_3_2 => "vm_1_3_2/proved_block.yul/proved_block.yul.zbin",
+        VmVersion::VmVirtualBlocks | VmVersion::VmVirtualBlocksRefundsEnhancement => {
+            "vm_virtual_blocks_finish_upgrade_fix/proved_batch.yul/proved_batch.yul.zbin"
+        }
+        VmVersion::VmBoojumIntegration => {
+            "vm_remove_allowlist/proved_batch.yul/proved_batch.yul.zbin"
+        }
+        VmVersion::Vm1_4_1 => "vm_1_4_1/proved_batch.yul/proved_batch.yul.zbin",
+        VmVersion::Vm1_4_2 => "vm_1_4_2/proved_batch.yul/proved_batch.yul.zbin",
+        VmVersion::Vm1_5_0SmallBootloaderMemory => {
+            "vm_1_5_0_small_memory/proved_batch.yul/proved_batch.yul.zbin"
+        }
+        VmVersion::Vm1_5_0IncreasedBootloaderMemory => return Some(SYSTEM_CONTRACTS.clone()),
+    };
+    let bootloader_bytecode =
+        read_zbin_bytecode(format!("etc/multivm_bootloaders/{bootloader_path}"));
+    Some(BaseSystemContracts::load_with_bootloader(
+        bootloader_bytecode,
+    ))
+}
+
+/// VM of a specific version executing transactions on top of a storage with the system contracts.
+///
+/// Note that the storage always contains the current system contracts, so for older VM versions execution
+/// can differ from what happened on-chain. The results are still useful to compare the VM implementations
+/// with each other.
+pub struct BenchmarkingVm {
+    vm: VmInstance<StorageView<&'static InMemoryStorage>, HistoryEnabled>,
+    version: VmVersion,
+}
 
 impl BenchmarkingVm {
+    /// Creates a VM of the latest version.
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
+        Self::with_version(VmVersion::latest()).expect("latest VM version is always supported")
+    }
+
+    /// Creates a VM of the specified version. Returns `None` if the version is not supported;
+    /// see [`BENCHMARKED_VM_VERSIONS`] for the list of supported versions.
+    pub fn with_version(version: VmVersion) -> Option<Self> {
+        let protocol_version = protocol_version_for(version)?;
+        let base_system_smart_contracts = VERSIONED_SYSTEM_CONTRACTS.get(&version)?.clone();
         let timestamp = unix_timestamp_ms();
 
-        Self(Vm::new(
+        let vm = VmInstance::new_with_specific_version(
             zksync_multivm::interface::L1BatchEnv {
                 previous_batch_hash: None,
                 number: L1BatchNumber(1),
@@ -88,39 +181,51 @@ impl BenchmarkingVm {
             },
             zksync_multivm::interface::SystemEnv {
                 zk_porter_available: false,
-                version: ProtocolVersionId::latest(),
-                base_system_smart_contracts: SYSTEM_CONTRACTS.clone(),
+                version: protocol_version,
+                base_system_smart_contracts,
                 bootloader_gas_limit: BATCH_COMPUTATIONAL_GAS_LIMIT,
                 execution_mode: TxExecutionMode::VerifyExecute,
                 default_validation_computational_gas_limit: BATCH_COMPUTATIONAL_GAS_LIMIT,
                 chain_id: L2ChainId::from(270),
             },
             Rc::new(RefCell::new(StorageView::new(&*STORAGE))),
-        ))
+            version,
+        );
+        Some(Self { vm, version })
+    }
+
+    pub fn version(&self) -> VmVersion {
+        self.version
     }
 
     pub fn run_transaction(&mut self, tx: &Transaction) -> VmExecutionResultAndLogs {
-        self.0.push_transaction(tx.clone());
-        self.0.execute(VmExecutionMode::OneTx)
+        self.vm.push_transaction(tx.clone());
+        self.vm.execute(VmExecutionMode::OneTx)
     }
 
-    pub fn instruction_count(&mut self, tx: &Transaction) -> usize {
-        self.0.push_transaction(tx.clone());
+    /// Returns the number of instructions executed by the VM for the transaction, or `None` if the VM version
+    /// doesn't support tracers (i.e., is older than `VmVirtualBlocks`).
+    pub fn instruction_count(&mut self, tx: &Transaction) -> Option<usize> {
+        if matches!(self.version, VmVersion::Vm1_3_2) {
+            return None;
+        }
+        self.vm.push_transaction(tx.clone());
 
         let count = Rc::new(RefCell::new(0));
+        let tracer = InstructionCounter::new(count.clone()).into_tracer_pointer();
+        self.vm
+            .inspect(TracerDispatcher::from(tracer), VmExecutionMode::OneTx);
 
-        self.0.inspect(
-            TracerDispatcher::new(vec![Box::new(
-                instruction_counter::InstructionCounter::new(count.clone()),
-            )]),
-            VmExecutionMode::OneTx,
-        );
-
-        count.take()
+        Some(count.take())
     }
 }
 
 pub fn get_deploy_tx(code: &[u8]) -> Transaction {
+    get_deploy_tx_for_version(code, VmVersion::latest())
+}
+
+/// Creates a deployment transaction with the gas per pubdata limit accepted by the specified VM version.
+pub fn get_deploy_tx_for_version(code: &[u8], version: VmVersion) -> Transaction {
     let params = [
         Token::FixedBytes(vec![0u8; 32]),
         Token::FixedBytes(hash_bytecode(code).0.to_vec()),
@@ -140,9 +245,7 @@ pub fn get_deploy_tx(code: &[u8]) -> Transaction {
             gas_limit: U256::from(30000000u32),
             max_fee_per_gas: U256::from(250_000_000),
             max_priority_fee_per_gas: U256::from(0),
-            gas_per_pubdata_limit: U256::from(get_max_gas_per_pubdata_byte(
-                ProtocolVersionId::latest().into(),
-            )),
+            gas_per_pubdata_limit: U256::from(get_max_gas_per_pubdata_byte(version)),
         },
         U256::zero(),
         L2ChainId::from(270),
@@ -176,4 +279,27 @@ mod tests {
             zksync_multivm::interface::ExecutionResult::Success { .. }
         ));
     }
+
+    #[test]
+    fn can_run_contract_deployment_on_all_versions() {
+        let test_contract = read_bytecode(
+            "etc/contracts-test-data/artifacts-zk/contracts/counter/counter.sol/Counter.json",
+        );
+        for version in BENCHMARKED_VM_VERSIONS {
+            let tx = get_deploy_tx_for_version(&test_contract, version);
+            let mut vm = BenchmarkingVm::with_version(version).unwrap();
+            assert_eq!(vm.version(), version);
+            vm.run_transaction(&tx);
+
+            let count = BenchmarkingVm::with_version(version)
+                .unwrap()
+                .instruction_count(&tx);
+            if version == VmVersion::Vm1_3_2 {
+                assert_eq!(count, None);
+            } else {
+                assert!(count.unwrap() > 0, "{version:?}");
+            }
+        }
+        assert!(BenchmarkingVm::with_version(VmVersion::M6Initial).is_none());
+    }
 }
@@ -0,0 +1,202 @@
+//! Stores benchmark results per commit and checks them for regressions against historical baselines.
+//!
+//! Typical usage in CI:
+//!
+//! ```sh
+//! cargo bench --bench iai | tee iai-result
+//! cargo run --release --bin instruction-counts | tee opcodes
+//! cargo run --release --bin benchmark_history -- record --history history.json --commit $SHA --iai iai-result --opcodes opcodes
+//! cargo run --release --bin benchmark_history -- check --history history.json --commit $SHA --report report.json
+//! ```
+
+use std::{
+    collections::BTreeSet,
+    fs::{self, File},
+    io::BufReader,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context as _;
+use clap::{Parser, Subcommand};
+use vm_benchmark::{
+    history::{BenchmarkHistory, BenchmarkRecord, RegressionPolicy, RegressionReport},
+    parse_iai::parse_iai,
+};
+
+#[derive(Debug, Parser)]
+#[command(about = "Historical VM benchmark results and regression gate")]
+struct Cli {
+    /// Path to the JSON database with benchmark history. Created if missing.
+    #[arg(long, global = true, default_value = "vm-benchmark-history.json")]
+    history: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Records benchmark results for a commit, replacing previous results for it.
+    Record {
+        #[arg(long)]
+        commit: String,
+        /// Output of the `iai` benchmark.
+        #[arg(long)]
+        iai: Option<PathBuf>,
+        /// Output of the `instruction-counts` binary.
+        #[arg(long)]
+        opcodes: Option<PathBuf>,
+        /// Maximum number of records to keep in the history.
+        #[arg(long, default_value_t = 500)]
+        max_records: usize,
+    },
+    /// Compares recorded results for a commit with the baseline formed by the latest other commits.
+    /// Exits with a non-zero code if regressions are found.
+    Check {
+        #[arg(long)]
+        commit: String,
+        /// Path to write the JSON report to. If not specified, the report is written to stdout.
+        #[arg(long)]
+        report: Option<PathBuf>,
+        #[arg(long, default_value_t = RegressionPolicy::default().window)]
+        window: usize,
+        #[arg(long, default_value_t = RegressionPolicy::default().min_samples)]
+        min_samples: usize,
+        #[arg(long, default_value_t = RegressionPolicy::default().threshold_percent)]
+        threshold_percent: f64,
+        #[arg(long, default_value_t = RegressionPolicy::default().threshold_sigmas)]
+        threshold_sigmas: f64,
+        /// Directory with benchmarks that must be present in the results.
+        #[arg(long)]
+        benchmarks_dir: Option<PathBuf>,
+    },
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+    let cli = Cli::parse();
+    let mut history = BenchmarkHistory::load(&cli.history)?;
+
+    match cli.command {
+        Command::Record {
+            commit,
+            iai,
+            opcodes,
+            max_records,
+        } => {
+            let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+            let mut record = BenchmarkRecord::new(commit, timestamp);
+            if let Some(path) = &iai {
+                let file = File::open(path)
+                    .with_context(|| format!("failed opening {}", path.display()))?;
+                record.add_iai_results(parse_iai(BufReader::new(file)));
+            }
+            if let Some(path) = &opcodes {
+                let output = fs::read_to_string(path)
+                    .with_context(|| format!("failed reading {}", path.display()))?;
+                record.add_opcode_counts(&output)?;
+            }
+            anyhow::ensure!(
+                !record.benchmarks.is_empty(),
+                "no benchmark results provided"
+            );
+
+            println!(
+                "Recorded {} benchmarks for commit {}",
+                record.benchmarks.len(),
+                record.commit
+            );
+            history.insert(record);
+            history.truncate(max_records);
+            history.save(&cli.history)?;
+            Ok(ExitCode::SUCCESS)
+        }
+
+        Command::Check {
+            commit,
+            report,
+            window,
+            min_samples,
+            threshold_percent,
+            threshold_sigmas,
+            benchmarks_dir,
+        } => {
+            let policy = RegressionPolicy {
+                window,
+                min_samples,
+                threshold_percent,
+                threshold_sigmas,
+            };
+            let current = history
+                .get(&commit)
+                .with_context(|| format!("no results recorded for commit {commit}"))?;
+            let benchmarks_dir = benchmarks_dir.unwrap_or_else(default_benchmarks_dir);
+            let expected_benchmarks = benchmark_names(&benchmarks_dir)?;
+            let regression_report =
+                RegressionReport::new(current, &history, &expected_benchmarks, policy);
+
+            print_summary(&regression_report);
+            let json = serde_json::to_string_pretty(&regression_report)?;
+            match &report {
+                Some(path) => fs::write(path, json)
+                    .with_context(|| format!("failed writing report to {}", path.display()))?,
+                None => println!("{json}"),
+            }
+
+            Ok(if regression_report.has_regressions {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            })
+        }
+    }
+}
+
+fn default_benchmarks_dir() -> PathBuf {
+    // using source file location because this is just a script, the binary isn't meant to be reused
+    Path::new(file!())
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .join("deployment_benchmarks")
+}
+
+fn benchmark_names(dir: &Path) -> anyhow::Result<BTreeSet<String>> {
+    let entries = fs::read_dir(dir).with_context(|| format!("failed listing {}", dir.display()))?;
+    let mut names = BTreeSet::new();
+    for entry in entries {
+        let name = entry?.file_name();
+        names.insert(name.to_string_lossy().into_owned());
+    }
+    Ok(names)
+}
+
+/// Prints a human-readable summary of notable changes to stderr, so that it doesn't mix with the JSON report.
+fn print_summary(report: &RegressionReport) {
+    eprintln!(
+        "Compared commit {} against {} baseline commits",
+        report.commit,
+        report.baseline_commits.len()
+    );
+    let mut has_changes = false;
+    for comparison in report.notable_changes() {
+        if !has_changes {
+            eprintln!("Benchmark | metric | status | change\n--- | --- | --- | ---");
+            has_changes = true;
+        }
+        let metric = comparison
+            .metric
+            .map_or_else(|| "N/A".to_owned(), |metric| metric.to_string());
+        let change = comparison
+            .change_percent
+            .map_or_else(|| "N/A".to_owned(), |change| format!("{change:+.1}%"));
+        eprintln!(
+            "{} | {metric} | {:?} | {change}",
+            comparison.benchmark, comparison.status
+        );
+    }
+    if !has_changes {
+        eprintln!("No notable changes");
+    }
+}
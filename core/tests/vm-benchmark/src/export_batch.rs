@@ -0,0 +1,109 @@
+//! Exports an L1 batch from Postgres into a file that can be replayed with `replay_batch` without a database.
+//!
+//! The batch is executed once against Postgres storage to determine the storage slots and factory dependencies
+//! it accesses; only those are included into the file.
+
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use clap::Parser;
+use tokio::runtime::Handle;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_state::PostgresStorage;
+use zksync_types::{url::SensitiveUrl, L1BatchNumber, L2ChainId};
+use zksync_vm_benchmark_harness::batch::{execute_batch, RecordedBatch, RecordingStorage};
+use zksync_vm_utils::storage::L1BatchParamsProvider;
+
+#[derive(Debug, Parser)]
+#[command(about = "Exports an L1 batch from Postgres for VM benchmarks")]
+struct Cli {
+    /// URL of the Postgres database of a node that has executed the batch.
+    #[arg(long)]
+    database_url: SensitiveUrl,
+    /// Number of the L1 batch to export.
+    #[arg(long)]
+    l1_batch: u32,
+    /// L2 chain ID of the node.
+    #[arg(long, default_value_t = 270)]
+    chain_id: u64,
+    /// Path to write the exported batch to.
+    #[arg(long)]
+    output: PathBuf,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let l1_batch_number = L1BatchNumber(cli.l1_batch);
+    let chain_id = L2ChainId::try_from(cli.chain_id)
+        .map_err(|err| anyhow::anyhow!("invalid chain ID: {err}"))?;
+    let pool = ConnectionPool::<Core>::singleton(cli.database_url)
+        .build()
+        .await
+        .context("failed to build connection pool")?;
+
+    let mut connection = pool.connection().await?;
+    let l1_batch_params_provider = L1BatchParamsProvider::new(&mut connection)
+        .await
+        .context("failed initializing L1 batch params provider")?;
+    let first_l2_block_in_batch = l1_batch_params_provider
+        .load_first_l2_block_in_batch(&mut connection, l1_batch_number)
+        .await
+        .with_context(|| format!("failed loading first L2 block in L1 batch #{l1_batch_number}"))?
+        .with_context(|| format!("no L2 blocks persisted for L1 batch #{l1_batch_number}"))?;
+    // The batch has already been executed by the state keeper, so we don't want to reject any execution.
+    let (system_env, l1_batch_env) = l1_batch_params_provider
+        .load_l1_batch_params(
+            &mut connection,
+            &first_l2_block_in_batch,
+            u32::MAX,
+            chain_id,
+        )
+        .await
+        .with_context(|| format!("failed loading params for L1 batch #{l1_batch_number}"))?;
+    let l2_blocks = connection
+        .transactions_dal()
+        .get_l2_blocks_to_execute_for_l1_batch(l1_batch_number)
+        .await?;
+    anyhow::ensure!(
+        !l2_blocks.is_empty(),
+        "no L2 blocks with transactions persisted for L1 batch #{l1_batch_number}"
+    );
+    drop(connection);
+
+    let storage_l2_block_number = first_l2_block_in_batch.number() - 1;
+    let rt_handle = Handle::current();
+    let recorded_batch = tokio::task::spawn_blocking(move || {
+        let connection = rt_handle.block_on(pool.connection())?;
+        let pg_storage = PostgresStorage::new(rt_handle, connection, storage_l2_block_number, true);
+        let storage = RecordingStorage::new(pg_storage);
+        let accesses = storage.accesses();
+        execute_batch(
+            l1_batch_env.clone(),
+            system_env.clone(),
+            &l2_blocks,
+            storage,
+        )?;
+
+        let accesses = accesses.borrow();
+        anyhow::Ok(RecordedBatch {
+            l1_batch_env,
+            system_env,
+            l2_blocks,
+            storage_slots: accesses.storage_slots(),
+            factory_deps: accesses.factory_deps(),
+        })
+    })
+    .await
+    .context("batch execution panicked")??;
+
+    recorded_batch.save(&cli.output)?;
+    println!(
+        "Exported L1 batch #{l1_batch_number} with {} transactions, {} storage slots and {} factory deps to {}",
+        recorded_batch.transaction_count(),
+        recorded_batch.storage_slots.len(),
+        recorded_batch.factory_deps.len(),
+        cli.output.display()
+    );
+    Ok(())
+}
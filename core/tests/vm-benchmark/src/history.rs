@@ -0,0 +1,444 @@
+//! Historical benchmark results and regression detection.
+//!
+//! Results are stored per commit in a JSON file. A new result is compared against a rolling baseline
+//! formed by the latest recorded commits: a metric is considered regressed if it exceeds the baseline mean
+//! both by a relative threshold and by a number of baseline standard deviations. The latter makes the gate
+//! robust to noisy metrics, while the former prevents flagging negligible changes of stable metrics.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt, fs,
+    path::Path,
+};
+
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+
+use crate::parse_iai::IaiResult;
+
+/// Benchmark metric tracked in the history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Metric {
+    /// Estimated cycles reported by cachegrind.
+    Cycles,
+    /// Instructions reported by cachegrind.
+    Instructions,
+    /// Number of zkEVM opcodes executed.
+    Opcodes,
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(match self {
+            Self::Cycles => "cycles",
+            Self::Instructions => "instructions",
+            Self::Opcodes => "opcodes",
+        })
+    }
+}
+
+/// Results of all benchmarks for a single commit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkRecord {
+    pub commit: String,
+    /// UNIX timestamp (in seconds) the results were recorded at.
+    pub timestamp: u64,
+    /// Metric values keyed by the benchmark name.
+    pub benchmarks: BTreeMap<String, BTreeMap<Metric, u64>>,
+}
+
+impl BenchmarkRecord {
+    pub fn new(commit: String, timestamp: u64) -> Self {
+        Self {
+            commit,
+            timestamp,
+            benchmarks: BTreeMap::new(),
+        }
+    }
+
+    /// Adds results parsed from the `iai` benchmark output.
+    pub fn add_iai_results(&mut self, results: impl IntoIterator<Item = IaiResult>) {
+        for result in results {
+            let metrics = self.benchmarks.entry(result.name).or_default();
+            metrics.insert(Metric::Cycles, result.cycles);
+            metrics.insert(Metric::Instructions, result.instructions);
+        }
+    }
+
+    /// Adds opcode counts as output by the `instruction-counts` binary. Each line has either the `{name} {count}`
+    /// format, or the `{name} {vm_version} {count}` format if the binary was run with `--all-versions`. In the latter
+    /// case, counts are recorded for the `{name}/{vm_version}` benchmark, and versions without opcode counting
+    /// support (`N/A` count) are skipped.
+    pub fn add_opcode_counts(&mut self, output: &str) -> anyhow::Result<()> {
+        for line in output.lines().filter(|line| !line.trim().is_empty()) {
+            let parts: Vec<_> = line.split_whitespace().collect();
+            let (name, count) = match parts.as_slice() {
+                [name, count] => ((*name).to_owned(), *count),
+                [_, _, "N/A"] => continue,
+                [name, version, count] => (format!("{name}/{version}"), *count),
+                _ => anyhow::bail!(
+                    "invalid opcode count line, expected `{{name}} {{count}}` or \
+                     `{{name}} {{vm_version}} {{count}}`: {line}"
+                ),
+            };
+            let count = count
+                .parse()
+                .with_context(|| format!("invalid opcode count for {name}"))?;
+            self.benchmarks
+                .entry(name)
+                .or_default()
+                .insert(Metric::Opcodes, count);
+        }
+        Ok(())
+    }
+}
+
+/// Local database of benchmark results ordered by the recording time.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkHistory {
+    pub records: Vec<BenchmarkRecord>,
+}
+
+impl BenchmarkHistory {
+    /// Loads the history from a JSON file. A missing file is treated as an empty history.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed reading history from {}", path.display()))?;
+        serde_json::from_str(&contents).context("invalid benchmark history")
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let contents = serde_json::to_string_pretty(self)?;
+        fs::write(path, contents)
+            .with_context(|| format!("failed writing history to {}", path.display()))
+    }
+
+    /// Inserts a record replacing the existing record for the same commit, if any.
+    pub fn insert(&mut self, record: BenchmarkRecord) {
+        self.records
+            .retain(|existing| existing.commit != record.commit);
+        self.records.push(record);
+    }
+
+    /// Removes the oldest records so that at most `max_records` remain.
+    pub fn truncate(&mut self, max_records: usize) {
+        let excess = self.records.len().saturating_sub(max_records);
+        self.records.drain(..excess);
+    }
+
+    pub fn get(&self, commit: &str) -> Option<&BenchmarkRecord> {
+        self.records.iter().find(|record| record.commit == commit)
+    }
+
+    /// Returns up to `window` latest records excluding the specified commit.
+    pub fn baseline(&self, exclude_commit: &str, window: usize) -> Vec<&BenchmarkRecord> {
+        let mut baseline: Vec<_> = self
+            .records
+            .iter()
+            .rev()
+            .filter(|record| record.commit != exclude_commit)
+            .take(window)
+            .collect();
+        baseline.reverse();
+        baseline
+    }
+}
+
+/// Policy used to decide whether a metric change is a regression.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RegressionPolicy {
+    /// Number of latest commits forming the baseline.
+    pub window: usize,
+    /// Minimum number of baseline samples required to check a metric.
+    pub min_samples: usize,
+    /// Minimum relative change (in percent) to be considered significant.
+    pub threshold_percent: f64,
+    /// Minimum change measured in baseline standard deviations to be considered significant.
+    pub threshold_sigmas: f64,
+}
+
+impl Default for RegressionPolicy {
+    fn default() -> Self {
+        Self {
+            window: 10,
+            min_samples: 3,
+            threshold_percent: 2.0,
+            threshold_sigmas: 3.0,
+        }
+    }
+}
+
+/// Outcome of comparing a metric against its baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComparisonStatus {
+    Unchanged,
+    Improvement,
+    Regression,
+    /// There are not enough baseline samples to check the metric.
+    NoBaseline,
+    /// The benchmark is expected, but missing from the checked results.
+    Missing,
+}
+
+/// Comparison of a single benchmark metric against its baseline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricComparison {
+    pub benchmark: String,
+    /// Compared metric; `None` for missing benchmarks.
+    pub metric: Option<Metric>,
+    pub current: Option<u64>,
+    pub baseline_mean: Option<f64>,
+    pub baseline_stddev: Option<f64>,
+    pub baseline_samples: usize,
+    pub change_percent: Option<f64>,
+    pub status: ComparisonStatus,
+}
+
+/// Machine-readable regression report.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegressionReport {
+    pub commit: String,
+    pub baseline_commits: Vec<String>,
+    pub threshold_percent: f64,
+    pub threshold_sigmas: f64,
+    pub comparisons: Vec<MetricComparison>,
+    pub has_regressions: bool,
+}
+
+impl RegressionReport {
+    /// Compares `current` results with the baseline from `history`. Benchmarks from `expected_benchmarks`
+    /// missing in the current results are reported as missing and fail the check.
+    pub fn new(
+        current: &BenchmarkRecord,
+        history: &BenchmarkHistory,
+        expected_benchmarks: &BTreeSet<String>,
+        policy: RegressionPolicy,
+    ) -> Self {
+        let baseline = history.baseline(&current.commit, policy.window);
+
+        let mut comparisons = vec![];
+        for name in expected_benchmarks {
+            if !current.benchmarks.contains_key(name) {
+                comparisons.push(MetricComparison {
+                    benchmark: name.clone(),
+                    metric: None,
+                    current: None,
+                    baseline_mean: None,
+                    baseline_stddev: None,
+                    baseline_samples: 0,
+                    change_percent: None,
+                    status: ComparisonStatus::Missing,
+                });
+            }
+        }
+
+        for (name, metrics) in &current.benchmarks {
+            for (&metric, &value) in metrics {
+                let samples: Vec<_> = baseline
+                    .iter()
+                    .filter_map(|record| record.benchmarks.get(name)?.get(&metric))
+                    .map(|&value| value as f64)
+                    .collect();
+                comparisons.push(compare_metric(name, metric, value, &samples, policy));
+            }
+        }
+
+        let has_regressions = comparisons.iter().any(|comparison| {
+            matches!(
+                comparison.status,
+                ComparisonStatus::Regression | ComparisonStatus::Missing
+            )
+        });
+        Self {
+            commit: current.commit.clone(),
+            baseline_commits: baseline
+                .iter()
+                .map(|record| record.commit.clone())
+                .collect(),
+            threshold_percent: policy.threshold_percent,
+            threshold_sigmas: policy.threshold_sigmas,
+            comparisons,
+            has_regressions,
+        }
+    }
+
+    /// Returns comparisons with a status other than `Unchanged`.
+    pub fn notable_changes(&self) -> impl Iterator<Item = &MetricComparison> + '_ {
+        self.comparisons
+            .iter()
+            .filter(|comparison| comparison.status != ComparisonStatus::Unchanged)
+    }
+}
+
+fn compare_metric(
+    benchmark: &str,
+    metric: Metric,
+    current: u64,
+    samples: &[f64],
+    policy: RegressionPolicy,
+) -> MetricComparison {
+    let mut comparison = MetricComparison {
+        benchmark: benchmark.to_owned(),
+        metric: Some(metric),
+        current: Some(current),
+        baseline_mean: None,
+        baseline_stddev: None,
+        baseline_samples: samples.len(),
+        change_percent: None,
+        status: ComparisonStatus::NoBaseline,
+    };
+    if samples.len() < policy.min_samples.max(1) {
+        return comparison;
+    }
+
+    let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+    let variance = samples.iter().map(|&x| (x - mean).powi(2)).sum::<f64>() / samples.len() as f64;
+    let stddev = variance.sqrt();
+    let diff = current as f64 - mean;
+    let change_percent = if mean == 0.0 {
+        0.0
+    } else {
+        diff / mean * 100.0
+    };
+
+    let is_significant = change_percent.abs() > policy.threshold_percent
+        && diff.abs() > policy.threshold_sigmas * stddev;
+    comparison.status = match (is_significant, diff > 0.0) {
+        (false, _) => ComparisonStatus::Unchanged,
+        (true, true) => ComparisonStatus::Regression,
+        (true, false) => ComparisonStatus::Improvement,
+    };
+    comparison.baseline_mean = Some(mean);
+    comparison.baseline_stddev = Some(stddev);
+    comparison.change_percent = Some(change_percent);
+    comparison
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(commit: &str, cycles: u64) -> BenchmarkRecord {
+        let mut record = BenchmarkRecord::new(commit.to_owned(), 0);
+        record
+            .benchmarks
+            .entry("call_far".to_owned())
+            .or_default()
+            .insert(Metric::Cycles, cycles);
+        record
+    }
+
+    fn history(cycles: &[u64]) -> BenchmarkHistory {
+        let mut history = BenchmarkHistory::default();
+        for (i, &cycles) in cycles.iter().enumerate() {
+            history.insert(record(&format!("commit{i}"), cycles));
+        }
+        history
+    }
+
+    fn status(report: &RegressionReport) -> ComparisonStatus {
+        assert_eq!(report.comparisons.len(), 1);
+        report.comparisons[0].status
+    }
+
+    #[test]
+    fn parsing_opcode_counts() {
+        let mut record = BenchmarkRecord::new("test".to_owned(), 0);
+        record
+            .add_opcode_counts("call_far 123\naccess_memory 456\n\n")
+            .unwrap();
+        assert_eq!(record.benchmarks["call_far"][&Metric::Opcodes], 123);
+        assert_eq!(record.benchmarks["access_memory"][&Metric::Opcodes], 456);
+        assert!(record.add_opcode_counts("call_far").is_err());
+        assert!(record.add_opcode_counts("call_far 1 2 3").is_err());
+    }
+
+    #[test]
+    fn parsing_opcode_counts_for_all_versions() {
+        let mut record = BenchmarkRecord::new("test".to_owned(), 0);
+        record
+            .add_opcode_counts(
+                "call_far Vm1_4_2 N/A\ncall_far Vm1_5_0IncreasedBootloaderMemory 123\n",
+            )
+            .unwrap();
+        assert_eq!(record.benchmarks.len(), 1);
+        assert_eq!(
+            record.benchmarks["call_far/Vm1_5_0IncreasedBootloaderMemory"][&Metric::Opcodes],
+            123
+        );
+        assert!(record.add_opcode_counts("call_far Vm1_4_2 many").is_err());
+    }
+
+    #[test]
+    fn baseline_excludes_current_commit() {
+        let mut history = history(&[100, 101, 102, 103]);
+        history.insert(record("commit1", 200));
+        let baseline: Vec<_> = history
+            .baseline("commit3", 2)
+            .into_iter()
+            .map(|record| record.commit.as_str())
+            .collect();
+        assert_eq!(baseline, ["commit2", "commit1"]);
+
+        history.truncate(2);
+        assert_eq!(history.records.len(), 2);
+        assert_eq!(history.records[0].commit, "commit3");
+    }
+
+    #[test]
+    fn detecting_regressions() {
+        let policy = RegressionPolicy::default();
+        let history = history(&[1_000, 1_010, 990, 1_000]);
+        let expected = BTreeSet::new();
+
+        let report = RegressionReport::new(&record("new", 1_005), &history, &expected, policy);
+        assert_eq!(status(&report), ComparisonStatus::Unchanged);
+        assert!(!report.has_regressions);
+        assert_eq!(report.baseline_commits.len(), 4);
+
+        let report = RegressionReport::new(&record("new", 1_100), &history, &expected, policy);
+        assert_eq!(status(&report), ComparisonStatus::Regression);
+        assert!(report.has_regressions);
+
+        let report = RegressionReport::new(&record("new", 900), &history, &expected, policy);
+        assert_eq!(status(&report), ComparisonStatus::Improvement);
+        assert!(!report.has_regressions);
+    }
+
+    #[test]
+    fn noisy_metrics_require_larger_changes() {
+        let policy = RegressionPolicy::default();
+        let history = history(&[800, 1_200, 900, 1_100]);
+        // +5% is above the relative threshold, but within the noise.
+        let report =
+            RegressionReport::new(&record("new", 1_050), &history, &BTreeSet::new(), policy);
+        assert_eq!(status(&report), ComparisonStatus::Unchanged);
+    }
+
+    #[test]
+    fn insufficient_baseline_and_missing_benchmarks() {
+        let policy = RegressionPolicy::default();
+        let history = history(&[1_000]);
+        let expected = BTreeSet::from(["call_far".to_owned(), "event_spam".to_owned()]);
+        let report = RegressionReport::new(&record("new", 2_000), &history, &expected, policy);
+
+        let statuses: Vec<_> = report
+            .comparisons
+            .iter()
+            .map(|comparison| (comparison.benchmark.as_str(), comparison.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                ("event_spam", ComparisonStatus::Missing),
+                ("call_far", ComparisonStatus::NoBaseline)
+            ]
+        );
+        assert!(report.has_regressions);
+    }
+}
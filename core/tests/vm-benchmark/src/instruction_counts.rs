@@ -1,10 +1,28 @@
 //! Runs all benchmarks and prints out the number of zkEVM opcodes each one executed.
+//!
+//! By default, only the latest VM version is benchmarked, and each line has the `$benchmark $count` format.
+//! With `--all-versions`, every supported VM version is benchmarked, and each line has
+//! the `$benchmark $vm_version $count` format.
 
 use std::path::Path;
 
-use zksync_vm_benchmark_harness::{cut_to_allowed_bytecode_size, get_deploy_tx, BenchmarkingVm};
+use clap::Parser;
+use zksync_vm_benchmark_harness::{
+    cut_to_allowed_bytecode_size, get_deploy_tx, get_deploy_tx_for_version, BenchmarkingVm,
+    BENCHMARKED_VM_VERSIONS,
+};
+
+#[derive(Debug, Parser)]
+#[command(about = "Prints the number of zkEVM opcodes executed by each benchmark")]
+struct Cli {
+    /// Count opcodes for all supported VM versions rather than only for the latest one.
+    #[arg(long)]
+    all_versions: bool,
+}
 
 fn main() {
+    let cli = Cli::parse();
+
     // using source file location because this is just a script, the binary isn't meant to be reused
     let benchmark_folder = Path::new(file!())
         .parent()
@@ -19,10 +37,24 @@ fn main() {
         let test_contract = std::fs::read(&path).expect("failed to read file");
 
         let code = cut_to_allowed_bytecode_size(&test_contract).unwrap();
-        let tx = get_deploy_tx(code);
-
         let name = path.file_name().unwrap().to_str().unwrap();
 
-        println!("{} {}", name, BenchmarkingVm::new().instruction_count(&tx));
+        if !cli.all_versions {
+            let tx = get_deploy_tx(code);
+            let count = BenchmarkingVm::new()
+                .instruction_count(&tx)
+                .expect("latest VM supports tracers");
+            println!("{name} {count}");
+            continue;
+        }
+
+        for version in BENCHMARKED_VM_VERSIONS {
+            let tx = get_deploy_tx_for_version(code, version);
+            let mut vm = BenchmarkingVm::with_version(version).expect("VM version is supported");
+            match vm.instruction_count(&tx) {
+                Some(count) => println!("{name} {version:?} {count}"),
+                None => println!("{name} {version:?} N/A"),
+            }
+        }
     }
 }
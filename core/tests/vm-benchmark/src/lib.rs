@@ -1,2 +1,3 @@
+pub mod history;
 pub mod parse_iai;
 pub mod with_prometheus;
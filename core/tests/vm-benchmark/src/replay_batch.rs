@@ -0,0 +1,54 @@
+//! Replays L1 batches exported with `export_batch` and prints their execution time.
+
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use clap::Parser;
+use zksync_vm_benchmark_harness::batch::RecordedBatch;
+
+#[derive(Debug, Parser)]
+#[command(about = "Replays exported L1 batches and measures VM execution time")]
+struct Cli {
+    /// Number of times each batch is executed.
+    #[arg(long, default_value_t = 10)]
+    iterations: usize,
+    /// Paths to exported batches.
+    #[arg(required = true)]
+    batches: Vec<PathBuf>,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    anyhow::ensure!(cli.iterations > 0, "at least one iteration is required");
+
+    for path in &cli.batches {
+        let batch = RecordedBatch::load(path)?;
+        let storage = batch.storage();
+        let mut timings = Vec::with_capacity(cli.iterations);
+        for _ in 0..cli.iterations {
+            let started_at = Instant::now();
+            batch.execute(&storage)?;
+            timings.push(started_at.elapsed());
+        }
+        timings.sort_unstable();
+
+        let median = timings[timings.len() / 2];
+        let tx_count = batch.transaction_count();
+        println!(
+            "{} (L1 batch #{}, {:?}, {tx_count} txs): min {:?}, median {median:?}, max {:?}, {:.1} tx/s",
+            path.display(),
+            batch.l1_batch_env.number,
+            batch.system_env.version,
+            timings[0],
+            timings[timings.len() - 1],
+            throughput(tx_count, median)
+        );
+    }
+    Ok(())
+}
+
+fn throughput(tx_count: usize, duration: Duration) -> f64 {
+    tx_count as f64 / duration.as_secs_f64()
+}